@@ -1,4 +1,4 @@
-import { Args, Command } from "@oclif/core";
+import { Args, Command, Flags } from "@oclif/core";
 import { PublicKey } from "@solana/web3.js";
 import {
   CustomLoader,
@@ -11,6 +11,13 @@ class TransactionInitializeCommand extends Command {
 
   static examples = ["light transaction-merkle-tree:initialize -p <pubKey>"];
 
+  static flags = {
+    height: Flags.integer({
+      description: "Height of the new Merkle Tree.",
+      default: 18,
+    }),
+  };
+
   static args = {
     oldTree: Args.string({
       description: "Solana public key of the old Merkle Tree.",
@@ -24,7 +31,7 @@ class TransactionInitializeCommand extends Command {
   };
 
   async run() {
-    const { args } = await this.parse(TransactionInitializeCommand);
+    const { args, flags } = await this.parse(TransactionInitializeCommand);
     const { oldTree, newTree } = args;
 
     const oldMerkleTreePubkey = new PublicKey(oldTree);
@@ -45,7 +52,8 @@ class TransactionInitializeCommand extends Command {
       let merkleTreeConfig = await getWalletConfig(connection);
       await merkleTreeConfig.initializeNewTransactionMerkleTree(
         oldMerkleTreePubkey,
-        newMerkleTreePubkey
+        newMerkleTreePubkey,
+        flags.height
       );
       this.log(
         "Transaction Merkle Tree initialized successfully \x1b[32m✔\x1b[0m"
@@ -0,0 +1,186 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+use std::io::{Error, ErrorKind, Result};
+
+/// Version of the events verifiers log.
+pub const CURRENT_VERSION: u8 = 1;
+
+/// Kind of a transaction, derived from its public amounts.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionKind {
+    Shield,
+    Transfer,
+    Unshield,
+}
+
+/// Event verifiers log through the noop program for every transaction.
+/// The variant index is the version of the event.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub enum TransactionIndexerEvent {
+    V0(TransactionIndexerEventV0),
+    V1(TransactionIndexerEventV1),
+}
+
+/// Event logged before events were versioned. It was logged without a
+/// version tag and is only decoded, never logged.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub struct TransactionIndexerEventV0 {
+    pub leaves: Vec<[u8; 32]>,
+    pub public_amount_spl: [u8; 32],
+    pub public_amount_sol: [u8; 32],
+    pub relayer_fee: u64,
+    pub encrypted_utxos: Vec<u8>,
+    pub nullifiers: Vec<[u8; 32]>,
+    pub first_leaf_index: u64,
+    pub message: Vec<u8>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub struct TransactionIndexerEventV1 {
+    /// Transaction Merkle tree the leaves were inserted into.
+    pub transaction_merkle_tree: Pubkey,
+    /// Number of the event Merkle tree the event hash was inserted into.
+    pub event_merkle_tree_index: u64,
+    /// Number of transactions which inserted leaves into the transaction
+    /// Merkle tree, including this one. Increases by one with every event of
+    /// the tree, a gap means an indexer missed events.
+    pub sequence_number: u64,
+    pub verifier: Pubkey,
+    pub kind: TransactionKind,
+    pub leaves: Vec<[u8; 32]>,
+    pub public_amount_spl: [u8; 32],
+    pub public_amount_sol: [u8; 32],
+    pub relayer_fee: u64,
+    /// Encrypted utxo of each output, in the order of the leaves.
+    pub encrypted_utxos: Vec<Vec<u8>>,
+    pub nullifiers: Vec<[u8; 32]>,
+    pub first_leaf_index: u64,
+    pub message: Vec<u8>,
+}
+
+impl TransactionIndexerEvent {
+    /// Decodes the data of a noop instruction into an event of any version.
+    /// The data has to be consumed completely, noop instructions of other
+    /// events fail to decode.
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        match Self::try_from_slice(data) {
+            Ok(TransactionIndexerEvent::V0(_)) => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Version 0 events are not tagged",
+            )),
+            Ok(event) => Ok(event),
+            // Unversioned events start with the number of leaves, which is
+            // never a valid version tag.
+            Err(err) => match TransactionIndexerEventV0::try_from_slice(data) {
+                Ok(event) => Ok(TransactionIndexerEvent::V0(event)),
+                Err(_) => Err(err),
+            },
+        }
+    }
+
+    pub fn version(&self) -> u8 {
+        match self {
+            TransactionIndexerEvent::V0(_) => 0,
+            TransactionIndexerEvent::V1(_) => 1,
+        }
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        match self {
+            TransactionIndexerEvent::V0(event) => &event.leaves,
+            TransactionIndexerEvent::V1(event) => &event.leaves,
+        }
+    }
+
+    pub fn nullifiers(&self) -> &[[u8; 32]] {
+        match self {
+            TransactionIndexerEvent::V0(event) => &event.nullifiers,
+            TransactionIndexerEvent::V1(event) => &event.nullifiers,
+        }
+    }
+
+    pub fn first_leaf_index(&self) -> u64 {
+        match self {
+            TransactionIndexerEvent::V0(event) => event.first_leaf_index,
+            TransactionIndexerEvent::V1(event) => event.first_leaf_index,
+        }
+    }
+
+    /// Transaction Merkle tree of the event, unknown for version 0 events.
+    pub fn transaction_merkle_tree(&self) -> Option<Pubkey> {
+        match self {
+            TransactionIndexerEvent::V0(_) => None,
+            TransactionIndexerEvent::V1(event) => Some(event.transaction_merkle_tree),
+        }
+    }
+
+    /// Sequence number of the event, unknown for version 0 events.
+    pub fn sequence_number(&self) -> Option<u64> {
+        match self {
+            TransactionIndexerEvent::V0(_) => None,
+            TransactionIndexerEvent::V1(event) => Some(event.sequence_number),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn event_v0() -> TransactionIndexerEventV0 {
+        TransactionIndexerEventV0 {
+            leaves: vec![[1u8; 32], [2u8; 32]],
+            public_amount_spl: [3u8; 32],
+            public_amount_sol: [4u8; 32],
+            relayer_fee: 5_000,
+            encrypted_utxos: vec![6u8; 256],
+            nullifiers: vec![[7u8; 32], [8u8; 32]],
+            first_leaf_index: 10,
+            message: vec![],
+        }
+    }
+
+    fn event_v1() -> TransactionIndexerEventV1 {
+        TransactionIndexerEventV1 {
+            transaction_merkle_tree: Pubkey::new_unique(),
+            event_merkle_tree_index: 0,
+            sequence_number: 6,
+            verifier: Pubkey::new_unique(),
+            kind: TransactionKind::Transfer,
+            leaves: vec![[1u8; 32], [2u8; 32]],
+            public_amount_spl: [0u8; 32],
+            public_amount_sol: [4u8; 32],
+            relayer_fee: 5_000,
+            encrypted_utxos: vec![vec![6u8; 120]; 2],
+            nullifiers: vec![[7u8; 32], [8u8; 32]],
+            first_leaf_index: 10,
+            message: vec![9u8; 3],
+        }
+    }
+
+    #[test]
+    fn test_decode() {
+        let event = TransactionIndexerEvent::V1(event_v1());
+        let data = event.try_to_vec().unwrap();
+        assert_eq!(data[0], CURRENT_VERSION);
+        assert_eq!(TransactionIndexerEvent::decode(&data).unwrap(), event);
+        assert_eq!(event.sequence_number(), Some(6));
+
+        // Unversioned events.
+        let data = event_v0().try_to_vec().unwrap();
+        let event = TransactionIndexerEvent::decode(&data).unwrap();
+        assert_eq!(event, TransactionIndexerEvent::V0(event_v0()));
+        assert_eq!(event.version(), 0);
+        assert_eq!(event.first_leaf_index(), 10);
+        assert_eq!(event.transaction_merkle_tree(), None);
+
+        // Tagged version 0 events are never logged.
+        let data = TransactionIndexerEvent::V0(event_v0()).try_to_vec().unwrap();
+        assert!(TransactionIndexerEvent::decode(&data).is_err());
+        // Trailing bytes of other events.
+        let mut data = TransactionIndexerEvent::V1(event_v1()).try_to_vec().unwrap();
+        data.push(0);
+        assert!(TransactionIndexerEvent::decode(&data).is_err());
+        assert!(TransactionIndexerEvent::decode(&[1u8; 16]).is_err());
+    }
+}
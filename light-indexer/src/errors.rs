@@ -0,0 +1,42 @@
+use anchor_lang::prelude::Pubkey;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("Transaction Merkle tree {0} is not registered")]
+    UnknownMerkleTree(Pubkey),
+    #[error("Transaction Merkle tree {0} is registered already")]
+    MerkleTreeAlreadyRegistered(Pubkey),
+    #[error("Height {0} of the transaction Merkle tree is out of bounds")]
+    InvalidMerkleTreeHeight(u64),
+    #[error("Missed events of Merkle tree {merkle_tree}, expected sequence number {expected}, got {got}")]
+    MissedEvents {
+        merkle_tree: Pubkey,
+        expected: u64,
+        got: u64,
+    },
+    #[error("First leaf index {got} of the event does not match the next leaf index {expected}")]
+    InvalidFirstLeafIndex { expected: u64, got: u64 },
+    #[error("Leaves of the event at first leaf index {first_leaf_index} conflict with the indexed leaves")]
+    ConflictingLeaves { first_leaf_index: u64 },
+    #[error("Events have to insert pairs of leaves")]
+    OddNumberOfLeaves,
+    #[error("Merkle tree is full")]
+    MerkleTreeFull,
+    #[error("Height {expected} of the Merkle tree does not match height {got} of the account")]
+    InvalidHeight { expected: u64, got: u64 },
+    #[error("Indexed {indexed} leaves, the Merkle tree holds {expected} leaves")]
+    NotSynced { expected: u64, indexed: u64 },
+    #[error("Root mismatch at {num_leaves} leaves")]
+    RootMismatch { num_leaves: u64 },
+    #[error("Leaf {0} is not inserted into the Merkle tree yet")]
+    LeafNotInserted(u64),
+    #[error("Unversioned events do not carry the transaction Merkle tree")]
+    UnversionedEvent,
+    #[error("Poseidon hash failed")]
+    PoseidonHashFailed,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, IndexerError>;
@@ -0,0 +1,580 @@
+//! Off-chain indexer which rebuilds the transaction Merkle trees (Poseidon) from
+//! transaction indexer events and the event Merkle trees (SHA-256) from the
+//! leaves of `insert_two_leaves_event` instructions. State is persisted to a
+//! local file and Merkle proofs are served against roots of the on-chain trees.
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hash};
+use borsh::{BorshDeserialize, BorshSerialize};
+use light_indexer_events::{TransactionIndexerEvent, TransactionIndexerEventV0};
+use merkle_tree_program::{
+    event_merkle_tree::EventMerkleTree,
+    transaction_merkle_tree::state::{RootUpdateEvent, TransactionMerkleTree},
+    utils::{
+        config::{MERKLE_TREE_MAX_HEIGHT, MERKLE_TREE_MIN_HEIGHT},
+        constants::EVENT_MERKLE_TREE_HEIGHT,
+    },
+};
+
+pub mod errors;
+pub mod merkle_tree;
+
+pub use errors::{IndexerError, Result};
+pub use merkle_tree::{MerkleProof, MerkleTree, TreeHash};
+
+/// Indexed state of a transaction Merkle tree.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub struct TransactionMerkleTreeState {
+    /// All queued leaves, the on-chain tree holds the first `inserted_leaves`.
+    pub merkle_tree: MerkleTree,
+    /// Sequence number of the last processed event.
+    pub sequence_number: u64,
+    /// Leaves of the last root inserted on-chain and its index in the roots
+    /// of the Merkle tree account.
+    pub inserted_leaves: u64,
+    pub root_index: u64,
+}
+
+/// Merkle proof of a leaf of a transaction Merkle tree against a root of the
+/// on-chain tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionMerkleProof {
+    pub proof: MerkleProof,
+    /// Index of the root in the roots of the Merkle tree account, the
+    /// `merkle_root_index` of a transaction.
+    pub root_index: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Indexer {
+    pub transaction_merkle_trees: BTreeMap<Pubkey, TransactionMerkleTreeState>,
+    /// Event Merkle trees by their number.
+    pub event_merkle_trees: BTreeMap<u64, MerkleTree>,
+}
+
+impl Indexer {
+    /// Loads the state saved at `path`, or an empty state if there is none.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Indexer::default());
+        }
+        Ok(Indexer::try_from_slice(&fs::read(path)?)?)
+    }
+
+    /// Saves the state to `path`. The state is written to a temporary file
+    /// first, an interrupted save does not corrupt the previous state.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, self.try_to_vec()?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Starts indexing a new transaction Merkle tree.
+    pub fn register_transaction_merkle_tree(&mut self, pubkey: Pubkey, height: u64) -> Result<()> {
+        if !(MERKLE_TREE_MIN_HEIGHT..=MERKLE_TREE_MAX_HEIGHT).contains(&height) {
+            return Err(IndexerError::InvalidMerkleTreeHeight(height));
+        }
+        if self.transaction_merkle_trees.contains_key(&pubkey) {
+            return Err(IndexerError::MerkleTreeAlreadyRegistered(pubkey));
+        }
+        self.transaction_merkle_trees.insert(
+            pubkey,
+            TransactionMerkleTreeState {
+                merkle_tree: MerkleTree::new(height, TreeHash::Poseidon),
+                sequence_number: 0,
+                inserted_leaves: 0,
+                root_index: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn transaction_merkle_tree(&self, pubkey: &Pubkey) -> Result<&TransactionMerkleTreeState> {
+        self.transaction_merkle_trees
+            .get(pubkey)
+            .ok_or(IndexerError::UnknownMerkleTree(*pubkey))
+    }
+
+    fn transaction_merkle_tree_mut(
+        &mut self,
+        pubkey: &Pubkey,
+    ) -> Result<&mut TransactionMerkleTreeState> {
+        self.transaction_merkle_trees
+            .get_mut(pubkey)
+            .ok_or(IndexerError::UnknownMerkleTree(*pubkey))
+    }
+
+    /// Appends the leaves of a transaction to its transaction Merkle tree.
+    /// Events have to be processed in the order of their sequence numbers.
+    /// Returns false if the event was processed already.
+    pub fn process_event(&mut self, event: &TransactionIndexerEvent) -> Result<bool> {
+        let event = match event {
+            TransactionIndexerEvent::V0(_) => return Err(IndexerError::UnversionedEvent),
+            TransactionIndexerEvent::V1(event) => event,
+        };
+        let state = self.transaction_merkle_tree_mut(&event.transaction_merkle_tree)?;
+        if event.sequence_number <= state.sequence_number {
+            return Ok(false);
+        }
+        if event.sequence_number != state.sequence_number + 1 {
+            return Err(IndexerError::MissedEvents {
+                merkle_tree: event.transaction_merkle_tree,
+                expected: state.sequence_number + 1,
+                got: event.sequence_number,
+            });
+        }
+        append_leaves(
+            &mut state.merkle_tree,
+            event.first_leaf_index,
+            &event.leaves,
+        )?;
+        state.sequence_number = event.sequence_number;
+        Ok(true)
+    }
+
+    /// Appends the leaves of an event logged before events were versioned, these
+    /// do not carry their transaction Merkle tree nor a sequence number.
+    /// Events are ordered by their first leaf index instead.
+    /// Returns false if the leaves of the event are indexed already.
+    pub fn process_unversioned_event(
+        &mut self,
+        transaction_merkle_tree: &Pubkey,
+        event: &TransactionIndexerEventV0,
+    ) -> Result<bool> {
+        let state = self.transaction_merkle_tree_mut(transaction_merkle_tree)?;
+        insert_leaves(
+            &mut state.merkle_tree,
+            event.first_leaf_index,
+            &event.leaves,
+        )
+    }
+
+    /// Records a root inserted on-chain after checking it against the indexed leaves.
+    pub fn process_root_update(&mut self, event: &RootUpdateEvent) -> Result<()> {
+        let state = self.transaction_merkle_tree_mut(&event.merkle_tree)?;
+        check_root(&state.merkle_tree, event.end_index, &event.root)?;
+        state.inserted_leaves = event.end_index;
+        state.root_index = event.root_index;
+        Ok(())
+    }
+
+    /// Checks the indexed leaves against the current root of a transaction Merkle
+    /// tree account and records the root.
+    pub fn sync_transaction_merkle_tree(
+        &mut self,
+        pubkey: &Pubkey,
+        account: &TransactionMerkleTree,
+    ) -> Result<()> {
+        let state = self.transaction_merkle_tree_mut(pubkey)?;
+        if state.merkle_tree.height != account.height {
+            return Err(IndexerError::InvalidHeight {
+                expected: state.merkle_tree.height,
+                got: account.height,
+            });
+        }
+        check_root(
+            &state.merkle_tree,
+            account.next_index,
+            &account.roots[account.current_root_index as usize],
+        )?;
+        state.inserted_leaves = account.next_index;
+        state.root_index = account.current_root_index;
+        Ok(())
+    }
+
+    /// Circuit-ready Merkle proof of a leaf against the last root inserted on-chain.
+    pub fn transaction_merkle_proof(
+        &self,
+        pubkey: &Pubkey,
+        leaf_index: u64,
+    ) -> Result<TransactionMerkleProof> {
+        let state = self.transaction_merkle_tree(pubkey)?;
+        Ok(TransactionMerkleProof {
+            proof: state.merkle_tree.proof(leaf_index, state.inserted_leaves)?,
+            root_index: state.root_index,
+        })
+    }
+
+    /// Appends the leaves of an `insert_two_leaves_event` instruction to an
+    /// event Merkle tree, `first_leaf_index` is the `next_index` of the event
+    /// Merkle tree account before the instruction.
+    /// Returns false if the leaves are indexed already.
+    pub fn insert_event_leaves(
+        &mut self,
+        event_merkle_tree_index: u64,
+        first_leaf_index: u64,
+        leaf_left: [u8; 32],
+        leaf_right: [u8; 32],
+    ) -> Result<bool> {
+        let merkle_tree = self
+            .event_merkle_trees
+            .entry(event_merkle_tree_index)
+            .or_insert_with(|| MerkleTree::new(EVENT_MERKLE_TREE_HEIGHT as u64, TreeHash::Sha256));
+        insert_leaves(merkle_tree, first_leaf_index, &[leaf_left, leaf_right])
+    }
+
+    /// Checks the indexed leaves against the current root of an event Merkle tree account.
+    pub fn check_event_merkle_tree(&self, account: &EventMerkleTree) -> Result<()> {
+        let merkle_tree = self.event_merkle_trees.get(&account.merkle_tree_nr).ok_or(
+            IndexerError::NotSynced {
+                expected: account.merkle_tree.next_index,
+                indexed: 0,
+            },
+        )?;
+        check_root(
+            merkle_tree,
+            account.merkle_tree.next_index,
+            &account.merkle_tree.roots[account.merkle_tree.current_root_index as usize],
+        )
+    }
+
+    /// Merkle proof of a leaf of an event Merkle tree against its current root.
+    pub fn event_merkle_proof(
+        &self,
+        event_merkle_tree_index: u64,
+        leaf_index: u64,
+    ) -> Result<MerkleProof> {
+        let merkle_tree = self
+            .event_merkle_trees
+            .get(&event_merkle_tree_index)
+            .ok_or(IndexerError::LeafNotInserted(leaf_index))?;
+        merkle_tree.proof(leaf_index, merkle_tree.num_leaves())
+    }
+}
+
+/// Appends leaves which are ordered by their first leaf index only. Leaves at
+/// indexed leaf indices have to match the indexed leaves.
+fn insert_leaves(
+    merkle_tree: &mut MerkleTree,
+    first_leaf_index: u64,
+    leaves: &[[u8; 32]],
+) -> Result<bool> {
+    if first_leaf_index < merkle_tree.num_leaves() {
+        let start = first_leaf_index as usize;
+        let indexed_leaves = merkle_tree.leaves().get(start..start + leaves.len());
+        if indexed_leaves != Some(leaves) {
+            return Err(IndexerError::ConflictingLeaves { first_leaf_index });
+        }
+        return Ok(false);
+    }
+    append_leaves(merkle_tree, first_leaf_index, leaves)?;
+    Ok(true)
+}
+
+fn append_leaves(
+    merkle_tree: &mut MerkleTree,
+    first_leaf_index: u64,
+    leaves: &[[u8; 32]],
+) -> Result<()> {
+    if first_leaf_index != merkle_tree.num_leaves() {
+        return Err(IndexerError::InvalidFirstLeafIndex {
+            expected: merkle_tree.num_leaves(),
+            got: first_leaf_index,
+        });
+    }
+    if leaves.len() % 2 != 0 {
+        return Err(IndexerError::OddNumberOfLeaves);
+    }
+    merkle_tree.append(leaves)
+}
+
+fn check_root(merkle_tree: &MerkleTree, num_leaves: u64, root: &[u8; 32]) -> Result<()> {
+    if num_leaves > merkle_tree.num_leaves() {
+        return Err(IndexerError::NotSynced {
+            expected: num_leaves,
+            indexed: merkle_tree.num_leaves(),
+        });
+    }
+    if merkle_tree.root_at(num_leaves)? != *root {
+        return Err(IndexerError::RootMismatch { num_leaves });
+    }
+    Ok(())
+}
+
+/// Decodes the leaves of an `insert_two_leaves_event` instruction of the Merkle
+/// tree program from its instruction data.
+pub fn decode_insert_two_leaves_event(data: &[u8]) -> Option<([u8; 32], [u8; 32])> {
+    let discriminator = &hash(b"global:insert_two_leaves_event").to_bytes()[..8];
+    if data.len() != 8 + 64 || &data[..8] != discriminator {
+        return None;
+    }
+    Some((
+        data[8..40].try_into().unwrap(),
+        data[40..72].try_into().unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use light_indexer_events::{TransactionIndexerEventV1, TransactionKind};
+    use light_merkle_tree::HashFunction;
+
+    fn event(
+        transaction_merkle_tree: Pubkey,
+        sequence_number: u64,
+        first_leaf_index: u64,
+    ) -> TransactionIndexerEvent {
+        TransactionIndexerEvent::V1(TransactionIndexerEventV1 {
+            transaction_merkle_tree,
+            event_merkle_tree_index: 0,
+            sequence_number,
+            verifier: Pubkey::default(),
+            kind: TransactionKind::Transfer,
+            leaves: vec![[sequence_number as u8; 32]; 2],
+            public_amount_spl: [0u8; 32],
+            public_amount_sol: [0u8; 32],
+            relayer_fee: 0,
+            encrypted_utxos: vec![vec![0u8; 120]; 2],
+            nullifiers: vec![[0u8; 32]; 2],
+            first_leaf_index,
+            message: vec![],
+        })
+    }
+
+    #[test]
+    fn test_process_events() {
+        let pubkey = Pubkey::new_unique();
+        let mut indexer = Indexer::default();
+        assert!(matches!(
+            indexer.process_event(&event(pubkey, 1, 0)),
+            Err(IndexerError::UnknownMerkleTree(_))
+        ));
+        indexer
+            .register_transaction_merkle_tree(pubkey, MERKLE_TREE_MIN_HEIGHT)
+            .unwrap();
+
+        assert!(indexer.process_event(&event(pubkey, 1, 0)).unwrap());
+        assert!(!indexer.process_event(&event(pubkey, 1, 0)).unwrap());
+        assert!(matches!(
+            indexer.process_event(&event(pubkey, 3, 4)),
+            Err(IndexerError::MissedEvents {
+                expected: 2,
+                got: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            indexer.process_event(&event(pubkey, 2, 4)),
+            Err(IndexerError::InvalidFirstLeafIndex {
+                expected: 2,
+                got: 4
+            })
+        ));
+        assert!(indexer.process_event(&event(pubkey, 2, 2)).unwrap());
+
+        // No root was inserted on-chain yet.
+        assert!(indexer.transaction_merkle_proof(&pubkey, 0).is_err());
+        let merkle_tree = &indexer
+            .transaction_merkle_tree(&pubkey)
+            .unwrap()
+            .merkle_tree;
+        let root = merkle_tree.root_at(2).unwrap();
+        assert!(matches!(
+            indexer.process_root_update(&RootUpdateEvent {
+                merkle_tree: pubkey,
+                root: [0u8; 32],
+                root_index: 1,
+                sequence_number: 1,
+                start_index: 0,
+                end_index: 2,
+            }),
+            Err(IndexerError::RootMismatch { num_leaves: 2 })
+        ));
+        indexer
+            .process_root_update(&RootUpdateEvent {
+                merkle_tree: pubkey,
+                root,
+                root_index: 1,
+                sequence_number: 1,
+                start_index: 0,
+                end_index: 2,
+            })
+            .unwrap();
+        let proof = indexer.transaction_merkle_proof(&pubkey, 1).unwrap();
+        assert_eq!(proof.root_index, 1);
+        assert_eq!(proof.proof.root, root);
+        assert!(indexer.transaction_merkle_proof(&pubkey, 2).is_err());
+
+        assert!(indexer
+            .insert_event_leaves(0, 0, [1u8; 32], [0u8; 32])
+            .unwrap());
+        assert_eq!(
+            indexer.event_merkle_proof(0, 0).unwrap().path.len(),
+            EVENT_MERKLE_TREE_HEIGHT
+        );
+
+        let path = std::env::temp_dir().join(format!("light-indexer-{}", pubkey));
+        indexer.save(&path).unwrap();
+        assert_eq!(Indexer::load(&path).unwrap(), indexer);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Indexer::load(&path).unwrap(), Indexer::default());
+    }
+
+    #[test]
+    fn test_process_unversioned_events() {
+        let pubkey = Pubkey::new_unique();
+        let mut indexer = Indexer::default();
+        indexer
+            .register_transaction_merkle_tree(pubkey, MERKLE_TREE_MIN_HEIGHT)
+            .unwrap();
+        let event = |first_leaf_index: u64| TransactionIndexerEventV0 {
+            leaves: vec![[first_leaf_index as u8 + 1; 32]; 2],
+            public_amount_spl: [0u8; 32],
+            public_amount_sol: [0u8; 32],
+            relayer_fee: 0,
+            encrypted_utxos: vec![0u8; 240],
+            nullifiers: vec![[0u8; 32]; 2],
+            first_leaf_index,
+            message: vec![],
+        };
+
+        assert!(indexer
+            .process_unversioned_event(&pubkey, &event(0))
+            .unwrap());
+        assert!(!indexer
+            .process_unversioned_event(&pubkey, &event(0))
+            .unwrap());
+        assert!(matches!(
+            indexer.process_unversioned_event(&pubkey, &event(4)),
+            Err(IndexerError::InvalidFirstLeafIndex {
+                expected: 2,
+                got: 4
+            })
+        ));
+        assert!(indexer
+            .process_unversioned_event(&pubkey, &event(2))
+            .unwrap());
+
+        // An event at indexed leaf indices with other leaves conflicts.
+        let mut conflicting_event = event(2);
+        conflicting_event.leaves[1] = [0u8; 32];
+        assert!(matches!(
+            indexer.process_unversioned_event(&pubkey, &conflicting_event),
+            Err(IndexerError::ConflictingLeaves {
+                first_leaf_index: 2
+            })
+        ));
+        assert!(matches!(
+            indexer.process_unversioned_event(&pubkey, &event(3)),
+            Err(IndexerError::ConflictingLeaves {
+                first_leaf_index: 3
+            })
+        ));
+        assert_eq!(
+            indexer
+                .transaction_merkle_tree(&pubkey)
+                .unwrap()
+                .merkle_tree
+                .num_leaves(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_register_transaction_merkle_tree() {
+        let mut indexer = Indexer::default();
+        for height in [MERKLE_TREE_MIN_HEIGHT - 1, MERKLE_TREE_MAX_HEIGHT + 1] {
+            assert!(matches!(
+                indexer.register_transaction_merkle_tree(Pubkey::new_unique(), height),
+                Err(IndexerError::InvalidMerkleTreeHeight(h)) if h == height
+            ));
+        }
+        let pubkey = Pubkey::new_unique();
+        indexer
+            .register_transaction_merkle_tree(pubkey, MERKLE_TREE_MAX_HEIGHT)
+            .unwrap();
+        assert!(matches!(
+            indexer.register_transaction_merkle_tree(pubkey, MERKLE_TREE_MAX_HEIGHT),
+            Err(IndexerError::MerkleTreeAlreadyRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn test_insert_event_leaves() {
+        let mut indexer = Indexer::default();
+        assert!(indexer
+            .insert_event_leaves(0, 0, [1u8; 32], [2u8; 32])
+            .unwrap());
+        assert!(!indexer
+            .insert_event_leaves(0, 0, [1u8; 32], [2u8; 32])
+            .unwrap());
+        assert!(matches!(
+            indexer.insert_event_leaves(0, 4, [3u8; 32], [4u8; 32]),
+            Err(IndexerError::InvalidFirstLeafIndex {
+                expected: 2,
+                got: 4
+            })
+        ));
+        assert!(matches!(
+            indexer.insert_event_leaves(0, 0, [1u8; 32], [0u8; 32]),
+            Err(IndexerError::ConflictingLeaves {
+                first_leaf_index: 0
+            })
+        ));
+        assert!(indexer
+            .insert_event_leaves(0, 2, [3u8; 32], [4u8; 32])
+            .unwrap());
+        // Event Merkle trees are indexed independently.
+        assert!(indexer
+            .insert_event_leaves(1, 0, [3u8; 32], [4u8; 32])
+            .unwrap());
+        assert_eq!(indexer.event_merkle_trees[&0].num_leaves(), 4);
+        assert_eq!(indexer.event_merkle_trees[&1].num_leaves(), 2);
+    }
+
+    #[test]
+    fn test_check_event_merkle_tree() {
+        let mut account: EventMerkleTree = bytemuck::Zeroable::zeroed();
+        account.merkle_tree_nr = 1;
+        account
+            .merkle_tree
+            .init(EVENT_MERKLE_TREE_HEIGHT, HashFunction::Sha256);
+        let mut indexer = Indexer::default();
+        indexer.event_merkle_trees.insert(
+            1,
+            MerkleTree::new(EVENT_MERKLE_TREE_HEIGHT as u64, TreeHash::Sha256),
+        );
+        indexer.check_event_merkle_tree(&account).unwrap();
+
+        for i in 0..5u8 {
+            let (leaf_left, leaf_right) = ([2 * i + 1; 32], [2 * i + 2; 32]);
+            assert!(indexer
+                .insert_event_leaves(1, account.merkle_tree.next_index, leaf_left, leaf_right)
+                .unwrap());
+            account.merkle_tree.insert(leaf_left, leaf_right);
+            indexer.check_event_merkle_tree(&account).unwrap();
+        }
+
+        account.merkle_tree.insert([11u8; 32], [12u8; 32]);
+        assert!(matches!(
+            indexer.check_event_merkle_tree(&account),
+            Err(IndexerError::NotSynced {
+                expected: 12,
+                indexed: 10
+            })
+        ));
+        assert!(indexer
+            .insert_event_leaves(1, 10, [11u8; 32], [0u8; 32])
+            .unwrap());
+        assert!(matches!(
+            indexer.check_event_merkle_tree(&account),
+            Err(IndexerError::RootMismatch { num_leaves: 12 })
+        ));
+    }
+
+    #[test]
+    fn test_decode_insert_two_leaves_event() {
+        let mut data = hash(b"global:insert_two_leaves_event").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&[1u8; 32]);
+        data.extend_from_slice(&[2u8; 32]);
+        assert_eq!(
+            decode_insert_two_leaves_event(&data),
+            Some(([1u8; 32], [2u8; 32]))
+        );
+        assert_eq!(decode_insert_two_leaves_event(&data[..71]), None);
+        data[0] ^= 1;
+        assert_eq!(decode_insert_two_leaves_event(&data), None);
+    }
+}
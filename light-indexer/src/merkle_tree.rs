@@ -0,0 +1,237 @@
+use anchor_lang::solana_program::hash::hashv;
+use borsh::{BorshDeserialize, BorshSerialize};
+use light_merkle_tree::constants::sha256::ZERO_BYTES;
+use merkle_tree_program::{
+    transaction_merkle_tree::poseidon_native::poseidon_native,
+    utils::config::ZERO_BYTES_MERKLE_TREE,
+};
+
+use crate::errors::{IndexerError, Result};
+
+/// Hash function of a Merkle tree.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TreeHash {
+    /// Transaction Merkle trees, nodes are little endian field elements.
+    Poseidon,
+    /// Event Merkle trees.
+    Sha256,
+}
+
+impl TreeHash {
+    pub fn hash(&self, left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+        match self {
+            TreeHash::Poseidon => {
+                poseidon_native(left, right).map_err(|_| IndexerError::PoseidonHashFailed)
+            }
+            TreeHash::Sha256 => Ok(hashv(&[left, right]).to_bytes()),
+        }
+    }
+
+    /// Root of an empty subtree of height `level`.
+    pub fn zero_bytes(&self, level: usize) -> [u8; 32] {
+        match self {
+            TreeHash::Poseidon => ZERO_BYTES_MERKLE_TREE[level],
+            TreeHash::Sha256 => ZERO_BYTES[level],
+        }
+    }
+}
+
+/// Inclusion proof of a leaf against a root of the Merkle tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MerkleProof {
+    pub leaf_index: u64,
+    pub root: [u8; 32],
+    /// Siblings from the leaf up to the root, with whether the sibling is the
+    /// left node. Same order as `convert_path_to_circuit_ready_merkle_proof`.
+    pub path: Vec<(bool, [u8; 32])>,
+}
+
+impl MerkleProof {
+    /// Path elements as the circuits expect them, the path indices are the bits
+    /// of `leaf_index`.
+    pub fn path_elements(&self) -> Vec<[u8; 32]> {
+        self.path.iter().map(|(_, node)| *node).collect()
+    }
+}
+
+/// Append only Merkle tree which keeps all nodes to serve proofs against the
+/// root of any number of leaves.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Eq, PartialEq)]
+pub struct MerkleTree {
+    pub height: u64,
+    pub hash: TreeHash,
+    /// Nodes of every level, leaves first. A node which covers leaves which
+    /// are not appended yet is computed with empty subtrees.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(height: u64, hash: TreeHash) -> Self {
+        MerkleTree {
+            height,
+            hash,
+            layers: vec![Vec::new(); height as usize + 1],
+        }
+    }
+
+    pub fn num_leaves(&self) -> u64 {
+        self.layers[0].len() as u64
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.layers[0]
+    }
+
+    pub fn capacity(&self) -> u64 {
+        1 << self.height
+    }
+
+    /// Appends leaves and updates the nodes above them.
+    pub fn append(&mut self, leaves: &[[u8; 32]]) -> Result<()> {
+        if self.num_leaves() + leaves.len() as u64 > self.capacity() {
+            return Err(IndexerError::MerkleTreeFull);
+        }
+        for leaf in leaves {
+            let mut index = self.layers[0].len();
+            self.layers[0].push(*leaf);
+            let mut node = *leaf;
+            for level in 0..self.height as usize {
+                node = if index % 2 == 0 {
+                    self.hash.hash(&node, &self.hash.zero_bytes(level))?
+                } else {
+                    self.hash.hash(&self.layers[level][index - 1], &node)?
+                };
+                index /= 2;
+                let layer = &mut self.layers[level + 1];
+                if index == layer.len() {
+                    layer.push(node);
+                } else {
+                    layer[index] = node;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Root of the tree with all appended leaves.
+    pub fn root(&self) -> Result<[u8; 32]> {
+        self.root_at(self.num_leaves())
+    }
+
+    /// Root of the tree with its first `num_leaves` leaves, on-chain trees can
+    /// lag behind the queued leaves.
+    pub fn root_at(&self, num_leaves: u64) -> Result<[u8; 32]> {
+        self.node(self.height as usize, 0, num_leaves)
+    }
+
+    /// Node at `level` and `index` of the tree with its first `num_leaves` leaves.
+    fn node(&self, level: usize, index: u64, num_leaves: u64) -> Result<[u8; 32]> {
+        if index << level >= num_leaves {
+            return Ok(self.hash.zero_bytes(level));
+        }
+        if (index + 1) << level <= num_leaves {
+            return Ok(self.layers[level][index as usize]);
+        }
+        self.hash.hash(
+            &self.node(level - 1, 2 * index, num_leaves)?,
+            &self.node(level - 1, 2 * index + 1, num_leaves)?,
+        )
+    }
+
+    /// Proof of the leaf at `leaf_index` against the root of the tree with its
+    /// first `num_leaves` leaves.
+    pub fn proof(&self, leaf_index: u64, num_leaves: u64) -> Result<MerkleProof> {
+        if leaf_index >= num_leaves || num_leaves > self.num_leaves() {
+            return Err(IndexerError::LeafNotInserted(leaf_index));
+        }
+        let mut path = Vec::with_capacity(self.height as usize);
+        let mut index = leaf_index;
+        for level in 0..self.height as usize {
+            let sibling_is_left = index % 2 == 1;
+            path.push((sibling_is_left, self.node(level, index ^ 1, num_leaves)?));
+            index /= 2;
+        }
+        Ok(MerkleProof {
+            leaf_index,
+            root: self.root_at(num_leaves)?,
+            path,
+        })
+    }
+
+    /// Recomputes the root from a proof.
+    pub fn verify(&self, leaf: &[u8; 32], proof: &MerkleProof) -> Result<bool> {
+        let mut node = *leaf;
+        for (sibling_is_left, sibling) in proof.path.iter() {
+            node = if *sibling_is_left {
+                self.hash.hash(sibling, &node)?
+            } else {
+                self.hash.hash(&node, sibling)?
+            };
+        }
+        Ok(node == proof.root)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use merkle_tree_program::transaction_merkle_tree::{
+        instructions::insert_leaves_batch, state::TransactionMerkleTree,
+    };
+
+    fn leaf(i: u8) -> [u8; 32] {
+        let mut leaf = [0u8; 32];
+        leaf[0] = i;
+        leaf
+    }
+
+    #[test]
+    fn test_roots_match_transaction_merkle_tree() {
+        let mut merkle_tree_account: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree_account.height = 4;
+        merkle_tree_account.filled_subtrees[..4].copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..4]);
+        let mut merkle_tree = MerkleTree::new(4, TreeHash::Poseidon);
+        assert_eq!(merkle_tree.root().unwrap(), ZERO_BYTES_MERKLE_TREE[4]);
+
+        let mut roots = vec![];
+        for i in 0..5u8 {
+            let pair = [leaf(2 * i + 1), leaf(2 * i + 2)];
+            insert_leaves_batch(&mut merkle_tree_account, &[pair]).unwrap();
+            merkle_tree.append(&pair).unwrap();
+            let root = merkle_tree_account.roots[merkle_tree_account.current_root_index as usize];
+            assert_eq!(merkle_tree.root().unwrap(), root);
+            roots.push(root);
+        }
+        // Roots of earlier states.
+        for (i, root) in roots.iter().enumerate() {
+            assert_eq!(merkle_tree.root_at(2 * i as u64 + 2).unwrap(), *root);
+        }
+        assert!(matches!(
+            merkle_tree.append(&[leaf(0); 8]),
+            Err(IndexerError::MerkleTreeFull)
+        ));
+    }
+
+    #[test]
+    fn test_proof() {
+        let mut merkle_tree = MerkleTree::new(3, TreeHash::Sha256);
+        let leaves: Vec<[u8; 32]> = (1..7).map(leaf).collect();
+        merkle_tree.append(&leaves).unwrap();
+
+        for num_leaves in 1..=6 {
+            for leaf_index in 0..num_leaves {
+                let proof = merkle_tree.proof(leaf_index, num_leaves).unwrap();
+                assert_eq!(proof.root, merkle_tree.root_at(num_leaves).unwrap());
+                assert_eq!(proof.path.len(), 3);
+                assert!(merkle_tree
+                    .verify(&leaves[leaf_index as usize], &proof)
+                    .unwrap());
+                assert!(!merkle_tree.verify(&leaf(9), &proof).unwrap());
+            }
+        }
+        let proof = merkle_tree.proof(5, 6).unwrap();
+        assert_eq!(proof.path[0], (true, leaves[4]));
+        assert_eq!(proof.path[1], (false, ZERO_BYTES[1]));
+        assert!(merkle_tree.proof(6, 6).is_err());
+    }
+}
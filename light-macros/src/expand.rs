@@ -1,6 +1,6 @@
 use bs58::decode;
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parse::Parse, parse_quote, punctuated::Punctuated, token::Brace, ConstParam, Error, Expr,
     Field, Fields, FieldsNamed, GenericParam, ItemStruct, LifetimeDef, LitStr, Result, Token,
@@ -135,7 +135,7 @@ pub(crate) fn light_verifier_accounts(
     let (spl_fields, spl_getters) = if args.spl {
         (
             quote! {
-                pub token_program: Program<'info, ::anchor_spl::token::Token>,
+                pub token_program: Interface<'info, ::anchor_spl::token_interface::TokenInterface>,
                 /// CHECK: Is checked when it is used during spl withdrawals.
                 #[account(
                     mut,
@@ -149,11 +149,17 @@ pub(crate) fn light_verifier_accounts(
                 /// CHECK: Is checked depending on deposit or withdrawal.
                 #[account(mut)]
                 pub recipient_spl: UncheckedAccount<'info>,
+                /// CHECK: Config of the spl pool, is checked when it is used for
+                /// deposit caps and withdrawal limits.
+                #[account(mut)]
+                pub registered_asset_pool_spl: UncheckedAccount<'info>,
+                /// CHECK: Is checked against the mint of the pool token account when it is used.
+                pub mint_spl: UncheckedAccount<'info>,
             },
             quote! {
-                fn get_token_program(&self) -> Option<&Program<
+                fn get_token_program(&self) -> Option<&Interface<
                     'info,
-                    ::anchor_spl::token::Token
+                    ::anchor_spl::token_interface::TokenInterface
                 >> {
                     Some(&self.token_program)
                 }
@@ -169,15 +175,23 @@ pub(crate) fn light_verifier_accounts(
                 fn get_recipient_spl(&self) -> Option<&UncheckedAccount<'info>> {
                     Some(&self.recipient_spl)
                 }
+
+                fn get_registered_asset_pool_spl(&self) -> Option<&UncheckedAccount<'info>> {
+                    Some(&self.registered_asset_pool_spl)
+                }
+
+                fn get_mint_spl(&self) -> Option<&UncheckedAccount<'info>> {
+                    Some(&self.mint_spl)
+                }
             },
         )
     } else {
         (
             quote! {},
             quote! {
-                fn get_token_program(&self) -> Option<&Program<
+                fn get_token_program(&self) -> Option<&Interface<
                     'info,
-                    ::anchor_spl::token::Token
+                    ::anchor_spl::token_interface::TokenInterface
                 >> {
                     None
                 }
@@ -193,6 +207,14 @@ pub(crate) fn light_verifier_accounts(
                 fn get_recipient_spl(&self) -> Option<&UncheckedAccount<'info>> {
                     None
                 }
+
+                fn get_registered_asset_pool_spl(&self) -> Option<&UncheckedAccount<'info>> {
+                    None
+                }
+
+                fn get_mint_spl(&self) -> Option<&UncheckedAccount<'info>> {
+                    None
+                }
             },
         )
     };
@@ -270,6 +292,16 @@ pub(crate) fn light_verifier_accounts(
             pub log_wrapper: UncheckedAccount<'info>,
             #[account(mut)]
             pub event_merkle_tree: AccountLoader<'info, ::merkle_tree_program::event_merkle_tree::EventMerkleTree>,
+            /// Holds the pause state which is checked before any transaction.
+            #[account(
+                seeds = [::merkle_tree_program::utils::constants::MERKLE_TREE_AUTHORITY_SEED],
+                bump,
+                seeds::program = MerkleTreeProgram::id()
+            )]
+            pub merkle_tree_authority_pda: Account<
+                'info,
+                ::merkle_tree_program::config_accounts::merkle_tree_authority::MerkleTreeAuthority
+            >,
         }
     };
 
@@ -398,6 +430,190 @@ pub(crate) fn light_verifier_accounts(
             > {
                 &self.event_merkle_tree
             }
+
+            fn get_merkle_tree_authority_pda(&self) -> &Account<
+                'info,
+                ::merkle_tree_program::config_accounts::merkle_tree_authority::MerkleTreeAuthority
+            > {
+                &self.merkle_tree_authority_pda
+            }
+        }
+    })
+}
+
+pub(crate) struct LightVerifierStateArgs {
+    nullifiers: Expr,
+    leaves: Expr,
+    checked_inputs: Expr,
+    encrypted_utxos: Option<Expr>,
+}
+
+impl Parse for LightVerifierStateArgs {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let mut nullifiers = None;
+        let mut leaves = None;
+        let mut checked_inputs = None;
+        let mut encrypted_utxos = None;
+
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            let _eq_token: syn::Token![=] = input.parse()?;
+            let expr: Expr = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "nullifiers" => nullifiers = Some(expr),
+                "leaves" => leaves = Some(expr),
+                "checked_inputs" => checked_inputs = Some(expr),
+                "encrypted_utxos" => encrypted_utxos = Some(expr),
+                _ => return Err(Error::new(ident.span(), "Unexpected identifier")),
+            }
+
+            if input.peek(syn::token::Comma) {
+                let _ = input.parse::<syn::token::Comma>();
+            } else {
+                break;
+            }
+        }
+
+        Ok(Self {
+            nullifiers: nullifiers.ok_or_else(|| input.error("Expected `nullifiers = <n>`"))?,
+            leaves: leaves.ok_or_else(|| input.error("Expected `leaves = <n>`"))?,
+            checked_inputs: checked_inputs.unwrap_or_else(|| parse_quote! { 0 }),
+            encrypted_utxos,
+        })
+    }
+}
+
+pub(crate) fn light_verifier_state(
+    args: LightVerifierStateArgs,
+    strct: ItemStruct,
+) -> Result<TokenStream> {
+    let LightVerifierStateArgs {
+        nullifiers,
+        leaves,
+        checked_inputs,
+        encrypted_utxos,
+    } = args;
+    // Every leaf comes with the encrypted utxo of the verifier using the state.
+    let encrypted_utxos =
+        encrypted_utxos.unwrap_or_else(|| parse_quote! { (#leaves) * crate::ENCRYPTED_UTXO_LEN });
+
+    // Common fields are the prefix of every verifier state, system verifiers
+    // deserialize them from the state of the invoking program.
+    let common_fields_strct: ItemStruct = parse_quote! {
+        pub struct CommonFields {
+            pub signer: Pubkey,
+            pub nullifiers: Vec<[u8; 32]>,
+            pub leaves: Vec<[u8; 32]>,
+            pub public_amount_spl: [u8; 32],
+            pub public_amount_sol: [u8; 32],
+            pub mint_pubkey: [u8; 32],
+            pub merkle_root: [u8; 32],
+            pub tx_integrity_hash: [u8; 32],
+            pub relayer_fee: u64,
+            pub encrypted_utxos: Vec<u8>,
+            pub merkle_root_index: u64,
+            pub checked_public_inputs: [[u8; 32]; #checked_inputs],
+            pub proof_a: [u8; 64],
+            pub proof_b: [u8; 128],
+            pub proof_c: [u8; 64],
+            pub transaction_hash: [u8; 32],
+        }
+    };
+
+    let ident = strct.ident.clone();
+    let (impl_generics, ty_generics, where_clause) = strct.generics.split_for_impl();
+
+    // App fields are sized with `InitSpace`, which also reads their `max_len` attributes.
+    let app_fields_ident = format_ident!("{}AppFields", ident);
+    let app_fields_strct = ItemStruct {
+        attrs: Vec::new(),
+        vis: syn::Visibility::Inherited,
+        ident: app_fields_ident.clone(),
+        fields: Fields::Named(FieldsNamed {
+            brace_token: Brace {
+                span: Span::call_site(),
+            },
+            named: strct.fields.iter().cloned().collect(),
+        }),
+        ..strct.clone()
+    };
+
+    let mut fields = Punctuated::new();
+    for field in common_fields_strct.fields.iter().chain(strct.fields.iter()) {
+        let mut field = field.clone();
+        field.attrs.retain(|attr| !attr.path.is_ident("max_len"));
+        fields.push(field);
+    }
+    let fields = Fields::Named(FieldsNamed {
+        brace_token: Brace {
+            span: Span::call_site(),
+        },
+        named: fields,
+    });
+
+    let strct = ItemStruct {
+        fields,
+        ..strct.clone()
+    };
+
+    Ok(quote! {
+        #[account]
+        #strct
+
+        #[allow(dead_code)]
+        #[derive(InitSpace)]
+        #app_fields_strct
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            pub const NR_NULLIFIERS: usize = #nullifiers;
+            pub const NR_LEAVES: usize = #leaves;
+            pub const NR_CHECKED_INPUTS: usize = #checked_inputs;
+            pub const ENCRYPTED_UTXOS_LEN: usize = #encrypted_utxos;
+            /// Size of the account including the discriminator. App fields of
+            /// variable size need a `max_len` attribute.
+            pub const LEN: usize = 8
+                + 32
+                + 4 + 32 * Self::NR_NULLIFIERS
+                + 4 + 32 * Self::NR_LEAVES
+                + 5 * 32
+                + 8
+                + 4 + Self::ENCRYPTED_UTXOS_LEN
+                + 8
+                + 32 * Self::NR_CHECKED_INPUTS
+                + 64 + 128 + 64
+                + 32
+                + <#app_fields_ident #ty_generics as ::anchor_lang::Space>::INIT_SPACE;
+
+            /// Checks that the state fits into `LEN` bytes and has the number
+            /// of nullifiers and leaves system verifiers expect.
+            pub fn check_layout(&self) -> Result<()> {
+                if self.nullifiers.len() != Self::NR_NULLIFIERS {
+                    msg!(
+                        "Verifier state has {} nullifiers, expected {}",
+                        self.nullifiers.len(),
+                        Self::NR_NULLIFIERS
+                    );
+                    return err!(::light_verifier_sdk::errors::VerifierSdkError::InvalidNrNullifiers);
+                }
+                if self.leaves.len() != Self::NR_LEAVES {
+                    msg!(
+                        "Verifier state has {} leaves, expected {}",
+                        self.leaves.len(),
+                        Self::NR_LEAVES
+                    );
+                    return err!(::light_verifier_sdk::errors::VerifierSdkError::InvalidNrLeaves);
+                }
+                if self.encrypted_utxos.len() > Self::ENCRYPTED_UTXOS_LEN {
+                    msg!(
+                        "Verifier state has {} bytes of encrypted utxos, expected at most {}",
+                        self.encrypted_utxos.len(),
+                        Self::ENCRYPTED_UTXOS_LEN
+                    );
+                    return err!(::light_verifier_sdk::errors::VerifierSdkError::InvalidUtxoSize);
+                }
+                Ok(())
+            }
         }
     })
 }
@@ -483,4 +699,54 @@ mod tests {
         assert!(res_verifier_program_id
             .contains("seeds = [VerifierProgramTwo :: id () . to_bytes () . as_ref ()]"))
     }
+
+    #[test]
+    fn test_light_verifier_state() {
+        let strct: ItemStruct = parse_quote! {
+            pub struct VerifierState {
+                pub app_input: [u8; 32],
+            }
+        };
+
+        let res = light_verifier_state(parse_quote! { nullifiers = 4, leaves = 4 }, strct.clone())
+            .expect("Failed to expand light_verifier_state")
+            .to_string();
+
+        assert!(res.starts_with("# [account] pub struct VerifierState"));
+        assert!(res.contains("pub signer : Pubkey , pub nullifiers : Vec < [u8 ; 32] >"));
+        assert!(res.contains("pub transaction_hash : [u8 ; 32] , pub app_input : [u8 ; 32]"));
+        assert!(res.contains("pub checked_public_inputs : [[u8 ; 32] ; 0]"));
+        assert!(res.contains(
+            "pub const ENCRYPTED_UTXOS_LEN : usize = (4) * crate :: ENCRYPTED_UTXO_LEN ;"
+        ));
+        assert!(res.contains("# [derive (InitSpace)] struct VerifierStateAppFields"));
+        assert!(
+            res.contains("+ < VerifierStateAppFields as :: anchor_lang :: Space > :: INIT_SPACE")
+        );
+
+        let strct_vec: ItemStruct = parse_quote! {
+            pub struct VerifierState {
+                #[max_len(4)]
+                pub app_inputs: Vec<u64>,
+            }
+        };
+        let res = light_verifier_state(parse_quote! { nullifiers = 4, leaves = 4 }, strct_vec)
+            .expect("Failed to expand light_verifier_state")
+            .to_string();
+        // max_len is only kept for the size computation
+        assert!(res.contains("pub transaction_hash : [u8 ; 32] , pub app_inputs : Vec < u64 > }"));
+        assert!(res.contains("struct VerifierStateAppFields { # [max_len (4)] pub app_inputs"));
+
+        let res = light_verifier_state(
+            parse_quote! { nullifiers = 2, leaves = 2, checked_inputs = 2, encrypted_utxos = 512 },
+            strct.clone(),
+        )
+        .expect("Failed to expand light_verifier_state")
+        .to_string();
+
+        assert!(res.contains("pub checked_public_inputs : [[u8 ; 32] ; 2]"));
+        assert!(res.contains("pub const ENCRYPTED_UTXOS_LEN : usize = 512 ;"));
+
+        assert!(syn::parse2::<LightVerifierStateArgs>(quote! { leaves = 2 }).is_err());
+    }
 }
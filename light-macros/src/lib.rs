@@ -22,3 +22,29 @@ pub fn light_verifier_accounts(attr: TokenStream, item: TokenStream) -> TokenStr
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+/// Generates a verifier state account with the fields system verifiers expect,
+/// followed by the fields of the annotated struct.
+///
+/// Example:
+/// ```ignore
+/// #[light_verifier_state(nullifiers = 4, leaves = 4, checked_inputs = 2)]
+/// pub struct VerifierState {
+///     pub app_input: [u8; 32],
+/// }
+/// ```
+///
+/// `encrypted_utxos` defaults to `ENCRYPTED_UTXO_LEN` bytes per leaf, the
+/// constant has to be defined at the crate root. The generated `LEN` is the
+/// account size, app fields are sized with `InitSpace` and fields of variable
+/// size need a `max_len` attribute. `check_layout` checks the number of
+/// nullifiers and leaves before a system verifier is invoked.
+#[proc_macro_attribute]
+pub fn light_verifier_state(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as expand::LightVerifierStateArgs);
+    let strct = parse_macro_input!(item as ItemStruct);
+
+    expand::light_verifier_state(args, strct)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
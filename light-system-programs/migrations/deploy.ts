@@ -8,9 +8,15 @@ import { PublicKey } from "@solana/web3.js";
 
 module.exports = async function (provider) {
   anchor.setProvider(provider);
-  const newAuthority = new PublicKey(
-    "CLEuMG7pzJX9xAuKCFzBP154uiG1GaNo4Fq7x6KAcAfG",
-  );
+  const newAuthority = (provider.wallet as anchor.Wallet).payer;
+  // the new authority signs to accept the authority transfer
+  if (
+    !newAuthority.publicKey.equals(
+      new PublicKey("CLEuMG7pzJX9xAuKCFzBP154uiG1GaNo4Fq7x6KAcAfG"),
+    )
+  ) {
+    throw new Error("Provider wallet is not the new merkle tree authority");
+  }
   await createTestAccounts(provider.connection);
   await setUpMerkleTree(provider, newAuthority);
   process.exit();
@@ -1,15 +1,162 @@
-use crate::utils::constants::{POOL_CONFIG_SEED, POOL_SEED, POOL_TYPE_SEED, TOKEN_AUTHORITY_SEED};
+use crate::errors::ErrorCode;
+use crate::utils::config::{DELAYED_WITHDRAWAL_TIMELOCK, MAX_DELAYED_WITHDRAWALS};
+use crate::utils::constants::{
+    MERKLE_TREE_AUTHORITY_SEED, POOL_CONFIG_SEED, POOL_SEED, POOL_TYPE_SEED, TOKEN_AUTHORITY_SEED,
+};
 use crate::MerkleTreeAuthority;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-/// Nullfier pdas are derived from the nullifier
-/// existence of a nullifier is the check to prevent double spends.
+/// Withdrawal which exceeded the withdrawal limit of its pool,
+/// it can be claimed once release_slot is reached.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct DelayedWithdrawal {
+    /// Recipient sol account or recipient token account.
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub release_slot: u64,
+}
+
+/// Config of a sol or spl token liquidity pool.
+/// Deposits are capped by deposit_cap, withdrawals are rate limited to withdrawal_limit
+/// per withdrawal_window slots. Withdrawals over the limit are queued in delayed_withdrawals
+/// if they are at least min_delayed_withdrawal, claims of delayed withdrawals count against
+/// the limit as well.
 #[account]
 pub struct RegisteredAssetPool {
     pub asset_pool_pubkey: Pubkey,
     pub pool_type: [u8; 32],
     pub index: u64,
+    /// Maximum pool balance after a deposit, u64::MAX if uncapped.
+    pub deposit_cap: u64,
+    /// Maximum amount withdrawn per window, u64::MAX if unlimited.
+    pub withdrawal_limit: u64,
+    pub withdrawal_window: u64,
+    pub window_start_slot: u64,
+    pub withdrawn_in_window: u64,
+    /// Minimum amount of a delayed withdrawal, smaller withdrawals over the limit fail
+    /// such that dust withdrawals cannot fill the queue.
+    pub min_delayed_withdrawal: u64,
+    pub delayed_withdrawals: Vec<DelayedWithdrawal>,
+}
+
+impl RegisteredAssetPool {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 6 * 8 + 4 + MAX_DELAYED_WITHDRAWALS * (32 + 8 + 8);
+
+    pub fn check_deposit(&self, balance_after_deposit: u64) -> Result<()> {
+        if balance_after_deposit > self.deposit_cap {
+            msg!(
+                "Pool balance {} would exceed deposit cap {}.",
+                balance_after_deposit,
+                self.deposit_cap
+            );
+            return err!(ErrorCode::DepositCapExceeded);
+        }
+        Ok(())
+    }
+
+    /// Accounts the withdrawal to the current window.
+    /// Returns false without accounting it if it exceeds the withdrawal limit.
+    pub fn track_withdrawal(&mut self, amount: u64, slot: u64) -> bool {
+        self.update_window(slot);
+        match self.withdrawn_in_window.checked_add(amount) {
+            Some(withdrawn) if withdrawn <= self.withdrawal_limit => {
+                self.withdrawn_in_window = withdrawn;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Accounts a claim of delayed withdrawals to the current window. A claim over the
+    /// withdrawal limit is only accepted in a window without other withdrawals.
+    pub fn track_claim(&mut self, amount: u64, slot: u64) -> Result<()> {
+        self.update_window(slot);
+        if self.withdrawn_in_window == 0 {
+            self.withdrawn_in_window = amount;
+        } else if !self.track_withdrawal(amount, slot) {
+            msg!(
+                "Claim of {} exceeds the withdrawal limit of the current window.",
+                amount
+            );
+            return err!(ErrorCode::WithdrawalLimitExceeded);
+        }
+        Ok(())
+    }
+
+    fn update_window(&mut self, slot: u64) {
+        if slot
+            >= self
+                .window_start_slot
+                .saturating_add(self.withdrawal_window)
+        {
+            self.window_start_slot = slot;
+            self.withdrawn_in_window = 0;
+        }
+    }
+
+    pub fn queue_withdrawal(&mut self, recipient: Pubkey, amount: u64, slot: u64) -> Result<()> {
+        if amount < self.min_delayed_withdrawal {
+            msg!(
+                "Withdrawal of {} exceeds the withdrawal limit and is below the minimum delayed withdrawal {}.",
+                amount,
+                self.min_delayed_withdrawal
+            );
+            return err!(ErrorCode::DelayedWithdrawalTooSmall);
+        }
+        if self.delayed_withdrawals.len() >= MAX_DELAYED_WITHDRAWALS {
+            return err!(ErrorCode::DelayedWithdrawalQueueFull);
+        }
+        msg!(
+            "Withdrawal of {} exceeds the withdrawal limit, it is delayed.",
+            amount
+        );
+        self.delayed_withdrawals.push(DelayedWithdrawal {
+            recipient,
+            amount,
+            release_slot: slot + DELAYED_WITHDRAWAL_TIMELOCK,
+        });
+        Ok(())
+    }
+
+    /// Removes all released withdrawals of recipient and returns their total amount.
+    pub fn take_released_withdrawals(&mut self, recipient: &Pubkey, slot: u64) -> Result<u64> {
+        let mut amount: u64 = 0;
+        let mut i = 0;
+        while i < self.delayed_withdrawals.len() {
+            let withdrawal = &self.delayed_withdrawals[i];
+            if withdrawal.recipient == *recipient && withdrawal.release_slot <= slot {
+                amount = amount
+                    .checked_add(self.delayed_withdrawals.remove(i).amount)
+                    .ok_or(ProgramError::InvalidAccountData)?;
+            } else {
+                i += 1;
+            }
+        }
+        if amount == 0 {
+            return err!(ErrorCode::NoReleasedWithdrawal);
+        }
+        Ok(amount)
+    }
+
+    /// Removes all delayed withdrawals of recipient, released or not, and returns their total amount.
+    /// The amount remains in the pool.
+    pub fn cancel_withdrawals(&mut self, recipient: &Pubkey) -> Result<u64> {
+        let len = self.delayed_withdrawals.len();
+        let mut amount: u64 = 0;
+        self.delayed_withdrawals.retain(|withdrawal| {
+            if withdrawal.recipient == *recipient {
+                amount = amount.saturating_add(withdrawal.amount);
+                false
+            } else {
+                true
+            }
+        });
+        if self.delayed_withdrawals.len() == len {
+            return err!(ErrorCode::NoDelayedWithdrawal);
+        }
+        Ok(amount)
+    }
 }
 
 /// Pool type
@@ -45,7 +192,7 @@ pub struct RegisterSplPool<'info> {
         payer = authority,
         seeds = [&mint.key().to_bytes(), &registered_pool_type_pda.pool_type, POOL_CONFIG_SEED],
         bump,
-        space = 8 + 32 + 32 + 8
+        space = RegisteredAssetPool::LEN
     )]
     pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
     #[account(init,
@@ -58,19 +205,19 @@ pub struct RegisterSplPool<'info> {
               token::mint = mint,
               token::authority = token_authority
     )]
-    pub merkle_tree_pda_token: Account<'info, TokenAccount>,
+    pub merkle_tree_pda_token: InterfaceAccount<'info, TokenAccount>,
     /// CHECK:` Signer is checked according to authority pda in instruction
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
-    /// CHECK:
+    /// Spl token or Token-2022 mint, extensions are checked in instruction.
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
     /// CHECK:
     #[account(mut, seeds=[TOKEN_AUTHORITY_SEED], bump)]
     pub token_authority: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     /// Just needs to exist and be derived correctly.
     #[account(
         seeds = [&registered_pool_type_pda.pool_type[..], POOL_TYPE_SEED],
@@ -89,7 +236,7 @@ pub struct RegisterSolPool<'info> {
         payer = authority,
         seeds = [&[0u8;32], &registered_pool_type_pda.pool_type, POOL_CONFIG_SEED],
         bump,
-        space = 8 + 32 + 32 + 8
+        space = RegisteredAssetPool::LEN
     )]
     pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
     /// CHECK:` Signer is checked according to authority pda in instruction
@@ -106,3 +253,130 @@ pub struct RegisterSolPool<'info> {
     #[account(mut)]
     pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateAssetPoolLimits<'info> {
+    #[account(mut)]
+    pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account(address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+#[derive(Accounts)]
+pub struct CancelDelayedWithdrawal<'info> {
+    #[account(mut)]
+    pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
+    /// CHECK:` Signer is checked according to authority pda in instruction
+    pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn asset_pool() -> RegisteredAssetPool {
+        RegisteredAssetPool {
+            asset_pool_pubkey: Pubkey::new_unique(),
+            pool_type: [0u8; 32],
+            index: 0,
+            deposit_cap: 100,
+            withdrawal_limit: 10,
+            withdrawal_window: 5,
+            window_start_slot: 0,
+            withdrawn_in_window: 0,
+            min_delayed_withdrawal: 20,
+            delayed_withdrawals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_asset_pool_limits() {
+        let mut pool = asset_pool();
+        assert!(pool.check_deposit(100).is_ok());
+        assert_eq!(pool.check_deposit(101), err!(ErrorCode::DepositCapExceeded));
+
+        assert!(pool.track_withdrawal(6, 1));
+        assert!(!pool.track_withdrawal(5, 4));
+        assert!(pool.track_withdrawal(4, 4));
+        assert_eq!(pool.withdrawn_in_window, 10);
+        // new window
+        assert!(pool.track_withdrawal(10, 6));
+        assert_eq!(pool.window_start_slot, 6);
+        assert!(!pool.track_withdrawal(u64::MAX, 6));
+
+        let recipient = Pubkey::new_unique();
+        assert_eq!(
+            pool.queue_withdrawal(recipient, 19, 6),
+            err!(ErrorCode::DelayedWithdrawalTooSmall)
+        );
+        for _ in 0..MAX_DELAYED_WITHDRAWALS {
+            pool.queue_withdrawal(recipient, 20, 6).unwrap();
+        }
+        assert_eq!(
+            pool.queue_withdrawal(recipient, 20, 6),
+            err!(ErrorCode::DelayedWithdrawalQueueFull)
+        );
+        let release_slot = 6 + DELAYED_WITHDRAWAL_TIMELOCK;
+        assert_eq!(
+            pool.take_released_withdrawals(&recipient, release_slot - 1),
+            err!(ErrorCode::NoReleasedWithdrawal)
+        );
+        assert_eq!(
+            pool.take_released_withdrawals(&Pubkey::new_unique(), release_slot),
+            err!(ErrorCode::NoReleasedWithdrawal)
+        );
+        assert_eq!(
+            pool.take_released_withdrawals(&recipient, release_slot),
+            Ok(20 * MAX_DELAYED_WITHDRAWALS as u64)
+        );
+        assert!(pool.delayed_withdrawals.is_empty());
+
+        pool.delayed_withdrawals = vec![DelayedWithdrawal::default(); MAX_DELAYED_WITHDRAWALS];
+        assert_eq!(
+            pool.try_to_vec().unwrap().len() + 8,
+            RegisteredAssetPool::LEN
+        );
+    }
+
+    #[test]
+    fn test_claims_count_against_withdrawal_limit() {
+        let mut pool = asset_pool();
+        // a claim over the limit is accepted in an empty window and exhausts it
+        pool.track_claim(20, 1).unwrap();
+        assert_eq!(pool.withdrawn_in_window, 20);
+        assert!(!pool.track_withdrawal(1, 2));
+        assert_eq!(
+            pool.track_claim(1, 2),
+            err!(ErrorCode::WithdrawalLimitExceeded)
+        );
+        // new window
+        assert!(pool.track_withdrawal(6, 6));
+        pool.track_claim(4, 7).unwrap();
+        assert_eq!(
+            pool.track_claim(1, 7),
+            err!(ErrorCode::WithdrawalLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn test_cancel_withdrawals() {
+        let mut pool = asset_pool();
+        let recipient = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        pool.queue_withdrawal(recipient, 20, 0).unwrap();
+        pool.queue_withdrawal(other, 30, 0).unwrap();
+        pool.queue_withdrawal(recipient, 40, 1).unwrap();
+        assert_eq!(pool.cancel_withdrawals(&recipient), Ok(60));
+        assert_eq!(
+            pool.cancel_withdrawals(&recipient),
+            err!(ErrorCode::NoDelayedWithdrawal)
+        );
+        assert_eq!(pool.delayed_withdrawals.len(), 1);
+        assert_eq!(pool.delayed_withdrawals[0].recipient, other);
+    }
+}
@@ -1,14 +1,19 @@
-use crate::config;
+use crate::config::{self, MAX_MULTISIG_SIGNERS};
 use crate::errors::ErrorCode;
-use crate::transaction_merkle_tree::state::TransactionMerkleTree;
-use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, TRANSACTION_MERKLE_TREE_SEED};
+use crate::nullifier_tree::indexed_tree::NullifierTree;
+use crate::transaction_merkle_tree::{leaves_queue::LeavesQueue, state::TransactionMerkleTree};
+use crate::utils::constants::{
+    LEAVES_QUEUE_SEED, MERKLE_TREE_AUTHORITY_SEED, NULLIFIER_TREE_SEED,
+    TRANSACTION_MERKLE_TREE_SEED,
+};
+use anchor_lang::prelude::borsh;
 use anchor_lang::prelude::*;
 
 /// Configures the authority of the merkle tree which can:
 /// - register new verifiers
 /// - register new asset pools
 /// - register new asset pool types
-/// - set permissions for new asset pool creation
+/// - set permissions for new asset pool creation, Merkle tree creation and verifier registration
 /// - keeps current highest index for assets and merkle trees to enable lookups of these
 #[account]
 pub struct MerkleTreeAuthority {
@@ -19,6 +24,153 @@ pub struct MerkleTreeAuthority {
     pub enable_nfts: bool,
     pub enable_permissionless_spl_tokens: bool,
     pub enable_permissionless_merkle_tree_registration: bool,
+    pub enable_permissionless_verifier_registration: bool,
+    /// Proposed new authority, the default pubkey if no transfer is pending.
+    pub pending_authority: Pubkey,
+    /// Number of multisig signers which have to sign authority instructions,
+    /// zero if no multisig is configured.
+    pub multisig_threshold: u8,
+    pub nr_multisig_signers: u8,
+    pub multisig_signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+    /// Guardian which can pause but not unpause, the default pubkey if not set.
+    pub guardian: Pubkey,
+    /// Bitmask of the paused transaction kinds.
+    pub paused: u8,
+    /// Nullifiers are queued for the nullifier tree instead of stored in nullifier pdas.
+    /// Set once by the authority and never unset, otherwise a nullifier could be spent
+    /// once in each mode.
+    pub nullifier_tree_enabled: bool,
+}
+
+/// Permissions which can be granted to anyone by the merkle tree authority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    Nfts,
+    SplTokens,
+    MerkleTreeRegistration,
+    VerifierRegistration,
+}
+
+#[event]
+pub struct PermissionUpdated {
+    pub permission: Permission,
+    pub enabled: bool,
+}
+
+impl MerkleTreeAuthority {
+    pub const PAUSE_SHIELD: u8 = 1;
+    pub const PAUSE_UNSHIELD: u8 = 1 << 1;
+    pub const PAUSE_TRANSFER: u8 = 1 << 2;
+    pub const PAUSE_ALL: u8 = Self::PAUSE_SHIELD | Self::PAUSE_UNSHIELD | Self::PAUSE_TRANSFER;
+
+    /// Returns true if any transaction kind in `flags` is paused.
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.paused & flags != 0
+    }
+
+    /// Pauses the transaction kinds in `flags`, can be called by the authority or guardian.
+    pub fn pause(
+        &mut self,
+        signer: &AccountInfo,
+        flags: u8,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<()> {
+        let is_guardian =
+            self.guardian != Pubkey::default() && signer.is_signer && signer.key() == self.guardian;
+        if !is_guardian {
+            self.check_authority(signer, remaining_accounts)?;
+        }
+        if flags & !Self::PAUSE_ALL != 0 {
+            return err!(ErrorCode::InvalidPauseFlags);
+        }
+        self.paused |= flags;
+        Ok(())
+    }
+
+    /// Unpauses the transaction kinds in `flags`, only the authority can unpause.
+    pub fn unpause(
+        &mut self,
+        authority: &AccountInfo,
+        flags: u8,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<()> {
+        self.check_authority(authority, remaining_accounts)?;
+        if flags & !Self::PAUSE_ALL != 0 {
+            return err!(ErrorCode::InvalidPauseFlags);
+        }
+        self.paused &= !flags;
+        Ok(())
+    }
+
+    /// Checks that the authority signed and, if a multisig is configured, that at least
+    /// multisig_threshold multisig signers signed. Multisig signers are passed in as
+    /// remaining accounts, the authority counts if it is a multisig signer itself.
+    pub fn check_authority(
+        &self,
+        authority: &AccountInfo,
+        remaining_accounts: &[AccountInfo],
+    ) -> Result<()> {
+        if authority.key() != self.pubkey || !authority.is_signer {
+            return err!(ErrorCode::InvalidAuthority);
+        }
+        if self.multisig_threshold == 0 {
+            return Ok(());
+        }
+        let nr_signatures = self.multisig_signers[..self.nr_multisig_signers as usize]
+            .iter()
+            .filter(|signer| {
+                authority.key == *signer
+                    || remaining_accounts
+                        .iter()
+                        .any(|account| account.is_signer && account.key == *signer)
+            })
+            .count();
+        if nr_signatures < self.multisig_threshold as usize {
+            msg!(
+                "{} of {} required multisig signatures.",
+                nr_signatures,
+                self.multisig_threshold
+            );
+            return err!(ErrorCode::NotEnoughMultisigSignatures);
+        }
+        Ok(())
+    }
+
+    pub fn set_multisig(&mut self, signers: &[Pubkey], threshold: u8) -> Result<()> {
+        let has_duplicates = signers
+            .iter()
+            .enumerate()
+            .any(|(i, signer)| signers[..i].contains(signer));
+        if signers.len() > MAX_MULTISIG_SIGNERS
+            || threshold as usize > signers.len()
+            || has_duplicates
+        {
+            return err!(ErrorCode::InvalidMultisigConfig);
+        }
+        self.multisig_signers = [Pubkey::default(); MAX_MULTISIG_SIGNERS];
+        self.multisig_signers[..signers.len()].copy_from_slice(signers);
+        self.nr_multisig_signers = signers.len() as u8;
+        self.multisig_threshold = threshold;
+        Ok(())
+    }
+
+    /// Sets a permissionless flag and emits a PermissionUpdated event.
+    pub fn set_permission(&mut self, permission: Permission, enabled: bool) {
+        match permission {
+            Permission::Nfts => self.enable_nfts = enabled,
+            Permission::SplTokens => self.enable_permissionless_spl_tokens = enabled,
+            Permission::MerkleTreeRegistration => {
+                self.enable_permissionless_merkle_tree_registration = enabled
+            }
+            Permission::VerifierRegistration => {
+                self.enable_permissionless_verifier_registration = enabled
+            }
+        }
+        emit!(PermissionUpdated {
+            permission,
+            enabled
+        });
+    }
 }
 
 #[derive(Accounts)]
@@ -28,7 +180,7 @@ pub struct InitializeMerkleTreeAuthority<'info> {
         payer = authority,
         seeds = [MERKLE_TREE_AUTHORITY_SEED],
         bump,
-        space = 8 + 32 + 8 + 8 + 8 + 8
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 32 + 1 + 1 + 32 * MAX_MULTISIG_SIGNERS + 32 + 1 + 1
     )]
     pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
     #[account(
@@ -39,9 +191,17 @@ pub struct InitializeMerkleTreeAuthority<'info> {
         ],
         bump,
         payer = authority,
-        space = 8880
+        space = 9424
     )]
     pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    #[account(
+        init,
+        seeds = [transaction_merkle_tree.key().to_bytes().as_ref(), LEAVES_QUEUE_SEED],
+        bump,
+        payer = authority,
+        space = 8232
+    )]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
     /// CHECK:` Signer is merkle tree init authority.
     #[account(mut, address=anchor_lang::prelude::Pubkey::try_from(config::INITIAL_MERKLE_TREE_AUTHORITY).map_err(|_| ErrorCode::PubkeyTryFromFailed)? @ErrorCode::InvalidAuthority)]
     pub authority: Signer<'info>,
@@ -56,10 +216,19 @@ pub struct UpdateMerkleTreeAuthority<'info> {
     /// CHECK:` Signer is merkle tree authority.
     #[account(address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
     pub authority: Signer<'info>,
-    /// CHECK:` New authority no need to be checked
+    /// CHECK:` New authority has to sign when accepting the transfer.
     pub new_authority: UncheckedAccount<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptMerkleTreeAuthority<'info> {
+    #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    /// CHECK:` Signer is the pending merkle tree authority.
+    #[account(address=merkle_tree_authority_pda.pending_authority @ErrorCode::InvalidAuthority)]
+    pub new_authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateMerkleTreeAuthorityConfig<'info> {
     #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
@@ -69,6 +238,26 @@ pub struct UpdateMerkleTreeAuthorityConfig<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct EnableNullifierTree<'info> {
+    #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account( address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    /// The nullifier tree has to be initialized before nullifiers can be queued.
+    #[account(seeds = [NULLIFIER_TREE_SEED], bump)]
+    pub nullifier_tree: AccountLoader<'info, NullifierTree>,
+}
+
+#[derive(Accounts)]
+pub struct PauseProtocol<'info> {
+    #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    /// CHECK:` Signer is the merkle tree authority or guardian, checked in instruction.
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateLockDuration<'info> {
     #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
@@ -79,3 +268,155 @@ pub struct UpdateLockDuration<'info> {
     #[account(mut)]
     pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_authority_multisig() {
+        let authority = Pubkey::new_unique();
+        let signers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut merkle_tree_authority = MerkleTreeAuthority {
+            pubkey: authority,
+            transaction_merkle_tree_index: 0,
+            event_merkle_tree_index: 0,
+            registered_asset_index: 0,
+            enable_nfts: false,
+            enable_permissionless_spl_tokens: false,
+            enable_permissionless_merkle_tree_registration: false,
+            enable_permissionless_verifier_registration: false,
+            pending_authority: Pubkey::default(),
+            multisig_threshold: 0,
+            nr_multisig_signers: 0,
+            multisig_signers: [Pubkey::default(); MAX_MULTISIG_SIGNERS],
+            guardian: Pubkey::default(),
+            paused: 0,
+            nullifier_tree_enabled: false,
+        };
+        let mut lamports = [0u64; 3];
+        let mut data = [[0u8; 0]; 3];
+        let mut accounts = Vec::new();
+        for ((key, lamports), data) in std::iter::once(&authority)
+            .chain(signers.iter())
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+        {
+            accounts.push(AccountInfo::new(
+                key,
+                true,
+                false,
+                lamports,
+                data,
+                &crate::ID,
+                false,
+                0,
+            ));
+        }
+
+        assert!(merkle_tree_authority
+            .check_authority(&accounts[0], &[])
+            .is_ok());
+        assert_eq!(
+            merkle_tree_authority.check_authority(&accounts[1], &[]),
+            err!(ErrorCode::InvalidAuthority)
+        );
+
+        assert_eq!(
+            merkle_tree_authority.set_multisig(&[signers[0], signers[0]], 1),
+            err!(ErrorCode::InvalidMultisigConfig)
+        );
+        assert_eq!(
+            merkle_tree_authority.set_multisig(&signers, 3),
+            err!(ErrorCode::InvalidMultisigConfig)
+        );
+        merkle_tree_authority.set_multisig(&signers, 2).unwrap();
+        assert_eq!(
+            merkle_tree_authority.check_authority(&accounts[0], &accounts[1..2]),
+            err!(ErrorCode::NotEnoughMultisigSignatures)
+        );
+        // duplicate signatures are counted once
+        assert_eq!(
+            merkle_tree_authority
+                .check_authority(&accounts[0], &[accounts[1].clone(), accounts[1].clone()]),
+            err!(ErrorCode::NotEnoughMultisigSignatures)
+        );
+        assert!(merkle_tree_authority
+            .check_authority(&accounts[0], &accounts[1..])
+            .is_ok());
+    }
+
+    #[test]
+    fn test_pause() {
+        let authority = Pubkey::new_unique();
+        let guardian = Pubkey::new_unique();
+        let mut merkle_tree_authority = MerkleTreeAuthority {
+            pubkey: authority,
+            transaction_merkle_tree_index: 0,
+            event_merkle_tree_index: 0,
+            registered_asset_index: 0,
+            enable_nfts: false,
+            enable_permissionless_spl_tokens: false,
+            enable_permissionless_merkle_tree_registration: false,
+            enable_permissionless_verifier_registration: false,
+            pending_authority: Pubkey::default(),
+            multisig_threshold: 0,
+            nr_multisig_signers: 0,
+            multisig_signers: [Pubkey::default(); MAX_MULTISIG_SIGNERS],
+            guardian,
+            paused: 0,
+            nullifier_tree_enabled: false,
+        };
+        let mut lamports = [0u64; 2];
+        let mut data = [[0u8; 0]; 2];
+        let [authority_lamports, guardian_lamports] = &mut lamports;
+        let [authority_data, guardian_data] = &mut data;
+        let authority_info = AccountInfo::new(
+            &authority,
+            true,
+            false,
+            authority_lamports,
+            authority_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let guardian_info = AccountInfo::new(
+            &guardian,
+            true,
+            false,
+            guardian_lamports,
+            guardian_data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        merkle_tree_authority
+            .pause(&guardian_info, MerkleTreeAuthority::PAUSE_SHIELD, &[])
+            .unwrap();
+        assert!(merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_SHIELD));
+        assert!(merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_ALL));
+        assert!(!merkle_tree_authority
+            .is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD | MerkleTreeAuthority::PAUSE_TRANSFER));
+        assert_eq!(
+            merkle_tree_authority.unpause(&guardian_info, MerkleTreeAuthority::PAUSE_SHIELD, &[]),
+            err!(ErrorCode::InvalidAuthority)
+        );
+        assert_eq!(
+            merkle_tree_authority.pause(&authority_info, 1 << 3, &[]),
+            err!(ErrorCode::InvalidPauseFlags)
+        );
+        merkle_tree_authority
+            .pause(&authority_info, MerkleTreeAuthority::PAUSE_ALL, &[])
+            .unwrap();
+        assert!(merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_ALL));
+        merkle_tree_authority
+            .unpause(&authority_info, MerkleTreeAuthority::PAUSE_SHIELD, &[])
+            .unwrap();
+        assert!(!merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_SHIELD));
+        assert!(merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD));
+        assert!(merkle_tree_authority
+            .is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD | MerkleTreeAuthority::PAUSE_TRANSFER));
+    }
+}
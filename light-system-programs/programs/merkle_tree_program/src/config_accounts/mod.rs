@@ -4,5 +4,17 @@ pub use init_asset_pda::*;
 pub mod merkle_tree_authority;
 pub use merkle_tree_authority::*;
 
+pub mod nft;
+pub use nft::*;
+
+pub mod registered_relayer;
+pub use registered_relayer::*;
+
 pub mod register_verifier;
 pub use register_verifier::*;
+
+pub mod token_extensions;
+pub use token_extensions::*;
+
+pub mod verifying_key;
+pub use verifying_key::*;
@@ -0,0 +1,134 @@
+use crate::errors::ErrorCode;
+use crate::utils::constants::{METADATA_SEED, NFT_POOL_TYPE, TOKEN_METADATA_PROGRAM_ID};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+/// Account keys of metaplex master editions, the key is the first byte of the account data.
+const MASTER_EDITION_V1_KEY: u8 = 2;
+const MASTER_EDITION_V2_KEY: u8 = 6;
+
+/// A mint with supply one and zero decimals is treated as nft.
+pub fn is_nft(mint: &Mint) -> bool {
+    mint.decimals == 0 && mint.supply == 1
+}
+
+/// Checks that the master edition account is the metaplex master edition of the mint.
+/// The master edition holds the mint authority, thus the supply cannot change anymore.
+pub fn check_master_edition(mint: &Pubkey, master_edition: &AccountInfo) -> Result<()> {
+    let (master_edition_pubkey, _) = Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    if master_edition.key() != master_edition_pubkey
+        || *master_edition.owner != TOKEN_METADATA_PROGRAM_ID
+    {
+        msg!("Invalid master edition account {}.", master_edition.key());
+        return err!(ErrorCode::InvalidMasterEdition);
+    }
+    match master_edition.try_borrow_data()?.first() {
+        Some(&MASTER_EDITION_V1_KEY) | Some(&MASTER_EDITION_V2_KEY) => Ok(()),
+        _ => {
+            msg!("Master edition account has an invalid key.");
+            err!(ErrorCode::InvalidMasterEdition)
+        }
+    }
+}
+
+/// Nft pools have to use the nft pool type, fungible tokens cannot use it.
+pub fn check_pool_type(is_nft: bool, pool_type: &[u8; 32]) -> Result<()> {
+    if is_nft != (*pool_type == NFT_POOL_TYPE) {
+        msg!("Nft pools have to use the nft pool type, fungible token pools cannot.");
+        return err!(ErrorCode::InvalidPoolType);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_master_edition() {
+        let mint = Pubkey::new_unique();
+        let (key, _) = Pubkey::find_program_address(
+            &[
+                METADATA_SEED,
+                TOKEN_METADATA_PROGRAM_ID.as_ref(),
+                mint.as_ref(),
+                b"edition",
+            ],
+            &TOKEN_METADATA_PROGRAM_ID,
+        );
+        let mut lamports = 0;
+        let mut data = vec![MASTER_EDITION_V2_KEY, 0, 0];
+        let master_edition = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &TOKEN_METADATA_PROGRAM_ID,
+            false,
+            0,
+        );
+        assert!(check_master_edition(&mint, &master_edition).is_ok());
+        assert_eq!(
+            check_master_edition(&Pubkey::new_unique(), &master_edition),
+            err!(ErrorCode::InvalidMasterEdition)
+        );
+
+        let mut lamports = 0;
+        let mut data = vec![4u8, 0, 0];
+        let edition = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &TOKEN_METADATA_PROGRAM_ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            check_master_edition(&mint, &edition),
+            err!(ErrorCode::InvalidMasterEdition)
+        );
+
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![MASTER_EDITION_V2_KEY, 0, 0];
+        let fake_edition = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(
+            check_master_edition(&mint, &fake_edition),
+            err!(ErrorCode::InvalidMasterEdition)
+        );
+    }
+
+    #[test]
+    fn test_check_pool_type() {
+        assert!(check_pool_type(true, &NFT_POOL_TYPE).is_ok());
+        assert!(check_pool_type(false, &[0u8; 32]).is_ok());
+        assert_eq!(
+            check_pool_type(true, &[0u8; 32]),
+            err!(ErrorCode::InvalidPoolType)
+        );
+        assert_eq!(
+            check_pool_type(false, &NFT_POOL_TYPE),
+            err!(ErrorCode::InvalidPoolType)
+        );
+    }
+}
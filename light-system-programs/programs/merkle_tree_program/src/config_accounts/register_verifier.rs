@@ -1,11 +1,104 @@
 use crate::errors::ErrorCode;
+use crate::utils::config::{MAX_VERIFIER_ALLOWED_MERKLE_TREES, MAX_VERIFIER_ALLOWED_POOL_TYPES};
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, POOL_SEED};
 use crate::MerkleTreeAuthority;
+use anchor_lang::prelude::borsh;
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
-///
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifierStatus {
+    Active,
+    Paused,
+    /// Deregistered verifiers cannot be resumed.
+    Deregistered,
+}
+
+/// Registered verifiers can invoke the verifier invoked instructions they have
+/// capabilities for, as long as they are active.
 #[account]
 pub struct RegisteredVerifier {
     pub pubkey: Pubkey,
+    pub status: VerifierStatus,
+    /// Bitmask of the capability flags defined on RegisteredVerifier.
+    pub capabilities: u8,
+    /// Pool types the verifier can withdraw from, any pool type if empty.
+    pub allowed_pool_types: Vec<[u8; 32]>,
+    /// Merkle trees the verifier can insert leaves into, any tree if empty.
+    /// Nullifiers are inserted into the single nullifier tree.
+    pub allowed_merkle_trees: Vec<Pubkey>,
+}
+
+impl RegisteredVerifier {
+    pub const WITHDRAW_SOL: u8 = 1;
+    pub const WITHDRAW_SPL: u8 = 1 << 1;
+    pub const INSERT_LEAVES: u8 = 1 << 2;
+    pub const INSERT_NULLIFIERS: u8 = 1 << 3;
+    pub const ALL_CAPABILITIES: u8 =
+        Self::WITHDRAW_SOL | Self::WITHDRAW_SPL | Self::INSERT_LEAVES | Self::INSERT_NULLIFIERS;
+
+    pub const LEN: usize = 8
+        + 32
+        + 1
+        + 1
+        + 4
+        + 32 * MAX_VERIFIER_ALLOWED_POOL_TYPES
+        + 4
+        + 32 * MAX_VERIFIER_ALLOWED_MERKLE_TREES;
+
+    pub fn is_active(&self) -> bool {
+        self.status == VerifierStatus::Active
+    }
+
+    pub fn has_capability(&self, capability: u8) -> bool {
+        self.capabilities & capability == capability
+    }
+
+    pub fn is_merkle_tree_allowed(&self, merkle_tree: &Pubkey) -> bool {
+        self.allowed_merkle_trees.is_empty() || self.allowed_merkle_trees.contains(merkle_tree)
+    }
+
+    pub fn is_pool_type_allowed(&self, pool_type: &[u8; 32]) -> bool {
+        self.allowed_pool_types.is_empty() || self.allowed_pool_types.contains(pool_type)
+    }
+
+    /// Spl pools do not store their pool type, it is checked by deriving the pool address.
+    pub fn is_spl_pool_allowed(&self, pool: &InterfaceAccount<TokenAccount>) -> bool {
+        self.allowed_pool_types.is_empty()
+            || self.allowed_pool_types.iter().any(|pool_type| {
+                Pubkey::find_program_address(
+                    &[&pool.mint.to_bytes(), pool_type, POOL_SEED],
+                    &crate::ID,
+                )
+                .0 == pool.key()
+            })
+    }
+
+    pub fn set_status(&mut self, status: VerifierStatus) -> Result<()> {
+        if self.status == VerifierStatus::Deregistered {
+            return err!(ErrorCode::VerifierDeregistered);
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    pub fn set_capabilities(
+        &mut self,
+        capabilities: u8,
+        allowed_pool_types: Vec<[u8; 32]>,
+        allowed_merkle_trees: Vec<Pubkey>,
+    ) -> Result<()> {
+        if capabilities & !Self::ALL_CAPABILITIES != 0
+            || allowed_pool_types.len() > MAX_VERIFIER_ALLOWED_POOL_TYPES
+            || allowed_merkle_trees.len() > MAX_VERIFIER_ALLOWED_MERKLE_TREES
+        {
+            return err!(ErrorCode::InvalidVerifierCapabilities);
+        }
+        self.capabilities = capabilities;
+        self.allowed_pool_types = allowed_pool_types;
+        self.allowed_merkle_trees = allowed_merkle_trees;
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -16,13 +109,97 @@ pub struct RegisterVerifier<'info> {
         payer = authority,
         seeds = [&verifier_pubkey.to_bytes()],
         bump,
-        space = 8 + 32
+        space = RegisteredVerifier::LEN
     )]
     pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
     /// CHECK:` Signer is checked according to authority pda in instruction
-    #[account(mut, address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    #[account(mut)]
     pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
     pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+#[derive(Accounts)]
+pub struct UpdateRegisteredVerifier<'info> {
+    #[account(mut, seeds = [&registered_verifier_pda.pubkey.to_bytes()], bump)]
+    pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account(address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registered_verifier_status_and_capabilities() {
+        let mut registered_verifier = RegisteredVerifier {
+            pubkey: Pubkey::new_unique(),
+            status: VerifierStatus::Active,
+            capabilities: RegisteredVerifier::ALL_CAPABILITIES,
+            allowed_pool_types: Vec::new(),
+            allowed_merkle_trees: Vec::new(),
+        };
+        let merkle_tree = Pubkey::new_unique();
+        assert!(registered_verifier.is_merkle_tree_allowed(&merkle_tree));
+        assert!(registered_verifier.is_pool_type_allowed(&[0u8; 32]));
+
+        registered_verifier
+            .set_capabilities(
+                RegisteredVerifier::INSERT_LEAVES | RegisteredVerifier::INSERT_NULLIFIERS,
+                vec![[1u8; 32]],
+                vec![merkle_tree],
+            )
+            .unwrap();
+        assert!(registered_verifier.has_capability(RegisteredVerifier::INSERT_LEAVES));
+        assert!(!registered_verifier.has_capability(RegisteredVerifier::WITHDRAW_SOL));
+        assert!(registered_verifier.is_merkle_tree_allowed(&merkle_tree));
+        assert!(!registered_verifier.is_merkle_tree_allowed(&Pubkey::new_unique()));
+        assert!(!registered_verifier.is_pool_type_allowed(&[0u8; 32]));
+        assert_eq!(
+            registered_verifier.set_capabilities(1 << 7, Vec::new(), Vec::new()),
+            err!(ErrorCode::InvalidVerifierCapabilities)
+        );
+        assert_eq!(
+            registered_verifier.set_capabilities(
+                0,
+                vec![[0u8; 32]; MAX_VERIFIER_ALLOWED_POOL_TYPES + 1],
+                Vec::new()
+            ),
+            err!(ErrorCode::InvalidVerifierCapabilities)
+        );
+        // the account fits the maximum number of allowed pool types and trees
+        registered_verifier
+            .set_capabilities(
+                0,
+                vec![[0u8; 32]; MAX_VERIFIER_ALLOWED_POOL_TYPES],
+                vec![merkle_tree; MAX_VERIFIER_ALLOWED_MERKLE_TREES],
+            )
+            .unwrap();
+        assert_eq!(
+            registered_verifier.try_to_vec().unwrap().len() + 8,
+            RegisteredVerifier::LEN
+        );
+
+        registered_verifier
+            .set_status(VerifierStatus::Paused)
+            .unwrap();
+        assert!(!registered_verifier.is_active());
+        registered_verifier
+            .set_status(VerifierStatus::Active)
+            .unwrap();
+        assert!(registered_verifier.is_active());
+        registered_verifier
+            .set_status(VerifierStatus::Deregistered)
+            .unwrap();
+        assert_eq!(
+            registered_verifier.set_status(VerifierStatus::Active),
+            err!(ErrorCode::VerifierDeregistered)
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::config::{RELAYER_STAKE_PENALTY, RELAYER_UNSTAKE_DELAY};
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::state::TransactionMerkleTree;
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, RELAYER_SEED};
+use crate::MerkleTreeAuthority;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+/// Registers a relayer for Merkle tree updates of gated trees.
+/// Relayers are either whitelisted by the merkle tree authority or stake lamports
+/// which are held by this account.
+#[account]
+#[derive(Debug)]
+pub struct RegisteredRelayer {
+    pub relayer: Pubkey,
+    pub whitelisted: bool,
+    /// Staked lamports, held in addition to the rent of this account.
+    pub stake: u64,
+    /// Slot from which stake can be withdrawn.
+    pub unlock_slot: u64,
+    /// Number of expired update states of the relayer which were taken over or closed.
+    pub penalties: u64,
+}
+
+impl RegisteredRelayer {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8 + 8;
+
+    pub fn is_eligible(&self, min_relayer_stake: u64) -> bool {
+        self.whitelisted || self.stake >= min_relayer_stake
+    }
+
+    /// Locks the stake for RELAYER_UNSTAKE_DELAY slots, stake cannot be withdrawn while
+    /// an update state of the relayer can still expire.
+    pub fn lock_stake(&mut self, slot: u64) {
+        self.unlock_slot = self.unlock_slot.max(slot + RELAYER_UNSTAKE_DELAY);
+    }
+
+    /// Returns the stake which is slashed for an abandoned update state.
+    pub fn penalize(&mut self) -> u64 {
+        let penalty = self.stake * RELAYER_STAKE_PENALTY / 100;
+        self.stake -= penalty;
+        self.penalties += 1;
+        penalty
+    }
+}
+
+/// Checks that the relayer can update a gated tree.
+pub fn check_registered_relayer(
+    merkle_tree: &TransactionMerkleTree,
+    relayer: &Pubkey,
+    registered_relayer: Option<&RegisteredRelayer>,
+) -> Result<()> {
+    if merkle_tree.gated_relayers == 0 {
+        return Ok(());
+    }
+    match registered_relayer {
+        Some(registered_relayer) if registered_relayer.relayer == *relayer => {
+            if !registered_relayer.is_eligible(merkle_tree.min_relayer_stake) {
+                return err!(ErrorCode::InsufficientRelayerStake);
+            }
+            Ok(())
+        }
+        _ => err!(ErrorCode::RelayerNotRegistered),
+    }
+}
+
+/// Checks that the relayer of the last root does not start the next update of a gated tree
+/// within lock_duration slots. Other relayers get the chance to update the tree first.
+pub fn check_relayer_rotation(
+    merkle_tree: &TransactionMerkleTree,
+    relayer: &Pubkey,
+    slot: u64,
+) -> Result<()> {
+    if merkle_tree.gated_relayers != 0
+        && merkle_tree.last_relayer == *relayer
+        && slot <= merkle_tree.last_root_slot + merkle_tree.lock_duration
+    {
+        return err!(ErrorCode::RelayerRotation);
+    }
+    Ok(())
+}
+
+/// Moves the penalty of an abandoned update state from the stake to the recipient.
+pub fn penalize_relayer<'info>(
+    registered_relayer: &mut Account<'info, RegisteredRelayer>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    let penalty = registered_relayer.penalize();
+    let registered_relayer_info = registered_relayer.to_account_info();
+    **registered_relayer_info.lamports.borrow_mut() = registered_relayer_info
+        .lamports()
+        .checked_sub(penalty)
+        .ok_or(ErrorCode::CloseAccountFailed)?;
+    **recipient.lamports.borrow_mut() = recipient
+        .lamports()
+        .checked_add(penalty)
+        .ok_or(ErrorCode::CloseAccountFailed)?;
+    msg!(
+        "Relayer {} penalized {} lamports",
+        registered_relayer.relayer,
+        penalty
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    #[account(
+        init,
+        seeds = [relayer.key().to_bytes().as_ref(), RELAYER_SEED],
+        bump,
+        payer = relayer,
+        space = RegisteredRelayer::LEN,
+    )]
+    pub registered_relayer: Account<'info, RegisteredRelayer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRelayerStake<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [relayer.key().to_bytes().as_ref(), RELAYER_SEED],
+        bump,
+        has_one = relayer @ErrorCode::InvalidAuthority,
+    )]
+    pub registered_relayer: Account<'info, RegisteredRelayer>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WhitelistRelayer<'info> {
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account(address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub registered_relayer: Account<'info, RegisteredRelayer>,
+}
+
+pub fn process_stake_relayer(ctx: Context<UpdateRelayerStake>, amount: u64) -> Result<()> {
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.relayer.to_account_info(),
+                to: ctx.accounts.registered_relayer.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    let registered_relayer = &mut ctx.accounts.registered_relayer;
+    registered_relayer.stake = registered_relayer
+        .stake
+        .checked_add(amount)
+        .ok_or(ErrorCode::InvalidRelayerStake)?;
+    registered_relayer.lock_stake(Clock::get()?.slot);
+    Ok(())
+}
+
+pub fn process_unstake_relayer(ctx: Context<UpdateRelayerStake>, amount: u64) -> Result<()> {
+    let registered_relayer = &mut ctx.accounts.registered_relayer;
+    if Clock::get()?.slot < registered_relayer.unlock_slot {
+        msg!(
+            "Relayer stake is locked until slot {}.",
+            registered_relayer.unlock_slot
+        );
+        return err!(ErrorCode::RelayerStakeLocked);
+    }
+    registered_relayer.stake = registered_relayer
+        .stake
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InvalidRelayerStake)?;
+    let registered_relayer_info = registered_relayer.to_account_info();
+    **registered_relayer_info.lamports.borrow_mut() = registered_relayer_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InvalidRelayerStake)?;
+    let relayer = ctx.accounts.relayer.to_account_info();
+    **relayer.lamports.borrow_mut() = relayer
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ErrorCode::InvalidRelayerStake)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check_relayer() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        let relayer = Pubkey::new_unique();
+        let mut registered_relayer = RegisteredRelayer {
+            relayer,
+            whitelisted: false,
+            stake: 100,
+            unlock_slot: 0,
+            penalties: 0,
+        };
+        // Trees are not gated by default.
+        check_registered_relayer(&merkle_tree, &relayer, None).unwrap();
+
+        merkle_tree.gated_relayers = 1;
+        merkle_tree.min_relayer_stake = 1_000;
+        merkle_tree.lock_duration = 50;
+        assert_eq!(
+            check_registered_relayer(&merkle_tree, &relayer, None),
+            err!(ErrorCode::RelayerNotRegistered)
+        );
+        assert_eq!(
+            check_registered_relayer(&merkle_tree, &relayer, Some(&registered_relayer)),
+            err!(ErrorCode::InsufficientRelayerStake)
+        );
+        registered_relayer.whitelisted = true;
+        check_registered_relayer(&merkle_tree, &relayer, Some(&registered_relayer)).unwrap();
+        assert_eq!(
+            check_registered_relayer(
+                &merkle_tree,
+                &Pubkey::new_unique(),
+                Some(&registered_relayer)
+            ),
+            err!(ErrorCode::RelayerNotRegistered)
+        );
+
+        merkle_tree.record_root_insert(relayer, 100);
+        assert_eq!(
+            check_relayer_rotation(&merkle_tree, &relayer, 150),
+            err!(ErrorCode::RelayerRotation)
+        );
+        check_relayer_rotation(&merkle_tree, &relayer, 151).unwrap();
+        check_relayer_rotation(&merkle_tree, &Pubkey::new_unique(), 100).unwrap();
+
+        assert_eq!(registered_relayer.penalize(), 10);
+        assert_eq!(registered_relayer.stake, 90);
+        assert_eq!(registered_relayer.penalties, 1);
+    }
+}
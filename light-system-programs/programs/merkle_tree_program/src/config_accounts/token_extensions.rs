@@ -0,0 +1,149 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+
+/// Token-2022 mint extensions pools support.
+/// Other extensions can move or lock pool funds (permanent delegate, default frozen accounts,
+/// non transferable), hide amounts (confidential transfers) or are unknown to this program
+/// (transfer hooks), mints with these extensions are rejected.
+pub const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::InterestBearingConfig,
+];
+
+/// Checks that a Token-2022 mint only uses supported extensions, spl token mints are accepted.
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+    // Fails for extension types which are unknown to this version of the token program.
+    let extensions = mint_state
+        .get_extension_types()
+        .map_err(|_| error!(ErrorCode::UnsupportedMintExtension))?;
+    for extension in extensions {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("Unsupported mint extension {:?}.", extension);
+            return err!(ErrorCode::UnsupportedMintExtension);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the decimals of a spl token or Token-2022 mint.
+pub fn get_mint_decimals(mint: &AccountInfo) -> Result<u8> {
+    let data = mint.try_borrow_data()?;
+    Ok(StateWithExtensions::<Mint>::unpack(&data)?.base.decimals)
+}
+
+/// Returns the fee which has to be added to a transfer for the recipient to receive amount.
+pub fn get_inverse_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(error!(ErrorCode::UnsupportedMintExtension)),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Unpacks spl token and Token-2022 token accounts.
+pub fn unpack_token_account(data: &[u8]) -> Result<Account> {
+    Ok(StateWithExtensions::<Account>::unpack(data)?.base)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token_2022::spl_token_2022::extension::{
+        mint_close_authority::MintCloseAuthority, permanent_delegate::PermanentDelegate, Extension,
+        StateWithExtensionsMut,
+    };
+
+    /// Returns an initialized mint with the extension E.
+    fn mint_data<E: Extension>() -> Vec<u8> {
+        let mut data = vec![0u8; ExtensionType::get_account_len::<Mint>(&[E::TYPE])];
+        let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+        state.init_extension::<E>(true).unwrap();
+        state.base = Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        data
+    }
+
+    #[test]
+    fn test_check_mint_extensions() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = mint_data::<MintCloseAuthority>();
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+        assert!(check_mint_extensions(&mint).is_ok());
+        assert_eq!(get_mint_decimals(&mint).unwrap(), 6);
+
+        let mut lamports = 0;
+        let mut data = mint_data::<PermanentDelegate>();
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &spl_token_2022::ID,
+            false,
+            0,
+        );
+        assert_eq!(
+            check_mint_extensions(&mint),
+            err!(ErrorCode::UnsupportedMintExtension)
+        );
+
+        let mut lamports = 0;
+        let mut data = vec![0u8; Mint::LEN];
+        Mint {
+            decimals: 9,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        let mint = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &anchor_spl::token::ID,
+            false,
+            0,
+        );
+        assert!(check_mint_extensions(&mint).is_ok());
+        assert_eq!(get_inverse_transfer_fee(&mint, 100).unwrap(), 0);
+        assert_eq!(get_mint_decimals(&mint).unwrap(), 9);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::errors::ErrorCode;
+use crate::utils::config::{MAX_VERIFYING_KEY_PUBLIC_INPUTS, VERIFYING_KEY_TIMELOCK};
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, VERIFYING_KEY_SEED};
+use crate::{MerkleTreeAuthority, RegisteredVerifier};
+use anchor_lang::prelude::*;
+
+/// Groth16 verifying key of a registered verifier for transactions with `nr_inputs` input
+/// and `nr_outputs` output utxos.
+/// The key is uploaded in chunks, once all vk_ic points are written it becomes active
+/// after VERIFYING_KEY_TIMELOCK slots. A deactivated key cannot be activated again,
+/// keys are rotated by registering a new version.
+#[account]
+pub struct VerifyingKeyAccount {
+    pub verifier: Pubkey,
+    pub nr_inputs: u8,
+    pub nr_outputs: u8,
+    pub version: u64,
+    /// Slot from which on the key can be used, u64::MAX until the upload is complete.
+    pub activation_slot: u64,
+    pub deactivated: bool,
+    pub nr_pubinputs: u64,
+    pub vk_alpha_g1: [u8; 64],
+    pub vk_beta_g2: [u8; 128],
+    pub vk_gamme_g2: [u8; 128],
+    pub vk_delta_g2: [u8; 128],
+    pub vk_ic: Vec<[u8; 64]>,
+}
+
+impl VerifyingKeyAccount {
+    pub fn size(nr_pubinputs: u64) -> usize {
+        8 + 32 + 2 + 8 + 8 + 1 + 8 + 64 + 3 * 128 + 4 + (nr_pubinputs as usize + 1) * 64
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.vk_ic.len() == self.nr_pubinputs as usize + 1
+    }
+
+    pub fn is_active(&self, slot: u64) -> bool {
+        !self.deactivated && self.is_complete() && slot >= self.activation_slot
+    }
+
+    /// Appends vk_ic points, starts the timelock once the key is complete.
+    pub fn append_vk_ic(&mut self, vk_ic: &[[u8; 64]], slot: u64) -> Result<()> {
+        if self.is_complete() || self.vk_ic.len() + vk_ic.len() > self.nr_pubinputs as usize + 1 {
+            msg!(
+                "Verifying key expects {} vk_ic points, has {}.",
+                self.nr_pubinputs + 1,
+                self.vk_ic.len()
+            );
+            return err!(ErrorCode::InvalidVerifyingKey);
+        }
+        self.vk_ic.extend_from_slice(vk_ic);
+        if self.is_complete() {
+            self.activation_slot = slot + VERIFYING_KEY_TIMELOCK;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(verifier_pubkey: Pubkey, nr_inputs: u8, nr_outputs: u8, version: u64, nr_pubinputs: u64)]
+pub struct RegisterVerifyingKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        seeds = [
+            &verifier_pubkey.to_bytes(),
+            VERIFYING_KEY_SEED,
+            &[nr_inputs, nr_outputs],
+            version.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = VerifyingKeyAccount::size(nr_pubinputs)
+    )]
+    pub verifying_key_pda: Account<'info, VerifyingKeyAccount>,
+    #[account(seeds = [&verifier_pubkey.to_bytes()], bump)]
+    pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account(mut, address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateVerifyingKey<'info> {
+    #[account(mut)]
+    pub verifying_key_pda: Account<'info, VerifyingKeyAccount>,
+    /// CHECK:` Signer is merkle tree authority.
+    #[account(address=merkle_tree_authority_pda.pubkey @ErrorCode::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_register_verifying_key(
+    ctx: Context<RegisterVerifyingKey>,
+    verifier_pubkey: Pubkey,
+    nr_inputs: u8,
+    nr_outputs: u8,
+    version: u64,
+    nr_pubinputs: u64,
+    vk_alpha_g1: [u8; 64],
+    vk_beta_g2: [u8; 128],
+    vk_gamme_g2: [u8; 128],
+    vk_delta_g2: [u8; 128],
+) -> Result<()> {
+    if nr_pubinputs == 0 || nr_pubinputs > MAX_VERIFYING_KEY_PUBLIC_INPUTS {
+        msg!("Verifying key has {} public inputs.", nr_pubinputs);
+        return err!(ErrorCode::InvalidVerifyingKey);
+    }
+    ctx.accounts
+        .verifying_key_pda
+        .set_inner(VerifyingKeyAccount {
+            verifier: verifier_pubkey,
+            nr_inputs,
+            nr_outputs,
+            version,
+            activation_slot: u64::MAX,
+            deactivated: false,
+            nr_pubinputs,
+            vk_alpha_g1,
+            vk_beta_g2,
+            vk_gamme_g2,
+            vk_delta_g2,
+            vk_ic: Vec::with_capacity(nr_pubinputs as usize + 1),
+        });
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn verifying_key(nr_pubinputs: u64) -> VerifyingKeyAccount {
+        VerifyingKeyAccount {
+            verifier: Pubkey::new_unique(),
+            nr_inputs: 2,
+            nr_outputs: 2,
+            version: 0,
+            activation_slot: u64::MAX,
+            deactivated: false,
+            nr_pubinputs,
+            vk_alpha_g1: [0u8; 64],
+            vk_beta_g2: [0u8; 128],
+            vk_gamme_g2: [0u8; 128],
+            vk_delta_g2: [0u8; 128],
+            vk_ic: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_verifying_key_activation() {
+        let mut vk = verifying_key(2);
+        assert_eq!(
+            vk.try_to_vec().unwrap().len() + 8 + 3 * 64,
+            VerifyingKeyAccount::size(2)
+        );
+
+        vk.append_vk_ic(&[[1u8; 64]; 2], 10).unwrap();
+        assert!(!vk.is_active(u64::MAX - 1));
+        assert_eq!(
+            vk.append_vk_ic(&[[1u8; 64]; 2], 10),
+            err!(ErrorCode::InvalidVerifyingKey)
+        );
+
+        vk.append_vk_ic(&[[1u8; 64]], 10).unwrap();
+        assert_eq!(vk.activation_slot, 10 + VERIFYING_KEY_TIMELOCK);
+        assert!(!vk.is_active(10 + VERIFYING_KEY_TIMELOCK - 1));
+        assert!(vk.is_active(10 + VERIFYING_KEY_TIMELOCK));
+        assert_eq!(
+            vk.append_vk_ic(&[[1u8; 64]], 10),
+            err!(ErrorCode::InvalidVerifyingKey)
+        );
+
+        vk.deactivated = true;
+        assert!(!vk.is_active(10 + VERIFYING_KEY_TIMELOCK));
+    }
+}
@@ -46,4 +46,106 @@ pub enum ErrorCode {
     InvalidOldMerkleTree,
     #[msg("Provided old Merkle tree is not the newest one.")]
     NotNewestOldMerkleTree,
+    #[msg("Poseidon hash computation failed.")]
+    PoseidonHashFailed,
+    #[msg("Native Poseidon is not available, use the multi-instruction Merkle tree update.")]
+    NativePoseidonUnavailable,
+    #[msg("Merkle tree height is out of the supported range.")]
+    InvalidMerkleTreeHeight,
+    #[msg("Merkle tree is full.")]
+    MerkleTreeFull,
+    #[msg("Merkle tree is read-only, leaves have to be inserted into the newest Merkle tree.")]
+    MerkleTreeReadOnly,
+    #[msg("Merkle tree update state is outdated.")]
+    MerkleTreeUpdateStateOutdated,
+    #[msg("Nullifier queue is full.")]
+    NullifierQueueFull,
+    #[msg("Nullifier queue element is not pending insertion.")]
+    NullifierQueueElementNotPending,
+    #[msg("Nullifier queue does not belong to the nullifier tree.")]
+    InvalidNullifierQueue,
+    #[msg("Low element does not enclose the inserted value.")]
+    InvalidLowElement,
+    #[msg("Merkle proof does not match the current root.")]
+    InvalidMerkleProof,
+    #[msg("Number of nullifiers does not match the number of nullifier accounts.")]
+    InvalidNumberOfNullifiers,
+    #[msg("Verifying key is invalid or already complete.")]
+    InvalidVerifyingKey,
+    #[msg("Master edition account does not belong to the nft mint.")]
+    InvalidMasterEdition,
+    #[msg("Pool type does not match the asset type of the mint.")]
+    InvalidPoolType,
+    #[msg("Not enough multisig signers signed the transaction.")]
+    NotEnoughMultisigSignatures,
+    #[msg("Invalid multisig signers or threshold.")]
+    InvalidMultisigConfig,
+    #[msg("Verifier is paused or deregistered.")]
+    VerifierNotActive,
+    #[msg("Verifier is deregistered.")]
+    VerifierDeregistered,
+    #[msg("Verifier does not have the capability for this instruction.")]
+    MissingVerifierCapability,
+    #[msg("Verifier is not allowed to use this Merkle tree.")]
+    MerkleTreeNotAllowed,
+    #[msg("Verifier is not allowed to use this pool type.")]
+    PoolTypeNotAllowed,
+    #[msg("Invalid verifier capabilities.")]
+    InvalidVerifierCapabilities,
+    #[msg("Transactions are paused.")]
+    ProtocolPaused,
+    #[msg("Invalid pause flags.")]
+    InvalidPauseFlags,
+    #[msg("Deposit exceeds the deposit cap of the pool.")]
+    DepositCapExceeded,
+    #[msg("Delayed withdrawal queue of the pool is full.")]
+    DelayedWithdrawalQueueFull,
+    #[msg("No released delayed withdrawal for this recipient.")]
+    NoReleasedWithdrawal,
+    #[msg("Asset pool does not belong to the pool account.")]
+    InvalidAssetPool,
+    #[msg("Withdrawal window needs to be greater than zero.")]
+    InvalidWithdrawalWindow,
+    #[msg("Mint uses a token extension which is not supported by pools.")]
+    UnsupportedMintExtension,
+    #[msg("Merkle tree update state is not expired.")]
+    UpdateStateNotExpired,
+    #[msg("Relayer is not registered for this Merkle tree.")]
+    RelayerNotRegistered,
+    #[msg("Relayer is not whitelisted and its stake is below the minimum stake.")]
+    InsufficientRelayerStake,
+    #[msg("Relayer inserted the last root, other relayers have priority.")]
+    RelayerRotation,
+    #[msg("Relayer stake is locked.")]
+    RelayerStakeLocked,
+    #[msg("Invalid relayer stake amount.")]
+    InvalidRelayerStake,
+    #[msg("Leaves queue is full, the Merkle tree has to be updated first.")]
+    LeavesQueueFull,
+    #[msg("Leaves queue does not belong to the Merkle tree.")]
+    InvalidLeavesQueue,
+    #[msg("Invalid noop program key.")]
+    InvalidNoopPubkey,
+    #[msg("Leaves before the update state are not inserted into the Merkle tree yet.")]
+    LeavesBeforeUpdateNotInserted,
+    #[msg("Sequence number of the update state is ahead of the Merkle tree.")]
+    InvalidUpdateStateSequenceNumber,
+    #[msg("Start index of the update has to be a queued pair of leaves.")]
+    InvalidUpdateStartIndex,
+    #[msg("Withdrawal over the withdrawal limit is below the minimum delayed withdrawal.")]
+    DelayedWithdrawalTooSmall,
+    #[msg("Claim exceeds the withdrawal limit of the current window.")]
+    WithdrawalLimitExceeded,
+    #[msg("No delayed withdrawal for this recipient.")]
+    NoDelayedWithdrawal,
+    #[msg("Nullifier pdas are disabled, nullifiers are inserted into the nullifier tree.")]
+    NullifierPdasDisabled,
+    #[msg("Nullifier tree is not enabled.")]
+    NullifierTreeNotEnabled,
+    #[msg("Nullifier pda address does not match the nullifier.")]
+    InvalidNullifierPda,
+    #[msg("Account is not a transaction Merkle tree in the legacy layout.")]
+    InvalidLegacyMerkleTree,
+    #[msg("Queued leaves of the legacy Merkle tree have to be inserted before the migration.")]
+    LegacyMerkleTreeHasQueuedLeaves,
 }
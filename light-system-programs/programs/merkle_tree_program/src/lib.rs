@@ -17,6 +17,8 @@ pub mod event_merkle_tree;
 pub use event_merkle_tree::*;
 pub mod transaction_merkle_tree;
 pub use transaction_merkle_tree::*;
+pub mod nullifier_tree;
+pub use nullifier_tree::*;
 pub mod verifier_invoked_instructions;
 pub use verifier_invoked_instructions::*;
 pub mod errors;
@@ -29,9 +31,10 @@ pub use config_accounts::*;
 use crate::errors::ErrorCode;
 
 use crate::{
+    nullifier_tree::indexed_tree::IndexedElement,
     transaction_merkle_tree::state::TransactionMerkleTree,
     utils::{
-        config::{self, ZERO_BYTES_MERKLE_TREE_18},
+        config::{self, MERKLE_TREE_HEIGHT},
         constants::{EVENT_MERKLE_TREE_HEIGHT, TRANSACTION_MERKLE_TREE_SEED},
     },
 };
@@ -41,21 +44,19 @@ pub mod merkle_tree_program {
     use super::*;
 
     /// Initializes a new Merkle tree from config bytes.
-    /// Can only be called from the merkle_tree_authority.
+    /// Can only be called from the merkle_tree_authority, or by anyone once the
+    /// newest Merkle tree reached the rollover threshold or if permissionless Merkle
+    /// tree registration is enabled. A tree created by anyone else than the authority
+    /// has to use the height and lock duration of the old Merkle tree.
+    /// The height has to be within MERKLE_TREE_MIN_HEIGHT and MERKLE_TREE_MAX_HEIGHT.
+    /// The old Merkle tree is the first remaining account, multisig signers of the
+    /// authority follow it.
     pub fn initialize_new_transaction_merkle_tree(
         ctx: Context<InitializeNewTransactionMerkleTree>,
         lock_duration: u64,
+        height: u64,
     ) -> Result<()> {
-        if !ctx
-            .accounts
-            .merkle_tree_authority_pda
-            .enable_permissionless_merkle_tree_registration
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
-        {
-            return err!(ErrorCode::InvalidAuthority);
-        }
-
-        if ctx.remaining_accounts.len() != 1 {
+        if ctx.remaining_accounts.is_empty() {
             return err!(ErrorCode::ExpectedOldMerkleTree);
         }
 
@@ -76,23 +77,72 @@ pub mod merkle_tree_program {
         if old_merkle_tree.newest != 1 {
             return err!(ErrorCode::NotNewestOldMerkleTree);
         }
+
+        // Rollovers are always permissionless, otherwise new leaves could stall once the
+        // newest Merkle tree is full.
+        if ctx
+            .accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, &ctx.remaining_accounts[1..])
+            .is_err()
+        {
+            if !old_merkle_tree.is_rollover_threshold_reached()
+                && !ctx
+                    .accounts
+                    .merkle_tree_authority_pda
+                    .enable_permissionless_merkle_tree_registration
+            {
+                msg!(
+                    "Merkle tree {} has not reached the rollover threshold.",
+                    old_merkle_tree.merkle_tree_nr
+                );
+                return err!(ErrorCode::InvalidAuthority);
+            }
+            if height != old_merkle_tree.height || lock_duration != old_merkle_tree.lock_duration {
+                msg!("Rollover Merkle tree has to use the config of the old Merkle tree.");
+                return err!(ErrorCode::InvalidAuthority);
+            }
+        }
+        // The old Merkle tree is read-only for new leaves from now on.
         old_merkle_tree.newest = 0;
 
         let new_merkle_tree = &mut ctx.accounts.new_transaction_merkle_tree.load_init()?;
         let merkle_tree_authority = &mut ctx.accounts.merkle_tree_authority_pda;
 
-        process_initialize_new_merkle_tree_18(
+        process_initialize_new_transaction_merkle_tree(
             new_merkle_tree,
             merkle_tree_authority,
-            18,
-            ZERO_BYTES_MERKLE_TREE_18.to_vec(),
-        );
+            height,
+        )?;
 
         new_merkle_tree.lock_duration = lock_duration;
+        new_merkle_tree.leaf_fee = old_merkle_tree.leaf_fee;
+        ctx.accounts
+            .leaves_queue
+            .load_init()?
+            .transaction_merkle_tree = ctx.accounts.new_transaction_merkle_tree.key();
 
         Ok(())
     }
 
+    /// Migrates a transaction Merkle tree created before the account grew from 8880
+    /// to 9424 bytes, these do not deserialize anymore. Reallocates the account, the
+    /// authority pays the additional rent, and creates its leaves queue.
+    /// Can only be called from the merkle_tree_authority once all queued leaves of the
+    /// Merkle tree are inserted, multisig signers of the authority follow as remaining
+    /// accounts.
+    pub fn migrate_transaction_merkle_tree(
+        ctx: Context<MigrateTransactionMerkleTree>,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        process_migrate_transaction_merkle_tree(ctx)
+    }
+
+    /// Initializes a new event Merkle tree.
+    /// Can only be called from the merkle_tree_authority, or by anyone if permissionless
+    /// Merkle tree registration is enabled.
     pub fn initialize_new_event_merkle_tree(
         ctx: Context<InitializeNewEventMerkleTree>,
     ) -> Result<()> {
@@ -100,9 +150,10 @@ pub mod merkle_tree_program {
             .accounts
             .merkle_tree_authority_pda
             .enable_permissionless_merkle_tree_registration
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
         {
-            return err!(ErrorCode::InvalidAuthority);
+            ctx.accounts
+                .merkle_tree_authority_pda
+                .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         }
         let merkle_tree = &mut ctx.accounts.event_merkle_tree.load_init()?;
 
@@ -123,6 +174,29 @@ pub mod merkle_tree_program {
         Ok(())
     }
 
+    /// Initializes the nullifier tree and its nullifier queue.
+    /// Can only be called from the merkle_tree_authority, permissionless Merkle tree
+    /// registration does not apply to the single nullifier tree.
+    pub fn initialize_new_nullifier_tree(ctx: Context<InitializeNewNullifierTree>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        process_initialize_new_nullifier_tree(ctx)
+    }
+
+    /// Switches nullifier storage from nullifier pdas to the nullifier tree, can not be undone.
+    /// Verifiers which store nullifiers in pdas fail afterwards, the authority enables the
+    /// nullifier tree once these are migrated or deregistered.
+    pub fn enable_nullifier_tree(ctx: Context<EnableNullifierTree>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .nullifier_tree_enabled = true;
+        Ok(())
+    }
+
     /// Initializes a new merkle tree authority which can register new verifiers and configure
     /// permissions to create new pools.
     pub fn initialize_merkle_tree_authority(
@@ -132,28 +206,109 @@ pub mod merkle_tree_program {
 
         let merkle_tree = &mut ctx.accounts.transaction_merkle_tree.load_init()?;
         let merkle_tree_authority = &mut ctx.accounts.merkle_tree_authority_pda;
-        process_initialize_new_merkle_tree_18(
+        process_initialize_new_transaction_merkle_tree(
             merkle_tree,
             merkle_tree_authority,
-            18,
-            ZERO_BYTES_MERKLE_TREE_18.to_vec(),
-        );
+            MERKLE_TREE_HEIGHT,
+        )?;
+        ctx.accounts
+            .leaves_queue
+            .load_init()?
+            .transaction_merkle_tree = ctx.accounts.transaction_merkle_tree.key();
+
+        Ok(())
+    }
+
+    /// Proposes a new merkle tree authority, the new authority has to accept the
+    /// transfer with accept_merkle_tree_authority.
+    pub fn propose_merkle_tree_authority(ctx: Context<UpdateMerkleTreeAuthority>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.merkle_tree_authority_pda.pending_authority = ctx.accounts.new_authority.key();
+        Ok(())
+    }
 
+    /// Transfers the merkle tree authority to the pending authority.
+    pub fn accept_merkle_tree_authority(ctx: Context<AcceptMerkleTreeAuthority>) -> Result<()> {
+        // pending authority is checked in ctx struct
+        let merkle_tree_authority = &mut ctx.accounts.merkle_tree_authority_pda;
+        merkle_tree_authority.pubkey = merkle_tree_authority.pending_authority;
+        merkle_tree_authority.pending_authority = Pubkey::default();
         Ok(())
     }
 
-    /// Updates the merkle tree authority to a new authority.
-    pub fn update_merkle_tree_authority(ctx: Context<UpdateMerkleTreeAuthority>) -> Result<()> {
-        // account is checked in ctx struct
-        ctx.accounts.merkle_tree_authority_pda.pubkey = ctx.accounts.new_authority.key();
+    /// Cancels a proposed merkle tree authority transfer.
+    pub fn cancel_merkle_tree_authority_transfer(
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.merkle_tree_authority_pda.pending_authority = Pubkey::default();
         Ok(())
     }
 
+    /// Configures an M-of-N multisig, once configured authority instructions need the
+    /// signatures of threshold multisig signers passed in as remaining accounts.
+    /// A threshold of zero disables the multisig.
+    pub fn configure_multisig(
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .set_multisig(&signers, threshold)
+    }
+
+    /// Sets the guardian which can pause but not unpause transactions.
+    /// The default pubkey removes the guardian.
+    pub fn set_guardian(
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+        guardian: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.merkle_tree_authority_pda.guardian = guardian;
+        Ok(())
+    }
+
+    /// Pauses shields, unshields and or transfers, flags are defined on MerkleTreeAuthority.
+    /// Can be called by the merkle_tree_authority or the guardian.
+    /// Leaves and nullifiers are rejected if any kind is paused, the merkle tree program can
+    /// not tell the kind of a transaction. Only withdrawals distinguish kinds, unshields
+    /// pause withdrawals and claims, transfers pause withdrawals of relayer fees.
+    pub fn pause(ctx: Context<PauseProtocol>, flags: u8) -> Result<()> {
+        ctx.accounts.merkle_tree_authority_pda.pause(
+            &ctx.accounts.authority,
+            flags,
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Unpauses shields, unshields and or transfers.
+    /// Can only be called by the merkle_tree_authority.
+    pub fn unpause(ctx: Context<PauseProtocol>, flags: u8) -> Result<()> {
+        ctx.accounts.merkle_tree_authority_pda.unpause(
+            &ctx.accounts.authority,
+            flags,
+            ctx.remaining_accounts,
+        )
+    }
+
     /// Updates the lock duration for a specific merkle tree.
     pub fn update_lock_duration(
         ctx: Context<UpdateLockDuration>,
         lock_duration: u64,
     ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         ctx.accounts
             .transaction_merkle_tree
             .load_mut()?
@@ -161,13 +316,68 @@ pub mod merkle_tree_program {
         Ok(())
     }
 
-    /// Enables permissionless deposits of any spl token with supply of one and zero decimals.
+    /// Updates the fee paid into the fee vault of a Merkle tree for every inserted pair of leaves.
+    pub fn update_leaf_fee(ctx: Context<UpdateLockDuration>, leaf_fee: u64) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.transaction_merkle_tree.load_mut()?.leaf_fee = leaf_fee;
+        Ok(())
+    }
+
+    /// Restricts updates of a Merkle tree to registered relayers which are whitelisted or
+    /// staked at least min_relayer_stake lamports.
+    pub fn update_relayer_gating(
+        ctx: Context<UpdateLockDuration>,
+        gated_relayers: bool,
+        min_relayer_stake: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        let mut merkle_tree = ctx.accounts.transaction_merkle_tree.load_mut()?;
+        merkle_tree.gated_relayers = gated_relayers.into();
+        merkle_tree.min_relayer_stake = min_relayer_stake;
+        Ok(())
+    }
+
+    /// Registers the signer as relayer, registered relayers can be whitelisted or stake.
+    pub fn register_relayer(ctx: Context<RegisterRelayer>) -> Result<()> {
+        ctx.accounts.registered_relayer.relayer = ctx.accounts.relayer.key();
+        Ok(())
+    }
+
+    /// Adds lamports to the stake of a relayer, the stake is locked for RELAYER_UNSTAKE_DELAY slots.
+    pub fn stake_relayer(ctx: Context<UpdateRelayerStake>, amount: u64) -> Result<()> {
+        process_stake_relayer(ctx, amount)
+    }
+
+    /// Withdraws unlocked stake of a relayer.
+    pub fn unstake_relayer(ctx: Context<UpdateRelayerStake>, amount: u64) -> Result<()> {
+        process_unstake_relayer(ctx, amount)
+    }
+
+    /// Whitelists a registered relayer, whitelisted relayers do not need to stake.
+    pub fn whitelist_relayer(ctx: Context<WhitelistRelayer>, whitelisted: bool) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.registered_relayer.whitelisted = whitelisted;
+        Ok(())
+    }
+
+    /// Enables anyone to create nft pools for spl tokens with supply of one and zero decimals.
     pub fn enable_nfts(
-        _ctx: Context<UpdateMerkleTreeAuthorityConfig>,
-        _enable_permissionless: bool,
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+        enable_permissionless: bool,
     ) -> Result<()> {
-        // ctx.accounts.merkle_tree_authority_pda.enable_nfts = enable_permissionless;
-        unimplemented!();
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .set_permission(Permission::Nfts, enable_permissionless);
+        Ok(())
     }
 
     /// Enables anyone to create token pools.
@@ -177,31 +387,183 @@ pub mod merkle_tree_program {
     ) -> Result<()> {
         ctx.accounts
             .merkle_tree_authority_pda
-            .enable_permissionless_spl_tokens = enable_permissionless;
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .set_permission(Permission::SplTokens, enable_permissionless);
+        Ok(())
+    }
+
+    /// Enables anyone to create new transaction and event Merkle trees, new transaction
+    /// Merkle trees use the config of the old Merkle tree. The nullifier tree is always
+    /// created by the authority.
+    pub fn enable_permissionless_merkle_tree_registration(
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+        enable_permissionless: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .set_permission(Permission::MerkleTreeRegistration, enable_permissionless);
         Ok(())
     }
 
-    // Unactivated feature listed for completeness.
-    // pub fn enable_permissionless_merkle_tree_registration(ctx: Context<UpdateMerkleTreeAuthorityConfig>, enable_permissionless: bool) -> Result<()> {
-    //     ctx.accounts.merkle_tree_authority_pda.enable_permissionless_merkle_tree_registration = enable_permissionless;
-    //     Ok(())
-    // }
+    /// Enables anyone to register new verifiers.
+    pub fn enable_permissionless_verifier_registration(
+        ctx: Context<UpdateMerkleTreeAuthorityConfig>,
+        enable_permissionless: bool,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .set_permission(Permission::VerifierRegistration, enable_permissionless);
+        Ok(())
+    }
 
     /// Registers a new verifier which can withdraw tokens, insert new nullifiers, add new leaves.
     /// These functions can only be invoked from registered verifiers.
+    /// Verifiers registered by the authority get all capabilities, permissionless registrations
+    /// get none until the authority sets them with set verifier capabilities.
     pub fn register_verifier(
         ctx: Context<RegisterVerifier>,
         verifier_pubkey: Pubkey,
     ) -> Result<()> {
-        if !ctx
+        let capabilities = match ctx
             .accounts
             .merkle_tree_authority_pda
-            .enable_permissionless_merkle_tree_registration
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)
         {
-            return err!(ErrorCode::InvalidAuthority);
-        }
-        ctx.accounts.registered_verifier_pda.pubkey = verifier_pubkey;
+            Ok(()) => RegisteredVerifier::ALL_CAPABILITIES,
+            Err(_)
+                if ctx
+                    .accounts
+                    .merkle_tree_authority_pda
+                    .enable_permissionless_verifier_registration =>
+            {
+                0
+            }
+            Err(error) => return Err(error),
+        };
+        ctx.accounts
+            .registered_verifier_pda
+            .set_inner(RegisteredVerifier {
+                pubkey: verifier_pubkey,
+                status: VerifierStatus::Active,
+                capabilities,
+                allowed_pool_types: Vec::new(),
+                allowed_merkle_trees: Vec::new(),
+            });
+        Ok(())
+    }
+
+    /// Pauses a registered verifier, paused verifiers cannot invoke any verifier invoked
+    /// instruction until they are resumed.
+    pub fn pause_verifier(ctx: Context<UpdateRegisteredVerifier>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .registered_verifier_pda
+            .set_status(VerifierStatus::Paused)
+    }
+
+    /// Resumes a paused verifier.
+    pub fn resume_verifier(ctx: Context<UpdateRegisteredVerifier>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .registered_verifier_pda
+            .set_status(VerifierStatus::Active)
+    }
+
+    /// Permanently deregisters a verifier, a deregistered verifier cannot be resumed.
+    pub fn deregister_verifier(ctx: Context<UpdateRegisteredVerifier>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .registered_verifier_pda
+            .set_status(VerifierStatus::Deregistered)
+    }
+
+    /// Sets the capabilities of a registered verifier and restricts it to pool types and
+    /// Merkle trees. Empty lists allow any pool type or Merkle tree.
+    pub fn set_verifier_capabilities(
+        ctx: Context<UpdateRegisteredVerifier>,
+        capabilities: u8,
+        allowed_pool_types: Vec<[u8; 32]>,
+        allowed_merkle_trees: Vec<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.registered_verifier_pda.set_capabilities(
+            capabilities,
+            allowed_pool_types,
+            allowed_merkle_trees,
+        )
+    }
+
+    /// Registers a verifying key for transactions with `nr_inputs` input and `nr_outputs`
+    /// output utxos of a registered verifier. The vk_ic points are appended
+    /// with append_verifying_key_ic, the key becomes active VERIFYING_KEY_TIMELOCK slots
+    /// after the upload is complete.
+    /// Can only be called from the merkle_tree_authority.
+    #[allow(clippy::too_many_arguments)]
+    pub fn register_verifying_key(
+        ctx: Context<RegisterVerifyingKey>,
+        verifier_pubkey: Pubkey,
+        nr_inputs: u8,
+        nr_outputs: u8,
+        version: u64,
+        nr_pubinputs: u64,
+        vk_alpha_g1: [u8; 64],
+        vk_beta_g2: [u8; 128],
+        vk_gamme_g2: [u8; 128],
+        vk_delta_g2: [u8; 128],
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        process_register_verifying_key(
+            ctx,
+            verifier_pubkey,
+            nr_inputs,
+            nr_outputs,
+            version,
+            nr_pubinputs,
+            vk_alpha_g1,
+            vk_beta_g2,
+            vk_gamme_g2,
+            vk_delta_g2,
+        )
+    }
+
+    /// Appends vk_ic points to an incomplete verifying key.
+    pub fn append_verifying_key_ic(
+        ctx: Context<UpdateVerifyingKey>,
+        vk_ic: Vec<[u8; 64]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts
+            .verifying_key_pda
+            .append_vk_ic(&vk_ic, Clock::get()?.slot)
+    }
+
+    /// Deactivates a verifying key, for example if the circuit is compromised.
+    /// Deactivation takes effect immediately and cannot be reverted.
+    pub fn deactivate_verifying_key(ctx: Context<UpdateVerifyingKey>) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        ctx.accounts.verifying_key_pda.deactivated = true;
         Ok(())
     }
 
@@ -211,42 +573,51 @@ pub mod merkle_tree_program {
             .accounts
             .merkle_tree_authority_pda
             .enable_permissionless_spl_tokens
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
         {
-            return err!(ErrorCode::InvalidAuthority);
+            ctx.accounts
+                .merkle_tree_authority_pda
+                .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         }
         ctx.accounts.registered_pool_type_pda.pool_type = pool_type;
         Ok(())
     }
 
     /// Creates a new spl token pool which can be used by any registered verifier.
-    pub fn register_spl_pool(ctx: Context<RegisterSplPool>) -> Result<()> {
-        // let is_nft = false;
-        // ctx.accounts.mint.decimals == 0
-        // && ctx.accounts.mint.supply == 1
-        // should add check that authority is metaplex nft
-        // && metaplex_token_metadata::state::get_master_edition(&ctx.accounts.metaplex_token.to_account_info()).is_ok();
-        // msg!("is_nft {}", is_nft);
-        // nfts enabled
-        // if is_nft
-        //     && !ctx.accounts.merkle_tree_authority_pda.enable_nfts
-        //     && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
-        // {
-        //     return err!(ErrorCode::InvalidAuthority);
-        // }
-
-        // any token enabled
-        if !ctx
-            .accounts
-            .merkle_tree_authority_pda
-            .enable_permissionless_spl_tokens
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
-        {
-            return err!(ErrorCode::InvalidAuthority);
+    /// Nft pools are created for mints with supply one and zero decimals, the metaplex
+    /// master edition of the mint has to be passed in as remaining account.
+    pub fn register_spl_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, RegisterSplPool<'info>>,
+    ) -> Result<()> {
+        check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+        let is_nft = is_nft(&ctx.accounts.mint);
+        check_pool_type(is_nft, &ctx.accounts.registered_pool_type_pda.pool_type)?;
+
+        let permissionless = if is_nft {
+            let master_edition = ctx
+                .remaining_accounts
+                .first()
+                .ok_or(ErrorCode::InvalidMasterEdition)?;
+            check_master_edition(&ctx.accounts.mint.key(), master_edition)?;
+            // nfts enabled
+            ctx.accounts.merkle_tree_authority_pda.enable_nfts
+        } else {
+            // any token enabled
+            ctx.accounts
+                .merkle_tree_authority_pda
+                .enable_permissionless_spl_tokens
+        };
+        if !permissionless {
+            ctx.accounts
+                .merkle_tree_authority_pda
+                .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         }
 
         ctx.accounts.registered_asset_pool_pda.asset_pool_pubkey =
             ctx.accounts.merkle_tree_pda_token.key();
+        ctx.accounts.registered_asset_pool_pda.deposit_cap = u64::MAX;
+        ctx.accounts.registered_asset_pool_pda.withdrawal_limit = u64::MAX;
+        ctx.accounts.registered_asset_pool_pda.withdrawal_window =
+            config::DEFAULT_WITHDRAWAL_WINDOW;
         ctx.accounts.registered_asset_pool_pda.pool_type =
             ctx.accounts.registered_pool_type_pda.pool_type;
         ctx.accounts.registered_asset_pool_pda.index = ctx
@@ -265,13 +636,18 @@ pub mod merkle_tree_program {
             .accounts
             .merkle_tree_authority_pda
             .enable_permissionless_spl_tokens
-            && ctx.accounts.authority.key() != ctx.accounts.merkle_tree_authority_pda.pubkey
         {
-            return err!(ErrorCode::InvalidAuthority);
+            ctx.accounts
+                .merkle_tree_authority_pda
+                .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
         }
 
         ctx.accounts.registered_asset_pool_pda.asset_pool_pubkey =
             ctx.accounts.registered_asset_pool_pda.key();
+        ctx.accounts.registered_asset_pool_pda.deposit_cap = u64::MAX;
+        ctx.accounts.registered_asset_pool_pda.withdrawal_limit = u64::MAX;
+        ctx.accounts.registered_asset_pool_pda.withdrawal_window =
+            config::DEFAULT_WITHDRAWAL_WINDOW;
         ctx.accounts.registered_asset_pool_pda.pool_type =
             ctx.accounts.registered_pool_type_pda.pool_type;
         ctx.accounts.registered_asset_pool_pda.index = ctx
@@ -284,17 +660,62 @@ pub mod merkle_tree_program {
         Ok(())
     }
 
+    /// Updates the deposit cap, withdrawal limit and minimum delayed withdrawal of an asset pool.
+    pub fn update_asset_pool_limits(
+        ctx: Context<UpdateAssetPoolLimits>,
+        deposit_cap: u64,
+        withdrawal_limit: u64,
+        withdrawal_window: u64,
+        min_delayed_withdrawal: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        if withdrawal_window == 0 {
+            return err!(ErrorCode::InvalidWithdrawalWindow);
+        }
+        let asset_pool = &mut ctx.accounts.registered_asset_pool_pda;
+        asset_pool.deposit_cap = deposit_cap;
+        asset_pool.withdrawal_limit = withdrawal_limit;
+        asset_pool.withdrawal_window = withdrawal_window;
+        asset_pool.min_delayed_withdrawal = min_delayed_withdrawal;
+        Ok(())
+    }
+
+    /// Cancels all delayed withdrawals of a recipient, the amounts remain in the pool.
+    /// Can only be called from the merkle_tree_authority.
+    pub fn cancel_delayed_withdrawal(
+        ctx: Context<CancelDelayedWithdrawal>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .merkle_tree_authority_pda
+            .check_authority(&ctx.accounts.authority, ctx.remaining_accounts)?;
+        let amount = ctx
+            .accounts
+            .registered_asset_pool_pda
+            .cancel_withdrawals(&recipient)?;
+        msg!(
+            "Cancelled delayed withdrawals of {} to {}.",
+            amount,
+            recipient
+        );
+        Ok(())
+    }
+
     /// Initializes a merkle tree update state pda. This pda stores the leaves to be inserted
     /// and state of the computation of poseidon hashes to update the Merkle tree.
-    /// A maximum of 16 pairs of leaves can be passed in as leaves accounts as remaining accounts.
-    /// Every leaf is copied into this account such that no further accounts or data have to be
+    /// Up to 16 pairs of leaves are read from the leaves queue, starting at `start_index` or
+    /// the next index of the Merkle tree. Leaves before `start_index` are inserted by other
+    /// relayers in parallel. Every leaf is copied into this account such that no further accounts or data have to be
     /// passed in during the following instructions which compute the poseidon hashes to update the tree.
     /// The hashes are computed with the update merkle tree instruction and the new root is inserted
     /// with the insert root merkle tree instruction.
     pub fn initialize_merkle_tree_update_state<'info>(
         ctx: Context<'_, '_, '_, 'info, InitializeUpdateState<'info>>,
+        start_index: Option<u64>,
     ) -> Result<()> {
-        process_initialize_update_state(ctx)
+        process_initialize_update_state(ctx, start_index)
     }
 
     /// Computes poseidon hashes to update the Merkle tree.
@@ -307,6 +728,7 @@ pub mod merkle_tree_program {
 
     /// This is the last step of a Merkle tree update which inserts the prior computed Merkle tree
     /// root.
+    /// The new root and the inserted leaves are logged as a RootUpdateEvent through the noop program.
     pub fn insert_root_merkle_tree<'info>(
         mut ctx: Context<'_, '_, '_, 'info, InsertRoot<'info>>,
         _bump: u64,
@@ -314,25 +736,48 @@ pub mod merkle_tree_program {
         process_insert_root(&mut ctx)
     }
 
+    /// Inserts up to 16 pairs of leaves and the resulting root in one instruction.
+    /// Hashes are computed with the Poseidon syscall, if the runtime does not provide it
+    /// the multi-instruction update (initialize_merkle_tree_update_state,
+    /// update_transaction_merkle_tree, insert_root_merkle_tree) has to be used.
+    /// Leaves are read from the leaves queue of the Merkle tree, the new root is logged
+    /// through the noop program.
+    pub fn batch_update_transaction_merkle_tree<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchUpdateTransactionMerkleTree<'info>>,
+    ) -> Result<()> {
+        process_batch_update_merkle_tree(ctx)
+    }
+
     /// Closes the Merkle tree update state.
-    /// A relayer can only close its own update state account.
+    /// A relayer can close its own update state account, expired update states can be closed
+    /// by anyone for a share of the rent.
     pub fn close_merkle_tree_update_state(
-        _ctx: Context<CloseUpdateState>,
+        ctx: Context<CloseUpdateState>,
     ) -> anchor_lang::Result<()> {
-        Ok(())
+        process_close_update_state(ctx)
+    }
+
+    /// Takes over an expired Merkle tree update state of another relayer.
+    /// The update continues from the saved instruction index.
+    pub fn take_over_merkle_tree_update_state(
+        ctx: Context<TakeOverUpdateState>,
+    ) -> anchor_lang::Result<()> {
+        process_take_over_update_state(ctx)
     }
 
-    /// Creates and initializes a pda which stores two merkle tree leaves and encrypted Utxos.
+    /// Appends two merkle tree leaves to the leaves queue of the Merkle tree.
     /// The inserted leaves are not part of the Merkle tree yet and marked accordingly.
     /// The Merkle tree has to be updated after.
     /// Can only be called from a registered verifier program.
+    /// `first_leaves` is set for the first pair of leaves of a transaction, it counts
+    /// the transaction in the sequence number of the Merkle tree.
     pub fn insert_two_leaves<'info>(
         ctx: Context<'_, '_, '_, 'info, InsertTwoLeaves<'info>>,
         leaf_left: [u8; 32],
         leaf_right: [u8; 32],
-        encrypted_utxo: [u8; 256],
+        first_leaves: bool,
     ) -> Result<()> {
-        process_insert_two_leaves(ctx, leaf_left, leaf_right, encrypted_utxo)
+        process_insert_two_leaves(ctx, leaf_left, leaf_right, first_leaves)
     }
 
     pub fn insert_two_leaves_event<'info>(
@@ -346,20 +791,18 @@ pub mod merkle_tree_program {
     /// Withdraws sol from a liquidity pool.
     /// An arbitrary number of recipients can be passed in with remaining accounts.
     /// Can only be called from a registered verifier program.
+    /// Withdrawals over the withdrawal limit of the pool are delayed.
     pub fn withdraw_sol<'info>(
         ctx: Context<'_, '_, '_, 'info, WithdrawSol<'info>>,
         amount: u64,
     ) -> Result<()> {
-        process_sol_transfer(
-            &ctx.accounts.merkle_tree_token.to_account_info(),
-            &ctx.accounts.recipient.to_account_info(),
-            amount,
-        )
+        process_withdraw_sol(ctx, amount)
     }
 
     /// Withdraws spl tokens from a liquidity pool.
     /// An arbitrary number of recipients can be passed in with remaining accounts.
     /// Can only be called from a registered verifier program.
+    /// Withdrawals over the withdrawal limit of the pool are delayed.
     pub fn withdraw_spl<'info>(
         ctx: Context<'_, '_, '_, 'info, WithdrawSpl<'info>>,
         amount: u64,
@@ -367,10 +810,54 @@ pub mod merkle_tree_program {
         process_spl_transfer(ctx, amount)
     }
 
+    /// Pays out the released delayed sol withdrawals of a recipient.
+    /// Claims count against the withdrawal limit of the pool.
+    pub fn claim_delayed_sol_withdrawal(ctx: Context<ClaimDelayedSolWithdrawal>) -> Result<()> {
+        process_claim_delayed_sol_withdrawal(ctx)
+    }
+
+    /// Pays out the released delayed spl withdrawals of a recipient token account.
+    /// Claims count against the withdrawal limit of the pool.
+    pub fn claim_delayed_spl_withdrawal(ctx: Context<ClaimDelayedSplWithdrawal>) -> Result<()> {
+        process_claim_delayed_spl_withdrawal(ctx)
+    }
+
+    /// Creates a pda for every nullifier, fails if a nullifier is spent already.
+    /// Can only be called from a registered verifier program, fails once the nullifier
+    /// tree is enabled.
     pub fn initialize_nullifiers<'info>(
         ctx: Context<'_, '_, '_, 'info, InitializeNullifiers<'info>>,
         nullifiers: Vec<[u8; 32]>,
     ) -> Result<()> {
         process_insert_nullifiers(ctx, nullifiers)
     }
+
+    /// Queues nullifiers for insertion into the nullifier tree, fails if a nullifier
+    /// is queued already or has a nullifier pda.
+    /// Can only be called from a registered verifier program.
+    pub fn insert_nullifiers_into_queue<'info>(
+        ctx: Context<'_, '_, '_, 'info, InsertNullifiersIntoQueue<'info>>,
+        nullifiers: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        process_insert_nullifiers_into_queue(ctx, nullifiers)
+    }
+
+    /// Inserts a queued nullifier into the nullifier tree. The low element is the element
+    /// which encloses the nullifier, it is proven against the current root with a Merkle proof.
+    /// Can be called by anyone, hashes are computed with the Poseidon syscall.
+    pub fn insert_nullifier_into_tree(
+        ctx: Context<InsertNullifierIntoTree>,
+        queue_index: u64,
+        low_element: IndexedElement,
+        low_element_index: u64,
+        low_element_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        process_insert_nullifier_into_tree(
+            ctx,
+            queue_index,
+            low_element,
+            low_element_index,
+            low_element_proof,
+        )
+    }
 }
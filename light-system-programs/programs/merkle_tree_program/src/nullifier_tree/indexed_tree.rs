@@ -0,0 +1,369 @@
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::update_merkle_tree_lib::poseidon_native::{
+    poseidon_native, poseidon_native_hashv,
+};
+use crate::utils::config::{
+    NULLIFIER_QUEUE_SIZE, NULLIFIER_TREE_HEIGHT, NULLIFIER_TREE_HISTORY_SIZE,
+    ZERO_BYTES_MERKLE_TREE,
+};
+use anchor_lang::prelude::*;
+
+/// Indexed Merkle tree of nullifiers. Its elements form a linked list sorted
+/// by value, a nullifier is not part of the tree if an element encloses it.
+/// Nullifiers are big endian, nodes and roots little endian.
+#[account(zero_copy)]
+#[derive(Eq, PartialEq, Debug)]
+pub struct NullifierTree {
+    pub nullifier_queue: Pubkey,
+    /// Only the first `height` subtrees are used.
+    pub filled_subtrees: [[u8; 32]; NULLIFIER_TREE_HEIGHT as usize],
+    pub roots: [[u8; 32]; NULLIFIER_TREE_HISTORY_SIZE as usize],
+    pub height: u64,
+    pub current_root_index: u64,
+    pub next_index: u64,
+    /// Number of inserted nullifiers.
+    pub sequence_number: u64,
+}
+
+/// Element of the nullifier tree, points to the element with the next higher value.
+/// The element with the highest value points to zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct IndexedElement {
+    pub value: [u8; 32],
+    pub next_index: u64,
+    pub next_value: [u8; 32],
+}
+
+impl IndexedElement {
+    /// Leaf of the element, the Poseidon hash of value, next index and next value.
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        let mut next_index = [0u8; 32];
+        next_index[..8].copy_from_slice(&self.next_index.to_le_bytes());
+        poseidon_native_hashv(&[&to_le(&self.value), &next_index, &to_le(&self.next_value)])
+    }
+
+    /// Whether `value` lies between this element and the next one.
+    pub fn encloses(&self, value: &[u8; 32]) -> bool {
+        self.value < *value && (self.next_value == [0u8; 32] || *value < self.next_value)
+    }
+}
+
+fn to_le(value: &[u8; 32]) -> [u8; 32] {
+    let mut value = *value;
+    value.reverse();
+    value
+}
+
+impl NullifierTree {
+    /// Initializes the tree with the zero element, every nullifier is enclosed
+    /// by an element from then on.
+    pub fn init(&mut self, nullifier_queue: Pubkey, height: u64) -> Result<()> {
+        if height == 0 || height > NULLIFIER_TREE_HEIGHT {
+            msg!(
+                "Nullifier tree height {} is not in range 1..={}",
+                height,
+                NULLIFIER_TREE_HEIGHT
+            );
+            return err!(ErrorCode::InvalidMerkleTreeHeight);
+        }
+        self.nullifier_queue = nullifier_queue;
+        self.height = height;
+        self.roots[0] = self.append(&IndexedElement::default())?;
+        Ok(())
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.roots[usize::try_from(self.current_root_index).unwrap()]
+    }
+
+    /// Inserts `value` into the tree. `low_element` is the element at
+    /// `low_element_index` which encloses `value`, `proof` its Merkle proof
+    /// against the current root.
+    pub fn insert(
+        &mut self,
+        value: [u8; 32],
+        low_element: &IndexedElement,
+        low_element_index: u64,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        if self.next_index >= 1 << self.height {
+            return err!(ErrorCode::MerkleTreeFull);
+        }
+        if low_element_index >= self.next_index || !low_element.encloses(&value) {
+            msg!(
+                "Element {} does not enclose value {:?}",
+                low_element_index,
+                value
+            );
+            return err!(ErrorCode::InvalidLowElement);
+        }
+        if proof.len() != usize::try_from(self.height).unwrap()
+            || self.compute_root(low_element.hash()?, low_element_index, proof)? != self.root()
+        {
+            return err!(ErrorCode::InvalidMerkleProof);
+        }
+
+        let new_element = IndexedElement {
+            value,
+            next_index: low_element.next_index,
+            next_value: low_element.next_value,
+        };
+        let low_element = IndexedElement {
+            value: low_element.value,
+            next_index: self.next_index,
+            next_value: value,
+        };
+
+        // Updates the path of the low element up to where it meets the path of
+        // the new element. There the low element path node is the left sibling
+        // of the new element path, nodes above are recomputed by the append.
+        let mut node = low_element.hash()?;
+        let mut index = low_element_index;
+        let mut next_index = self.next_index;
+        for (level, sibling) in proof.iter().enumerate() {
+            if next_index % 2 == 1 && index == next_index - 1 {
+                self.filled_subtrees[level] = node;
+                break;
+            }
+            node = if index % 2 == 0 {
+                poseidon_native(&node, sibling)?
+            } else {
+                poseidon_native(sibling, &node)?
+            };
+            index /= 2;
+            next_index /= 2;
+        }
+
+        let root = self.append(&new_element)?;
+        self.sequence_number += 1;
+        self.current_root_index = (self.current_root_index + 1) % NULLIFIER_TREE_HISTORY_SIZE;
+        self.roots[usize::try_from(self.current_root_index).unwrap()] = root;
+        Ok(())
+    }
+
+    fn compute_root(&self, leaf: [u8; 32], index: u64, proof: &[[u8; 32]]) -> Result<[u8; 32]> {
+        let mut node = leaf;
+        let mut index = index;
+        for sibling in proof.iter() {
+            node = if index % 2 == 0 {
+                poseidon_native(&node, sibling)?
+            } else {
+                poseidon_native(sibling, &node)?
+            };
+            index /= 2;
+        }
+        Ok(node)
+    }
+
+    /// Appends `element` at the next index and returns the new root.
+    fn append(&mut self, element: &IndexedElement) -> Result<[u8; 32]> {
+        let mut node = element.hash()?;
+        let mut index = self.next_index;
+        let height = usize::try_from(self.height).unwrap();
+        for (filled_subtree, zero_bytes) in self
+            .filled_subtrees
+            .iter_mut()
+            .zip(ZERO_BYTES_MERKLE_TREE.iter())
+            .take(height)
+        {
+            if index % 2 == 0 {
+                *filled_subtree = node;
+                node = poseidon_native(&node, zero_bytes)?;
+            } else {
+                node = poseidon_native(filled_subtree, &node)?;
+            }
+            index /= 2;
+        }
+        self.next_index += 1;
+        Ok(node)
+    }
+}
+
+/// Nullifiers inserted by verifier programs, they are inserted into the
+/// nullifier tree afterwards. Inserted nullifiers stay in the queue until all
+/// roots in the history of the nullifier tree contain them.
+#[account(zero_copy)]
+#[derive(Eq, PartialEq, Debug)]
+pub struct NullifierQueue {
+    pub nullifier_tree: Pubkey,
+    pub elements: [NullifierQueueElement; NULLIFIER_QUEUE_SIZE as usize],
+}
+
+#[zero_copy]
+#[derive(Eq, PartialEq, Debug)]
+pub struct NullifierQueueElement {
+    /// Zero if the element is empty.
+    pub value: [u8; 32],
+    /// Sequence number of the nullifier tree after the nullifier was inserted,
+    /// zero while it is pending.
+    pub sequence_number: u64,
+}
+
+impl NullifierQueueElement {
+    fn is_free(&self, tree_sequence_number: u64) -> bool {
+        self.value == [0u8; 32]
+            || (self.sequence_number != 0
+                && tree_sequence_number >= self.sequence_number + NULLIFIER_TREE_HISTORY_SIZE)
+    }
+}
+
+impl NullifierQueue {
+    /// Queues `value`, fails if the nullifier is queued already.
+    pub fn insert(&mut self, value: [u8; 32], tree_sequence_number: u64) -> Result<()> {
+        // Zero is the first element of every nullifier tree.
+        if value == [0u8; 32] {
+            return err!(ErrorCode::NullifierAlreadyExists);
+        }
+        let mut free_index = None;
+        for (i, element) in self.elements.iter().enumerate() {
+            if element.value == value {
+                msg!("Nullifier {:?} is queued already", value);
+                return err!(ErrorCode::NullifierAlreadyExists);
+            }
+            if free_index.is_none() && element.is_free(tree_sequence_number) {
+                free_index = Some(i);
+            }
+        }
+        match free_index {
+            Some(i) => {
+                self.elements[i] = NullifierQueueElement {
+                    value,
+                    sequence_number: 0,
+                };
+                Ok(())
+            }
+            None => err!(ErrorCode::NullifierQueueFull),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST_TREE_HEIGHT: usize = 4;
+
+    fn value(byte: u8) -> [u8; 32] {
+        let mut value = [0u8; 32];
+        value[31] = byte;
+        value
+    }
+
+    /// Computes all levels of a tree with the given elements.
+    fn levels(elements: &[IndexedElement]) -> Vec<Vec<[u8; 32]>> {
+        let mut level: Vec<[u8; 32]> = (0..1 << TEST_TREE_HEIGHT)
+            .map(|i| match elements.get(i) {
+                Some(element) => element.hash().unwrap(),
+                None => ZERO_BYTES_MERKLE_TREE[0],
+            })
+            .collect();
+        let mut levels = vec![level.clone()];
+        for _ in 0..TEST_TREE_HEIGHT {
+            level = level
+                .chunks(2)
+                .map(|pair| poseidon_native(&pair[0], &pair[1]).unwrap())
+                .collect();
+            levels.push(level.clone());
+        }
+        levels
+    }
+
+    fn proof(elements: &[IndexedElement], index: usize) -> Vec<[u8; 32]> {
+        levels(elements)[..TEST_TREE_HEIGHT]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| nodes[(index >> level) ^ 1])
+            .collect()
+    }
+
+    /// Inserts `value` into the tree and the reference elements.
+    fn insert(tree: &mut NullifierTree, elements: &mut Vec<IndexedElement>, value: [u8; 32]) {
+        let low_index = elements.iter().position(|e| e.encloses(&value)).unwrap();
+        let low_element = elements[low_index];
+        tree.insert(
+            value,
+            &low_element,
+            low_index as u64,
+            &proof(elements, low_index),
+        )
+        .unwrap();
+
+        elements.push(IndexedElement {
+            value,
+            next_index: low_element.next_index,
+            next_value: low_element.next_value,
+        });
+        elements[low_index].next_index = elements.len() as u64 - 1;
+        elements[low_index].next_value = value;
+        assert_eq!(tree.root(), levels(elements)[TEST_TREE_HEIGHT][0]);
+    }
+
+    #[test]
+    fn test_account_sizes() {
+        assert_eq!(8 + std::mem::size_of::<NullifierTree>(), 1928);
+        assert_eq!(8 + std::mem::size_of::<NullifierQueue>(), 5160);
+    }
+
+    #[test]
+    fn test_nullifier_tree_insert() {
+        let mut tree: NullifierTree = bytemuck::Zeroable::zeroed();
+        tree.init(Pubkey::default(), TEST_TREE_HEIGHT as u64)
+            .unwrap();
+        let mut elements = vec![IndexedElement::default()];
+        assert_eq!(tree.root(), levels(&elements)[TEST_TREE_HEIGHT][0]);
+
+        for byte in [30, 10, 20, 40, 11, 1, 255, 39] {
+            insert(&mut tree, &mut elements, value(byte));
+        }
+        assert_eq!(tree.sequence_number, 8);
+        assert_eq!(tree.next_index, 9);
+
+        // 20 is part of the tree, it is not enclosed by any element.
+        let low_element = elements[3];
+        assert_eq!(
+            tree.insert(value(20), &low_element, 3, &proof(&elements, 3)),
+            err!(ErrorCode::InvalidLowElement)
+        );
+        // The low element has to match the current root.
+        let low_element = IndexedElement {
+            next_value: value(35),
+            ..elements[3]
+        };
+        assert_eq!(
+            tree.insert(value(25), &low_element, 3, &proof(&elements, 3)),
+            err!(ErrorCode::InvalidMerkleProof)
+        );
+    }
+
+    #[test]
+    fn test_nullifier_queue_insert() {
+        let mut queue: NullifierQueue = bytemuck::Zeroable::zeroed();
+        assert_eq!(
+            queue.insert([0u8; 32], 0),
+            err!(ErrorCode::NullifierAlreadyExists)
+        );
+        for i in 0..NULLIFIER_QUEUE_SIZE {
+            queue.insert(value(i as u8 + 1), 0).unwrap();
+        }
+        assert_eq!(
+            queue.insert(value(1), 0),
+            err!(ErrorCode::NullifierAlreadyExists)
+        );
+        assert_eq!(
+            queue.insert(value(200), 0),
+            err!(ErrorCode::NullifierQueueFull)
+        );
+
+        // The first nullifier was inserted into the tree with sequence number 1,
+        // its element is free once no root in the history misses it.
+        queue.elements[0].sequence_number = 1;
+        assert_eq!(
+            queue.insert(value(200), NULLIFIER_TREE_HISTORY_SIZE),
+            err!(ErrorCode::NullifierQueueFull)
+        );
+        queue
+            .insert(value(200), NULLIFIER_TREE_HISTORY_SIZE + 1)
+            .unwrap();
+        assert_eq!(queue.elements[0].value, value(200));
+    }
+}
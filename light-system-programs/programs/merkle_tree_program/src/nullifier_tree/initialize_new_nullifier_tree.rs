@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::nullifier_tree::indexed_tree::{NullifierQueue, NullifierTree};
+use crate::utils::config::NULLIFIER_TREE_HEIGHT;
+use crate::utils::constants::{
+    MERKLE_TREE_AUTHORITY_SEED, NULLIFIER_QUEUE_SEED, NULLIFIER_TREE_SEED,
+};
+use crate::MerkleTreeAuthority;
+
+/// Creates the nullifier tree, there is a single nullifier tree since non-inclusion
+/// has to be proven against every spent nullifier.
+#[derive(Accounts)]
+pub struct InitializeNewNullifierTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        seeds = [NULLIFIER_TREE_SEED],
+        bump,
+        payer = authority,
+        space = 1928
+    )]
+    pub nullifier_tree: AccountLoader<'info, NullifierTree>,
+    #[account(
+        init,
+        seeds = [NULLIFIER_QUEUE_SEED],
+        bump,
+        payer = authority,
+        space = 5160
+    )]
+    pub nullifier_queue: AccountLoader<'info, NullifierQueue>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+pub fn process_initialize_new_nullifier_tree(
+    ctx: Context<InitializeNewNullifierTree>,
+) -> Result<()> {
+    let nullifier_queue_key = ctx.accounts.nullifier_queue.key();
+    let nullifier_tree_key = ctx.accounts.nullifier_tree.key();
+
+    ctx.accounts
+        .nullifier_tree
+        .load_init()?
+        .init(nullifier_queue_key, NULLIFIER_TREE_HEIGHT)?;
+    ctx.accounts.nullifier_queue.load_init()?.nullifier_tree = nullifier_tree_key;
+    Ok(())
+}
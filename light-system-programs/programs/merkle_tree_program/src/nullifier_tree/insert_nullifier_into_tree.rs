@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::nullifier_tree::indexed_tree::{IndexedElement, NullifierQueue, NullifierTree};
+
+#[derive(Accounts)]
+pub struct InsertNullifierIntoTree<'info> {
+    #[account(mut, has_one = nullifier_tree @ErrorCode::InvalidNullifierQueue)]
+    pub nullifier_queue: AccountLoader<'info, NullifierQueue>,
+    #[account(mut)]
+    pub nullifier_tree: AccountLoader<'info, NullifierTree>,
+}
+
+pub fn process_insert_nullifier_into_tree(
+    ctx: Context<InsertNullifierIntoTree>,
+    queue_index: u64,
+    low_element: IndexedElement,
+    low_element_index: u64,
+    low_element_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let mut nullifier_queue = ctx.accounts.nullifier_queue.load_mut()?;
+    let mut nullifier_tree = ctx.accounts.nullifier_tree.load_mut()?;
+
+    let element = match nullifier_queue
+        .elements
+        .get_mut(usize::try_from(queue_index).unwrap())
+    {
+        Some(element) if element.value != [0u8; 32] && element.sequence_number == 0 => element,
+        _ => {
+            msg!("Nullifier queue element {} is not pending", queue_index);
+            return err!(ErrorCode::NullifierQueueElementNotPending);
+        }
+    };
+
+    nullifier_tree.insert(
+        element.value,
+        &low_element,
+        low_element_index,
+        &low_element_proof,
+    )?;
+    element.sequence_number = nullifier_tree.sequence_number;
+    Ok(())
+}
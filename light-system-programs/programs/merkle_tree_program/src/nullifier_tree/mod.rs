@@ -0,0 +1,7 @@
+pub mod indexed_tree;
+
+pub mod initialize_new_nullifier_tree;
+pub use initialize_new_nullifier_tree::*;
+
+pub mod insert_nullifier_into_tree;
+pub use insert_nullifier_into_tree::*;
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::{leaves_queue::LeavesQueue, state::TransactionMerkleTree};
+use crate::utils::config::{
+    DEFAULT_LEAF_FEE, MERKLE_TREE_MAX_HEIGHT, MERKLE_TREE_MIN_HEIGHT, ZERO_BYTES_MERKLE_TREE,
+};
+use crate::utils::constants::{
+    LEAVES_QUEUE_SEED, MERKLE_TREE_AUTHORITY_SEED, TRANSACTION_MERKLE_TREE_SEED,
+};
+use crate::MerkleTreeAuthority;
+use anchor_lang::solana_program::{msg, pubkey::Pubkey};
+use std::cell::RefMut;
+
+#[derive(Accounts)]
+pub struct InitializeNewTransactionMerkleTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: it should be unpacked internally
+    #[account(
+        init,
+        seeds = [
+            TRANSACTION_MERKLE_TREE_SEED,
+            merkle_tree_authority_pda.transaction_merkle_tree_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = 9424
+    )]
+    pub new_transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    #[account(
+        init,
+        seeds = [new_transaction_merkle_tree.key().to_bytes().as_ref(), LEAVES_QUEUE_SEED],
+        bump,
+        payer = authority,
+        space = 8232
+    )]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(mut, seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+// keeps track of leaves which have been queued but not inserted into the merkle tree yet
+#[account]
+pub struct MerkleTreePdaToken {}
+
+// keeps track of leaves which have been queued but not inserted into the merkle tree yet
+#[account]
+pub struct PreInsertedLeavesIndex {
+    pub next_index: u64,
+}
+
+pub fn process_initialize_new_transaction_merkle_tree(
+    merkle_tree: &mut RefMut<'_, TransactionMerkleTree>,
+    merkle_tree_authority: &mut Account<'_, MerkleTreeAuthority>,
+    height: u64,
+) -> Result<()> {
+    if !(MERKLE_TREE_MIN_HEIGHT..=MERKLE_TREE_MAX_HEIGHT).contains(&height) {
+        msg!(
+            "Merkle tree height {} is not in range {}..={}",
+            height,
+            MERKLE_TREE_MIN_HEIGHT,
+            MERKLE_TREE_MAX_HEIGHT
+        );
+        return err!(ErrorCode::InvalidMerkleTreeHeight);
+    }
+    let height_usize = usize::try_from(height).unwrap();
+
+    merkle_tree.newest = 1;
+    merkle_tree.leaf_fee = DEFAULT_LEAF_FEE;
+
+    merkle_tree.filled_subtrees[..height_usize]
+        .copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..height_usize]);
+
+    merkle_tree.height = height;
+    merkle_tree.merkle_tree_nr = merkle_tree_authority.transaction_merkle_tree_index;
+    merkle_tree.roots[0] = ZERO_BYTES_MERKLE_TREE[height_usize];
+    msg!(
+        "merkle_tree_state_data.roots[0]: {:?}",
+        merkle_tree.roots[0]
+    );
+
+    merkle_tree_authority.transaction_merkle_tree_index += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::config::{MAX_MULTISIG_SIGNERS, MERKLE_TREE_HEIGHT};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_init_merkle_tree() {
+        let merkle_tree_authority = MerkleTreeAuthority {
+            pubkey: Pubkey::new_unique(),
+            transaction_merkle_tree_index: 0,
+            event_merkle_tree_index: 0,
+            registered_asset_index: 0,
+            enable_nfts: false,
+            enable_permissionless_spl_tokens: false,
+            enable_permissionless_merkle_tree_registration: false,
+            enable_permissionless_verifier_registration: false,
+            pending_authority: Pubkey::default(),
+            multisig_threshold: 0,
+            nr_multisig_signers: 0,
+            multisig_signers: [Pubkey::default(); MAX_MULTISIG_SIGNERS],
+            guardian: Pubkey::default(),
+            paused: 0,
+            nullifier_tree_enabled: false,
+        };
+        let mut data = Vec::new();
+        merkle_tree_authority.try_serialize(&mut data).unwrap();
+        let key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let mut merkle_tree_authority =
+            Account::<MerkleTreeAuthority>::try_from(&account_info).unwrap();
+
+        let mt: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        let binding = &mut RefCell::new(mt);
+        let mut ref_mt = binding.borrow_mut();
+
+        for height in [MERKLE_TREE_MIN_HEIGHT - 1, MERKLE_TREE_MAX_HEIGHT + 1] {
+            assert!(process_initialize_new_transaction_merkle_tree(
+                &mut ref_mt,
+                &mut merkle_tree_authority,
+                height,
+            )
+            .is_err());
+        }
+
+        process_initialize_new_transaction_merkle_tree(
+            &mut ref_mt,
+            &mut merkle_tree_authority,
+            MERKLE_TREE_HEIGHT,
+        )
+        .unwrap();
+
+        assert_eq!(ref_mt.height, 18, "height inited wrong");
+        assert_eq!(ref_mt.merkle_tree_nr, 0, "merkle_tree_nr inited wrong");
+        assert_eq!(ref_mt.newest, 1, "newest inited wrong");
+        assert_eq!(ref_mt.sequence_number, 0, "sequence_number inited wrong");
+        assert_eq!(ref_mt.next_index, 0, "next_index inited wrong");
+        assert_eq!(
+            ref_mt.current_root_index, 0,
+            "current_root_index inited wrong"
+        );
+        assert_eq!(
+            ref_mt.roots[0], ZERO_BYTES_MERKLE_TREE[18],
+            "root inited wrong"
+        );
+        assert_eq!(
+            ref_mt.filled_subtrees[..18],
+            ZERO_BYTES_MERKLE_TREE[..18],
+            "filled_subtrees inited wrong"
+        );
+        assert_eq!(merkle_tree_authority.transaction_merkle_tree_index, 1);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::state::TransactionMerkleTree;
+use crate::utils::config::LEAVES_QUEUE_SIZE;
+use anchor_lang::prelude::*;
+
+/// Leaves inserted by verifier programs which are not inserted into the transaction
+/// Merkle tree yet. The pair of leaves starting at `leaf_index` is stored at
+/// `(leaf_index / 2) % LEAVES_QUEUE_SIZE`, it can be overwritten once it is inserted.
+/// Encrypted utxos are not stored, they are emitted with the indexer transaction event.
+#[account(zero_copy)]
+#[derive(Eq, PartialEq, Debug)]
+pub struct LeavesQueue {
+    pub transaction_merkle_tree: Pubkey,
+    pub leaves: [[[u8; 32]; 2]; LEAVES_QUEUE_SIZE as usize],
+}
+
+impl LeavesQueue {
+    fn position(leaf_index: u64) -> usize {
+        usize::try_from((leaf_index / 2) % LEAVES_QUEUE_SIZE).unwrap()
+    }
+
+    /// Appends a pair of leaves at the next queued index of the Merkle tree.
+    /// Fails if the queue is full, the Merkle tree has to be updated first.
+    pub fn push(
+        &mut self,
+        merkle_tree: &mut TransactionMerkleTree,
+        leaf_left: [u8; 32],
+        leaf_right: [u8; 32],
+    ) -> Result<u64> {
+        let leaf_index = merkle_tree.next_queued_index;
+        if leaf_index - merkle_tree.next_index >= 2 * LEAVES_QUEUE_SIZE {
+            msg!(
+                "Leaves queue is full, {} leaves are not inserted into the Merkle tree.",
+                leaf_index - merkle_tree.next_index
+            );
+            return err!(ErrorCode::LeavesQueueFull);
+        }
+        self.leaves[Self::position(leaf_index)] = [leaf_left, leaf_right];
+        merkle_tree.next_queued_index += 2;
+        Ok(leaf_index)
+    }
+
+    /// Returns up to `max_pairs` pairs of queued leaves starting at `start_index`, which is
+    /// the next index of the Merkle tree unless leaves before are inserted in parallel.
+    pub fn queued_leaves(
+        &self,
+        merkle_tree: &TransactionMerkleTree,
+        start_index: u64,
+        max_pairs: usize,
+    ) -> Result<Vec<[[u8; 32]; 2]>> {
+        if start_index < merkle_tree.next_index || start_index % 2 != 0 {
+            msg!(
+                "Start index {} is not a queued pair of leaves, next index {}.",
+                start_index,
+                merkle_tree.next_index
+            );
+            return err!(ErrorCode::InvalidUpdateStartIndex);
+        }
+        let nr_pairs =
+            usize::try_from(merkle_tree.next_queued_index.saturating_sub(start_index) / 2)
+                .unwrap()
+                .min(max_pairs);
+        if nr_pairs == 0 {
+            msg!("No queued leaves.");
+            return err!(ErrorCode::InvalidNumberOfLeaves);
+        }
+        Ok((0..nr_pairs as u64)
+            .map(|i| self.leaves[Self::position(start_index + 2 * i)])
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_leaves_queue() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        let mut queue: LeavesQueue = bytemuck::Zeroable::zeroed();
+        assert_eq!(
+            queue.queued_leaves(&merkle_tree, 0, 16),
+            err!(ErrorCode::InvalidNumberOfLeaves)
+        );
+        for i in 0..LEAVES_QUEUE_SIZE {
+            let leaf_index = queue
+                .push(&mut merkle_tree, [i as u8; 32], [i as u8 + 1; 32])
+                .unwrap();
+            assert_eq!(leaf_index, 2 * i);
+        }
+        assert_eq!(
+            queue.push(&mut merkle_tree, [1; 32], [1; 32]),
+            err!(ErrorCode::LeavesQueueFull)
+        );
+        let leaves = queue.queued_leaves(&merkle_tree, 0, 16).unwrap();
+        assert_eq!(leaves.len(), 16);
+        assert_eq!(leaves[3], [[3; 32], [4; 32]]);
+        // Leaves after the ones another relayer inserts.
+        let leaves = queue.queued_leaves(&merkle_tree, 32, 16).unwrap();
+        assert_eq!(leaves[0], [[16; 32], [17; 32]]);
+
+        // Inserted leaves free up their positions.
+        merkle_tree.next_index = 4;
+        queue.push(&mut merkle_tree, [9; 32], [9; 32]).unwrap();
+        assert_eq!(queue.leaves[0], [[9; 32], [9; 32]]);
+        assert_eq!(
+            queue.queued_leaves(&merkle_tree, 2, 16),
+            err!(ErrorCode::InvalidUpdateStartIndex)
+        );
+        let leaves = queue.queued_leaves(&merkle_tree, 4, 300).unwrap();
+        assert_eq!(leaves.len(), LEAVES_QUEUE_SIZE as usize - 1);
+        assert_eq!(leaves[0], [[2; 32], [3; 32]]);
+        assert_eq!(leaves[leaves.len() - 1], [[9; 32], [9; 32]]);
+    }
+}
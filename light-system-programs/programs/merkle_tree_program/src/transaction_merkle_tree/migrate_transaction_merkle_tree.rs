@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::{leaves_queue::LeavesQueue, state::TransactionMerkleTree};
+use crate::utils::config::{DEFAULT_LEAF_FEE, MERKLE_TREE_HISTORY_SIZE};
+use crate::utils::constants::{
+    LEAVES_QUEUE_SEED, MERKLE_TREE_AUTHORITY_SEED, TRANSACTION_MERKLE_TREE_SEED,
+};
+use crate::MerkleTreeAuthority;
+
+/// Account size of legacy transaction Merkle trees, 8 bytes more than the layout.
+pub const LEGACY_TRANSACTION_MERKLE_TREE_SIZE: usize = 8880;
+
+/// Layout of transaction Merkle trees created before the account grew to 9424 bytes.
+/// It only supports height 18 and updates are locked to one relayer at a time.
+#[zero_copy]
+#[derive(Eq, PartialEq, Debug)]
+pub struct LegacyTransactionMerkleTree {
+    pub filled_subtrees: [[u8; 32]; 18],
+    pub current_root_index: u64,
+    pub next_index: u64,
+    pub roots: [[u8; 32]; MERKLE_TREE_HISTORY_SIZE as usize],
+    pub pubkey_locked: Pubkey,
+    pub time_locked: u64,
+    pub height: u64,
+    pub merkle_tree_nr: u64,
+    pub lock_duration: u64,
+    pub next_queued_index: u64,
+    pub newest: u8,
+    _padding: [u8; 7],
+}
+
+#[derive(Accounts)]
+pub struct MigrateTransactionMerkleTree<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: the legacy layout is unpacked and checked internally.
+    #[account(mut, owner = crate::ID)]
+    pub transaction_merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [transaction_merkle_tree.key().to_bytes().as_ref(), LEAVES_QUEUE_SEED],
+        bump,
+        payer = authority,
+        space = 8232
+    )]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
+    pub system_program: Program<'info, System>,
+    #[account(seeds = [MERKLE_TREE_AUTHORITY_SEED], bump)]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+pub fn process_migrate_transaction_merkle_tree(
+    ctx: Context<MigrateTransactionMerkleTree>,
+) -> Result<()> {
+    let merkle_tree_info = ctx.accounts.transaction_merkle_tree.to_account_info();
+    let legacy_merkle_tree: LegacyTransactionMerkleTree = {
+        let data = merkle_tree_info.try_borrow_data()?;
+        if data.len() != LEGACY_TRANSACTION_MERKLE_TREE_SIZE
+            || data[..8] != TransactionMerkleTree::discriminator()
+        {
+            msg!(
+                "Transaction Merkle tree {} is not a legacy Merkle tree.",
+                merkle_tree_info.key
+            );
+            return err!(ErrorCode::InvalidLegacyMerkleTree);
+        }
+        bytemuck::pod_read_unaligned(
+            &data[8..8 + std::mem::size_of::<LegacyTransactionMerkleTree>()],
+        )
+    };
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[
+            TRANSACTION_MERKLE_TREE_SEED,
+            legacy_merkle_tree.merkle_tree_nr.to_le_bytes().as_ref(),
+        ],
+        ctx.program_id,
+    );
+    if *merkle_tree_info.key != pubkey {
+        return err!(ErrorCode::InvalidLegacyMerkleTree);
+    }
+
+    // The account is topped up to stay rent exempt at its new size.
+    let size = 8 + std::mem::size_of::<TransactionMerkleTree>();
+    let lamports = Rent::get()?
+        .minimum_balance(size)
+        .saturating_sub(merkle_tree_info.lamports());
+    if lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: merkle_tree_info.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    merkle_tree_info.realloc(size, false)?;
+    merkle_tree_info.try_borrow_mut_data()?[8..].fill(0);
+
+    let merkle_tree_loader = AccountLoader::<TransactionMerkleTree>::try_from(&merkle_tree_info)?;
+    migrate_legacy_transaction_merkle_tree(
+        &legacy_merkle_tree,
+        &mut *merkle_tree_loader.load_mut()?,
+    )?;
+    ctx.accounts
+        .leaves_queue
+        .load_init()?
+        .transaction_merkle_tree = pubkey;
+    Ok(())
+}
+
+/// Copies the state of a legacy transaction Merkle tree into a zeroed transaction
+/// Merkle tree. Leaves queued in the legacy layout are not part of the leaves
+/// queue, they have to be inserted before the migration.
+pub fn migrate_legacy_transaction_merkle_tree(
+    legacy_merkle_tree: &LegacyTransactionMerkleTree,
+    merkle_tree: &mut TransactionMerkleTree,
+) -> Result<()> {
+    if legacy_merkle_tree.next_queued_index != legacy_merkle_tree.next_index {
+        msg!(
+            "Leaves {}..{} of the legacy Merkle tree are not inserted yet.",
+            legacy_merkle_tree.next_index,
+            legacy_merkle_tree.next_queued_index
+        );
+        return err!(ErrorCode::LegacyMerkleTreeHasQueuedLeaves);
+    }
+    merkle_tree.filled_subtrees[..legacy_merkle_tree.filled_subtrees.len()]
+        .copy_from_slice(&legacy_merkle_tree.filled_subtrees);
+    merkle_tree.current_root_index = legacy_merkle_tree.current_root_index;
+    merkle_tree.next_index = legacy_merkle_tree.next_index;
+    merkle_tree.roots = legacy_merkle_tree.roots;
+    merkle_tree.height = legacy_merkle_tree.height;
+    merkle_tree.merkle_tree_nr = legacy_merkle_tree.merkle_tree_nr;
+    merkle_tree.lock_duration = legacy_merkle_tree.lock_duration;
+    merkle_tree.next_queued_index = legacy_merkle_tree.next_queued_index;
+    merkle_tree.newest = legacy_merkle_tree.newest;
+    merkle_tree.leaf_fee = DEFAULT_LEAF_FEE;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::config::{MERKLE_TREE_HEIGHT, ZERO_BYTES_MERKLE_TREE};
+
+    #[test]
+    fn test_migrate_legacy_transaction_merkle_tree() {
+        assert!(
+            8 + std::mem::size_of::<LegacyTransactionMerkleTree>()
+                <= LEGACY_TRANSACTION_MERKLE_TREE_SIZE
+        );
+        assert_eq!(8 + std::mem::size_of::<TransactionMerkleTree>(), 9424);
+
+        let mut legacy_merkle_tree: LegacyTransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        legacy_merkle_tree
+            .filled_subtrees
+            .copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..18]);
+        legacy_merkle_tree.filled_subtrees[0] = [1u8; 32];
+        legacy_merkle_tree.current_root_index = 3;
+        legacy_merkle_tree.next_index = 6;
+        legacy_merkle_tree.next_queued_index = 8;
+        legacy_merkle_tree.roots[3] = [2u8; 32];
+        legacy_merkle_tree.pubkey_locked = Pubkey::new_unique();
+        legacy_merkle_tree.time_locked = 100;
+        legacy_merkle_tree.height = MERKLE_TREE_HEIGHT;
+        legacy_merkle_tree.merkle_tree_nr = 2;
+        legacy_merkle_tree.lock_duration = 50;
+        legacy_merkle_tree.newest = 1;
+
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        assert!(
+            migrate_legacy_transaction_merkle_tree(&legacy_merkle_tree, &mut merkle_tree).is_err()
+        );
+        legacy_merkle_tree.next_queued_index = 6;
+        migrate_legacy_transaction_merkle_tree(&legacy_merkle_tree, &mut merkle_tree).unwrap();
+
+        assert_eq!(
+            merkle_tree.filled_subtrees[..18],
+            legacy_merkle_tree.filled_subtrees
+        );
+        assert_eq!(merkle_tree.filled_subtrees[18..], [[0u8; 32]; 8]);
+        assert_eq!(merkle_tree.current_root_index, 3);
+        assert_eq!(merkle_tree.next_index, 6);
+        assert_eq!(merkle_tree.next_queued_index, 6);
+        assert_eq!(merkle_tree.roots, legacy_merkle_tree.roots);
+        assert_eq!(merkle_tree.height, MERKLE_TREE_HEIGHT);
+        assert_eq!(merkle_tree.merkle_tree_nr, 2);
+        assert_eq!(merkle_tree.lock_duration, 50);
+        assert_eq!(merkle_tree.newest, 1);
+        assert_eq!(merkle_tree.sequence_number, 0);
+        assert_eq!(merkle_tree.leaf_fee, DEFAULT_LEAF_FEE);
+        assert_eq!(merkle_tree.fee_vault_balance, 0);
+    }
+}
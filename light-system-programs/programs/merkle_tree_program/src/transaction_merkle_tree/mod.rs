@@ -1,10 +1,16 @@
 pub mod state;
 
+pub mod leaves_queue;
+pub use leaves_queue::*;
+
 pub mod update_instructions;
 pub use update_instructions::*;
 
-pub mod initialize_new_merkle_tree_18;
-pub use initialize_new_merkle_tree_18::*;
+pub mod initialize_new_transaction_merkle_tree;
+pub use initialize_new_transaction_merkle_tree::*;
+
+pub mod migrate_transaction_merkle_tree;
+pub use migrate_transaction_merkle_tree::*;
 
 pub mod update_merkle_tree_lib;
 pub use update_merkle_tree_lib::*;
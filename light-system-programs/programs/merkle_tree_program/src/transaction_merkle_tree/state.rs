@@ -1,4 +1,8 @@
-use crate::utils::config::MERKLE_TREE_HISTORY_SIZE;
+use crate::errors::ErrorCode;
+use crate::utils::config::{
+    MERKLE_TREE_CHANGELOG_SIZE, MERKLE_TREE_HISTORY_SIZE, MERKLE_TREE_MAX_HEIGHT,
+    MERKLE_TREE_ROLLOVER_THRESHOLD,
+};
 use anchor_lang::prelude::*;
 
 // NOTE(vadorovsky): This implementation of Merkle tree exists only for
@@ -8,26 +12,337 @@ use anchor_lang::prelude::*;
 #[account(zero_copy)]
 #[derive(Eq, PartialEq, Debug)]
 pub struct TransactionMerkleTree {
-    pub filled_subtrees: [[u8; 32]; 18],
+    /// Only the first `height` subtrees are used.
+    pub filled_subtrees: [[u8; 32]; MERKLE_TREE_MAX_HEIGHT as usize],
     pub current_root_index: u64,
     pub next_index: u64,
     pub roots: [[u8; 32]; MERKLE_TREE_HISTORY_SIZE as usize],
-    pub pubkey_locked: Pubkey,
-    pub time_locked: u64,
+    /// Number of root updates, the changelog entry of an update is stored at
+    /// `sequence_number % MERKLE_TREE_CHANGELOG_SIZE`.
+    pub sequence_number: u64,
+    pub changelog: [ChangelogEntry; MERKLE_TREE_CHANGELOG_SIZE as usize],
     pub height: u64,
     pub merkle_tree_nr: u64,
     pub lock_duration: u64,
     pub next_queued_index: u64,
+    /// Relayer which inserted the last root and the slot it was inserted at.
+    pub last_relayer: Pubkey,
+    pub last_root_slot: u64,
+    /// Stake relayers which are not whitelisted need if relayers are gated.
+    pub min_relayer_stake: u64,
+    /// Fee paid into the fee vault for every inserted pair of leaves.
+    pub leaf_fee: u64,
+    /// Fees of queued leaves, held by this account in addition to its rent.
+    pub fee_vault_balance: u64,
+    /// Number of transactions which inserted leaves, indexer events carry it
+    /// to detect missed events.
+    pub transaction_sequence_number: u64,
+    /// Only the newest tree accepts new leaves, older trees are read-only.
     pub newest: u8,
-    _padding: [u8; 7],
+    /// If set, only whitelisted or staked relayers can update the tree.
+    pub gated_relayers: u8,
+    _padding: [u8; 6],
 }
 
-#[account]
+/// Emitted through the noop program when a root is inserted.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RootUpdateEvent {
+    pub merkle_tree: Pubkey,
+    pub root: [u8; 32],
+    pub root_index: u64,
+    pub sequence_number: u64,
+    /// Leaves from `start_index` to `end_index` (exclusive) are part of the root.
+    pub start_index: u64,
+    pub end_index: u64,
+}
+
+/// Leaves range inserted by a root update.
+#[zero_copy]
 #[derive(Eq, PartialEq, Debug)]
-pub struct TwoLeavesBytesPda {
-    pub node_left: [u8; 32],
-    pub node_right: [u8; 32],
-    pub merkle_tree_pubkey: Pubkey,
-    pub encrypted_utxos: [u8; 256],
-    pub left_leaf_index: u64,
+pub struct ChangelogEntry {
+    pub start_index: u64,
+    pub end_index: u64,
+}
+
+impl TransactionMerkleTree {
+    /// Number of leaves the tree can hold.
+    pub fn capacity(&self) -> u64 {
+        1 << self.height
+    }
+
+    /// Whether enough leaves are queued to create the next tree.
+    pub fn is_rollover_threshold_reached(&self) -> bool {
+        self.next_queued_index >= self.capacity() / 100 * MERKLE_TREE_ROLLOVER_THRESHOLD
+    }
+
+    /// Returns the share of the fee vault for inserting the queued leaves up to `end_index`.
+    /// Updaters are paid in proportion to the inserted leaves of all queued leaves.
+    pub fn take_updater_fee(&mut self, end_index: u64) -> u64 {
+        let queued_leaves = self.next_queued_index.saturating_sub(self.next_index);
+        let inserted_leaves = end_index.saturating_sub(self.next_index).min(queued_leaves);
+        if queued_leaves == 0 {
+            return 0;
+        }
+        let fee = u64::try_from(
+            u128::from(self.fee_vault_balance) * u128::from(inserted_leaves)
+                / u128::from(queued_leaves),
+        )
+        .unwrap();
+        self.fee_vault_balance -= fee;
+        fee
+    }
+
+    /// Records the relayer which inserted a root.
+    pub fn record_root_insert(&mut self, relayer: Pubkey, slot: u64) {
+        self.last_relayer = relayer;
+        self.last_root_slot = slot;
+    }
+
+    /// Records that the leaves from `start_index` to `end_index` were inserted
+    /// with a new root.
+    pub fn push_changelog_entry(&mut self, start_index: u64, end_index: u64) {
+        self.sequence_number += 1;
+        let index = usize::try_from(self.sequence_number % MERKLE_TREE_CHANGELOG_SIZE).unwrap();
+        self.changelog[index] = ChangelogEntry {
+            start_index,
+            end_index,
+        };
+    }
+
+    /// Returns the event of the last root update.
+    pub fn root_update_event(&self, merkle_tree: Pubkey) -> RootUpdateEvent {
+        let entry = &self.changelog
+            [usize::try_from(self.sequence_number % MERKLE_TREE_CHANGELOG_SIZE).unwrap()];
+        RootUpdateEvent {
+            merkle_tree,
+            root: self.roots[usize::try_from(self.current_root_index).unwrap()],
+            root_index: self.current_root_index,
+            sequence_number: self.sequence_number,
+            start_index: entry.start_index,
+            end_index: entry.end_index,
+        }
+    }
+
+    /// Checks whether an update of the leaves from `start_index` to `end_index`,
+    /// created at `sequence_number` when the tree held `base_index` leaves, can
+    /// still be applied. Root updates since then have to be contiguous from
+    /// `base_index`. They have to insert all leaves before `start_index`, leaves
+    /// between `base_index` and `start_index` are inserted by other relayers in
+    /// parallel. Root updates which inserted leaves of this update fast-forward it,
+    /// its root already contains these leaves.
+    pub fn check_changelog(
+        &self,
+        sequence_number: u64,
+        base_index: u64,
+        start_index: u64,
+        end_index: u64,
+    ) -> Result<()> {
+        let updates = match self.sequence_number.checked_sub(sequence_number) {
+            Some(updates) => updates,
+            None => {
+                msg!(
+                    "Update state sequence number {} is ahead of the Merkle tree {}.",
+                    sequence_number,
+                    self.sequence_number
+                );
+                return err!(ErrorCode::InvalidUpdateStateSequenceNumber);
+            }
+        };
+        if updates > MERKLE_TREE_CHANGELOG_SIZE {
+            msg!(
+                "Update state is {} root updates behind, the changelog holds {}.",
+                updates,
+                MERKLE_TREE_CHANGELOG_SIZE
+            );
+            return err!(ErrorCode::MerkleTreeUpdateStateOutdated);
+        }
+        let mut next_index = base_index;
+        for seq in sequence_number + 1..=self.sequence_number {
+            let entry = &self.changelog[usize::try_from(seq % MERKLE_TREE_CHANGELOG_SIZE).unwrap()];
+            if entry.start_index != next_index {
+                msg!(
+                    "Leaves {}..{} are not contiguous with the leaves of the update state.",
+                    entry.start_index,
+                    entry.end_index
+                );
+                return err!(ErrorCode::MerkleTreeUpdateStateOutdated);
+            }
+            next_index = entry.end_index;
+        }
+        if next_index < start_index {
+            msg!(
+                "Leaves {}..{} are not inserted yet.",
+                next_index,
+                start_index
+            );
+            return err!(ErrorCode::LeavesBeforeUpdateNotInserted);
+        }
+        if next_index >= end_index {
+            msg!(
+                "Leaves {}..{} were inserted since the update state was created.",
+                start_index,
+                next_index
+            );
+            return err!(ErrorCode::MerkleTreeUpdateStateOutdated);
+        }
+        Ok(())
+    }
+
+    /// Returns the node at `level` which ends at leaf `node_end`, to patch the stale
+    /// copy of an update state which was created before its leaves were inserted.
+    /// The filled subtree of the level is this node until leaves of the next node
+    /// at the level, which starts at `node_end + 2^level`, are inserted.
+    pub fn patched_filled_subtree(&self, level: usize, node_end: u64) -> Result<[u8; 32]> {
+        if self.next_index < node_end {
+            msg!(
+                "Leaves up to {} are not inserted yet, the Merkle tree holds {}.",
+                node_end,
+                self.next_index
+            );
+            return err!(ErrorCode::LeavesBeforeUpdateNotInserted);
+        }
+        if self.next_index > node_end + (1 << level) {
+            msg!(
+                "Node at level {} ending at {} was overwritten.",
+                level,
+                node_end
+            );
+            return err!(ErrorCode::MerkleTreeUpdateStateOutdated);
+        }
+        Ok(self.filled_subtrees[level])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::config::MERKLE_TREE_TMP_PDA_SIZE;
+    use crate::MerkleTreeUpdateState;
+
+    #[test]
+    fn test_account_sizes() {
+        assert_eq!(8 + std::mem::size_of::<TransactionMerkleTree>(), 9424);
+        assert!(8 + std::mem::size_of::<MerkleTreeUpdateState>() <= MERKLE_TREE_TMP_PDA_SIZE);
+        assert_eq!(
+            8 + std::mem::size_of::<crate::transaction_merkle_tree::LeavesQueue>(),
+            8232
+        );
+    }
+
+    #[test]
+    fn test_take_updater_fee() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        assert_eq!(merkle_tree.take_updater_fee(0), 0);
+        merkle_tree.next_queued_index = 8;
+        merkle_tree.fee_vault_balance = 4 * 5_000;
+        // Inserting 2 of 8 queued leaves pays a quarter of the vault.
+        assert_eq!(merkle_tree.take_updater_fee(2), 5_000);
+        merkle_tree.next_index = 2;
+        assert_eq!(merkle_tree.take_updater_fee(8), 15_000);
+        assert_eq!(merkle_tree.fee_vault_balance, 0);
+    }
+
+    #[test]
+    fn test_check_changelog() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        // Update state created at sequence number 0 for the leaves 0..8.
+        merkle_tree.check_changelog(0, 0, 0, 8).unwrap();
+
+        // Another relayer inserted the leaves 0..4.
+        merkle_tree.push_changelog_entry(0, 4);
+        merkle_tree.check_changelog(0, 0, 0, 8).unwrap();
+        // The leaves 0..4 are inserted already.
+        assert!(merkle_tree.check_changelog(0, 0, 0, 4).is_err());
+
+        // Another relayer inserted the leaves 4..10.
+        merkle_tree.push_changelog_entry(4, 10);
+        assert!(merkle_tree.check_changelog(0, 0, 0, 8).is_err());
+        merkle_tree.check_changelog(0, 0, 0, 12).unwrap();
+        merkle_tree.check_changelog(1, 4, 4, 12).unwrap();
+        // Update states can not be ahead of the Merkle tree.
+        assert_eq!(
+            merkle_tree.check_changelog(3, 10, 10, 12),
+            err!(ErrorCode::InvalidUpdateStateSequenceNumber)
+        );
+
+        // The changelog does not reach back far enough.
+        for i in 0..MERKLE_TREE_CHANGELOG_SIZE {
+            merkle_tree.push_changelog_entry(10 + i * 2, 12 + i * 2);
+        }
+        assert!(merkle_tree.check_changelog(0, 0, 0, 1000).is_err());
+        merkle_tree.check_changelog(2, 10, 10, 1000).unwrap();
+    }
+
+    #[test]
+    fn test_check_changelog_disjoint_updates() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        // Two update states created at sequence number 0, one for the leaves 0..4
+        // and one for the leaves 4..8.
+        assert_eq!(
+            merkle_tree.check_changelog(0, 0, 4, 8),
+            err!(ErrorCode::LeavesBeforeUpdateNotInserted)
+        );
+        merkle_tree.push_changelog_entry(0, 2);
+        assert_eq!(
+            merkle_tree.check_changelog(0, 0, 4, 8),
+            err!(ErrorCode::LeavesBeforeUpdateNotInserted)
+        );
+        merkle_tree.push_changelog_entry(2, 4);
+        merkle_tree.check_changelog(0, 0, 4, 8).unwrap();
+        // Leaves of the update were inserted by another relayer.
+        merkle_tree.push_changelog_entry(4, 6);
+        merkle_tree.check_changelog(0, 0, 4, 8).unwrap();
+        merkle_tree.push_changelog_entry(6, 8);
+        assert_eq!(
+            merkle_tree.check_changelog(0, 0, 4, 8),
+            err!(ErrorCode::MerkleTreeUpdateStateOutdated)
+        );
+        // Root updates which are not contiguous with the update state.
+        assert_eq!(
+            merkle_tree.check_changelog(0, 2, 4, 10),
+            err!(ErrorCode::MerkleTreeUpdateStateOutdated)
+        );
+    }
+
+    #[test]
+    fn test_patched_filled_subtree() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree.filled_subtrees[2] = [1u8; 32];
+        merkle_tree.next_index = 2;
+        // The node at level 2 covering the leaves 0..4 is not complete.
+        assert_eq!(
+            merkle_tree.patched_filled_subtree(2, 4),
+            err!(ErrorCode::LeavesBeforeUpdateNotInserted)
+        );
+        merkle_tree.next_index = 4;
+        assert_eq!(merkle_tree.patched_filled_subtree(2, 4).unwrap(), [1u8; 32]);
+        merkle_tree.next_index = 8;
+        assert_eq!(merkle_tree.patched_filled_subtree(2, 4).unwrap(), [1u8; 32]);
+        // Leaves of the node covering 8..12 overwrote the filled subtree.
+        merkle_tree.next_index = 10;
+        assert_eq!(
+            merkle_tree.patched_filled_subtree(2, 4),
+            err!(ErrorCode::MerkleTreeUpdateStateOutdated)
+        );
+    }
+
+    #[test]
+    fn test_root_update_event() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree.push_changelog_entry(0, 4);
+        merkle_tree.push_changelog_entry(4, 10);
+        merkle_tree.current_root_index = 2;
+        merkle_tree.roots[2] = [1u8; 32];
+        assert_eq!(
+            merkle_tree.root_update_event(Pubkey::default()),
+            RootUpdateEvent {
+                merkle_tree: Pubkey::default(),
+                root: [1u8; 32],
+                root_index: 2,
+                sequence_number: 2,
+                start_index: 4,
+                end_index: 10,
+            }
+        );
+    }
 }
@@ -0,0 +1,74 @@
+use crate::config_accounts::registered_relayer::{
+    check_registered_relayer, check_relayer_rotation, RegisteredRelayer,
+};
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::update_instructions::insert_root::{
+    invoke_root_update_event, pay_updater_fee,
+};
+use crate::transaction_merkle_tree::{
+    instructions::insert_leaves_batch, leaves_queue::LeavesQueue, state::TransactionMerkleTree,
+};
+use crate::utils::constants::RELAYER_SEED;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::msg;
+
+/// Maximum number of pairs of leaves inserted in one batch update.
+const MAX_BATCH_LEAVES_PAIRS: usize = 16;
+
+#[derive(Accounts)]
+pub struct BatchUpdateTransactionMerkleTree<'info> {
+    /// Receives the updater fee.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    #[account(has_one = transaction_merkle_tree @ErrorCode::InvalidLeavesQueue)]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
+    /// Required if the Merkle tree is gated to registered relayers.
+    #[account(seeds = [authority.key().to_bytes().as_ref(), RELAYER_SEED], bump)]
+    pub registered_relayer: Option<Account<'info, RegisteredRelayer>>,
+    /// CHECK:` checking manually in wrapper function
+    pub log_wrapper: UncheckedAccount<'info>,
+}
+
+/// Inserts up to 16 pairs of queued leaves and the resulting root in one instruction.
+/// Leaves are read from the leaves queue, starting at the next index of the Merkle tree.
+/// The new root is logged as root update event like in insert root.
+pub fn process_batch_update_merkle_tree(
+    ctx: Context<BatchUpdateTransactionMerkleTree>,
+) -> Result<()> {
+    let mut merkle_tree_pda_data = ctx.accounts.transaction_merkle_tree.load_mut()?;
+    let slot = Clock::get()?.slot;
+    check_registered_relayer(
+        &merkle_tree_pda_data,
+        ctx.accounts.authority.key,
+        ctx.accounts.registered_relayer.as_deref(),
+    )?;
+    check_relayer_rotation(&merkle_tree_pda_data, ctx.accounts.authority.key, slot)?;
+
+    let leaves = ctx.accounts.leaves_queue.load()?.queued_leaves(
+        &merkle_tree_pda_data,
+        merkle_tree_pda_data.next_index,
+        MAX_BATCH_LEAVES_PAIRS,
+    )?;
+    let end_index = merkle_tree_pda_data.next_index + 2 * leaves.len() as u64;
+
+    let fee = merkle_tree_pda_data.take_updater_fee(end_index);
+    insert_leaves_batch(&mut merkle_tree_pda_data, &leaves)?;
+    merkle_tree_pda_data.record_root_insert(ctx.accounts.authority.key(), slot);
+    pay_updater_fee(
+        &ctx.accounts.transaction_merkle_tree.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        fee,
+    )?;
+    msg!(
+        "Inserted {} leaves, next index: {}",
+        leaves.len() * 2,
+        merkle_tree_pda_data.next_index
+    );
+
+    let event = merkle_tree_pda_data.root_update_event(ctx.accounts.transaction_merkle_tree.key());
+    invoke_root_update_event(&event, &ctx.accounts.log_wrapper.to_account_info())?;
+
+    Ok(())
+}
@@ -1,18 +1,65 @@
-use crate::utils::constants::STORAGE_SEED;
+use crate::config_accounts::registered_relayer::{penalize_relayer, RegisteredRelayer};
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::{
+    state::TransactionMerkleTree, update_instructions::insert_root::close_account,
+};
+use crate::utils::config::UPDATE_STATE_TAKEOVER_REWARD;
+use crate::utils::constants::RELAYER_SEED;
 use crate::MerkleTreeUpdateState;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-#[instruction(merkle_tree_index: u64)]
 pub struct CloseUpdateState<'info> {
-    #[account(mut, address=merkle_tree_update_state.load()?.relayer)]
+    /// The relayer of the update state or anyone if the update state is expired.
+    #[account(mut)]
     pub authority: Signer<'info>,
-    /// CHECK:` Is the merkle_tree_update_state of an authority.
-    #[account(
-        mut,
-        seeds = [authority.key().to_bytes().as_ref(), STORAGE_SEED],
-        bump,
-        close=authority
-    )]
+    /// CHECK:` is the relayer of the update state and receives its rent.
+    #[account(mut, address=merkle_tree_update_state.load()?.relayer @ErrorCode::InvalidAuthority)]
+    pub relayer: UncheckedAccount<'info>,
+    #[account(mut)]
     pub merkle_tree_update_state: AccountLoader<'info, MerkleTreeUpdateState>,
+    #[account(address= merkle_tree_update_state.load()?.merkle_tree_pda_pubkey @ErrorCode::InvalidMerkleTree)]
+    pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    /// Registration of the relayer, its stake is penalized if the update state is expired
+    /// and the Merkle tree is gated to registered relayers.
+    #[account(mut, seeds = [relayer.key().to_bytes().as_ref(), RELAYER_SEED], bump)]
+    pub penalized_relayer: Option<Account<'info, RegisteredRelayer>>,
+}
+
+/// Closes the update state to its relayer.
+/// Expired update states can be closed by anyone for UPDATE_STATE_TAKEOVER_REWARD
+/// percent of the rent and, on Merkle trees gated to registered relayers, the stake
+/// penalty of the relayer.
+pub fn process_close_update_state(ctx: Context<CloseUpdateState>) -> Result<()> {
+    let update_state = ctx.accounts.merkle_tree_update_state.to_account_info();
+    let relayer = ctx.accounts.relayer.to_account_info();
+
+    if ctx.accounts.authority.key() != ctx.accounts.relayer.key() {
+        let lock_duration = ctx.accounts.transaction_merkle_tree.load()?.lock_duration;
+        if !ctx
+            .accounts
+            .merkle_tree_update_state
+            .load()?
+            .is_expired(Clock::get()?.slot, lock_duration)
+        {
+            return err!(ErrorCode::UpdateStateNotExpired);
+        }
+        let reward = update_state.lamports() * UPDATE_STATE_TAKEOVER_REWARD / 100;
+        let authority = ctx.accounts.authority.to_account_info();
+        **authority.lamports.borrow_mut() = authority
+            .lamports()
+            .checked_add(reward)
+            .ok_or(ErrorCode::CloseAccountFailed)?;
+        **update_state.lamports.borrow_mut() = update_state
+            .lamports()
+            .checked_sub(reward)
+            .ok_or(ErrorCode::CloseAccountFailed)?;
+        msg!("Closed expired update state, reward {}", reward);
+        if ctx.accounts.transaction_merkle_tree.load()?.gated_relayers != 0 {
+            if let Some(penalized_relayer) = ctx.accounts.penalized_relayer.as_mut() {
+                penalize_relayer(penalized_relayer, &authority)?;
+            }
+        }
+    }
+    close_account(&update_state, &relayer)
 }
@@ -1,10 +1,13 @@
+use crate::config_accounts::registered_relayer::{
+    check_registered_relayer, check_relayer_rotation, RegisteredRelayer,
+};
 use crate::errors::ErrorCode;
-use crate::transaction_merkle_tree::state::{TransactionMerkleTree, TwoLeavesBytesPda};
+use crate::transaction_merkle_tree::{leaves_queue::LeavesQueue, state::TransactionMerkleTree};
 use crate::utils::config::MERKLE_TREE_TMP_PDA_SIZE;
-use crate::utils::constants::STORAGE_SEED;
+use crate::utils::constants::{RELAYER_SEED, STORAGE_SEED};
 use crate::MerkleTreeUpdateState;
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{msg, sysvar};
+use anchor_lang::solana_program::msg;
 
 #[derive(Accounts)]
 pub struct InitializeUpdateState<'info> {
@@ -19,14 +22,21 @@ pub struct InitializeUpdateState<'info> {
         space = MERKLE_TREE_TMP_PDA_SIZE,
     )]
     pub merkle_tree_update_state: AccountLoader<'info, MerkleTreeUpdateState>,
-    #[account(mut)]
     pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    #[account(has_one = transaction_merkle_tree @ErrorCode::InvalidLeavesQueue)]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
+    /// CHECK:` is the registration pda of the authority.
+    /// The stake of a registered relayer is locked, the authority has to be registered
+    /// if the Merkle tree is gated to registered relayers.
+    #[account(mut, seeds = [authority.key().to_bytes().as_ref(), RELAYER_SEED], bump)]
+    pub registered_relayer: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn process_initialize_update_state<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, InitializeUpdateState<'info>>,
+    start_index: Option<u64>,
 ) -> Result<()> {
     msg!("InitializeUpdateState");
     let update_state_data = &mut ctx.accounts.merkle_tree_update_state.load_init()?;
@@ -34,91 +44,51 @@ pub fn process_initialize_update_state<'a, 'b, 'c, 'info>(
     update_state_data.merkle_tree_pda_pubkey = ctx.accounts.transaction_merkle_tree.key();
 
     update_state_data.current_instruction_index = 1;
-
-    // Checking that the number of remaining accounts is non zero and smaller than 16.
-    if ctx.remaining_accounts.is_empty() || ctx.remaining_accounts.len() > 16 {
-        msg!(
-            "Submitted number of leaves: {}",
-            ctx.remaining_accounts.len()
-        );
-        return err!(ErrorCode::InvalidNumberOfLeaves);
-    }
-
-    let mut merkle_tree_pda_data = ctx.accounts.transaction_merkle_tree.load_mut()?;
-
-    let mut tmp_index = merkle_tree_pda_data.next_index;
-    msg!("tmp_index: {}", tmp_index);
-    // Leaves are passed in as pdas in remaining accounts to allow for flexibility in their
-    // number.
-    // Checks are:
-    //             - are not inserted yet
-    //             - belong to merkle_tree
-    //             - the lowest index is the next index of the merkle_tree
-    //             - indices increase incrementally by 2 for subsequent leaves
-    // Copying leaves to tmp account.
-    for (index, account) in ctx.remaining_accounts.iter().enumerate() {
-        msg!("Copying leaves pair {}", index);
-
-        let leaves_pda_data: Account<'info, TwoLeavesBytesPda> = Account::try_from(account)?;
-
-        // Checking that leaves are not inserted already.
-        if leaves_pda_data.left_leaf_index < merkle_tree_pda_data.next_index {
-            msg!(
-                "Leaf pda state with address {:?} is already inserted",
-                *account.key
-            );
-            return err!(ErrorCode::LeafAlreadyInserted);
-        }
-
-        // Checking that the Merkle tree is the same as in leaves account.
-        if leaves_pda_data.merkle_tree_pubkey != ctx.accounts.transaction_merkle_tree.key() {
-            msg!(
-                "Leaf pda state merkle tree {} is different than passed in merkle tree {:?}",
-                leaves_pda_data.merkle_tree_pubkey,
-                ctx.accounts.transaction_merkle_tree.key()
-            );
-            return err!(ErrorCode::LeavesOfWrongTree);
-        }
-
-        // Checking that the lowest index is the next index of the merkle_tree.
-        // Check that following leaves are correct and in the right order.
-        if leaves_pda_data.left_leaf_index != tmp_index {
-            return err!(ErrorCode::FirstLeavesPdaIncorrectIndex);
-        }
-        // Copy leaves to tmp account.
-        update_state_data.leaves[index][0] = leaves_pda_data.node_left;
-        update_state_data.leaves[index][1] = leaves_pda_data.node_right;
-        update_state_data.number_of_leaves = (index + 1).try_into().unwrap();
-        tmp_index += 2;
-    }
-
-    // Get Merkle tree lock with update state account.
-    // The lock lasts merkle_tree_pda_data.lock_duration and is renewed every transaction.
-
-    let current_slot = <Clock as sysvar::Sysvar>::get()?.slot;
-    msg!("Current slot: {:?}", current_slot);
-
-    msg!("Locked at slot: {}", merkle_tree_pda_data.time_locked);
-    msg!(
-        "Lock ends at slot: {}",
-        merkle_tree_pda_data.time_locked + merkle_tree_pda_data.lock_duration
-    );
-
-    if merkle_tree_pda_data.time_locked == 0
-        || merkle_tree_pda_data.time_locked + merkle_tree_pda_data.lock_duration < current_slot
-    {
-        merkle_tree_pda_data.time_locked = current_slot;
-        merkle_tree_pda_data.pubkey_locked = ctx.accounts.merkle_tree_update_state.key();
-        msg!("Locked at slot: {}", merkle_tree_pda_data.time_locked);
-        msg!("Locked by: {:?}", merkle_tree_pda_data.pubkey_locked);
+    let slot = Clock::get()?.slot;
+    update_state_data.last_update_slot = slot;
+
+    let merkle_tree_pda_data = ctx.accounts.transaction_merkle_tree.load()?;
+    let registered_relayer_info = ctx.accounts.registered_relayer.to_account_info();
+    let mut registered_relayer = if *registered_relayer_info.owner == *ctx.program_id {
+        Some(Account::<RegisteredRelayer>::try_from(
+            &registered_relayer_info,
+        )?)
     } else {
-        msg!("Contract is still locked.");
-        return err!(ErrorCode::ContractStillLocked);
+        None
+    };
+    check_registered_relayer(
+        &merkle_tree_pda_data,
+        ctx.accounts.authority.key,
+        registered_relayer.as_deref(),
+    )?;
+    check_relayer_rotation(&merkle_tree_pda_data, ctx.accounts.authority.key, slot)?;
+    // The stake stays locked until the update state can no longer be penalized,
+    // also on ungated trees since the tree can be gated before the update state expires.
+    if let Some(registered_relayer) = registered_relayer.as_mut() {
+        registered_relayer.lock_stake(slot);
+        registered_relayer.exit(ctx.program_id)?;
     }
 
+    // Copying up to 16 pairs of queued leaves, starting at the next index of the Merkle tree
+    // or after the leaves other relayers insert in parallel, to the update state.
+    let start_index = start_index.unwrap_or(merkle_tree_pda_data.next_index);
+    let leaves = ctx.accounts.leaves_queue.load()?.queued_leaves(
+        &merkle_tree_pda_data,
+        start_index,
+        update_state_data.leaves.len(),
+    )?;
+    update_state_data.leaves[..leaves.len()].copy_from_slice(&leaves);
+    update_state_data.number_of_leaves = leaves.len().try_into().unwrap();
+    msg!("Copied {} pairs of leaves", leaves.len());
+
     // Copying Subtrees into update state.
     update_state_data.filled_subtrees = merkle_tree_pda_data.filled_subtrees;
-    update_state_data.tmp_leaves_index = merkle_tree_pda_data.next_index;
+    update_state_data.tmp_leaves_index = start_index;
+    // The Merkle tree is not locked, other relayers can insert leaves concurrently.
+    // Root updates since this point are checked against the changelog in insert root.
+    update_state_data.sequence_number = merkle_tree_pda_data.sequence_number;
+    update_state_data.base_index = merkle_tree_pda_data.next_index;
+    update_state_data.start_index = start_index;
 
     Ok(())
 }
@@ -1,29 +1,28 @@
 use crate::errors::ErrorCode;
 use crate::transaction_merkle_tree::{
     instructions::insert_last_double,
-    state::{TransactionMerkleTree, TwoLeavesBytesPda},
+    processor::instruction_id,
+    state::{RootUpdateEvent, TransactionMerkleTree},
 };
-use crate::utils::constants::{IX_ORDER, ROOT_INSERT, STORAGE_SEED};
+use crate::utils::constants::{NOOP_PROGRAM_ID, ROOT_INSERT};
 use crate::MerkleTreeUpdateState;
 
 use std::ops::DerefMut;
 
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{clock::Clock, pubkey::Pubkey, sysvar::Sysvar};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
 #[derive(Accounts)]
 pub struct InsertRoot<'info> {
     #[account(mut, address=merkle_tree_update_state.load()?.relayer @ErrorCode::InvalidAuthority)]
     pub authority: Signer<'info>,
     /// CHECK:` merkle_tree_update_state is derived correctly
-    /// Merkle tree is locked by merkle_tree_update_state
     /// Is in correct instruction for root insert thus Merkle Tree update has been completed.
     /// The account is closed to the authority at the end of the instruction.
     #[account(
         mut,
-        seeds = [authority.key().to_bytes().as_ref(), STORAGE_SEED],
-        bump,
-        constraint= transaction_merkle_tree.load()?.pubkey_locked == merkle_tree_update_state.key() @ErrorCode::ContractStillLocked,
-        constraint= IX_ORDER[usize::try_from(merkle_tree_update_state.load()?.current_instruction_index).unwrap()] == ROOT_INSERT @ErrorCode::MerkleTreeUpdateNotInRootInsert,
+        constraint= instruction_id(merkle_tree_update_state.load()?.current_instruction_index, transaction_merkle_tree.load()?.height) == Some(ROOT_INSERT) @ErrorCode::MerkleTreeUpdateNotInRootInsert,
         close = authority
     )]
     /// CHECK: we need to check it's a right recipient account.
@@ -51,82 +50,71 @@ pub fn close_account(account: &AccountInfo, dest_account: &AccountInfo) -> Resul
     Ok(())
 }
 
+/// Pays the updater fee from the fee vault held by the Merkle tree account.
+pub fn pay_updater_fee(merkle_tree: &AccountInfo, relayer: &AccountInfo, fee: u64) -> Result<()> {
+    **merkle_tree.lamports.borrow_mut() = merkle_tree
+        .lamports()
+        .checked_sub(fee)
+        .ok_or(ErrorCode::CloseAccountFailed)?;
+    **relayer.lamports.borrow_mut() = relayer
+        .lamports()
+        .checked_add(fee)
+        .ok_or(ErrorCode::CloseAccountFailed)?;
+    msg!("Paid updater fee {}", fee);
+    Ok(())
+}
+
+/// Logs a root update event through the noop program so that indexers can
+/// follow the Merkle tree from the transaction history.
+pub fn invoke_root_update_event<'info>(
+    event: &RootUpdateEvent,
+    noop_program: &AccountInfo<'info>,
+) -> Result<()> {
+    if noop_program.key() != NOOP_PROGRAM_ID {
+        return err!(ErrorCode::InvalidNoopPubkey);
+    }
+    let instruction = Instruction {
+        program_id: noop_program.key(),
+        accounts: vec![],
+        data: event.try_to_vec()?,
+    };
+    invoke(&instruction, &[noop_program.to_account_info()])?;
+    Ok(())
+}
+
 pub fn process_insert_root<'a, 'b, 'c, 'info>(
     ctx: &mut Context<'a, 'b, 'c, 'info, InsertRoot<'info>>,
 ) -> Result<()> {
     let merkle_tree_update_state_data = &mut ctx.accounts.merkle_tree_update_state.load_mut()?;
     let merkle_tree_pda_data = &mut ctx.accounts.transaction_merkle_tree.load_mut()?;
 
-    let id =
-        IX_ORDER[usize::try_from(merkle_tree_update_state_data.current_instruction_index).unwrap()];
-    msg!("Root insert Instruction: {}", id);
-
-    msg!(
-        "merkle_tree_pda_data.pubkey_locked: {:?}",
-        merkle_tree_pda_data.pubkey_locked
+    let id = instruction_id(
+        merkle_tree_update_state_data.current_instruction_index,
+        merkle_tree_pda_data.height,
     );
-
-    msg!(
-        "ctx.accounts.merkle_tree_update_state.key(): {:?}",
-        ctx.accounts.merkle_tree_update_state.key()
-    );
-
-    let mut tmp_index = merkle_tree_pda_data.next_index;
-    msg!("tmp_index: {}", tmp_index);
-
-    // insert root into merkle tree
-
-    // Release lock
-    msg!("Lock set at slot: {}", merkle_tree_pda_data.time_locked);
-    msg!("Lock released at slot: {}", <Clock as Sysvar>::get()?.slot);
-    merkle_tree_pda_data.time_locked = 0;
-    merkle_tree_pda_data.pubkey_locked =
-        Pubkey::try_from([0; 32]).map_err(|_| ErrorCode::PubkeyTryFromFailed)?;
-
-    msg!("start loop {}", ctx.remaining_accounts.len());
-    // Leaves are passed in as pdas in remaining accounts to allow for flexibility in their
-    // number.
-    // Checks are:
-    //             - are not inserted yet
-    //             - belong to merkle_tree
-    //             - the lowest index is the next index of the merkle_tree
-    //             - indices increase incrementally by 2 for subsequent leaves
-    // Copying leaves to tmp account.
-    for (index, account) in ctx.remaining_accounts.iter().enumerate() {
-        msg!("Copying leaves pair {}", index);
-        msg!("account {:?}", account);
-        let leaves_pda_data: Account<'info, TwoLeavesBytesPda> = Account::try_from(account)?;
-
-        // Checking that leaves are not inserted already.
-        if leaves_pda_data.left_leaf_index < merkle_tree_pda_data.next_index {
-            msg!(
-                "Leaf pda state with address {:?} is already inserted",
-                *account.key
-            );
-            return err!(ErrorCode::LeafAlreadyInserted);
-        }
-
-        // Checking that the Merkle tree is the same as in leaves account.
-        if leaves_pda_data.merkle_tree_pubkey != ctx.accounts.transaction_merkle_tree.key() {
-            msg!(
-                "Leaf pda state merkle tree {} is different than passed in merkle tree {:?}",
-                leaves_pda_data.merkle_tree_pubkey,
-                ctx.accounts.transaction_merkle_tree.key()
-            );
-            return err!(ErrorCode::LeavesOfWrongTree);
-        }
-
-        // Checking that the lowest index is the next index of the merkle_tree.
-        // Check that following leaves are correct and in the right order.
-        if leaves_pda_data.left_leaf_index != tmp_index {
-            return err!(ErrorCode::FirstLeavesPdaIncorrectIndex);
-        }
-        close_account(account, &ctx.accounts.authority.to_account_info())?;
-
-        tmp_index += 2;
-    }
-
+    msg!("Root insert Instruction: {:?}", id);
+
+    // Other relayers might have inserted leaves since the update state was created.
+    // These have to be the leaves before this update, whose nodes were patched while
+    // computing the update, or leaves of this update which are already included in its root.
+    merkle_tree_pda_data.check_changelog(
+        merkle_tree_update_state_data.sequence_number,
+        merkle_tree_update_state_data.base_index,
+        merkle_tree_update_state_data.start_index,
+        merkle_tree_update_state_data.tmp_leaves_index,
+    )?;
+
+    merkle_tree_pda_data.record_root_insert(ctx.accounts.authority.key(), Clock::get()?.slot);
+    let fee = merkle_tree_pda_data.take_updater_fee(merkle_tree_update_state_data.tmp_leaves_index);
+    pay_updater_fee(
+        &ctx.accounts.transaction_merkle_tree.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        fee,
+    )?;
     insert_last_double(merkle_tree_pda_data, merkle_tree_update_state_data)?;
 
+    let event = merkle_tree_pda_data.root_update_event(ctx.accounts.transaction_merkle_tree.key());
+    invoke_root_update_event(&event, &ctx.accounts.log_wrapper.to_account_info())?;
+
     Ok(())
 }
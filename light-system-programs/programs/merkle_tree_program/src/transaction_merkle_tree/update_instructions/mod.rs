@@ -9,3 +9,9 @@ pub use update_merkle_tree::*;
 
 pub mod insert_root;
 pub use insert_root::*;
+
+pub mod take_over_update_state;
+pub use take_over_update_state::*;
+
+pub mod batch_update_merkle_tree;
+pub use batch_update_merkle_tree::*;
@@ -0,0 +1,83 @@
+use crate::config_accounts::registered_relayer::{
+    check_registered_relayer, penalize_relayer, RegisteredRelayer,
+};
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::state::TransactionMerkleTree;
+use crate::utils::config::UPDATE_STATE_TAKEOVER_REWARD;
+use crate::utils::constants::RELAYER_SEED;
+use crate::MerkleTreeUpdateState;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct TakeOverUpdateState<'info> {
+    /// The new relayer of the update state.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK:` is the relayer which abandoned the update state.
+    #[account(mut, address=merkle_tree_update_state.load()?.relayer @ErrorCode::InvalidAuthority)]
+    pub relayer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = merkle_tree_update_state.load()?.is_expired(Clock::get()?.slot, transaction_merkle_tree.load()?.lock_duration) @ErrorCode::UpdateStateNotExpired
+    )]
+    pub merkle_tree_update_state: AccountLoader<'info, MerkleTreeUpdateState>,
+    #[account(address= merkle_tree_update_state.load()?.merkle_tree_pda_pubkey @ErrorCode::InvalidMerkleTree)]
+    pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+    /// Required if the Merkle tree is gated to registered relayers.
+    #[account(seeds = [authority.key().to_bytes().as_ref(), RELAYER_SEED], bump)]
+    pub registered_relayer: Option<Account<'info, RegisteredRelayer>>,
+    /// Registration of the previous relayer, its stake is penalized on gated Merkle trees.
+    #[account(mut, seeds = [relayer.key().to_bytes().as_ref(), RELAYER_SEED], bump)]
+    pub penalized_relayer: Option<Account<'info, RegisteredRelayer>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfers an expired update state to a new relayer which continues the update from the
+/// current instruction index.
+/// The new relayer refunds the rent minus UPDATE_STATE_TAKEOVER_REWARD percent to the
+/// previous relayer and receives the full rent once the update state is closed.
+/// If the Merkle tree is gated to registered relayers, RELAYER_STAKE_PENALTY percent of the
+/// stake of the previous relayer is paid to the new relayer.
+pub fn process_take_over_update_state(ctx: Context<TakeOverUpdateState>) -> Result<()> {
+    let gated_relayers = {
+        let merkle_tree = ctx.accounts.transaction_merkle_tree.load()?;
+        check_registered_relayer(
+            &merkle_tree,
+            ctx.accounts.authority.key,
+            ctx.accounts.registered_relayer.as_deref(),
+        )?;
+        merkle_tree.gated_relayers
+    };
+    let rent = ctx
+        .accounts
+        .merkle_tree_update_state
+        .to_account_info()
+        .lamports();
+    let refund = rent - rent * UPDATE_STATE_TAKEOVER_REWARD / 100;
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.relayer.to_account_info(),
+            },
+        ),
+        refund,
+    )?;
+    if gated_relayers != 0 {
+        if let Some(penalized_relayer) = ctx.accounts.penalized_relayer.as_mut() {
+            penalize_relayer(penalized_relayer, &ctx.accounts.authority.to_account_info())?;
+        }
+    }
+
+    let mut update_state_data = ctx.accounts.merkle_tree_update_state.load_mut()?;
+    msg!(
+        "Taking over update state of {} at instruction index {}",
+        update_state_data.relayer,
+        update_state_data.current_instruction_index
+    );
+    update_state_data.relayer = ctx.accounts.authority.key();
+    update_state_data.last_update_slot = Clock::get()?.slot;
+    Ok(())
+}
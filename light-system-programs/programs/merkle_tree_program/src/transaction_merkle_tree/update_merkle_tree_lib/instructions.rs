@@ -1,20 +1,24 @@
+use anchor_lang::err;
 use anchor_lang::solana_program::{msg, program_error::ProgramError};
 
+use crate::errors::ErrorCode;
 use crate::transaction_merkle_tree::state::TransactionMerkleTree;
 use crate::transaction_merkle_tree::update_merkle_tree_lib::merkle_tree_update_state::MerkleTreeUpdateState;
-use crate::utils::config::ZERO_BYTES_MERKLE_TREE_18;
+use crate::transaction_merkle_tree::update_merkle_tree_lib::poseidon_native::poseidon_native;
+use crate::utils::config::ZERO_BYTES_MERKLE_TREE;
 use std::cell::RefMut;
 
 pub fn insert_0_double(
-    merkle_tree_account: &mut RefMut<'_, TransactionMerkleTree>,
+    merkle_tree_account: &TransactionMerkleTree,
     update_state_data: &mut MerkleTreeUpdateState,
-) -> Result<(), ProgramError> {
+) -> anchor_lang::Result<()> {
     update_state_data.current_index =
-        (merkle_tree_account.next_index + update_state_data.insert_leaves_index as u64 * 2) / 2;
+        (update_state_data.start_index + update_state_data.insert_leaves_index as u64 * 2) / 2;
 
-    if update_state_data.current_index == 262144 {
+    // Leaves are inserted in pairs, a tree of height h holds 2^(h - 1) pairs.
+    if update_state_data.current_index >= 1 << (merkle_tree_account.height - 1) {
         msg!("Merkle tree full");
-        return Err(ProgramError::InvalidInstructionData);
+        return err!(ErrorCode::MerkleTreeFull);
     }
     update_state_data.node_left = update_state_data.leaves
         [usize::try_from(update_state_data.insert_leaves_index).unwrap()][0];
@@ -36,16 +40,16 @@ pub fn insert_0_double(
 }
 
 pub fn insert_1_inner_loop(
-    merkle_tree_account: &mut RefMut<'_, TransactionMerkleTree>,
+    merkle_tree_account: &TransactionMerkleTree,
     update_state_data: &mut MerkleTreeUpdateState,
-) -> Result<(), ProgramError> {
+) -> anchor_lang::Result<()> {
     if update_state_data.current_level != 0 {
         update_state_data.current_level_hash = update_state_data.state[0..32].try_into().unwrap();
     }
     if update_state_data.current_index % 2 == 0 {
         update_state_data.node_left = update_state_data.current_level_hash;
         update_state_data.node_right =
-            ZERO_BYTES_MERKLE_TREE_18[usize::try_from(update_state_data.current_level).unwrap()];
+            ZERO_BYTES_MERKLE_TREE[usize::try_from(update_state_data.current_level).unwrap()];
         update_state_data.filled_subtrees
             [usize::try_from(update_state_data.current_level).unwrap()] =
             update_state_data.current_level_hash;
@@ -61,8 +65,17 @@ pub fn insert_1_inner_loop(
             return Ok(());
         }
     } else {
-        update_state_data.node_left = update_state_data.filled_subtrees
-            [usize::try_from(update_state_data.current_level).unwrap()];
+        let level = usize::try_from(update_state_data.current_level).unwrap();
+        // The left node covers the leaves up to `left_node_end`. If these are inserted
+        // by other relayers in parallel the copied filled subtree is stale.
+        let left_node_end = update_state_data.current_index << update_state_data.current_level;
+        if left_node_end > update_state_data.base_index
+            && left_node_end <= update_state_data.start_index
+        {
+            update_state_data.filled_subtrees[level] =
+                merkle_tree_account.patched_filled_subtree(level, left_node_end)?;
+        }
+        update_state_data.node_left = update_state_data.filled_subtrees[level];
         update_state_data.node_right = update_state_data.current_level_hash;
     }
     update_state_data.current_index /= 2;
@@ -77,7 +90,9 @@ pub fn insert_last_double(
     merkle_tree_account.current_root_index = (merkle_tree_account.current_root_index + 1)
         % u64::try_from(merkle_tree_account.roots.len()).unwrap();
 
+    let start_index = merkle_tree_account.next_index;
     merkle_tree_account.next_index = update_state_data.tmp_leaves_index;
+    merkle_tree_account.push_changelog_entry(start_index, update_state_data.tmp_leaves_index);
     let index: usize = merkle_tree_account.current_root_index.try_into().unwrap();
 
     merkle_tree_account.roots[index] = update_state_data.state[0..32].try_into().unwrap();
@@ -86,3 +101,108 @@ pub fn insert_last_double(
 
     Ok(())
 }
+
+/// Appends pairs of leaves to the Merkle tree and inserts the resulting root.
+/// Equivalent to the multi-instruction update (insert_0_double,
+/// insert_1_inner_loop, insert_last_double) but computes all hashes at once
+/// with native Poseidon.
+pub fn insert_leaves_batch(
+    merkle_tree_account: &mut TransactionMerkleTree,
+    leaves: &[[[u8; 32]; 2]],
+) -> anchor_lang::Result<()> {
+    let start_index = merkle_tree_account.next_index;
+    let mut root = [0u8; 32];
+    for (i, leaves_pair) in leaves.iter().enumerate() {
+        let mut current_index = merkle_tree_account.next_index / 2;
+        if current_index >= 1 << (merkle_tree_account.height - 1) {
+            msg!("Merkle tree full");
+            return err!(ErrorCode::MerkleTreeFull);
+        }
+
+        let mut current_level_hash = poseidon_native(&leaves_pair[0], &leaves_pair[1])?;
+        for current_level in 1..usize::try_from(merkle_tree_account.height).unwrap() {
+            let (node_left, node_right) = if current_index % 2 == 0 {
+                merkle_tree_account.filled_subtrees[current_level] = current_level_hash;
+                // Upper levels are computed with the next pair of leaves.
+                if i + 1 < leaves.len() {
+                    break;
+                }
+                (current_level_hash, ZERO_BYTES_MERKLE_TREE[current_level])
+            } else {
+                (
+                    merkle_tree_account.filled_subtrees[current_level],
+                    current_level_hash,
+                )
+            };
+            current_level_hash = poseidon_native(&node_left, &node_right)?;
+            current_index /= 2;
+        }
+        root = current_level_hash;
+        merkle_tree_account.next_index += 2;
+    }
+
+    merkle_tree_account.current_root_index = (merkle_tree_account.current_root_index + 1)
+        % u64::try_from(merkle_tree_account.roots.len()).unwrap();
+    let index: usize = merkle_tree_account.current_root_index.try_into().unwrap();
+    merkle_tree_account.roots[index] = root;
+    merkle_tree_account.push_changelog_entry(start_index, merkle_tree_account.next_index);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert_leaves_batch() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree.height = 18;
+        merkle_tree.filled_subtrees[..18].copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..18]);
+
+        // Zero leaves do not change the root of an empty tree.
+        insert_leaves_batch(
+            &mut merkle_tree,
+            &[[ZERO_BYTES_MERKLE_TREE[0], ZERO_BYTES_MERKLE_TREE[0]]],
+        )
+        .unwrap();
+        assert_eq!(merkle_tree.next_index, 2);
+        assert_eq!(merkle_tree.current_root_index, 1);
+        assert_eq!(merkle_tree.roots[1], ZERO_BYTES_MERKLE_TREE[18]);
+
+        // Inserting leaves in one batch or one by one results in the same root.
+        let leaves = [
+            [[1u8; 32], [2u8; 32]],
+            [[3u8; 32], [4u8; 32]],
+            [[5u8; 32], [6u8; 32]],
+        ];
+        let mut merkle_tree_batch = merkle_tree;
+        insert_leaves_batch(&mut merkle_tree_batch, &leaves).unwrap();
+        for leaves_pair in leaves.iter() {
+            insert_leaves_batch(&mut merkle_tree, &[*leaves_pair]).unwrap();
+        }
+        assert_eq!(merkle_tree.next_index, 8);
+        assert_eq!(
+            merkle_tree.roots[merkle_tree.current_root_index as usize],
+            merkle_tree_batch.roots[merkle_tree_batch.current_root_index as usize]
+        );
+    }
+
+    #[test]
+    fn test_insert_leaves_batch_tree_height() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree.height = 16;
+        merkle_tree.filled_subtrees[..16].copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..16]);
+
+        insert_leaves_batch(
+            &mut merkle_tree,
+            &[[ZERO_BYTES_MERKLE_TREE[0], ZERO_BYTES_MERKLE_TREE[0]]],
+        )
+        .unwrap();
+        assert_eq!(merkle_tree.roots[1], ZERO_BYTES_MERKLE_TREE[16]);
+
+        // A full tree does not accept more leaves.
+        merkle_tree.next_index = 1 << 16;
+        assert!(insert_leaves_batch(&mut merkle_tree, &[[[1u8; 32], [2u8; 32]]]).is_err());
+    }
+}
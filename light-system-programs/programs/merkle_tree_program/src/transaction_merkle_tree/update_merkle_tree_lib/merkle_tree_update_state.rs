@@ -1,4 +1,4 @@
-use crate::utils::config::MERKLE_TREE_HEIGHT;
+use crate::utils::config::MERKLE_TREE_MAX_HEIGHT;
 use anchor_lang::prelude::*;
 
 #[account(zero_copy)]
@@ -20,11 +20,44 @@ pub struct MerkleTreeUpdateState {
     pub current_level: u64,
     pub current_level_hash: [u8; 32],
     pub tmp_leaves_index: u64,
-    pub filled_subtrees: [[u8; 32]; MERKLE_TREE_HEIGHT as usize],
+    pub filled_subtrees: [[u8; 32]; MERKLE_TREE_MAX_HEIGHT as usize],
 
     pub leaves: [[[u8; 32]; 2]; 16],
     pub number_of_leaves: u8,
     _padding1: [u8; 7],
     pub insert_leaves_index: u8,
     _padding2: [u8; 7],
+    /// Merkle tree state the update is computed for, the copied filled subtrees
+    /// are the ones of the tree with `base_index` leaves.
+    pub sequence_number: u64,
+    pub base_index: u64,
+    /// Leaves from `base_index` to `start_index` are inserted by other relayers in
+    /// parallel, nodes which cover them are patched once they are inserted.
+    pub start_index: u64,
+    /// Slot of the last instruction of the relayer, used to detect abandoned updates.
+    pub last_update_slot: u64,
+}
+
+impl MerkleTreeUpdateState {
+    /// An update state is expired if its relayer did not make progress for lock_duration slots.
+    /// Expired update states can be taken over or closed by anyone.
+    pub fn is_expired(&self, current_slot: u64, lock_duration: u64) -> bool {
+        current_slot > self.last_update_slot.saturating_add(lock_duration)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_update_state_expiry() {
+        let mut update_state: MerkleTreeUpdateState = bytemuck::Zeroable::zeroed();
+        update_state.last_update_slot = 100;
+        assert!(!update_state.is_expired(100, 50));
+        assert!(!update_state.is_expired(150, 50));
+        assert!(update_state.is_expired(151, 50));
+        update_state.last_update_slot = u64::MAX;
+        assert!(!update_state.is_expired(u64::MAX, 50));
+    }
 }
@@ -1,6 +1,7 @@
 pub mod instructions;
 pub mod instructions_poseidon;
 pub mod merkle_tree_update_state;
+pub mod poseidon_native;
 pub mod poseidon_round_constants_split;
 pub mod processor;
 pub use merkle_tree_update_state::*;
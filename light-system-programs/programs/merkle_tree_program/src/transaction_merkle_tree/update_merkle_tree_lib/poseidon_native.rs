@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Poseidon parameters of the `sol_poseidon` syscall (bn254, x^5 s-box).
+#[cfg(all(target_os = "solana", feature = "native-poseidon"))]
+const POSEIDON_PARAMETERS_BN254_X5: u64 = 0;
+/// Leaves and nodes of the transaction Merkle tree are stored in little endian.
+#[cfg(all(target_os = "solana", feature = "native-poseidon"))]
+const POSEIDON_ENDIANNESS_LE: u64 = 1;
+
+#[cfg(all(target_os = "solana", feature = "native-poseidon"))]
+extern "C" {
+    fn sol_poseidon(
+        parameters: u64,
+        endianness: u64,
+        vals: *const u8,
+        val_len: u64,
+        hash_result: *mut u8,
+    ) -> u64;
+}
+
+/// Computes the circom compatible Poseidon hash of two nodes.
+pub fn poseidon_native(left: &[u8; 32], right: &[u8; 32]) -> Result<[u8; 32]> {
+    poseidon_native_hashv(&[left, right])
+}
+
+/// Computes the circom compatible Poseidon hash of up to 12 little endian
+/// inputs with the `sol_poseidon` syscall.
+#[cfg(all(target_os = "solana", feature = "native-poseidon"))]
+pub fn poseidon_native_hashv(inputs: &[&[u8; 32]]) -> Result<[u8; 32]> {
+    let vals: Vec<&[u8]> = inputs.iter().map(|input| input.as_slice()).collect();
+    let mut hash_result = [0u8; 32];
+    let result = unsafe {
+        sol_poseidon(
+            POSEIDON_PARAMETERS_BN254_X5,
+            POSEIDON_ENDIANNESS_LE,
+            vals.as_ptr() as *const u8,
+            vals.len() as u64,
+            &mut hash_result as *mut _ as *mut u8,
+        )
+    };
+    if result != 0 {
+        msg!("Poseidon syscall failed with error {}", result);
+        return err!(ErrorCode::PoseidonHashFailed);
+    }
+    Ok(hash_result)
+}
+
+/// The runtime this program was built for does not provide the Poseidon
+/// syscall, the multi-instruction update has to be used instead.
+#[cfg(all(target_os = "solana", not(feature = "native-poseidon")))]
+pub fn poseidon_native_hashv(_inputs: &[&[u8; 32]]) -> Result<[u8; 32]> {
+    msg!("Native Poseidon is not available, use update_transaction_merkle_tree.");
+    err!(ErrorCode::NativePoseidonUnavailable)
+}
+
+/// Off-chain implementation of the syscall, used in tests and clients.
+#[cfg(not(target_os = "solana"))]
+pub fn poseidon_native_hashv(inputs: &[&[u8; 32]]) -> Result<[u8; 32]> {
+    use ark_bn254_04::Fr;
+    use light_poseidon::{Poseidon, PoseidonBytesHasher};
+
+    let inputs: Vec<&[u8]> = inputs.iter().map(|input| input.as_slice()).collect();
+    let mut hasher = Poseidon::<Fr>::new_circom(inputs.len())
+        .map_err(|_| error!(ErrorCode::PoseidonHashFailed))?;
+    hasher
+        .hash_bytes_le(&inputs)
+        .map_err(|_| error!(ErrorCode::PoseidonHashFailed))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::config::ZERO_BYTES_MERKLE_TREE;
+
+    #[test]
+    fn test_poseidon_native_zero_bytes() {
+        for level in 0..ZERO_BYTES_MERKLE_TREE.len() - 1 {
+            let hash = poseidon_native(
+                &ZERO_BYTES_MERKLE_TREE[level],
+                &ZERO_BYTES_MERKLE_TREE[level],
+            )
+            .unwrap();
+            assert_eq!(
+                hash,
+                ZERO_BYTES_MERKLE_TREE[level + 1],
+                "zero bytes mismatch at level {}",
+                level
+            );
+        }
+    }
+}
@@ -2,18 +2,42 @@ use crate::transaction_merkle_tree::instructions::*;
 use crate::transaction_merkle_tree::instructions_poseidon::{poseidon_0, poseidon_1, poseidon_2};
 use crate::transaction_merkle_tree::state::TransactionMerkleTree;
 use crate::transaction_merkle_tree::update_merkle_tree_lib::merkle_tree_update_state::MerkleTreeUpdateState;
-use crate::utils::config::MERKLE_TREE_HEIGHT;
-use crate::utils::constants::{HASH_0, HASH_1, HASH_2, MERKLE_TREE_UPDATE_START};
+use crate::utils::constants::{
+    HASH_0, HASH_1, HASH_2, LOCK_START, MERKLE_TREE_UPDATE_START, ROOT_INSERT,
+};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{msg, pubkey::Pubkey};
 
 use crate::errors::ErrorCode;
-use std::cell::RefMut;
+
+/// Index of the root insert instruction of a Merkle tree update.
+/// The update consists of the lock, the start of the update, three hash
+/// instructions per level and the root insert.
+pub fn root_insert_instruction_index(height: u64) -> u64 {
+    3 * height + 2
+}
+
+/// Returns the instruction identifier of the instruction at `current_instruction_index`
+/// of a Merkle tree update for a tree of height `height`.
+pub fn instruction_id(current_instruction_index: u64, height: u64) -> Option<u8> {
+    let root_insert_index = root_insert_instruction_index(height);
+    match current_instruction_index {
+        0 => Some(LOCK_START),
+        1 => Some(MERKLE_TREE_UPDATE_START),
+        index if index < root_insert_index => Some(match (index - 2) % 3 {
+            0 => HASH_0,
+            1 => HASH_1,
+            _ => HASH_2,
+        }),
+        index if index == root_insert_index => Some(ROOT_INSERT),
+        _ => None,
+    }
+}
 
 pub fn compute_updated_merkle_tree(
     id: u8,
     merkle_tree_update_state_data: &mut MerkleTreeUpdateState,
-    merkle_tree_pda_data: &mut RefMut<'_, TransactionMerkleTree>,
+    merkle_tree_pda_data: &TransactionMerkleTree,
 ) -> Result<()> {
     msg!("executing instruction {}", id);
     // Hash computation is split into three parts which can be executed in ~2m compute units
@@ -24,7 +48,7 @@ pub fn compute_updated_merkle_tree(
     } else if id == HASH_2 {
         poseidon_2(merkle_tree_update_state_data)?;
         // Updating the current level hash after a new hash is completely computed.
-        if merkle_tree_update_state_data.current_level < MERKLE_TREE_HEIGHT {
+        if merkle_tree_update_state_data.current_level < merkle_tree_pda_data.height {
             insert_1_inner_loop(merkle_tree_pda_data, merkle_tree_update_state_data)?;
         }
     } else if id == MERKLE_TREE_UPDATE_START {
@@ -41,3 +65,156 @@ pub fn pubkey_check(account_pubkey0: Pubkey, account_pubkey1: Pubkey, msg: Strin
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transaction_merkle_tree::update_merkle_tree_lib::instructions::insert_leaves_batch;
+    use crate::utils::config::ZERO_BYTES_MERKLE_TREE;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_instruction_id() {
+        // Instruction order of a Merkle tree with height 18.
+        let ix_order: [u8; 57] = [
+            34, 14, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1,
+            2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2,
+            241,
+        ];
+        for (index, id) in ix_order.iter().enumerate() {
+            assert_eq!(instruction_id(index as u64, 18), Some(*id));
+        }
+        assert_eq!(instruction_id(57, 18), None);
+        assert_eq!(root_insert_instruction_index(18), 56);
+        assert_eq!(instruction_id(26 * 3 + 2, 26), Some(ROOT_INSERT));
+    }
+
+    /// Creates an update state for `leaves` like initialize update state.
+    fn update_state(
+        merkle_tree: &TransactionMerkleTree,
+        start_index: u64,
+        leaves: &[[[u8; 32]; 2]],
+    ) -> MerkleTreeUpdateState {
+        let mut update_state: MerkleTreeUpdateState = bytemuck::Zeroable::zeroed();
+        update_state.current_instruction_index = 1;
+        update_state.leaves[..leaves.len()].copy_from_slice(leaves);
+        update_state.number_of_leaves = leaves.len() as u8;
+        update_state.filled_subtrees = merkle_tree.filled_subtrees;
+        update_state.tmp_leaves_index = start_index;
+        update_state.sequence_number = merkle_tree.sequence_number;
+        update_state.base_index = merkle_tree.next_index;
+        update_state.start_index = start_index;
+        update_state
+    }
+
+    /// Executes the hash instructions of an update like update merkle tree.
+    fn compute_update(
+        update_state: &mut MerkleTreeUpdateState,
+        merkle_tree: &TransactionMerkleTree,
+    ) -> Result<()> {
+        while update_state.current_instruction_index
+            < root_insert_instruction_index(merkle_tree.height)
+        {
+            let id =
+                instruction_id(update_state.current_instruction_index, merkle_tree.height).unwrap();
+            compute_updated_merkle_tree(id, update_state, merkle_tree)?;
+            update_state.current_instruction_index += 1;
+        }
+        Ok(())
+    }
+
+    /// Inserts the root of an update like insert root.
+    fn insert_root(
+        update_state: &mut MerkleTreeUpdateState,
+        merkle_tree: &mut TransactionMerkleTree,
+    ) -> Result<()> {
+        merkle_tree.check_changelog(
+            update_state.sequence_number,
+            update_state.base_index,
+            update_state.start_index,
+            update_state.tmp_leaves_index,
+        )?;
+        let update_state = RefCell::new(*update_state);
+        let merkle_tree_cell = RefCell::new(*merkle_tree);
+        insert_last_double(
+            &mut merkle_tree_cell.borrow_mut(),
+            &mut update_state.borrow_mut(),
+        )?;
+        *merkle_tree = merkle_tree_cell.into_inner();
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_matches_multi_instruction_update() {
+        for nr_pairs in [1u8, 2, 16] {
+            let leaves: Vec<[[u8; 32]; 2]> = (0..nr_pairs)
+                .map(|i| [[2 * i + 1; 32], [2 * i + 2; 32]])
+                .collect();
+            // Starts from an empty tree and from a tree with an odd number of pairs.
+            for inserted_pairs in [0, 1] {
+                let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+                merkle_tree.height = 18;
+                merkle_tree.filled_subtrees[..18].copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..18]);
+                for _ in 0..inserted_pairs {
+                    insert_leaves_batch(&mut merkle_tree, &[[[33u8; 32], [34u8; 32]]]).unwrap();
+                }
+
+                let mut batch = merkle_tree;
+                insert_leaves_batch(&mut batch, &leaves).unwrap();
+
+                let mut update_state = update_state(&merkle_tree, merkle_tree.next_index, &leaves);
+                compute_update(&mut update_state, &merkle_tree).unwrap();
+                insert_root(&mut update_state, &mut merkle_tree).unwrap();
+
+                assert_eq!(merkle_tree.next_index, batch.next_index);
+                assert_eq!(merkle_tree.current_root_index, batch.current_root_index);
+                assert_eq!(merkle_tree.roots, batch.roots);
+                assert_eq!(merkle_tree.filled_subtrees, batch.filled_subtrees);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_updates() {
+        let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+        merkle_tree.height = 18;
+        merkle_tree.filled_subtrees[..18].copy_from_slice(&ZERO_BYTES_MERKLE_TREE[..18]);
+        insert_leaves_batch(&mut merkle_tree, &[[[1u8; 32], [2u8; 32]]]).unwrap();
+
+        let leaves_first = [[[3u8; 32], [4u8; 32]], [[5u8; 32], [6u8; 32]]];
+        let leaves_second = [[[7u8; 32], [8u8; 32]], [[9u8; 32], [10u8; 32]]];
+        let mut expected = merkle_tree;
+        insert_leaves_batch(&mut expected, &leaves_first).unwrap();
+        insert_leaves_batch(&mut expected, &leaves_second).unwrap();
+
+        // Both relayers start from the same Merkle tree state, the second one
+        // skips the leaves of the first one.
+        let mut first = update_state(&merkle_tree, 2, &leaves_first);
+        let mut second = update_state(&merkle_tree, 6, &leaves_second);
+
+        // The second update depends on nodes of the leaves of the first update.
+        assert_eq!(
+            compute_update(&mut second.clone(), &merkle_tree),
+            err!(ErrorCode::LeavesBeforeUpdateNotInserted)
+        );
+
+        compute_update(&mut first, &merkle_tree).unwrap();
+        let mut outdated = first;
+        insert_root(&mut first, &mut merkle_tree).unwrap();
+        compute_update(&mut second, &merkle_tree).unwrap();
+        insert_root(&mut second, &mut merkle_tree).unwrap();
+
+        assert_eq!(merkle_tree.next_index, 10);
+        assert_eq!(
+            merkle_tree.roots[merkle_tree.current_root_index as usize],
+            expected.roots[expected.current_root_index as usize]
+        );
+        assert_eq!(merkle_tree.filled_subtrees, expected.filled_subtrees);
+
+        // An update of already inserted leaves is outdated.
+        assert_eq!(
+            insert_root(&mut outdated, &mut merkle_tree),
+            err!(ErrorCode::MerkleTreeUpdateStateOutdated)
+        );
+    }
+}
@@ -1,13 +1,72 @@
 use anchor_lang::constant;
 
 #[constant]
-pub const ENCRYPTED_UTXOS_LENGTH: usize = 174;
-#[constant]
-pub const MERKLE_TREE_TMP_PDA_SIZE: usize = 2048;
+pub const MERKLE_TREE_TMP_PDA_SIZE: usize = 2304;
 #[constant]
 pub const MERKLE_TREE_HISTORY_SIZE: u64 = 256;
+/// Number of root updates an update state can fall behind and still be applied.
+#[constant]
+pub const MERKLE_TREE_CHANGELOG_SIZE: u64 = 16;
+/// Number of pairs of leaves which can be queued for insertion into a transaction Merkle tree.
+#[constant]
+pub const LEAVES_QUEUE_SIZE: u64 = 128;
+/// Height of the transaction Merkle tree created with the Merkle tree authority.
 #[constant]
 pub const MERKLE_TREE_HEIGHT: u64 = 18;
+#[constant]
+pub const MERKLE_TREE_MIN_HEIGHT: u64 = 16;
+#[constant]
+pub const MERKLE_TREE_MAX_HEIGHT: u64 = 26;
+/// Percentage of queued leaves after which anyone can create the next
+/// transaction Merkle tree.
+#[constant]
+pub const MERKLE_TREE_ROLLOVER_THRESHOLD: u64 = 95;
+#[constant]
+pub const NULLIFIER_TREE_HEIGHT: u64 = 26;
+/// Number of roots a non-inclusion proof of a nullifier can be checked against.
+#[constant]
+pub const NULLIFIER_TREE_HISTORY_SIZE: u64 = 32;
+/// Number of nullifiers which can be queued for insertion into the nullifier tree.
+#[constant]
+pub const NULLIFIER_QUEUE_SIZE: u64 = 128;
+/// Number of slots after which an uploaded verifying key becomes active.
+#[constant]
+pub const VERIFYING_KEY_TIMELOCK: u64 = 216_000;
+/// Maximum number of signers of the merkle tree authority multisig.
+#[constant]
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+/// Maximum number of pool types a registered verifier can be restricted to.
+#[constant]
+pub const MAX_VERIFIER_ALLOWED_POOL_TYPES: usize = 4;
+/// Maximum number of Merkle trees a registered verifier can be restricted to.
+#[constant]
+pub const MAX_VERIFIER_ALLOWED_MERKLE_TREES: usize = 4;
+#[constant]
+pub const MAX_VERIFYING_KEY_PUBLIC_INPUTS: u64 = 64;
+/// Default withdrawal window of asset pools in slots (~1 hour).
+#[constant]
+pub const DEFAULT_WITHDRAWAL_WINDOW: u64 = 9_000;
+/// Number of slots after which a withdrawal over the withdrawal limit can be claimed.
+#[constant]
+pub const DELAYED_WITHDRAWAL_TIMELOCK: u64 = 216_000;
+/// Maximum number of delayed withdrawals queued per asset pool.
+#[constant]
+pub const MAX_DELAYED_WITHDRAWALS: usize = 16;
+/// Percentage of the rent of an expired Merkle tree update state paid to the relayer
+/// taking it over or closing it.
+#[constant]
+pub const UPDATE_STATE_TAKEOVER_REWARD: u64 = 20;
+/// Percentage of the stake a relayer loses when its expired update state is taken over or
+/// closed.
+#[constant]
+pub const RELAYER_STAKE_PENALTY: u64 = 10;
+/// Number of slots relayer stake stays locked after staking or creating an update state.
+#[constant]
+pub const RELAYER_UNSTAKE_DELAY: u64 = 216_000;
+/// Fee in lamports paid into the fee vault of a transaction Merkle tree for every inserted
+/// pair of leaves, paid out to the relayers updating the tree.
+#[constant]
+pub const DEFAULT_LEAF_FEE: u64 = 5_000;
 
 #[constant]
 pub const INITIAL_MERKLE_TREE_AUTHORITY: [u8; 32] = [
@@ -18,7 +77,7 @@ pub const INITIAL_MERKLE_TREE_AUTHORITY: [u8; 32] = [
 /// Zero values to fill an empty sparse merkle tree.
 /// Every hash is the hash of its previous hash ([prev_hash, prev_hash].concat())
 #[constant]
-pub const ZERO_BYTES_MERKLE_TREE_18: [[u8; 32]; 19] = [
+pub const ZERO_BYTES_MERKLE_TREE: [[u8; 32]; 27] = [
     [
         40, 66, 58, 227, 48, 224, 249, 227, 188, 18, 133, 168, 156, 214, 220, 144, 244, 144, 67,
         82, 76, 6, 135, 78, 64, 186, 52, 113, 234, 47, 27, 32,
@@ -95,4 +154,36 @@ pub const ZERO_BYTES_MERKLE_TREE_18: [[u8; 32]; 19] = [
         231, 174, 226, 37, 211, 160, 187, 178, 149, 82, 17, 60, 110, 116, 28, 61, 58, 145, 58, 71,
         25, 42, 67, 46, 189, 214, 248, 234, 182, 251, 238, 34,
     ],
+    [
+        154, 31, 139, 147, 198, 116, 198, 21, 178, 226, 37, 28, 188, 65, 80, 186, 104, 217, 80,
+        136, 219, 185, 137, 112, 222, 230, 85, 166, 242, 213, 78, 21,
+    ],
+    [
+        110, 143, 173, 69, 35, 46, 186, 59, 181, 249, 248, 202, 81, 141, 108, 105, 34, 44, 109, 82,
+        106, 111, 226, 5, 74, 57, 243, 224, 28, 83, 90, 9,
+    ],
+    [
+        76, 147, 78, 218, 174, 113, 73, 205, 158, 246, 173, 237, 116, 169, 81, 221, 246, 29, 244,
+        137, 63, 121, 192, 123, 107, 185, 66, 134, 97, 83, 101, 43,
+    ],
+    [
+        194, 76, 54, 227, 200, 20, 5, 189, 30, 137, 16, 50, 99, 252, 59, 94, 91, 147, 43, 95, 141,
+        5, 188, 200, 229, 207, 48, 41, 164, 126, 197, 23,
+    ],
+    [
+        153, 114, 73, 246, 25, 237, 228, 134, 41, 171, 246, 134, 69, 229, 115, 24, 160, 72, 104,
+        113, 255, 161, 176, 16, 221, 193, 139, 240, 55, 210, 134, 19,
+    ],
+    [
+        232, 96, 167, 26, 45, 54, 61, 216, 22, 255, 57, 109, 251, 171, 52, 39, 167, 112, 1, 100,
+        203, 164, 15, 14, 229, 67, 118, 16, 81, 45, 22, 25,
+    ],
+    [
+        159, 195, 41, 229, 0, 143, 186, 215, 216, 108, 12, 46, 212, 70, 87, 43, 215, 170, 178, 113,
+        116, 149, 237, 74, 154, 135, 23, 237, 87, 14, 191, 6,
+    ],
+    [
+        244, 145, 68, 51, 72, 253, 131, 90, 86, 87, 156, 15, 55, 205, 118, 157, 35, 1, 7, 111, 190,
+        73, 142, 7, 170, 199, 227, 136, 79, 228, 64, 7,
+    ],
 ];
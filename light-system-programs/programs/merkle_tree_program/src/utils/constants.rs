@@ -1,11 +1,6 @@
 // This file stores constants which do not have to be configured.
-use anchor_lang::constant;
-//instruction order
-#[constant]
-pub const IX_ORDER: [u8; 57] = [
-    34, 14, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1,
-    2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 241,
-];
+use anchor_lang::{constant, prelude::*};
+use light_macros::pubkey;
 // Identitifiers for instructions
 pub const MERKLE_TREE_UPDATE_START: u8 = 14;
 pub const LOCK_START: u8 = 34;
@@ -24,7 +19,9 @@ pub const TREE_ROOT_SEED: &[u8] = b"TREE_ROOT_SEED";
 #[constant]
 pub const STORAGE_SEED: &[u8] = b"storage";
 #[constant]
-pub const LEAVES_SEED: &[u8] = b"leaves";
+pub const RELAYER_SEED: &[u8] = b"relayer";
+#[constant]
+pub const LEAVES_QUEUE_SEED: &[u8] = b"leaves_queue";
 #[constant]
 pub const NULLIFIER_SEED: &[u8] = b"nf";
 #[constant]
@@ -39,6 +36,25 @@ pub const TOKEN_AUTHORITY_SEED: &[u8] = b"spl";
 pub const EVENT_MERKLE_TREE_SEED: &[u8] = b"event_merkle_tree";
 #[constant]
 pub const TRANSACTION_MERKLE_TREE_SEED: &[u8] = b"transaction_merkle_tree";
+#[constant]
+pub const NULLIFIER_TREE_SEED: &[u8] = b"nullifier_tree";
+#[constant]
+pub const NULLIFIER_QUEUE_SEED: &[u8] = b"nullifier_queue";
+#[constant]
+pub const METADATA_SEED: &[u8] = b"metadata";
+#[constant]
+pub const VERIFYING_KEY_SEED: &[u8] = b"verifying_key";
+
+/// Pool type of nft pools, fungible token pools cannot use this pool type.
+#[constant]
+pub const NFT_POOL_TYPE: [u8; 32] = [
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+/// Metaplex token metadata program.
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+/// Noop program which root update events are logged through.
+pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
 
 // Merkle tree parameters
 #[constant]
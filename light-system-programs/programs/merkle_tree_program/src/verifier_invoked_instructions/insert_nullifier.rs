@@ -1,4 +1,9 @@
-use crate::utils::{constants::NULLIFIER_SEED, create_pda::create_and_check_pda};
+use crate::errors::ErrorCode;
+use crate::utils::{
+    constants::{MERKLE_TREE_AUTHORITY_SEED, NULLIFIER_SEED},
+    create_pda::create_and_check_pda,
+};
+use crate::MerkleTreeAuthority;
 use crate::RegisteredVerifier;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{pubkey::Pubkey, sysvar};
@@ -9,18 +14,47 @@ pub struct InitializeNullifiers<'info> {
     #[account(mut, seeds=[__program_id.to_bytes().as_ref()], bump,seeds::program=registered_verifier_pda.pubkey)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
-    #[account(seeds=[&registered_verifier_pda.pubkey.to_bytes()],  bump )]
+    #[account(
+        seeds = [&registered_verifier_pda.pubkey.to_bytes()],
+        bump,
+        constraint = registered_verifier_pda.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier_pda.has_capability(RegisteredVerifier::INSERT_NULLIFIERS) @ErrorCode::MissingVerifierCapability,
+    )]
     pub registered_verifier_pda: Account<'info, RegisteredVerifier>, // nullifiers are sent in remaining accounts. @ErrorCode::InvalidVerifier
+    /// Every transaction inserts leaves and nullifiers, the kind of a transaction is only
+    /// known to the verifier. They are rejected if any transaction kind is paused.
+    /// Nullifier pdas are rejected once the nullifier tree is enabled.
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_ALL) @ErrorCode::ProtocolPaused,
+        constraint = !merkle_tree_authority_pda.nullifier_tree_enabled @ErrorCode::NullifierPdasDisabled,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
 }
 
 /// Inserts nullifiers, written in plain rust for memory optimization.
+/// Every nullifier pda is created once, an existing pda means the nullifier is spent already.
 pub fn process_insert_nullifiers<'info>(
     ctx: Context<'_, '_, '_, 'info, InitializeNullifiers<'info>>,
     nullifiers: Vec<[u8; 32]>,
 ) -> Result<()> {
+    if nullifiers.len() != ctx.remaining_accounts.len() {
+        msg!(
+            "{} nullifiers but {} nullifier accounts",
+            nullifiers.len(),
+            ctx.remaining_accounts.len()
+        );
+        return err!(ErrorCode::InvalidNumberOfNullifiers);
+    }
     let rent = <Rent as sysvar::Sysvar>::get()?;
 
-    for (nullifier_pda, nullifier) in ctx.remaining_accounts.iter().zip(nullifiers) {
+    for (i, (nullifier_pda, nullifier)) in ctx.remaining_accounts.iter().zip(nullifiers).enumerate()
+    {
+        if *nullifier_pda.owner == *ctx.program_id {
+            msg!("Nullifier {} is spent already.", i);
+            return err!(ErrorCode::NullifierAlreadyExists);
+        }
         create_and_check_pda(
             ctx.program_id,
             &ctx.accounts.authority.to_account_info(),
@@ -32,8 +66,7 @@ pub fn process_insert_nullifiers<'info>(
             1,    //bytes
             0,    //lamports
             true, //rent_exempt
-        )
-        .unwrap();
+        )?;
         nullifier_pda.to_account_info().data.borrow_mut()[0] = 1u8;
     }
     Ok(())
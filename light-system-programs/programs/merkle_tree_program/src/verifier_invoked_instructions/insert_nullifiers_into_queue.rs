@@ -0,0 +1,74 @@
+use crate::errors::ErrorCode;
+use crate::nullifier_tree::indexed_tree::{NullifierQueue, NullifierTree};
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, NULLIFIER_SEED, NULLIFIER_TREE_SEED};
+use crate::MerkleTreeAuthority;
+use crate::RegisteredVerifier;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InsertNullifiersIntoQueue<'info> {
+    /// CHECK:` Signer is owned by registered verifier program.
+    #[account(seeds=[__program_id.to_bytes().as_ref()], bump, seeds::program=registered_verifier_pda.pubkey)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [&registered_verifier_pda.pubkey.to_bytes()],
+        bump,
+        constraint = registered_verifier_pda.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier_pda.has_capability(RegisteredVerifier::INSERT_NULLIFIERS) @ErrorCode::MissingVerifierCapability,
+    )]
+    pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
+    #[account(mut, has_one = nullifier_tree @ErrorCode::InvalidNullifierQueue)]
+    pub nullifier_queue: AccountLoader<'info, NullifierQueue>,
+    /// Non-inclusion is proven against the single nullifier tree.
+    #[account(seeds = [NULLIFIER_TREE_SEED], bump)]
+    pub nullifier_tree: AccountLoader<'info, NullifierTree>,
+    /// Every transaction inserts leaves and nullifiers, the kind of a transaction is only
+    /// known to the verifier. They are rejected if any transaction kind is paused.
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_ALL) @ErrorCode::ProtocolPaused,
+        constraint = merkle_tree_authority_pda.nullifier_tree_enabled @ErrorCode::NullifierTreeNotEnabled,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+/// Queues nullifiers for insertion into the nullifier tree.
+/// Nullifiers spent before the nullifier tree was enabled are stored in nullifier pdas,
+/// the nullifier pdas are passed in as remaining accounts and must not exist.
+pub fn process_insert_nullifiers_into_queue(
+    ctx: Context<InsertNullifiersIntoQueue>,
+    nullifiers: Vec<[u8; 32]>,
+) -> Result<()> {
+    if nullifiers.len() != ctx.remaining_accounts.len() {
+        msg!(
+            "{} nullifiers but {} nullifier accounts",
+            nullifiers.len(),
+            ctx.remaining_accounts.len()
+        );
+        return err!(ErrorCode::InvalidNumberOfNullifiers);
+    }
+    for (i, (nullifier_pda, nullifier)) in ctx
+        .remaining_accounts
+        .iter()
+        .zip(nullifiers.iter())
+        .enumerate()
+    {
+        let (pubkey, _) =
+            Pubkey::find_program_address(&[nullifier.as_ref(), NULLIFIER_SEED], ctx.program_id);
+        if *nullifier_pda.key != pubkey {
+            msg!("Nullifier pda {} is invalid, expected: {}", i, pubkey);
+            return err!(ErrorCode::InvalidNullifierPda);
+        }
+        if *nullifier_pda.owner == *ctx.program_id {
+            msg!("Nullifier {} is spent already.", i);
+            return err!(ErrorCode::NullifierAlreadyExists);
+        }
+    }
+    let sequence_number = ctx.accounts.nullifier_tree.load()?.sequence_number;
+    let mut nullifier_queue = ctx.accounts.nullifier_queue.load_mut()?;
+    for nullifier in nullifiers {
+        nullifier_queue.insert(nullifier, sequence_number)?;
+    }
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    event_merkle_tree::EventMerkleTree, utils::constants::EVENT_MERKLE_TREE_SEED,
-    RegisteredVerifier,
+    errors::ErrorCode, event_merkle_tree::EventMerkleTree,
+    utils::constants::EVENT_MERKLE_TREE_SEED, RegisteredVerifier,
 };
 
 #[derive(Accounts)]
@@ -27,6 +27,9 @@ pub struct InsertTwoLeavesEvent<'info> {
     #[account(
         seeds = [&registered_verifier.pubkey.to_bytes()],
         bump,
+        constraint = registered_verifier.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier.has_capability(RegisteredVerifier::INSERT_LEAVES) @ErrorCode::MissingVerifierCapability,
+        constraint = registered_verifier.is_merkle_tree_allowed(&event_merkle_tree.key()) @ErrorCode::MerkleTreeNotAllowed,
     )]
     pub registered_verifier: Account<'info, RegisteredVerifier>,
 }
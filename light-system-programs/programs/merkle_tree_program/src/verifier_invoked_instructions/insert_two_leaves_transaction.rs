@@ -1,47 +1,90 @@
-use crate::transaction_merkle_tree::state::{TransactionMerkleTree, TwoLeavesBytesPda};
-use crate::utils::constants::{LEAVES_SEED, TRANSACTION_MERKLE_TREE_SEED};
+use crate::errors::ErrorCode;
+use crate::transaction_merkle_tree::{leaves_queue::LeavesQueue, state::TransactionMerkleTree};
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, TRANSACTION_MERKLE_TREE_SEED};
+use crate::MerkleTreeAuthority;
 use crate::RegisteredVerifier;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 #[derive(Accounts)]
-#[instruction(
-    leaf_left: [u8;32],
-    leaf_right: [u8;32],
-    encrypted_utxos: [u8;256],
-)]
 pub struct InsertTwoLeaves<'info> {
     /// CHECK:` should only be accessed by a registered verifier.
     #[account(mut, seeds=[__program_id.to_bytes().as_ref()],bump,seeds::program=registered_verifier_pda.pubkey)]
     pub authority: Signer<'info>,
-    // /// CHECK:` Leaves account should be checked by invoking verifier.
-    #[account(init, seeds= [&leaf_left, LEAVES_SEED], bump, payer=authority, space= 8 + 3 * 32 + 256 + 8 + 8)]
-    pub two_leaves_pda: Account<'info, TwoLeavesBytesPda>,
+    #[account(mut, has_one = transaction_merkle_tree @ErrorCode::InvalidLeavesQueue)]
+    pub leaves_queue: AccountLoader<'info, LeavesQueue>,
     #[account(mut, seeds = [
         TRANSACTION_MERKLE_TREE_SEED,
         transaction_merkle_tree.load().unwrap().merkle_tree_nr.to_le_bytes().as_ref()
     ], bump)]
     pub transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
     pub system_program: Program<'info, System>,
-    #[account(seeds=[&registered_verifier_pda.pubkey.to_bytes()],  bump)]
+    #[account(
+        seeds = [&registered_verifier_pda.pubkey.to_bytes()],
+        bump,
+        constraint = registered_verifier_pda.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier_pda.has_capability(RegisteredVerifier::INSERT_LEAVES) @ErrorCode::MissingVerifierCapability,
+        constraint = registered_verifier_pda.is_merkle_tree_allowed(&transaction_merkle_tree.key()) @ErrorCode::MerkleTreeNotAllowed,
+    )]
     pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
+    /// Every transaction inserts leaves and nullifiers, the kind of a transaction is only
+    /// known to the verifier. They are rejected if any transaction kind is paused.
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_ALL) @ErrorCode::ProtocolPaused,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
 }
 
 pub fn process_insert_two_leaves(
     ctx: Context<InsertTwoLeaves>,
     leaf_left: [u8; 32],
     leaf_right: [u8; 32],
-    encrypted_utxos: [u8; 256],
+    first_leaves: bool,
 ) -> Result<()> {
-    //save leaves into pda account
-    ctx.accounts.two_leaves_pda.node_left = leaf_left;
-    ctx.accounts.two_leaves_pda.node_right = leaf_right;
     let mut merkle_tree = ctx.accounts.transaction_merkle_tree.load_mut()?;
-    ctx.accounts.two_leaves_pda.left_leaf_index = merkle_tree.next_queued_index;
+    if merkle_tree.newest != 1 {
+        msg!(
+            "Merkle tree {} is read-only, use the newest Merkle tree.",
+            merkle_tree.merkle_tree_nr
+        );
+        return err!(ErrorCode::MerkleTreeReadOnly);
+    }
+    if merkle_tree.next_queued_index + 2 > merkle_tree.capacity() {
+        msg!("Merkle tree full");
+        return err!(ErrorCode::MerkleTreeFull);
+    }
+    if merkle_tree.is_rollover_threshold_reached() {
+        msg!(
+            "Merkle tree {} reached the rollover threshold, initialize the next Merkle tree.",
+            merkle_tree.merkle_tree_nr
+        );
+    }
 
-    ctx.accounts.two_leaves_pda.merkle_tree_pubkey = ctx.accounts.transaction_merkle_tree.key();
-    ctx.accounts.two_leaves_pda.encrypted_utxos = encrypted_utxos;
+    ctx.accounts
+        .leaves_queue
+        .load_mut()?
+        .push(&mut merkle_tree, leaf_left, leaf_right)?;
+    if first_leaves {
+        merkle_tree.transaction_sequence_number += 1;
+    }
 
-    // Increase next index by 2 because we're inserting 2 leaves at once.
-    merkle_tree.next_queued_index += 2;
+    // The fee is paid out to the relayer which inserts the leaves into the Merkle tree.
+    let leaf_fee = merkle_tree.leaf_fee;
+    if leaf_fee > 0 {
+        merkle_tree.fee_vault_balance += leaf_fee;
+        drop(merkle_tree);
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.transaction_merkle_tree.to_account_info(),
+                },
+            ),
+            leaf_fee,
+        )?;
+    }
     Ok(())
 }
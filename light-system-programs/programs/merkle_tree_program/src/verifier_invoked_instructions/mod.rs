@@ -12,3 +12,6 @@ pub use insert_two_leaves_event::*;
 
 pub mod insert_two_leaves_transaction;
 pub use insert_two_leaves_transaction::*;
+
+pub mod insert_nullifiers_into_queue;
+pub use insert_nullifiers_into_queue::*;
@@ -1,3 +1,6 @@
+use crate::errors::ErrorCode;
+use crate::utils::constants::MERKLE_TREE_AUTHORITY_SEED;
+use crate::MerkleTreeAuthority;
 use crate::RegisteredAssetPool;
 use crate::RegisteredVerifier;
 use anchor_lang::prelude::*;
@@ -8,11 +11,78 @@ pub struct WithdrawSol<'info> {
     pub authority: Signer<'info>,
     #[account(mut)]
     pub merkle_tree_token: Account<'info, RegisteredAssetPool>,
-    #[account(mut, seeds=[&registered_verifier_pda.pubkey.to_bytes()],  bump)]
+    #[account(
+        mut,
+        seeds = [&registered_verifier_pda.pubkey.to_bytes()],
+        bump,
+        constraint = registered_verifier_pda.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier_pda.has_capability(RegisteredVerifier::WITHDRAW_SOL) @ErrorCode::MissingVerifierCapability,
+        constraint = registered_verifier_pda.is_pool_type_allowed(&merkle_tree_token.pool_type) @ErrorCode::PoolTypeNotAllowed,
+    )]
     pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
     /// CHECK:`
     #[account(mut)]
     pub recipient: UncheckedAccount<'info>,
+    /// Withdrawals happen in unshields and relayer fee payments of transfers.
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD | MerkleTreeAuthority::PAUSE_TRANSFER) @ErrorCode::ProtocolPaused,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+/// Pays out released delayed sol withdrawals, can be called by anyone.
+#[derive(Accounts)]
+pub struct ClaimDelayedSolWithdrawal<'info> {
+    #[account(mut)]
+    pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
+    /// CHECK:` Is the recipient of the delayed withdrawal.
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD) @ErrorCode::ProtocolPaused,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+/// Transfers the amount from the sol pool if it is within the withdrawal limit,
+/// otherwise the withdrawal is delayed.
+pub fn process_withdraw_sol(ctx: Context<WithdrawSol>, amount: u64) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    if ctx
+        .accounts
+        .merkle_tree_token
+        .track_withdrawal(amount, slot)
+    {
+        process_sol_transfer(
+            &ctx.accounts.merkle_tree_token.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            amount,
+        )
+    } else {
+        ctx.accounts
+            .merkle_tree_token
+            .queue_withdrawal(ctx.accounts.recipient.key(), amount, slot)
+    }
+}
+
+pub fn process_claim_delayed_sol_withdrawal(ctx: Context<ClaimDelayedSolWithdrawal>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let amount = ctx
+        .accounts
+        .registered_asset_pool_pda
+        .take_released_withdrawals(&ctx.accounts.recipient.key(), slot)?;
+    ctx.accounts
+        .registered_asset_pool_pda
+        .track_claim(amount, slot)?;
+    process_sol_transfer(
+        &ctx.accounts.registered_asset_pool_pda.to_account_info(),
+        &ctx.accounts.recipient.to_account_info(),
+        amount,
+    )
 }
 
 pub fn process_sol_transfer(
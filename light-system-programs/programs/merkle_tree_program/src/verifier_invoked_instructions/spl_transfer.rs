@@ -1,7 +1,10 @@
-use crate::utils::constants::TOKEN_AUTHORITY_SEED;
+use crate::errors::ErrorCode;
+use crate::utils::constants::{MERKLE_TREE_AUTHORITY_SEED, TOKEN_AUTHORITY_SEED};
+use crate::MerkleTreeAuthority;
+use crate::RegisteredAssetPool;
 use crate::RegisteredVerifier;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 #[derive(Accounts)]
 pub struct WithdrawSpl<'info> {
     /// CHECK:` Signer is registered verifier program.
@@ -9,36 +12,136 @@ pub struct WithdrawSpl<'info> {
     pub authority: Signer<'info>,
     /// CHECK:` That the merkle tree token belongs to a registered Merkle tree.
     #[account(mut)]
-    pub merkle_tree_token: Account<'info, TokenAccount>,
+    pub merkle_tree_token: InterfaceAccount<'info, TokenAccount>,
     /// CHECK:` That the merkle tree token belongs to a registered Merkle tree.
     #[account(mut)]
-    pub recipient: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = merkle_tree_token.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
     /// CHECK:` that the token authority is derived in the correct way.
     #[account(mut, seeds=[TOKEN_AUTHORITY_SEED], bump)]
     pub token_authority: AccountInfo<'info>,
-    #[account(seeds=[&registered_verifier_pda.pubkey.to_bytes()],  bump)]
+    #[account(
+        seeds = [&registered_verifier_pda.pubkey.to_bytes()],
+        bump,
+        constraint = registered_verifier_pda.is_active() @ErrorCode::VerifierNotActive,
+        constraint = registered_verifier_pda.has_capability(RegisteredVerifier::WITHDRAW_SPL) @ErrorCode::MissingVerifierCapability,
+        constraint = registered_verifier_pda.is_spl_pool_allowed(&merkle_tree_token) @ErrorCode::PoolTypeNotAllowed,
+    )]
     pub registered_verifier_pda: Account<'info, RegisteredVerifier>,
+    /// Withdrawals happen in unshields and relayer fee payments of transfers.
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD | MerkleTreeAuthority::PAUSE_TRANSFER) @ErrorCode::ProtocolPaused,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    /// Tracks the withdrawal limit of the pool.
+    #[account(
+        mut,
+        constraint = registered_asset_pool_pda.asset_pool_pubkey == merkle_tree_token.key() @ErrorCode::InvalidAssetPool,
+    )]
+    pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
 }
 
+/// Pays out released delayed spl withdrawals, can be called by anyone.
+#[derive(Accounts)]
+pub struct ClaimDelayedSplWithdrawal<'info> {
+    #[account(
+        mut,
+        constraint = registered_asset_pool_pda.asset_pool_pubkey == merkle_tree_token.key() @ErrorCode::InvalidAssetPool,
+    )]
+    pub registered_asset_pool_pda: Account<'info, RegisteredAssetPool>,
+    #[account(mut)]
+    pub merkle_tree_token: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub recipient: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = merkle_tree_token.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK:` that the token authority is derived in the correct way.
+    #[account(mut, seeds=[TOKEN_AUTHORITY_SEED], bump)]
+    pub token_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [MERKLE_TREE_AUTHORITY_SEED],
+        bump,
+        constraint = !merkle_tree_authority_pda.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD) @ErrorCode::ProtocolPaused,
+    )]
+    pub merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+}
+
+/// Transfers the amount from the token pool if it is within the withdrawal limit,
+/// otherwise the withdrawal is delayed.
 pub fn process_spl_transfer<'info>(
     ctx: Context<'_, '_, '_, 'info, WithdrawSpl<'info>>,
     amount: u64,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    if !ctx
+        .accounts
+        .registered_asset_pool_pda
+        .track_withdrawal(amount, slot)
+    {
+        return ctx.accounts.registered_asset_pool_pda.queue_withdrawal(
+            ctx.accounts.recipient.key(),
+            amount,
+            slot,
+        );
+    }
+    transfer_from_pool(
+        ctx.program_id,
+        &ctx.accounts.token_program,
+        &ctx.accounts.merkle_tree_token,
+        &ctx.accounts.recipient,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_authority,
+        amount,
+    )
+}
+
+pub fn process_claim_delayed_spl_withdrawal(ctx: Context<ClaimDelayedSplWithdrawal>) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    let amount = ctx
+        .accounts
+        .registered_asset_pool_pda
+        .take_released_withdrawals(&ctx.accounts.recipient.key(), slot)?;
+    ctx.accounts
+        .registered_asset_pool_pda
+        .track_claim(amount, slot)?;
+    transfer_from_pool(
+        ctx.program_id,
+        &ctx.accounts.token_program,
+        &ctx.accounts.merkle_tree_token,
+        &ctx.accounts.recipient,
+        &ctx.accounts.mint,
+        &ctx.accounts.token_authority,
+        amount,
+    )
+}
+
+/// Transfers with transfer_checked which is required for Token-2022 mints with transfer fees,
+/// the recipient receives the amount minus the transfer fee.
+fn transfer_from_pool<'info>(
+    program_id: &Pubkey,
+    token_program: &Interface<'info, TokenInterface>,
+    merkle_tree_token: &InterfaceAccount<'info, TokenAccount>,
+    recipient: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_authority: &AccountInfo<'info>,
+    amount: u64,
 ) -> Result<()> {
     // msg!("Withdrawing spl token {}", amount);
     let (_, bump) =
-        anchor_lang::prelude::Pubkey::find_program_address(&[TOKEN_AUTHORITY_SEED], ctx.program_id);
+        anchor_lang::prelude::Pubkey::find_program_address(&[TOKEN_AUTHORITY_SEED], program_id);
     let bump = &[bump][..];
     let seeds = &[&[TOKEN_AUTHORITY_SEED, bump][..]];
-    let accounts = Transfer {
-        from: ctx.accounts.merkle_tree_token.to_account_info(),
-        to: ctx.accounts.recipient.to_account_info(),
-        authority: ctx.accounts.token_authority.to_account_info(),
+    let accounts = TransferChecked {
+        from: merkle_tree_token.to_account_info(),
+        mint: mint.to_account_info(),
+        to: recipient.to_account_info(),
+        authority: token_authority.to_account_info(),
     };
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        accounts,
-        seeds,
-    );
-    anchor_spl::token::transfer(cpi_ctx, amount)
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), accounts, seeds);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
 }
@@ -0,0 +1,308 @@
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, system_program, Discriminator,
+    InstructionData,
+};
+use merkle_tree_program::{
+    errors::ErrorCode,
+    transaction_merkle_tree::state::TransactionMerkleTree,
+    utils::{
+        config::MERKLE_TREE_HEIGHT,
+        constants::{
+            EVENT_MERKLE_TREE_SEED, LEAVES_QUEUE_SEED, MERKLE_TREE_AUTHORITY_SEED,
+            NULLIFIER_QUEUE_SEED, NULLIFIER_TREE_SEED, TRANSACTION_MERKLE_TREE_SEED,
+        },
+    },
+    MerkleTreeAuthority,
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    signature::{Keypair, Signer as _},
+    transaction::{Transaction, TransactionError},
+};
+
+const LOCK_DURATION: u64 = 50;
+
+fn merkle_tree_program_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &merkle_tree_program::ID).0
+}
+
+fn transaction_merkle_tree_pda(index: u64) -> Pubkey {
+    merkle_tree_program_pda(&[TRANSACTION_MERKLE_TREE_SEED, &index.to_le_bytes()])
+}
+
+fn program_account(data: Vec<u8>, len: usize) -> Account {
+    let mut data = data;
+    data.resize(len, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: merkle_tree_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Returns the banks client, payer and the merkle tree authority, the payer is not
+/// the authority. The newest transaction Merkle tree is number 0 and has not reached
+/// the rollover threshold.
+async fn setup() -> (BanksClient, Keypair, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "merkle_tree_program",
+        merkle_tree_program::ID,
+        processor!(merkle_tree_program::entry),
+    );
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut merkle_tree_authority = MerkleTreeAuthority::try_deserialize_unchecked(
+        &mut &[0u8; 8 + std::mem::size_of::<MerkleTreeAuthority>()][..],
+    )
+    .unwrap();
+    merkle_tree_authority.pubkey = authority.pubkey();
+    merkle_tree_authority.transaction_merkle_tree_index = 1;
+    let mut data = Vec::new();
+    merkle_tree_authority.try_serialize(&mut data).unwrap();
+    program_test.add_account(
+        merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+        program_account(data, 1024),
+    );
+
+    let mut merkle_tree: TransactionMerkleTree = bytemuck::Zeroable::zeroed();
+    merkle_tree.height = MERKLE_TREE_HEIGHT;
+    merkle_tree.lock_duration = LOCK_DURATION;
+    merkle_tree.newest = 1;
+    let mut data = TransactionMerkleTree::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&merkle_tree));
+    program_test.add_account(transaction_merkle_tree_pda(0), program_account(data, 9424));
+
+    let (banks_client, payer, _) = program_test.start().await;
+    (banks_client, payer, authority)
+}
+
+async fn process_instruction(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    instruction: Instruction,
+) -> std::result::Result<(), TransactionError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let mut signers = vec![payer];
+    if signer.pubkey() != payer.pubkey() {
+        signers.push(signer);
+    }
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+async fn enable_permissionless_merkle_tree_registration(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    authority: &Keypair,
+) {
+    let instruction = Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts: merkle_tree_program::accounts::UpdateMerkleTreeAuthorityConfig {
+            merkle_tree_authority_pda: merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+            authority: authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: merkle_tree_program::instruction::EnablePermissionlessMerkleTreeRegistration {
+            enable_permissionless: true,
+        }
+        .data(),
+    };
+    process_instruction(banks_client, payer, authority, instruction)
+        .await
+        .unwrap();
+}
+
+async fn initialize_transaction_merkle_tree(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    height: u64,
+) -> std::result::Result<(), TransactionError> {
+    let new_transaction_merkle_tree = transaction_merkle_tree_pda(1);
+    let mut accounts = merkle_tree_program::accounts::InitializeNewTransactionMerkleTree {
+        authority: signer.pubkey(),
+        new_transaction_merkle_tree,
+        leaves_queue: merkle_tree_program_pda(&[
+            new_transaction_merkle_tree.as_ref(),
+            LEAVES_QUEUE_SEED,
+        ]),
+        system_program: system_program::ID,
+        rent: anchor_lang::solana_program::sysvar::rent::ID,
+        merkle_tree_authority_pda: merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(transaction_merkle_tree_pda(0), false));
+    let instruction = Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts,
+        data: merkle_tree_program::instruction::InitializeNewTransactionMerkleTree {
+            lock_duration: LOCK_DURATION,
+            height,
+        }
+        .data(),
+    };
+    process_instruction(banks_client, payer, signer, instruction).await
+}
+
+async fn initialize_event_merkle_tree(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+) -> std::result::Result<(), TransactionError> {
+    let instruction = Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts: merkle_tree_program::accounts::InitializeNewEventMerkleTree {
+            authority: signer.pubkey(),
+            event_merkle_tree: merkle_tree_program_pda(&[
+                EVENT_MERKLE_TREE_SEED,
+                &0u64.to_le_bytes(),
+            ]),
+            system_program: system_program::ID,
+            merkle_tree_authority_pda: merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+        }
+        .to_account_metas(None),
+        data: merkle_tree_program::instruction::InitializeNewEventMerkleTree.data(),
+    };
+    process_instruction(banks_client, payer, signer, instruction).await
+}
+
+async fn initialize_nullifier_tree(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+) -> std::result::Result<(), TransactionError> {
+    let instruction = Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts: merkle_tree_program::accounts::InitializeNewNullifierTree {
+            authority: signer.pubkey(),
+            nullifier_tree: merkle_tree_program_pda(&[NULLIFIER_TREE_SEED]),
+            nullifier_queue: merkle_tree_program_pda(&[NULLIFIER_QUEUE_SEED]),
+            system_program: system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            merkle_tree_authority_pda: merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+        }
+        .to_account_metas(None),
+        data: merkle_tree_program::instruction::InitializeNewNullifierTree.data(),
+    };
+    process_instruction(banks_client, payer, signer, instruction).await
+}
+
+fn invalid_authority() -> TransactionError {
+    TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(ErrorCode::InvalidAuthority.into()),
+    )
+}
+
+async fn is_newest(banks_client: &mut BanksClient, merkle_tree: Pubkey) -> bool {
+    let account = banks_client
+        .get_account(merkle_tree)
+        .await
+        .unwrap()
+        .unwrap();
+    let merkle_tree: &TransactionMerkleTree =
+        bytemuck::from_bytes(&account.data[8..8 + std::mem::size_of::<TransactionMerkleTree>()]);
+    merkle_tree.newest == 1
+}
+
+#[tokio::test]
+async fn test_transaction_merkle_tree_registration_disabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    // Only the authority can create a new tree before the rollover threshold.
+    assert_eq!(
+        initialize_transaction_merkle_tree(&mut banks_client, &payer, &payer, MERKLE_TREE_HEIGHT)
+            .await,
+        Err(invalid_authority())
+    );
+    initialize_transaction_merkle_tree(&mut banks_client, &payer, &authority, 20)
+        .await
+        .unwrap();
+    assert!(!is_newest(&mut banks_client, transaction_merkle_tree_pda(0)).await);
+    assert!(is_newest(&mut banks_client, transaction_merkle_tree_pda(1)).await);
+}
+
+#[tokio::test]
+async fn test_transaction_merkle_tree_registration_enabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    enable_permissionless_merkle_tree_registration(&mut banks_client, &payer, &authority).await;
+    // Anyone else has to use the config of the old Merkle tree.
+    assert_eq!(
+        initialize_transaction_merkle_tree(&mut banks_client, &payer, &payer, 20).await,
+        Err(invalid_authority())
+    );
+    initialize_transaction_merkle_tree(&mut banks_client, &payer, &payer, MERKLE_TREE_HEIGHT)
+        .await
+        .unwrap();
+    assert!(!is_newest(&mut banks_client, transaction_merkle_tree_pda(0)).await);
+    assert!(is_newest(&mut banks_client, transaction_merkle_tree_pda(1)).await);
+}
+
+#[tokio::test]
+async fn test_event_merkle_tree_registration_disabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    assert_eq!(
+        initialize_event_merkle_tree(&mut banks_client, &payer, &payer).await,
+        Err(invalid_authority())
+    );
+    initialize_event_merkle_tree(&mut banks_client, &payer, &authority)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_event_merkle_tree_registration_enabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    enable_permissionless_merkle_tree_registration(&mut banks_client, &payer, &authority).await;
+    initialize_event_merkle_tree(&mut banks_client, &payer, &payer)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_nullifier_tree_registration_disabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    assert_eq!(
+        initialize_nullifier_tree(&mut banks_client, &payer, &payer).await,
+        Err(invalid_authority())
+    );
+    initialize_nullifier_tree(&mut banks_client, &payer, &authority)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_nullifier_tree_registration_enabled() {
+    let (mut banks_client, payer, authority) = setup().await;
+    enable_permissionless_merkle_tree_registration(&mut banks_client, &payer, &authority).await;
+    // The nullifier tree is always created by the authority.
+    assert_eq!(
+        initialize_nullifier_tree(&mut banks_client, &payer, &payer).await,
+        Err(invalid_authority())
+    );
+    initialize_nullifier_tree(&mut banks_client, &payer, &authority)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,209 @@
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, system_program, Discriminator,
+    InstructionData,
+};
+use merkle_tree_program::{
+    errors::ErrorCode,
+    transaction_merkle_tree::{
+        leaves_queue::LeavesQueue, state::TransactionMerkleTree, LegacyTransactionMerkleTree,
+        LEGACY_TRANSACTION_MERKLE_TREE_SIZE,
+    },
+    utils::{
+        config::{DEFAULT_LEAF_FEE, MERKLE_TREE_HEIGHT},
+        constants::{LEAVES_QUEUE_SEED, MERKLE_TREE_AUTHORITY_SEED, TRANSACTION_MERKLE_TREE_SEED},
+    },
+    MerkleTreeAuthority,
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    signature::{Keypair, Signer as _},
+    transaction::{Transaction, TransactionError},
+};
+
+fn merkle_tree_program_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &merkle_tree_program::ID).0
+}
+
+fn transaction_merkle_tree_pda(index: u64) -> Pubkey {
+    merkle_tree_program_pda(&[TRANSACTION_MERKLE_TREE_SEED, &index.to_le_bytes()])
+}
+
+fn program_account(data: Vec<u8>, len: usize) -> Account {
+    let mut data = data;
+    data.resize(len, 0);
+    Account {
+        lamports: Rent::default().minimum_balance(len),
+        data,
+        owner: merkle_tree_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn legacy_merkle_tree(next_queued_index: u64) -> LegacyTransactionMerkleTree {
+    let mut merkle_tree: LegacyTransactionMerkleTree = bytemuck::Zeroable::zeroed();
+    merkle_tree.filled_subtrees[0] = [1u8; 32];
+    merkle_tree.current_root_index = 1;
+    merkle_tree.next_index = 2;
+    merkle_tree.next_queued_index = next_queued_index;
+    merkle_tree.roots[1] = [2u8; 32];
+    merkle_tree.height = MERKLE_TREE_HEIGHT;
+    merkle_tree.lock_duration = 50;
+    merkle_tree.newest = 1;
+    merkle_tree
+}
+
+/// Returns the banks client, payer and the merkle tree authority with transaction
+/// Merkle tree 0 in the legacy layout.
+async fn setup(legacy_merkle_tree: LegacyTransactionMerkleTree) -> (BanksClient, Keypair, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "merkle_tree_program",
+        merkle_tree_program::ID,
+        processor!(merkle_tree_program::entry),
+    );
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let mut merkle_tree_authority = MerkleTreeAuthority::try_deserialize_unchecked(
+        &mut &[0u8; 8 + std::mem::size_of::<MerkleTreeAuthority>()][..],
+    )
+    .unwrap();
+    merkle_tree_authority.pubkey = authority.pubkey();
+    merkle_tree_authority.transaction_merkle_tree_index = 1;
+    let mut data = Vec::new();
+    merkle_tree_authority.try_serialize(&mut data).unwrap();
+    program_test.add_account(
+        merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+        program_account(data, 1024),
+    );
+
+    let mut data = TransactionMerkleTree::discriminator().to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&legacy_merkle_tree));
+    program_test.add_account(
+        transaction_merkle_tree_pda(0),
+        program_account(data, LEGACY_TRANSACTION_MERKLE_TREE_SIZE),
+    );
+
+    let (banks_client, payer, _) = program_test.start().await;
+    (banks_client, payer, authority)
+}
+
+async fn migrate_transaction_merkle_tree(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+) -> std::result::Result<(), TransactionError> {
+    let transaction_merkle_tree = transaction_merkle_tree_pda(0);
+    let instruction = Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts: merkle_tree_program::accounts::MigrateTransactionMerkleTree {
+            authority: signer.pubkey(),
+            transaction_merkle_tree,
+            leaves_queue: merkle_tree_program_pda(&[
+                transaction_merkle_tree.as_ref(),
+                LEAVES_QUEUE_SEED,
+            ]),
+            system_program: system_program::ID,
+            merkle_tree_authority_pda: merkle_tree_program_pda(&[MERKLE_TREE_AUTHORITY_SEED]),
+        }
+        .to_account_metas(None),
+        data: merkle_tree_program::instruction::MigrateTransactionMerkleTree.data(),
+    };
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let mut signers = vec![payer];
+    if signer.pubkey() != payer.pubkey() {
+        signers.push(signer);
+    }
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &signers,
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+fn custom_error(error: ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+}
+
+#[tokio::test]
+async fn test_migrate_transaction_merkle_tree() {
+    let legacy_merkle_tree = legacy_merkle_tree(2);
+    let (mut banks_client, payer, authority) = setup(legacy_merkle_tree).await;
+    assert_eq!(
+        migrate_transaction_merkle_tree(&mut banks_client, &payer, &payer).await,
+        Err(custom_error(ErrorCode::InvalidAuthority))
+    );
+    migrate_transaction_merkle_tree(&mut banks_client, &payer, &authority)
+        .await
+        .unwrap();
+
+    let account = banks_client
+        .get_account(transaction_merkle_tree_pda(0))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), 9424);
+    assert!(account.lamports >= Rent::default().minimum_balance(9424));
+    assert_eq!(account.data[..8], TransactionMerkleTree::discriminator());
+    let merkle_tree: &TransactionMerkleTree =
+        bytemuck::from_bytes(&account.data[8..8 + std::mem::size_of::<TransactionMerkleTree>()]);
+    assert_eq!(
+        merkle_tree.filled_subtrees[..18],
+        legacy_merkle_tree.filled_subtrees
+    );
+    assert_eq!(merkle_tree.roots, legacy_merkle_tree.roots);
+    assert_eq!(merkle_tree.current_root_index, 1);
+    assert_eq!(merkle_tree.next_index, 2);
+    assert_eq!(merkle_tree.next_queued_index, 2);
+    assert_eq!(merkle_tree.height, MERKLE_TREE_HEIGHT);
+    assert_eq!(merkle_tree.lock_duration, 50);
+    assert_eq!(merkle_tree.newest, 1);
+    assert_eq!(merkle_tree.leaf_fee, DEFAULT_LEAF_FEE);
+
+    let account = banks_client
+        .get_account(merkle_tree_program_pda(&[
+            transaction_merkle_tree_pda(0).as_ref(),
+            LEAVES_QUEUE_SEED,
+        ]))
+        .await
+        .unwrap()
+        .unwrap();
+    let leaves_queue: &LeavesQueue =
+        bytemuck::from_bytes(&account.data[8..8 + std::mem::size_of::<LeavesQueue>()]);
+    assert_eq!(
+        leaves_queue.transaction_merkle_tree,
+        transaction_merkle_tree_pda(0)
+    );
+
+    // A migrated Merkle tree can not be migrated again.
+    assert!(
+        migrate_transaction_merkle_tree(&mut banks_client, &payer, &authority)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_transaction_merkle_tree_with_queued_leaves() {
+    let (mut banks_client, payer, authority) = setup(legacy_merkle_tree(4)).await;
+    assert_eq!(
+        migrate_transaction_merkle_tree(&mut banks_client, &payer, &authority).await,
+        Err(custom_error(ErrorCode::LegacyMerkleTreeHasQueuedLeaves))
+    );
+}
@@ -0,0 +1,451 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+    system_program, InstructionData,
+};
+use merkle_tree_program::{
+    errors::ErrorCode,
+    utils::constants::{
+        MERKLE_TREE_AUTHORITY_SEED, NULLIFIER_QUEUE_SEED, NULLIFIER_SEED, NULLIFIER_TREE_SEED,
+    },
+    MerkleTreeAuthority, RegisteredVerifier, VerifierStatus,
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::InstructionError,
+    signature::{Keypair, Signer as _},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Program id of the mock verifier which forwards instructions to the merkle tree program.
+const VERIFIER_ID: Pubkey = Pubkey::new_from_array([7u8; 32]);
+
+/// Forwards the instruction data to the merkle tree program, signing with the
+/// authority pda of the verifier. The first account is the merkle tree program,
+/// the remaining accounts are passed through.
+fn process_mock_verifier(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (authority, bump) =
+        Pubkey::find_program_address(&[merkle_tree_program::ID.as_ref()], program_id);
+    let account_metas = accounts[1..]
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: *account.key,
+            is_signer: *account.key == authority,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    invoke_signed(
+        &Instruction {
+            program_id: merkle_tree_program::ID,
+            accounts: account_metas,
+            data: data.to_vec(),
+        },
+        accounts,
+        &[&[merkle_tree_program::ID.as_ref(), &[bump]]],
+    )
+}
+
+fn nullifier_pda(nullifier: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[nullifier.as_ref(), NULLIFIER_SEED],
+        &merkle_tree_program::ID,
+    )
+    .0
+}
+
+fn account_with_data<T: AccountSerialize>(account: &T, len: usize) -> Account {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(len, 0);
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: merkle_tree_program::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn merkle_tree_program_pda(seed: &[u8]) -> Pubkey {
+    Pubkey::find_program_address(&[seed], &merkle_tree_program::ID).0
+}
+
+/// Returns the banks client, payer, recent blockhash and the merkle tree authority.
+async fn setup() -> (BanksClient, Keypair, Hash, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "merkle_tree_program",
+        merkle_tree_program::ID,
+        processor!(merkle_tree_program::entry),
+    );
+    program_test.add_program(
+        "mock_verifier",
+        VERIFIER_ID,
+        processor!(process_mock_verifier),
+    );
+
+    let authority =
+        Pubkey::find_program_address(&[merkle_tree_program::ID.as_ref()], &VERIFIER_ID).0;
+    program_test.add_account(
+        authority,
+        Account {
+            lamports: 1_000_000_000,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let registered_verifier = RegisteredVerifier {
+        pubkey: VERIFIER_ID,
+        status: VerifierStatus::Active,
+        capabilities: RegisteredVerifier::ALL_CAPABILITIES,
+        allowed_pool_types: Vec::new(),
+        allowed_merkle_trees: Vec::new(),
+    };
+    program_test.add_account(
+        Pubkey::find_program_address(&[VERIFIER_ID.as_ref()], &merkle_tree_program::ID).0,
+        account_with_data(&registered_verifier, 256),
+    );
+    let authority = Keypair::new();
+    program_test.add_account(
+        authority.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            data: Vec::new(),
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    let mut merkle_tree_authority = MerkleTreeAuthority::try_deserialize_unchecked(
+        &mut &[0u8; 8 + std::mem::size_of::<MerkleTreeAuthority>()][..],
+    )
+    .unwrap();
+    merkle_tree_authority.pubkey = authority.pubkey();
+    program_test.add_account(
+        merkle_tree_program_pda(MERKLE_TREE_AUTHORITY_SEED),
+        account_with_data(&merkle_tree_authority, 1024),
+    );
+    let (banks_client, payer, recent_blockhash) = program_test.start().await;
+    (banks_client, payer, recent_blockhash, authority)
+}
+
+/// Initializes the nullifier tree and, if `enable`, switches nullifier storage to it.
+async fn initialize_nullifier_tree(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    authority: &Keypair,
+    enable: bool,
+) -> std::result::Result<(), TransactionError> {
+    let mut instructions = vec![Instruction {
+        program_id: merkle_tree_program::ID,
+        accounts: merkle_tree_program::accounts::InitializeNewNullifierTree {
+            authority: authority.pubkey(),
+            nullifier_tree: merkle_tree_program_pda(NULLIFIER_TREE_SEED),
+            nullifier_queue: merkle_tree_program_pda(NULLIFIER_QUEUE_SEED),
+            system_program: system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            merkle_tree_authority_pda: merkle_tree_program_pda(MERKLE_TREE_AUTHORITY_SEED),
+        }
+        .to_account_metas(None),
+        data: merkle_tree_program::instruction::InitializeNewNullifierTree.data(),
+    }];
+    if enable {
+        instructions.push(Instruction {
+            program_id: merkle_tree_program::ID,
+            accounts: merkle_tree_program::accounts::EnableNullifierTree {
+                merkle_tree_authority_pda: merkle_tree_program_pda(MERKLE_TREE_AUTHORITY_SEED),
+                authority: authority.pubkey(),
+                nullifier_tree: merkle_tree_program_pda(NULLIFIER_TREE_SEED),
+            }
+            .to_account_metas(None),
+            data: merkle_tree_program::instruction::EnableNullifierTree.data(),
+        });
+    }
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+/// Invokes the mock verifier with the merkle tree program instruction, the authority
+/// pda of the verifier signs in the cpi.
+async fn invoke_mock_verifier(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+) -> std::result::Result<(), TransactionError> {
+    let mut account_metas = vec![AccountMeta::new_readonly(merkle_tree_program::ID, false)];
+    account_metas.extend(accounts.into_iter().map(|mut meta| {
+        meta.is_signer = false;
+        meta
+    }));
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: VERIFIER_ID,
+            accounts: account_metas,
+            data,
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+/// Queues the nullifiers through the mock verifier, passing their nullifier pdas.
+async fn queue_nullifiers(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    nullifiers: Vec<[u8; 32]>,
+) -> std::result::Result<(), TransactionError> {
+    let mut accounts = merkle_tree_program::accounts::InsertNullifiersIntoQueue {
+        authority: Pubkey::find_program_address(&[merkle_tree_program::ID.as_ref()], &VERIFIER_ID)
+            .0,
+        registered_verifier_pda: merkle_tree_program_pda(VERIFIER_ID.as_ref()),
+        nullifier_queue: merkle_tree_program_pda(NULLIFIER_QUEUE_SEED),
+        nullifier_tree: merkle_tree_program_pda(NULLIFIER_TREE_SEED),
+        merkle_tree_authority_pda: merkle_tree_program_pda(MERKLE_TREE_AUTHORITY_SEED),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        nullifiers
+            .iter()
+            .map(|nullifier| AccountMeta::new_readonly(nullifier_pda(nullifier), false)),
+    );
+    let data = merkle_tree_program::instruction::InsertNullifiersIntoQueue { nullifiers }.data();
+    invoke_mock_verifier(banks_client, payer, accounts, data).await
+}
+
+/// Inserts the nullifiers through the mock verifier, passing the pdas of `nullifier_accounts`.
+async fn insert_nullifiers(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    nullifiers: Vec<[u8; 32]>,
+    nullifier_accounts: &[[u8; 32]],
+) -> std::result::Result<(), TransactionError> {
+    let accounts = merkle_tree_program::accounts::InitializeNullifiers {
+        authority: Pubkey::find_program_address(&[merkle_tree_program::ID.as_ref()], &VERIFIER_ID)
+            .0,
+        system_program: system_program::ID,
+        registered_verifier_pda: Pubkey::find_program_address(
+            &[VERIFIER_ID.as_ref()],
+            &merkle_tree_program::ID,
+        )
+        .0,
+        merkle_tree_authority_pda: Pubkey::find_program_address(
+            &[MERKLE_TREE_AUTHORITY_SEED],
+            &merkle_tree_program::ID,
+        )
+        .0,
+    };
+    let mut account_metas = vec![AccountMeta::new_readonly(merkle_tree_program::ID, false)];
+    account_metas.extend(accounts.to_account_metas(None).into_iter().map(|mut meta| {
+        // The authority signs in the cpi of the verifier.
+        meta.is_signer = false;
+        meta
+    }));
+    account_metas.extend(
+        nullifier_accounts
+            .iter()
+            .map(|nullifier| AccountMeta::new(nullifier_pda(nullifier), false)),
+    );
+    let data = merkle_tree_program::instruction::InitializeNullifiers { nullifiers }.data();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: VERIFIER_ID,
+            accounts: account_metas,
+            data,
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.unwrap())
+}
+
+fn custom_error(error: ErrorCode) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error.into()))
+}
+
+#[tokio::test]
+async fn test_nullifier_already_exists() {
+    let (mut banks_client, payer, recent_blockhash, _) = setup().await;
+    let nullifiers = vec![[1u8; 32], [2u8; 32]];
+    insert_nullifiers(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        nullifiers.clone(),
+        &nullifiers,
+    )
+    .await
+    .unwrap();
+    for nullifier in nullifiers.iter() {
+        let account = banks_client
+            .get_account(nullifier_pda(nullifier))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.owner, merkle_tree_program::ID);
+    }
+
+    // A spent nullifier is rejected, also next to an unspent one.
+    let nullifiers = vec![[3u8; 32], [1u8; 32]];
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    assert_eq!(
+        insert_nullifiers(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            nullifiers.clone(),
+            &nullifiers,
+        )
+        .await,
+        Err(custom_error(ErrorCode::NullifierAlreadyExists))
+    );
+    assert!(banks_client
+        .get_account(nullifier_pda(&nullifiers[0]))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_number_of_nullifiers() {
+    let (mut banks_client, payer, recent_blockhash, _) = setup().await;
+    let nullifiers = vec![[1u8; 32], [2u8; 32]];
+    assert_eq!(
+        insert_nullifiers(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            nullifiers.clone(),
+            &nullifiers[..1],
+        )
+        .await,
+        Err(custom_error(ErrorCode::InvalidNumberOfNullifiers))
+    );
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    assert_eq!(
+        insert_nullifiers(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            nullifiers[..1].to_vec(),
+            &nullifiers,
+        )
+        .await,
+        Err(custom_error(ErrorCode::InvalidNumberOfNullifiers))
+    );
+    assert!(banks_client
+        .get_account(nullifier_pda(&nullifiers[0]))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_nullifier_tree_not_enabled() {
+    let (mut banks_client, payer, _, authority) = setup().await;
+    initialize_nullifier_tree(&mut banks_client, &payer, &authority, false)
+        .await
+        .unwrap();
+    assert_eq!(
+        queue_nullifiers(&mut banks_client, &payer, vec![[1u8; 32]]).await,
+        Err(custom_error(ErrorCode::NullifierTreeNotEnabled))
+    );
+}
+
+#[tokio::test]
+async fn test_nullifier_tree() {
+    let (mut banks_client, payer, recent_blockhash, authority) = setup().await;
+    // Spent before the nullifier tree is enabled.
+    insert_nullifiers(
+        &mut banks_client,
+        &payer,
+        recent_blockhash,
+        vec![[1u8; 32]],
+        &[[1u8; 32]],
+    )
+    .await
+    .unwrap();
+    initialize_nullifier_tree(&mut banks_client, &payer, &authority, true)
+        .await
+        .unwrap();
+
+    queue_nullifiers(&mut banks_client, &payer, vec![[2u8; 32], [3u8; 32]])
+        .await
+        .unwrap();
+    // Queued nullifiers and nullifiers with a nullifier pda are spent.
+    assert_eq!(
+        queue_nullifiers(&mut banks_client, &payer, vec![[4u8; 32], [2u8; 32]]).await,
+        Err(custom_error(ErrorCode::NullifierAlreadyExists))
+    );
+    assert_eq!(
+        queue_nullifiers(&mut banks_client, &payer, vec![[1u8; 32]]).await,
+        Err(custom_error(ErrorCode::NullifierAlreadyExists))
+    );
+
+    // Nullifier pdas can not be created anymore.
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    assert_eq!(
+        insert_nullifiers(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            vec![[5u8; 32]],
+            &[[5u8; 32]],
+        )
+        .await,
+        Err(custom_error(ErrorCode::NullifierPdasDisabled))
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_nullifier_pda() {
+    let (mut banks_client, payer, _, authority) = setup().await;
+    initialize_nullifier_tree(&mut banks_client, &payer, &authority, true)
+        .await
+        .unwrap();
+    let mut accounts = merkle_tree_program::accounts::InsertNullifiersIntoQueue {
+        authority: Pubkey::find_program_address(&[merkle_tree_program::ID.as_ref()], &VERIFIER_ID)
+            .0,
+        registered_verifier_pda: merkle_tree_program_pda(VERIFIER_ID.as_ref()),
+        nullifier_queue: merkle_tree_program_pda(NULLIFIER_QUEUE_SEED),
+        nullifier_tree: merkle_tree_program_pda(NULLIFIER_TREE_SEED),
+        merkle_tree_authority_pda: merkle_tree_program_pda(MERKLE_TREE_AUTHORITY_SEED),
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new_readonly(nullifier_pda(&[2u8; 32]), false));
+    let data = merkle_tree_program::instruction::InsertNullifiersIntoQueue {
+        nullifiers: vec![[1u8; 32]],
+    }
+    .data();
+    assert_eq!(
+        invoke_mock_verifier(&mut banks_client, &payer, accounts, data).await,
+        Err(custom_error(ErrorCode::InvalidNullifierPda))
+    );
+}
@@ -34,7 +34,6 @@ use merkle_tree_program::poseidon_merkle_tree::update_merkle_tree_lib::instructi
 use merkle_tree_program::poseidon_merkle_tree::update_merkle_tree_lib::processor::compute_updated_merkle_tree;
 use merkle_tree_program::poseidon_merkle_tree::update_merkle_tree_lib::MerkleTreeUpdateState;
 use merkle_tree_program::utils::config;
-use merkle_tree_program::utils::config::ENCRYPTED_UTXOS_LENGTH;
 use std::cell::RefMut;
 use std::convert::TryInto;
 const INSTRUCTION_ORDER_POSEIDON_2_INPUTS: [u8; 3] = [0, 1, 2];
@@ -1,14 +1,15 @@
 use anchor_lang::prelude::*;
 
-use light_macros::{light_verifier_accounts, pubkey};
-use light_verifier_sdk::light_transaction::{
-    Config, Transaction, TransactionInput, VERIFIER_STATE_SEED,
+use light_macros::{light_verifier_accounts, light_verifier_state, pubkey};
+use light_verifier_sdk::{
+    light_transaction::{
+        Config, NullifierMode, Transaction, TransactionInput, VERIFIER_STATE_SEED,
+    },
+    utils::verifying_key::load_verifying_key,
 };
-use light_verifier_sdk::state::VerifierState10Ins;
-use merkle_tree_program::program::MerkleTreeProgram;
+use merkle_tree_program::{program::MerkleTreeProgram, VerifyingKeyAccount};
 
 pub mod verifying_key;
-use verifying_key::VERIFYINGKEY;
 
 #[cfg(not(feature = "no-entrypoint"))]
 solana_security_txt::security_txt! {
@@ -24,6 +25,10 @@ declare_id!("J85SuNBBsba7FQS66BiBCQjiQrQTif7v249zL2ffmRZc");
 #[constant]
 pub const PROGRAM_ID: &str = "J85SuNBBsba7FQS66BiBCQjiQrQTif7v249zL2ffmRZc";
 
+/// Length of the encrypted utxo of each output.
+#[constant]
+pub const ENCRYPTED_UTXO_LEN: usize = 120;
+
 #[derive(Clone)]
 pub struct TransactionConfig;
 impl Config for TransactionConfig {
@@ -33,8 +38,6 @@ impl Config for TransactionConfig {
 
 #[program]
 pub mod verifier_program_one {
-    use std::marker::PhantomData;
-
     use light_verifier_sdk::light_transaction::{Amounts, Proof};
 
     use super::*;
@@ -51,11 +54,7 @@ pub mod verifier_program_one {
             InstructionDataShieldedTransferFirst::try_deserialize_unchecked(
                 &mut [vec![0u8; 8], inputs].concat().as_slice(),
             )?;
-        let len_missing_bytes = 256 - inputs.encrypted_utxos.len();
-        let mut enc_utxos = inputs.encrypted_utxos;
-        enc_utxos.append(&mut vec![0u8; len_missing_bytes]);
-
-        let state = VerifierState10Ins {
+        let state = VerifierState {
             merkle_root_index: inputs.root_index,
             signer: Pubkey::from([0u8; 32]),
             nullifiers: inputs.input_nullifier.to_vec(),
@@ -66,14 +65,14 @@ pub mod verifier_program_one {
             merkle_root: [0u8; 32],
             tx_integrity_hash: [0u8; 32],
             relayer_fee: inputs.relayer_fee,
-            encrypted_utxos: enc_utxos,
+            encrypted_utxos: inputs.encrypted_utxos,
             checked_public_inputs: [],
             proof_a: [0u8; 64],
             proof_b: [0u8; 128],
             proof_c: [0u8; 64],
             transaction_hash: [0u8; 32],
-            e_phantom: PhantomData,
         };
+        state.check_layout()?;
         ctx.accounts.verifier_state.set_inner(state);
         ctx.accounts.verifier_state.signer = *ctx.accounts.signing_address.key;
 
@@ -113,6 +112,13 @@ pub mod verifier_program_one {
             .to_vec()
             .try_into()
             .unwrap();
+        let verifyingkey = load_verifying_key(
+            &ctx.accounts.verifying_key,
+            &crate::ID,
+            10,
+            2,
+            Clock::get()?.slot,
+        )?;
 
         let input = TransactionInput {
             ctx: &ctx,
@@ -122,6 +128,7 @@ pub mod verifier_program_one {
             nullifiers: &nullifier,
             leaves: &leaves,
             encrypted_utxos: &ctx.accounts.verifier_state.encrypted_utxos,
+            encrypted_utxo_len: ENCRYPTED_UTXO_LEN,
             relayer_fee: ctx.accounts.verifier_state.relayer_fee,
             merkle_root_index: ctx
                 .accounts
@@ -131,7 +138,8 @@ pub mod verifier_program_one {
                 .unwrap(),
             pool_type: &[0u8; 32],
             checked_public_inputs: &[],
-            verifyingkey: &VERIFYINGKEY,
+            verifyingkey: &verifyingkey,
+            nullifier_mode: NullifierMode::Pda,
         };
         let mut tx = Transaction::<0, 1, 10, 17, LightInstructionSecond<'info, 0>>::new(input);
         tx.transact()
@@ -159,12 +167,15 @@ pub struct LightInstructionFirst<'info, const NR_CHECKED_INPUTS: usize> {
             VERIFIER_STATE_SEED
         ],
         bump,
-        space = 3000 /*8 + 32 * 6 + 10 * 32 + 2 * 32 + 512 + 16 + 128*/,
+        space = VerifierState::LEN,
         payer = signing_address
     )]
-    pub verifier_state: Account<'info, VerifierState10Ins<NR_CHECKED_INPUTS, TransactionConfig>>,
+    pub verifier_state: Account<'info, VerifierState>,
 }
 
+#[light_verifier_state(nullifiers = 10, leaves = 2)]
+pub struct VerifierState {}
+
 #[derive(Debug)]
 #[account]
 pub struct InstructionDataShieldedTransferFirst {
@@ -194,7 +205,9 @@ pub struct LightInstructionSecond<'info, const NR_CHECKED_INPUTS: usize> {
         bump,
         close=signing_address
     )]
-    pub verifier_state: Account<'info, VerifierState10Ins<NR_CHECKED_INPUTS, TransactionConfig>>,
+    pub verifier_state: Account<'info, VerifierState>,
+    /// Verifying key of this verifier registered with the merkle tree program.
+    pub verifying_key: Account<'info, VerifyingKeyAccount>,
 }
 
 #[derive(Debug)]
@@ -210,5 +223,5 @@ pub struct CloseVerifierState<'info, const NR_CHECKED_INPUTS: usize> {
     #[account(mut, address=verifier_state.signer)]
     pub signing_address: Signer<'info>,
     #[account(mut, seeds = [&signing_address.key().to_bytes(), VERIFIER_STATE_SEED], bump, close=signing_address )]
-    pub verifier_state: Account<'info, VerifierState10Ins<NR_CHECKED_INPUTS, TransactionConfig>>,
+    pub verifier_state: Account<'info, VerifierState>,
 }
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 use light_macros::light_verifier_accounts;
-use light_verifier_sdk::light_transaction::{
-    Amounts, Message, Proof, Transaction, TransactionInput, VERIFIER_STATE_SEED,
+use light_verifier_sdk::{
+    light_transaction::{
+        Amounts, Message, NullifierMode, Proof, Transaction, TransactionInput, VERIFIER_STATE_SEED,
+    },
+    utils::verifying_key::load_verifying_key,
 };
 
-use merkle_tree_program::program::MerkleTreeProgram;
+use merkle_tree_program::{program::MerkleTreeProgram, VerifyingKeyAccount};
 
 pub mod verifying_key;
-use verifying_key::VERIFYINGKEY;
 
 declare_id!("DJpbogMSrK94E1zvvJydtkqoE4sknuzmMRoutd6B7TKj");
 
@@ -24,8 +26,9 @@ pub const MESSAGE_MAX_SIZE: usize = 2048;
 /// (message + discriminator).
 pub const VERIFIER_STATE_MAX_SIZE: usize = MESSAGE_MAX_SIZE + 8;
 
-/// Size of the encrypted UTXOs array (including padding).
-pub const ENCRYPTED_UTXOS_SIZE: usize = 256;
+/// Length of the encrypted utxo of each output.
+#[constant]
+pub const ENCRYPTED_UTXO_LEN: usize = 120;
 
 #[error_code]
 pub enum VerifierError {
@@ -88,7 +91,7 @@ pub mod verifier_program_storage {
     ) -> Result<()> {
         let inputs: InstructionDataShieldedTransferSecond =
             InstructionDataShieldedTransferSecond::try_deserialize_unchecked(
-                &mut [vec![0u8; 8], inputs, vec![0u8; 16]].concat().as_slice(),
+                &mut [vec![0u8; 8], inputs].concat().as_slice(),
             )?;
         let message = Message::new(&ctx.accounts.verifier_state.msg);
         let proof = Proof {
@@ -100,6 +103,13 @@ pub mod verifier_program_storage {
             sol: inputs.public_amount_sol,
             spl: [0u8; 32], // Verifier storage does not support SPL tokens.
         };
+        let verifyingkey = load_verifying_key(
+            &ctx.accounts.verifying_key,
+            &crate::ID,
+            2,
+            2,
+            Clock::get()?.slot,
+        )?;
 
         let input = TransactionInput {
             ctx: &ctx,
@@ -108,12 +118,14 @@ pub mod verifier_program_storage {
             public_amount: &public_amount,
             nullifiers: &inputs.input_nullifier,
             leaves: &[inputs.output_commitment; 1],
-            encrypted_utxos: &inputs.encrypted_utxos.to_vec(),
+            encrypted_utxos: &inputs.encrypted_utxos,
+            encrypted_utxo_len: ENCRYPTED_UTXO_LEN,
             merkle_root_index: inputs.root_index as usize,
             relayer_fee: inputs.relayer_fee,
             checked_public_inputs: &[],
             pool_type: &[0u8; 32],
-            verifyingkey: &VERIFYINGKEY,
+            verifyingkey: &verifyingkey,
+            nullifier_mode: NullifierMode::Pda,
         };
         let mut transaction = Transaction::<0, 1, 2, 9, LightInstructionSecond<'info>>::new(input);
 
@@ -165,6 +177,8 @@ pub struct LightInstructionSecond<'info> {
         close=signing_address
     )]
     pub verifier_state: Account<'info, VerifierState>,
+    /// Verifying key of this verifier registered with the merkle tree program.
+    pub verifying_key: Account<'info, VerifyingKeyAccount>,
 }
 
 #[derive(Debug)]
@@ -178,5 +192,5 @@ pub struct InstructionDataShieldedTransferSecond {
     public_amount_sol: [u8; 32],
     root_index: u64,
     relayer_fee: u64,
-    encrypted_utxos: [u8; 256],
+    encrypted_utxos: Vec<u8>,
 }
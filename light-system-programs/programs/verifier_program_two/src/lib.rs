@@ -2,13 +2,14 @@ use anchor_lang::{prelude::*, solana_program::hash::hash};
 
 use light_macros::{light_verifier_accounts, pubkey};
 use light_verifier_sdk::{
-    light_transaction::{Amounts, Config, Proof, Transaction, TransactionInput},
+    errors::VerifierSdkError,
+    light_transaction::{Amounts, Config, NullifierMode, Proof, Transaction, TransactionInput},
     state::VerifierState10Ins,
+    utils::verifying_key::load_verifying_key,
 };
-use merkle_tree_program::program::MerkleTreeProgram;
+use merkle_tree_program::{program::MerkleTreeProgram, VerifyingKeyAccount};
 
 pub mod verifying_key;
-use verifying_key::VERIFYINGKEY;
 
 #[cfg(not(feature = "no-entrypoint"))]
 solana_security_txt::security_txt! {
@@ -24,6 +25,10 @@ declare_id!("2cxC8e8uNYLcymH6RTGuJs3N8fXGkwmMpw45pY65Ay86");
 #[constant]
 pub const PROGRAM_ID: &str = "2cxC8e8uNYLcymH6RTGuJs3N8fXGkwmMpw45pY65Ay86";
 
+/// Length of the encrypted utxo of each output.
+#[constant]
+pub const ENCRYPTED_UTXO_LEN: usize = 120;
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("System program is no valid verifier.")]
@@ -55,9 +60,15 @@ pub mod verifier_program_two {
             c: proof_c,
         };
 
-        let verifier_state = VerifierState10Ins::<2, TransactionConfig>::deserialize(
-            &mut &*ctx.accounts.verifier_state.to_account_info().data.take(),
+        let verifier_state = VerifierState10Ins::<2, TransactionConfig>::deserialize_common_fields(
+            &ctx.accounts.verifier_state.to_account_info().data.borrow(),
         )?;
+        if verifier_state.nullifiers.len() != 4 {
+            return err!(VerifierSdkError::InvalidNrNullifiers);
+        }
+        if verifier_state.leaves.len() != 4 {
+            return err!(VerifierSdkError::InvalidNrLeaves);
+        }
 
         let public_amount = Amounts {
             sol: verifier_state.public_amount_sol,
@@ -78,6 +89,13 @@ pub mod verifier_program_two {
 
         let nullifiers: [[u8; 32]; 4] = verifier_state.nullifiers.to_vec().try_into().unwrap();
         let pool_type = [0u8; 32];
+        let verifyingkey = load_verifying_key(
+            &ctx.accounts.verifying_key,
+            &crate::ID,
+            4,
+            4,
+            Clock::get()?.slot,
+        )?;
         let input = TransactionInput {
             ctx: &ctx,
             message: None,
@@ -86,11 +104,13 @@ pub mod verifier_program_two {
             checked_public_inputs: &checked_inputs,
             nullifiers: &nullifiers,
             leaves: &leaves,
-            encrypted_utxos: &verifier_state.encrypted_utxos.to_vec(),
+            encrypted_utxos: &verifier_state.encrypted_utxos,
+            encrypted_utxo_len: ENCRYPTED_UTXO_LEN,
             relayer_fee: verifier_state.relayer_fee,
             merkle_root_index: verifier_state.merkle_root_index as usize,
             pool_type: &pool_type,
-            verifyingkey: &VERIFYINGKEY,
+            verifyingkey: &verifyingkey,
+            nullifier_mode: NullifierMode::Pda,
         };
         let mut tx = Transaction::<2, 2, 4, 15, LightInstruction<'info>>::new(input);
 
@@ -102,4 +122,6 @@ pub mod verifier_program_two {
 #[derive(Accounts)]
 pub struct LightInstruction<'info> {
     pub verifier_state: Signer<'info>,
+    /// Verifying key of this verifier registered with the merkle tree program.
+    pub verifying_key: Account<'info, VerifyingKeyAccount>,
 }
@@ -1,11 +1,17 @@
 use anchor_lang::prelude::*;
 
-use light_macros::light_verifier_accounts;
-use light_verifier_sdk::light_transaction::{Amounts, Proof, Transaction, TransactionInput};
-use merkle_tree_program::program::MerkleTreeProgram;
+use groth16_solana::groth16::Groth16Verifyingkey;
+use light_macros::{light_verifier_accounts, light_verifier_state};
+use light_verifier_sdk::{
+    errors::VerifierSdkError,
+    light_transaction::{
+        Amounts, NullifierMode, Proof, Transaction, TransactionInput, VERIFIER_STATE_SEED,
+    },
+    utils::verifying_key::load_verifying_key,
+};
+use merkle_tree_program::{program::MerkleTreeProgram, VerifyingKeyAccount};
 
 pub mod verifying_key;
-use verifying_key::VERIFYINGKEY;
 
 declare_id!("J1RRetZ4ujphU75LP8RadjXMf3sA12yC2R44CF7PmU7i");
 
@@ -21,6 +27,31 @@ solana_security_txt::security_txt! {
 #[constant]
 pub const PROGRAM_ID: &str = "J1RRetZ4ujphU75LP8RadjXMf3sA12yC2R44CF7PmU7i";
 
+/// Length of the encrypted utxo of each output.
+#[constant]
+pub const ENCRYPTED_UTXO_LEN: usize = 120;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Number of input and output utxos is not supported.")]
+    UnsupportedNumberOfUtxos,
+}
+
+/// Checks that a verifying key for `n_in` input and `n_out` output utxos is supported.
+pub fn check_number_of_utxos(n_in: usize, n_out: usize) -> Result<()> {
+    match (n_in, n_out) {
+        (2, 2) | (10, 2) => Ok(()),
+        _ => {
+            msg!(
+                "{} input and {} output utxos are not supported",
+                n_in,
+                n_out
+            );
+            err!(ErrorCode::UnsupportedNumberOfUtxos)
+        }
+    }
+}
+
 #[program]
 pub mod verifier_program_zero {
     use super::*;
@@ -30,6 +61,7 @@ pub mod verifier_program_zero {
     /// computation verifying the zero-knowledge proof (ZKP). Additionally, it stores other data
     /// such as leaves, amounts, recipients, nullifiers, etc. to execute the protocol logic
     /// in the last transaction after successful ZKP verification. light_verifier_sdk::light_instruction::LightInstruction2
+    /// Nullifiers are stored in nullifier pdas, fails once the nullifier tree is enabled.
     pub fn shielded_transfer_first<'info>(
         ctx: Context<'_, '_, '_, 'info, LightInstruction<'info>>,
         inputs: Vec<u8>,
@@ -38,9 +70,6 @@ pub mod verifier_program_zero {
             InstructionDataShieldedTransferFirst::try_deserialize_unchecked(
                 &mut [vec![0u8; 8], inputs].concat().as_slice(),
             )?;
-        let len_missing_bytes = 256 - inputs.encrypted_utxos.len();
-        let mut enc_utxos = inputs.encrypted_utxos;
-        enc_utxos.append(&mut vec![0u8; len_missing_bytes]);
         let proof = Proof {
             a: inputs.proof_a,
             b: inputs.proof_b,
@@ -50,6 +79,13 @@ pub mod verifier_program_zero {
             sol: inputs.public_amount_sol,
             spl: inputs.public_amount_spl,
         };
+        let verifyingkey = load_verifying_key(
+            &ctx.accounts.verifying_key,
+            &crate::ID,
+            2,
+            2,
+            Clock::get()?.slot,
+        )?;
 
         let input = TransactionInput {
             ctx: &ctx,
@@ -58,22 +94,200 @@ pub mod verifier_program_zero {
             public_amount: &public_amount,
             nullifiers: &inputs.input_nullifier,
             leaves: &[inputs.output_commitment; 1],
-            encrypted_utxos: &enc_utxos,
+            encrypted_utxos: &inputs.encrypted_utxos,
+            encrypted_utxo_len: ENCRYPTED_UTXO_LEN,
             merkle_root_index: inputs.root_index as usize,
             relayer_fee: inputs.relayer_fee,
             checked_public_inputs: &[],
             pool_type: &[0u8; 32],
-            verifyingkey: &VERIFYINGKEY,
+            verifyingkey: &verifyingkey,
+            nullifier_mode: NullifierMode::Pda,
         };
         let mut transaction = Transaction::<0, 1, 2, 9, LightInstruction<'info>>::new(input);
 
         transaction.transact()
     }
+
+    /// This instruction is the first step of a shielded transaction with any supported number
+    /// of input and output utxos. It creates and initializes a verifier state account which
+    /// stores the public inputs to execute the verification in the second transaction.
+    /// Nullifiers are queued for the nullifier tree if `nullifier_root_index` is set,
+    /// otherwise they are stored in nullifier pdas.
+    pub fn shielded_transfer_variable_first<'info>(
+        ctx: Context<'_, '_, '_, 'info, LightInstructionVariableFirst<'info>>,
+        inputs: Vec<u8>,
+    ) -> Result<()> {
+        let inputs: InstructionDataShieldedTransferVariableFirst =
+            InstructionDataShieldedTransferVariableFirst::try_deserialize_unchecked(
+                &mut [vec![0u8; 8], inputs].concat().as_slice(),
+            )?;
+        check_number_of_utxos(inputs.input_nullifier.len(), inputs.output_commitment.len())?;
+        if inputs.encrypted_utxos.len() > VerifierState::ENCRYPTED_UTXOS_LEN {
+            return err!(VerifierSdkError::InvalidUtxoSize);
+        }
+        let state = VerifierState {
+            merkle_root_index: inputs.root_index,
+            signer: *ctx.accounts.signing_address.key,
+            nullifiers: inputs.input_nullifier,
+            leaves: inputs.output_commitment,
+            public_amount_spl: inputs.public_amount_spl,
+            public_amount_sol: inputs.public_amount_sol,
+            mint_pubkey: [0u8; 32],
+            merkle_root: [0u8; 32],
+            tx_integrity_hash: [0u8; 32],
+            relayer_fee: inputs.relayer_fee,
+            encrypted_utxos: inputs.encrypted_utxos,
+            checked_public_inputs: [],
+            proof_a: [0u8; 64],
+            proof_b: [0u8; 128],
+            proof_c: [0u8; 64],
+            transaction_hash: [0u8; 32],
+            nullifier_root_index: inputs.nullifier_root_index,
+        };
+        ctx.accounts.verifier_state.set_inner(state);
+
+        Ok(())
+    }
+
+    /// This instruction is the second step of a shielded transaction with any supported number
+    /// of input and output utxos. The verifying key registered for the number of nullifiers and
+    /// output commitments saved in the first transaction is used to verify the proof.
+    pub fn shielded_transfer_variable_second<'info>(
+        ctx: Context<'_, '_, '_, 'info, LightInstructionVariableSecond<'info>>,
+        inputs: Vec<u8>,
+    ) -> Result<()> {
+        let inputs: InstructionDataShieldedTransferVariableSecond =
+            InstructionDataShieldedTransferVariableSecond::try_deserialize_unchecked(
+                &mut [vec![0u8; 8], inputs].concat().as_slice(),
+            )?;
+        let n_in = ctx.accounts.verifier_state.nullifiers.len();
+        let n_out = ctx.accounts.verifier_state.leaves.len();
+        check_number_of_utxos(n_in, n_out)?;
+        let verifyingkey = load_verifying_key(
+            &ctx.accounts.verifying_key,
+            &crate::ID,
+            n_in,
+            n_out,
+            Clock::get()?.slot,
+        )?;
+
+        let nullifier_mode = match ctx.accounts.verifier_state.nullifier_root_index {
+            Some(root_index) => NullifierMode::IndexedTree {
+                root_index: usize::try_from(root_index)
+                    .map_err(|_| error!(VerifierSdkError::InvalidMerkleTreeRoot))?,
+            },
+            None => NullifierMode::Pda,
+        };
+
+        // The nullifier tree root is an additional public input.
+        match (n_in, n_out, nullifier_mode) {
+            (2, 2, NullifierMode::Pda) => {
+                process_shielded_transfer::<1, 2, 9>(&ctx, inputs, &verifyingkey, nullifier_mode)
+            }
+            (2, 2, NullifierMode::IndexedTree { .. }) => {
+                process_shielded_transfer::<1, 2, 10>(&ctx, inputs, &verifyingkey, nullifier_mode)
+            }
+            (10, 2, NullifierMode::Pda) => {
+                process_shielded_transfer::<1, 10, 17>(&ctx, inputs, &verifyingkey, nullifier_mode)
+            }
+            (10, 2, NullifierMode::IndexedTree { .. }) => {
+                process_shielded_transfer::<1, 10, 18>(&ctx, inputs, &verifyingkey, nullifier_mode)
+            }
+            _ => err!(ErrorCode::UnsupportedNumberOfUtxos),
+        }
+    }
+
+    /// Close the verifier state to reclaim rent in case the proofdata is wrong and does not verify.
+    pub fn close_verifier_state<'info>(
+        _ctx: Context<'_, '_, '_, 'info, CloseVerifierState<'info>>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn process_shielded_transfer<
+    'info,
+    const NR_LEAVES: usize,
+    const NR_NULLIFIERS: usize,
+    const NR_PUBLIC_INPUTS: usize,
+>(
+    ctx: &Context<'_, '_, '_, 'info, LightInstructionVariableSecond<'info>>,
+    inputs: InstructionDataShieldedTransferVariableSecond,
+    verifyingkey: &Groth16Verifyingkey,
+    nullifier_mode: NullifierMode,
+) -> Result<()> {
+    // The verifying key of the nullifier tree circuit has the nullifier tree root as
+    // additional public input, it replaces the key of the nullifier pda circuit.
+    if verifyingkey.nr_pubinputs != NR_PUBLIC_INPUTS {
+        msg!(
+            "Verifying key has {} public inputs, expected {}.",
+            verifyingkey.nr_pubinputs,
+            NR_PUBLIC_INPUTS
+        );
+        return err!(VerifierSdkError::InvalidVerifyingKeyAccount);
+    }
+    let state = &ctx.accounts.verifier_state;
+    let nullifiers: [[u8; 32]; NR_NULLIFIERS] = state
+        .nullifiers
+        .clone()
+        .try_into()
+        .map_err(|_| error!(VerifierSdkError::InvalidNrNullifiers))?;
+    if state.leaves.len() != NR_LEAVES * 2 {
+        return err!(VerifierSdkError::InvalidNrLeaves);
+    }
+    let leaves: Vec<[[u8; 32]; 2]> = state
+        .leaves
+        .chunks_exact(2)
+        .map(|leaves| [leaves[0], leaves[1]])
+        .collect();
+    let leaves: [[[u8; 32]; 2]; NR_LEAVES] = leaves
+        .try_into()
+        .map_err(|_| error!(VerifierSdkError::InvalidNrLeaves))?;
+    let merkle_root_index = usize::try_from(state.merkle_root_index)
+        .map_err(|_| error!(VerifierSdkError::InvalidMerkleTreeRoot))?;
+    let proof = Proof {
+        a: inputs.proof_a,
+        b: inputs.proof_b,
+        c: inputs.proof_c,
+    };
+    let public_amount = Amounts {
+        sol: state.public_amount_sol,
+        spl: state.public_amount_spl,
+    };
+
+    let input = TransactionInput {
+        ctx,
+        message: None,
+        proof: &proof,
+        public_amount: &public_amount,
+        nullifiers: &nullifiers,
+        leaves: &leaves,
+        encrypted_utxos: &state.encrypted_utxos,
+        encrypted_utxo_len: ENCRYPTED_UTXO_LEN,
+        merkle_root_index,
+        relayer_fee: state.relayer_fee,
+        checked_public_inputs: &[],
+        pool_type: &[0u8; 32],
+        verifyingkey,
+        nullifier_mode,
+    };
+    let mut transaction = Transaction::<
+        0,
+        NR_LEAVES,
+        NR_NULLIFIERS,
+        NR_PUBLIC_INPUTS,
+        LightInstructionVariableSecond<'info>,
+    >::new(input);
+
+    transaction.transact()
 }
 
 #[light_verifier_accounts(sol, spl)]
 #[derive(Accounts)]
-pub struct LightInstruction<'info> {}
+pub struct LightInstruction<'info> {
+    /// Verifying key of this verifier registered with the merkle tree program.
+    pub verifying_key: Account<'info, VerifyingKeyAccount>,
+}
 
 #[derive(Debug)]
 #[account]
@@ -90,6 +304,84 @@ pub struct InstructionDataShieldedTransferFirst {
     encrypted_utxos: Vec<u8>,
 }
 
+/// Sends and stores the public inputs of a transaction with a variable number of utxos.
+#[derive(Accounts)]
+pub struct LightInstructionVariableFirst<'info> {
+    /// First transaction, therefore the signing address is not checked but saved to be checked in future instructions.
+    #[account(mut)]
+    pub signing_address: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        init,
+        seeds = [
+            &signing_address.key().to_bytes(),
+            VERIFIER_STATE_SEED
+        ],
+        bump,
+        space = VerifierState::LEN,
+        payer = signing_address
+    )]
+    pub verifier_state: Account<'info, VerifierState>,
+}
+
+/// Sized for the largest supported number of utxos.
+#[light_verifier_state(nullifiers = 10, leaves = 2)]
+pub struct VerifierState {
+    /// Index of the nullifier tree root, none if nullifiers are stored in nullifier pdas.
+    pub nullifier_root_index: Option<u64>,
+}
+
+#[derive(Debug)]
+#[account]
+pub struct InstructionDataShieldedTransferVariableFirst {
+    public_amount_spl: [u8; 32],
+    input_nullifier: Vec<[u8; 32]>,
+    output_commitment: Vec<[u8; 32]>,
+    public_amount_sol: [u8; 32],
+    root_index: u64,
+    relayer_fee: u64,
+    encrypted_utxos: Vec<u8>,
+    nullifier_root_index: Option<u64>,
+}
+
+/// Executes light transaction with state created in the first instruction.
+#[light_verifier_accounts(
+    sol,
+    spl,
+    signing_address=verifier_state.signer
+)]
+#[derive(Accounts)]
+pub struct LightInstructionVariableSecond<'info> {
+    #[account(
+        mut,
+        seeds = [
+            &signing_address.key().to_bytes(),
+            VERIFIER_STATE_SEED
+        ],
+        bump,
+        close=signing_address
+    )]
+    pub verifier_state: Account<'info, VerifierState>,
+    /// Verifying key of this verifier registered with the merkle tree program.
+    pub verifying_key: Account<'info, VerifyingKeyAccount>,
+}
+
+#[derive(Debug)]
+#[account]
+pub struct InstructionDataShieldedTransferVariableSecond {
+    proof_a: [u8; 64],
+    proof_b: [u8; 128],
+    proof_c: [u8; 64],
+}
+
+#[derive(Accounts)]
+pub struct CloseVerifierState<'info> {
+    #[account(mut, address=verifier_state.signer)]
+    pub signing_address: Signer<'info>,
+    #[account(mut, seeds = [&signing_address.key().to_bytes(), VERIFIER_STATE_SEED], bump, close=signing_address )]
+    pub verifier_state: Account<'info, VerifierState>,
+}
+
 #[allow(non_camel_case_types)]
 // helper struct to create anchor idl with u256 type
 #[account]
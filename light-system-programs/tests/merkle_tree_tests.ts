@@ -43,6 +43,7 @@ import {
   TestRelayer,
   executeUpdateMerkleTreeTransactions,
   RELAYER_FEE,
+  MERKLE_TREE_HEIGHT,
 } from "@lightprotocol/zk.js";
 import { SPL_NOOP_ADDRESS } from "@solana/spl-account-compression";
 
@@ -183,7 +184,7 @@ describe("Merkle Tree Tests", () => {
     // update merkle tree with invalid signer
     merkleTreeConfig.payer = INVALID_SIGNER;
     try {
-      await merkleTreeConfig.updateMerkleTreeAuthority(
+      await merkleTreeConfig.proposeMerkleTreeAuthority(
         newAuthority.publicKey,
         true,
       );
@@ -205,7 +206,7 @@ describe("Merkle Tree Tests", () => {
     // update merkle tree with INVALID_MERKLE_TREE_AUTHORITY_PDA
     merkleTreeConfig.merkleTreeAuthorityPda = INVALID_MERKLE_TREE_AUTHORITY_PDA;
     try {
-      await merkleTreeConfig.updateMerkleTreeAuthority(
+      await merkleTreeConfig.proposeMerkleTreeAuthority(
         newAuthority.publicKey,
         true,
       );
@@ -214,7 +215,7 @@ describe("Merkle Tree Tests", () => {
       error = e;
     }
     await merkleTreeConfig.getMerkleTreeAuthorityPda();
-    console.log("updateMerkleTreeAuthority ", error);
+    console.log("proposeMerkleTreeAuthority ", error);
 
     assert.isTrue(
       error.logs.includes(
@@ -223,12 +224,31 @@ describe("Merkle Tree Tests", () => {
     );
     error = undefined;
 
-    await merkleTreeConfig.updateMerkleTreeAuthority(newAuthority.publicKey);
+    // cancelled transfer cannot be accepted
+    await merkleTreeConfig.proposeMerkleTreeAuthority(newAuthority.publicKey);
+    await merkleTreeConfig.cancelMerkleTreeAuthorityTransfer();
     merkleTreeConfig.payer = newAuthority;
-    await merkleTreeConfig.updateMerkleTreeAuthority(
+    try {
+      await merkleTreeConfig.acceptMerkleTreeAuthority();
+    } catch (e) {
+      error = e;
+    }
+    assert.isTrue(
+      error.logs.includes(
+        "Program log: AnchorError caused by account: new_authority. Error Code: InvalidAuthority. Error Number: 6016. Error Message: InvalidAuthority.",
+      ),
+    );
+    error = undefined;
+    merkleTreeConfig.payer = ADMIN_AUTH_KEYPAIR;
+
+    await merkleTreeConfig.proposeMerkleTreeAuthority(newAuthority.publicKey);
+    merkleTreeConfig.payer = newAuthority;
+    await merkleTreeConfig.acceptMerkleTreeAuthority();
+    await merkleTreeConfig.proposeMerkleTreeAuthority(
       ADMIN_AUTH_KEYPAIR.publicKey,
     );
     merkleTreeConfig.payer = ADMIN_AUTH_KEYPAIR;
+    await merkleTreeConfig.acceptMerkleTreeAuthority();
 
     // invalid signer
     merkleTreeConfig.payer = INVALID_SIGNER;
@@ -240,8 +260,10 @@ describe("Merkle Tree Tests", () => {
     console.log(error);
 
     assert.isTrue(
-      error.logs.includes(
-        "Program log: AnchorError caused by account: authority. Error Code: InvalidAuthority. Error Number: 6016. Error Message: InvalidAuthority.",
+      error.logs.some((log) =>
+        log.includes(
+          "Error Code: InvalidAuthority. Error Number: 6016. Error Message: InvalidAuthority.",
+        ),
       ),
     );
     error = undefined;
@@ -266,43 +288,33 @@ describe("Merkle Tree Tests", () => {
     error = undefined;
 
     // update merkle tree with invalid signer
-    // merkleTreeConfig.payer = INVALID_SIGNER;
-    // try {
-    //   await merkleTreeConfig.enableNfts(true);
-    // } catch (e) {
-    //   error = e;
-    // }
-    // assert.equal(error.error.errorMessage, "InvalidAuthority");
-    // error = undefined;
-    // merkleTreeConfig.payer = ADMIN_AUTH_KEYPAIR;
-
-    // // update merkle tree with INVALID_MERKLE_TREE_AUTHORITY_PDA
-    // merkleTreeConfig.merkleTreeAuthorityPda = INVALID_MERKLE_TREE_AUTHORITY_PDA;
-    // try {
-    //   await merkleTreeConfig.enableNfts(true);
-    // } catch (e) {
-    //   error = e;
-    // }
-    // await merkleTreeConfig.getMerkleTreeAuthorityPda();
-    // assert.equal(
-    //   error.error.errorMessage,
-    //   "The program expected this account to be already initialized"
-    // );
-    // error = undefined;
-
-    // await merkleTreeConfig.enableNfts(true);
+    merkleTreeConfig.payer = INVALID_SIGNER;
+    try {
+      await merkleTreeConfig.enableNfts(true);
+    } catch (e) {
+      error = e;
+    }
+    assert.isTrue(
+      error.logs.includes(
+        "Program log: AnchorError caused by account: authority. Error Code: InvalidAuthority. Error Number: 6016. Error Message: InvalidAuthority.",
+      ),
+    );
+    error = undefined;
+    merkleTreeConfig.payer = ADMIN_AUTH_KEYPAIR;
+
+    await merkleTreeConfig.enableNfts(true);
 
     let merkleTreeAuthority =
       await merkleTreeProgram.account.merkleTreeAuthority.fetch(
         merkleTreeConfig.merkleTreeAuthorityPda,
       );
-    // assert.equal(merkleTreeAuthority.enableNfts, true);
-    // await merkleTreeConfig.enableNfts(false);
-    // merkleTreeAuthority =
-    //   await merkleTreeProgram.account.merkleTreeAuthority.fetch(
-    //     merkleTreeConfig.merkleTreeAuthorityPda
-    //   );
-    // assert.equal(merkleTreeAuthority.enableNfts, false);
+    assert.equal(merkleTreeAuthority.enableNfts, true);
+    await merkleTreeConfig.enableNfts(false);
+    merkleTreeAuthority =
+      await merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        merkleTreeConfig.merkleTreeAuthorityPda,
+      );
+    assert.equal(merkleTreeAuthority.enableNfts, false);
 
     // update lock duration with invalid signer
 
@@ -678,8 +690,13 @@ describe("Merkle Tree Tests", () => {
     );
     let error;
 
-    // fetch uninserted utxos from chain
-    let leavesPdas = await SolMerkleTree.getUninsertedLeavesRelayer(
+    // fetch uninserted utxos from chain, up to 16 pairs are inserted in one update
+    const numberOfLeaves = Math.min(
+      (await SolMerkleTree.getUninsertedLeaves(transactionMerkleTreePda))
+        .length,
+      16,
+    );
+    const leavesQueue = MerkleTreeConfig.getLeavesQueuePda(
       transactionMerkleTreePda,
     );
 
@@ -694,67 +711,8 @@ describe("Merkle Tree Tests", () => {
     )[0];
     let connection = provider.connection;
 
-    if (leavesPdas.length > 1) {
-      // test leaves with higher starting index than merkle tree next index
-      leavesPdas.reverse();
-      try {
-        await merkleTreeProgram.methods
-          .initializeMerkleTreeUpdateState()
-          .accounts({
-            authority: signer.publicKey,
-            merkleTreeUpdateState: merkleTreeUpdateState,
-            systemProgram: DEFAULT_PROGRAMS.systemProgram,
-            rent: DEFAULT_PROGRAMS.rent,
-            transactionMerkleTree: transactionMerkleTreePda,
-          })
-          .remainingAccounts(leavesPdas)
-          .preInstructions([
-            solana.ComputeBudgetProgram.setComputeUnitLimit({
-              units: 1_400_000,
-            }),
-          ])
-          .signers([signer])
-          .rpc(confirmConfig);
-        console.log("success 0");
-      } catch (e) {
-        error = e;
-      }
-      assert(error.error.errorCode.code == "FirstLeavesPdaIncorrectIndex");
-
-      leavesPdas.reverse();
-      assert((await connection.getAccountInfo(merkleTreeUpdateState)) == null);
-
-      console.log("Test property: 1");
-      // Test property: 1
-      // try with one leavespda of higher index
-      try {
-        await merkleTreeProgram.methods
-          .initializeMerkleTreeUpdateState()
-          .accounts({
-            authority: signer.publicKey,
-            merkleTreeUpdateState: merkleTreeUpdateState,
-            systemProgram: SystemProgram.programId,
-            rent: DEFAULT_PROGRAMS.rent,
-            transactionMerkleTree: transactionMerkleTreePda,
-          })
-          .remainingAccounts([leavesPdas[1]])
-          .preInstructions([
-            solana.ComputeBudgetProgram.setComputeUnitLimit({
-              units: 1_400_000,
-            }),
-          ])
-          .signers([signer])
-          .rpc(confirmConfig);
-        console.log("success 1");
-      } catch (e) {
-        error = e;
-      }
-      assert(error.error.errorCode.code == "FirstLeavesPdaIncorrectIndex");
-
-      assert((await connection.getAccountInfo(merkleTreeUpdateState)) == null);
-    } else {
-      console.log("pdas.length <=" + 1 + " skipping some tests");
-    }
+    // Test property: 1
+    // leaves are copied from the leaves queue starting at the next index of the Merkle tree
 
     // Test property: 3
     // try with different Merkle tree than leaves are queued for
@@ -774,21 +732,22 @@ describe("Merkle Tree Tests", () => {
       await merkleTreeConfig.initializeNewTransactionMerkleTree(
         transactionMerkleTreePda,
         different_merkle_tree,
+        MERKLE_TREE_HEIGHT,
       );
       console.log("created new merkle tree");
     }
 
     try {
       await merkleTreeProgram.methods
-        .initializeMerkleTreeUpdateState()
+        .initializeMerkleTreeUpdateState(null)
         .accounts({
           authority: signer.publicKey,
           merkleTreeUpdateState: merkleTreeUpdateState,
           systemProgram: SystemProgram.programId,
           rent: DEFAULT_PROGRAMS.rent,
           transactionMerkleTree: different_merkle_tree,
+          leavesQueue,
         })
-        .remainingAccounts(leavesPdas)
         .preInstructions([
           solana.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
         ])
@@ -799,22 +758,22 @@ describe("Merkle Tree Tests", () => {
       console.log(e);
       error = e;
     }
-    assert(error.error.errorCode.code == "LeavesOfWrongTree");
+    assert(error.error.errorCode.code == "InvalidLeavesQueue");
     assert((await connection.getAccountInfo(merkleTreeUpdateState)) == null);
     error = undefined;
 
     // correct
     try {
       await merkleTreeProgram.methods
-        .initializeMerkleTreeUpdateState()
+        .initializeMerkleTreeUpdateState(null)
         .accounts({
           authority: signer.publicKey,
           merkleTreeUpdateState: merkleTreeUpdateState,
           systemProgram: SystemProgram.programId,
           rent: DEFAULT_PROGRAMS.rent,
           transactionMerkleTree: transactionMerkleTreePda,
+          leavesQueue,
         })
-        .remainingAccounts([leavesPdas[0]])
         .preInstructions([
           solana.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
         ])
@@ -835,7 +794,7 @@ describe("Merkle Tree Tests", () => {
       merkleTreeUpdateState,
       transactionMerkleTree: transactionMerkleTreePda,
       relayer: signer.publicKey,
-      leavesPdas: [leavesPdas[0]],
+      numberOfLeaves,
       current_instruction_index: 1,
       merkleTreeProgram,
     });
@@ -857,7 +816,7 @@ describe("Merkle Tree Tests", () => {
       merkleTreeUpdateState,
       transactionMerkleTree: transactionMerkleTreePda,
       relayer: signer.publicKey,
-      leavesPdas: [leavesPdas[0]],
+      numberOfLeaves,
       current_instruction_index: 22, // 22 becaue one tx executes two instructions, it started out in ix index 1 and increments at the end of a tx
       merkleTreeProgram,
     });
@@ -897,25 +856,40 @@ describe("Merkle Tree Tests", () => {
     );
 
     // Test property: 4
-    // try to take lock
-    try {
-      await merkleTreeProgram.methods
-        .initializeMerkleTreeUpdateState()
-        .accounts({
-          authority: maliciousSigner.publicKey,
-          merkleTreeUpdateState: maliciousMerkleTreeUpdateState,
-          systemProgram: SystemProgram.programId,
-          rent: DEFAULT_PROGRAMS.rent,
-          transactionMerkleTree: transactionMerkleTreePda,
-        })
-        .remainingAccounts([leavesPdas[0]])
-        .signers([maliciousSigner])
-        .rpc(confirmConfig);
-    } catch (e) {
-      error = e;
-      console.log(e);
-    }
-    assert(error.error.errorCode.code == "ContractStillLocked");
+    // a second relayer can update the Merkle tree concurrently, the Merkle tree is not locked
+    await merkleTreeProgram.methods
+      .initializeMerkleTreeUpdateState(null)
+      .accounts({
+        authority: maliciousSigner.publicKey,
+        merkleTreeUpdateState: maliciousMerkleTreeUpdateState,
+        systemProgram: SystemProgram.programId,
+        rent: DEFAULT_PROGRAMS.rent,
+        transactionMerkleTree: transactionMerkleTreePda,
+        leavesQueue,
+      })
+      .signers([maliciousSigner])
+      .rpc(confirmConfig);
+
+    await checkMerkleTreeUpdateStateCreated({
+      connection: connection,
+      merkleTreeUpdateState: maliciousMerkleTreeUpdateState,
+      transactionMerkleTree: transactionMerkleTreePda,
+      relayer: maliciousSigner.publicKey,
+      numberOfLeaves,
+      current_instruction_index: 1,
+      merkleTreeProgram,
+    });
+
+    await merkleTreeProgram.methods
+      .closeMerkleTreeUpdateState()
+      .accounts({
+        authority: maliciousSigner.publicKey,
+        relayer: maliciousSigner.publicKey,
+        merkleTreeUpdateState: maliciousMerkleTreeUpdateState,
+        transactionMerkleTree: transactionMerkleTreePda,
+      })
+      .signers([maliciousSigner])
+      .rpc(confirmConfig);
 
     // Test property: 10
     // try insert root before completing update transaction
@@ -952,7 +926,7 @@ describe("Merkle Tree Tests", () => {
       merkleTreeUpdateState,
       transactionMerkleTree: transactionMerkleTreePda,
       relayer: signer.publicKey,
-      leavesPdas: [leavesPdas[0]],
+      numberOfLeaves,
       current_instruction_index: 56,
       merkleTreeProgram,
     });
@@ -974,7 +948,7 @@ describe("Merkle Tree Tests", () => {
     } catch (e) {
       error = e;
     }
-    assert(error.error.errorCode.code == "ContractStillLocked");
+    assert(error.error.errorCode.code == "InvalidMerkleTree");
 
     // Test property: 13
     // final tx to insert root different signer
@@ -1023,7 +997,6 @@ describe("Merkle Tree Tests", () => {
       .rpc(confirmConfig);
     console.log("merkleTreeUpdateState ", merkleTreeUpdateState);
     console.log("merkleTreeAccountPrior ", merkleTreeAccountPrior);
-    console.log("leavesPdas[0] ", leavesPdas[0]);
     console.log("merkleTree ", merkleTree);
     console.log("merkle_tree_pubkey ", transactionMerkleTreePda);
 
@@ -1031,8 +1004,7 @@ describe("Merkle Tree Tests", () => {
       connection: provider.connection,
       merkleTreeUpdateState: merkleTreeUpdateState,
       merkleTreeAccountPrior,
-      numberOfLeaves: 2,
-      leavesPdas: [leavesPdas[0]],
+      numberOfLeaves: numberOfLeaves * 2,
       transactionMerkleTree: transactionMerkleTreePda,
       merkleTreeProgram,
     });
@@ -1040,27 +1012,34 @@ describe("Merkle Tree Tests", () => {
     console.log("Test property: 2");
 
     // Test property: 2
-    // try to reinsert leavesPdas[0]
-    try {
-      await merkleTreeProgram.methods
-        .initializeMerkleTreeUpdateState()
-        .accounts({
-          authority: signer.publicKey,
-          merkleTreeUpdateState: merkleTreeUpdateState,
-          systemProgram: SystemProgram.programId,
-          rent: DEFAULT_PROGRAMS.rent,
-          transactionMerkleTree: transactionMerkleTreePda,
-        })
-        .remainingAccounts([leavesPdas[0]])
-        .preInstructions([
-          solana.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
-        ])
-        .signers([signer])
-        .rpc(confirmConfig);
-    } catch (e) {
-      error = e;
+    // inserted leaves are not queued anymore, try to create an update state without queued leaves
+    if (
+      (await SolMerkleTree.getUninsertedLeaves(transactionMerkleTreePda))
+        .length === 0
+    ) {
+      try {
+        await merkleTreeProgram.methods
+          .initializeMerkleTreeUpdateState(null)
+          .accounts({
+            authority: signer.publicKey,
+            merkleTreeUpdateState: merkleTreeUpdateState,
+            systemProgram: SystemProgram.programId,
+            rent: DEFAULT_PROGRAMS.rent,
+            transactionMerkleTree: transactionMerkleTreePda,
+            leavesQueue,
+          })
+          .preInstructions([
+            solana.ComputeBudgetProgram.setComputeUnitLimit({
+              units: 1_400_000,
+            }),
+          ])
+          .signers([signer])
+          .rpc(confirmConfig);
+      } catch (e) {
+        error = e;
+      }
+      assert(error.error.errorCode.code == "InvalidNumberOfLeaves");
     }
-    assert(error.error.errorCode.code == "LeafAlreadyInserted");
   });
 
   it("Switch to a new Merkle tree", async () => {
@@ -1106,24 +1085,32 @@ describe("Merkle Tree Tests", () => {
       provider: lightProvider,
       params: txParams,
     });
-    transaction.remainingAccounts!.nextTransactionMerkleTree = {
-      isSigner: false,
-      isWritable: true,
-      pubkey: newMerkleTreePubkey,
-    };
 
+    // Merkle tree 0 rolled over, the leaves go into the newest Merkle tree.
     await transaction.compileAndProve();
+    assert.equal(
+      transaction.remainingAccounts!.nextTransactionMerkleTree!.pubkey.toBase58(),
+      newMerkleTreePubkey.toBase58(),
+    );
+    const nextQueuedIndex = (
+      await merkleTreeProgram.account.transactionMerkleTree.fetch(
+        newMerkleTreePubkey,
+      )
+    ).nextQueuedIndex;
     await transaction.sendAndConfirmTransaction();
-
-    let leavesPdas = await SolMerkleTree.getUninsertedLeavesRelayer(
-      newMerkleTreePubkey,
+    assert.equal(
+      (
+        await merkleTreeProgram.account.transactionMerkleTree.fetch(
+          newMerkleTreePubkey,
+        )
+      ).nextQueuedIndex.toString(),
+      nextQueuedIndex.addn(2).toString(),
     );
 
     executeUpdateMerkleTreeTransactions({
       connection: provider.connection,
       signer: ADMIN_AUTH_KEYPAIR,
       merkleTreeProgram,
-      leavesPdas,
       transactionMerkleTree: newMerkleTreePubkey,
     });
   });
@@ -325,11 +325,23 @@ describe("Verifier Zero and One Tests", () => {
   it("Wrong encryptedUtxos", async () => {
     for (var tx in transactions) {
       var tmp_tx: Transaction = _.cloneDeep(transactions[tx]);
-      tmp_tx.params.encryptedUtxos = new Uint8Array(174).fill(2);
+      tmp_tx.params.encryptedUtxos = new Uint8Array(240).fill(2);
       await sendTestTx(tmp_tx, "ProofVerificationFails");
     }
   });
 
+  it("Wrong encryptedUtxos length", async () => {
+    for (var tx in transactions) {
+      var tmp_tx: Transaction = _.cloneDeep(transactions[tx]);
+      tmp_tx.params.encryptedUtxos = new Uint8Array(174).fill(2);
+      await sendTestTx(
+        tmp_tx,
+        "Includes",
+        "Program log: Encrypted utxos length 174 != 2 outputs * 120 bytes",
+      );
+    }
+  });
+
   it("Wrong relayerFee", async () => {
     for (var tx in transactions) {
       var tmp_tx: Transaction = _.cloneDeep(transactions[tx]);
@@ -449,28 +461,69 @@ describe("Verifier Zero and One Tests", () => {
     }
   });
 
-  it("Wrong leavesPdaPubkeys accounts", async () => {
+  it("Wrong leavesQueue account", async () => {
     for (var tx in transactions) {
       var tmp_tx: Transaction = _.cloneDeep(transactions[tx]);
       tmp_tx.getPdaAddresses();
-      for (
-        var i = 0;
-        i < tmp_tx.remainingAccounts.leavesPdaPubkeys.length;
-        i++
-      ) {
-        tmp_tx.remainingAccounts.leavesPdaPubkeys[i] = {
-          isSigner: false,
-          isWritable: true,
-          pubkey: SolanaKeypair.generate().publicKey,
-        };
-        await sleep(SLEEP_BUFFER);
-
-        await sendTestTx(
-          tmp_tx,
-          "Includes",
-          "Program log: AnchorError caused by account: two_leaves_pda. Error Code: ConstraintSeeds. Error Number: 2006. Error Message: A seeds constraint was violated.",
-        );
-      }
+      tmp_tx.remainingAccounts.leavesQueue = {
+        isSigner: false,
+        isWritable: true,
+        pubkey: SolanaKeypair.generate().publicKey,
+      };
+      await sleep(SLEEP_BUFFER);
+
+      await sendTestTx(
+        tmp_tx,
+        "Includes",
+        "Program log: AnchorError caused by account: leaves_queue. Error Code: AccountOwnedByWrongProgram. Error Number: 3007. Error Message: The given account is owned by a different program than expected.",
+      );
     }
   });
+
+  it("Double spend", async () => {
+    const lightProvider = await LightProvider.init({
+      wallet: ADMIN_AUTH_KEYPAIR,
+      relayer: RELAYER,
+      confirmConfig,
+    });
+    const user: User = await User.init({
+      provider: lightProvider,
+      account: ACCOUNT,
+    });
+    const inputUtxos: Utxo[] = [
+      user.balance.tokenBalances.get(MINT.toBase58()).utxos.values().next()
+        .value,
+    ];
+
+    // Both transactions spend the same utxo, their change utxos differ.
+    const getWithdrawalTx = async () => {
+      const txParams = new TransactionParameters({
+        inputUtxos,
+        eventMerkleTreePubkey: MerkleTreeConfig.getEventMerkleTreePda(),
+        transactionMerkleTreePubkey:
+          MerkleTreeConfig.getTransactionMerkleTreePda(),
+        recipientSpl: recipientTokenAccount,
+        recipientSol: ADMIN_AUTH_KEYPAIR.publicKey,
+        relayer: RELAYER,
+        poseidon: POSEIDON,
+        action: Action.UNSHIELD,
+        verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
+      });
+      const tx = new Transaction({
+        provider: lightProvider,
+        params: txParams,
+      });
+      await tx.compileAndProve();
+      return tx;
+    };
+    const tx = await getWithdrawalTx();
+    const doubleSpendTx = await getWithdrawalTx();
+
+    await tx.sendAndConfirmTransaction();
+    await sendTestTx(
+      doubleSpendTx,
+      "Includes",
+      "Program log: Nullifier 0 is spent already.",
+    );
+  });
 });
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Token;
+use anchor_spl::token_interface::TokenInterface;
 use merkle_tree_program::{
     event_merkle_tree::EventMerkleTree, program::MerkleTreeProgram,
-    transaction_merkle_tree::state::TransactionMerkleTree, RegisteredVerifier,
+    transaction_merkle_tree::state::TransactionMerkleTree, MerkleTreeAuthority,
+    RegisteredVerifier,
 };
 
 pub trait LightAccounts<'info> {
@@ -15,10 +16,13 @@ pub trait LightAccounts<'info> {
     fn get_registered_verifier_pda(&self) -> &Account<'info, RegisteredVerifier>;
     fn get_sender_sol(&self) -> Option<&UncheckedAccount<'info>>;
     fn get_recipient_sol(&self) -> Option<&UncheckedAccount<'info>>;
-    fn get_token_program(&self) -> Option<&Program<'info, Token>>;
+    fn get_token_program(&self) -> Option<&Interface<'info, TokenInterface>>;
     fn get_token_authority(&self) -> Option<&AccountInfo<'info>>;
     fn get_sender_spl(&self) -> Option<&UncheckedAccount<'info>>;
     fn get_recipient_spl(&self) -> Option<&UncheckedAccount<'info>>;
+    fn get_registered_asset_pool_spl(&self) -> Option<&UncheckedAccount<'info>>;
+    fn get_mint_spl(&self) -> Option<&UncheckedAccount<'info>>;
     fn get_log_wrapper(&self) -> &UncheckedAccount<'info>;
     fn get_event_merkle_tree(&self) -> &AccountLoader<'info, EventMerkleTree>;
+    fn get_merkle_tree_authority_pda(&self) -> &Account<'info, MerkleTreeAuthority>;
 }
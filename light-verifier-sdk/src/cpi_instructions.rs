@@ -2,10 +2,17 @@ use std::str::FromStr;
 
 use anchor_lang::{
     prelude::*,
-    solana_program::{instruction::Instruction, program::invoke},
+    solana_program::{
+        hash::hash,
+        instruction::Instruction,
+        program::{invoke, invoke_signed},
+    },
 };
 
-use crate::{errors::VerifierSdkError, state::TransactionIndexerEvent};
+use crate::{
+    accounts::LightAccounts, errors::VerifierSdkError, light_transaction::Proof,
+    state::TransactionIndexerEvent,
+};
 
 pub fn insert_nullifiers_cpi<'a, 'b>(
     program_id: &Pubkey,
@@ -13,6 +20,7 @@ pub fn insert_nullifiers_cpi<'a, 'b>(
     authority: &'b AccountInfo<'a>,
     system_program: &'b AccountInfo<'a>,
     registered_verifier_pda: &'b AccountInfo<'a>,
+    merkle_tree_authority_pda: &'b AccountInfo<'a>,
     nullifiers: Vec<[u8; 32]>,
     nullifier_pdas: Vec<AccountInfo<'a>>,
 ) -> Result<()> {
@@ -23,6 +31,7 @@ pub fn insert_nullifiers_cpi<'a, 'b>(
         authority: authority.clone(),
         system_program: system_program.clone(),
         registered_verifier_pda: registered_verifier_pda.clone(),
+        merkle_tree_authority_pda: merkle_tree_authority_pda.clone(),
     };
 
     let mut cpi_ctx = CpiContext::new_with_signer(merkle_tree_program_id.clone(), accounts, seeds);
@@ -31,6 +40,117 @@ pub fn insert_nullifiers_cpi<'a, 'b>(
     merkle_tree_program::cpi::initialize_nullifiers(cpi_ctx, nullifiers)
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn insert_nullifiers_into_queue_cpi<'a, 'b>(
+    program_id: &Pubkey,
+    merkle_tree_program_id: &'b AccountInfo<'a>,
+    authority: &'b AccountInfo<'a>,
+    registered_verifier_pda: &'b AccountInfo<'a>,
+    merkle_tree_authority_pda: &'b AccountInfo<'a>,
+    nullifier_queue: &'b AccountInfo<'a>,
+    nullifier_tree: &'b AccountInfo<'a>,
+    nullifiers: Vec<[u8; 32]>,
+    nullifier_pdas: Vec<AccountInfo<'a>>,
+) -> Result<()> {
+    let (seed, bump) = get_seeds(program_id, merkle_tree_program_id)?;
+    let bump = &[bump];
+    let seeds = &[&[seed.as_slice(), bump][..]];
+    let accounts = merkle_tree_program::cpi::accounts::InsertNullifiersIntoQueue {
+        authority: authority.clone(),
+        registered_verifier_pda: registered_verifier_pda.clone(),
+        merkle_tree_authority_pda: merkle_tree_authority_pda.clone(),
+        nullifier_queue: nullifier_queue.clone(),
+        nullifier_tree: nullifier_tree.clone(),
+    };
+
+    let mut cpi_ctx = CpiContext::new_with_signer(merkle_tree_program_id.clone(), accounts, seeds);
+    cpi_ctx = cpi_ctx.with_remaining_accounts(nullifier_pdas);
+
+    merkle_tree_program::cpi::insert_nullifiers_into_queue(cpi_ctx, nullifiers)
+}
+
+/// Invokes `shielded_transfer_inputs` of a system verifier with the accounts of
+/// `ctx`. The verifier state signs with `verifier_state_seeds`.
+pub fn shielded_transfer_inputs_cpi<'info, A: LightAccounts<'info>>(
+    ctx: &Context<'_, '_, '_, 'info, A>,
+    system_verifier_program: &AccountInfo<'info>,
+    verifier_state: &AccountInfo<'info>,
+    verifier_state_seeds: &[&[u8]],
+    system_verifying_key: &AccountInfo<'info>,
+    proof: &Proof,
+    connecting_hash: &[u8; 32],
+) -> Result<()> {
+    let missing_account = || error!(VerifierSdkError::MissingAccount);
+    let accounts = &ctx.accounts;
+    // Same order as the accounts of the system verifier.
+    let mut account_infos = vec![
+        accounts.get_signing_address().to_account_info(),
+        accounts.get_system_program().to_account_info(),
+        accounts.get_program_merkle_tree().to_account_info(),
+        accounts.get_transaction_merkle_tree().to_account_info(),
+        accounts.get_authority().to_account_info(),
+        accounts.get_relayer_recipient_sol().to_account_info(),
+        accounts
+            .get_sender_sol()
+            .ok_or_else(missing_account)?
+            .to_account_info(),
+        accounts
+            .get_recipient_sol()
+            .ok_or_else(missing_account)?
+            .to_account_info(),
+        accounts
+            .get_token_program()
+            .ok_or_else(missing_account)?
+            .to_account_info(),
+        accounts
+            .get_token_authority()
+            .ok_or_else(missing_account)?
+            .clone(),
+        accounts
+            .get_sender_spl()
+            .ok_or_else(missing_account)?
+            .to_account_info(),
+        accounts
+            .get_recipient_spl()
+            .ok_or_else(missing_account)?
+            .to_account_info(),
+        accounts.get_registered_verifier_pda().to_account_info(),
+        accounts.get_log_wrapper().to_account_info(),
+        accounts.get_event_merkle_tree().to_account_info(),
+        accounts.get_merkle_tree_authority_pda().to_account_info(),
+        verifier_state.clone(),
+        system_verifying_key.clone(),
+    ];
+    account_infos.extend(ctx.remaining_accounts.iter().cloned());
+
+    let account_metas = account_infos
+        .iter()
+        .map(|account| {
+            let is_signer = account.is_signer || account.key == verifier_state.key;
+            if account.is_writable {
+                AccountMeta::new(*account.key, is_signer)
+            } else {
+                AccountMeta::new_readonly(*account.key, is_signer)
+            }
+        })
+        .collect();
+
+    let mut data = hash(b"global:shielded_transfer_inputs").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&proof.a);
+    data.extend_from_slice(&proof.b);
+    data.extend_from_slice(&proof.c);
+    data.extend_from_slice(connecting_hash);
+
+    let instruction = Instruction {
+        program_id: system_verifier_program.key(),
+        accounts: account_metas,
+        data,
+    };
+    account_infos.push(system_verifier_program.clone());
+    invoke_signed(&instruction, &account_infos, &[verifier_state_seeds])?;
+    Ok(())
+}
+
 pub fn withdraw_sol_cpi<'a, 'b>(
     program_id: &Pubkey,
     merkle_tree_program_id: &'b AccountInfo<'a>,
@@ -38,6 +158,7 @@ pub fn withdraw_sol_cpi<'a, 'b>(
     merkle_tree_token: &'b AccountInfo<'a>,
     recipient: &'b AccountInfo<'a>,
     registered_verifier_pda: &'b AccountInfo<'a>,
+    merkle_tree_authority_pda: &'b AccountInfo<'a>,
     pub_amount_checked: u64,
 ) -> Result<()> {
     let (seed, bump) = get_seeds(program_id, merkle_tree_program_id)?;
@@ -48,6 +169,7 @@ pub fn withdraw_sol_cpi<'a, 'b>(
         authority: authority.clone(),
         merkle_tree_token: merkle_tree_token.clone(),
         registered_verifier_pda: registered_verifier_pda.clone(),
+        merkle_tree_authority_pda: merkle_tree_authority_pda.clone(),
         recipient: recipient.clone(),
     };
 
@@ -64,7 +186,10 @@ pub fn withdraw_spl_cpi<'a, 'b>(
     recipient: &'b AccountInfo<'a>,
     token_authority: &'b AccountInfo<'a>,
     token_program: &'b AccountInfo<'a>,
+    mint: &'b AccountInfo<'a>,
     registered_verifier_pda: &'b AccountInfo<'a>,
+    merkle_tree_authority_pda: &'b AccountInfo<'a>,
+    registered_asset_pool_pda: &'b AccountInfo<'a>,
     pub_amount_checked: u64,
 ) -> Result<()> {
     let (seed, bump) = get_seeds(program_id, merkle_tree_program_id)?;
@@ -76,8 +201,11 @@ pub fn withdraw_spl_cpi<'a, 'b>(
         merkle_tree_token: merkle_tree_token.clone(),
         token_authority: token_authority.clone(),
         token_program: token_program.clone(),
+        mint: mint.clone(),
         registered_verifier_pda: registered_verifier_pda.clone(),
+        merkle_tree_authority_pda: merkle_tree_authority_pda.clone(),
         recipient: recipient.clone(),
+        registered_asset_pool_pda: registered_asset_pool_pda.clone(),
     };
 
     let cpi_ctx = CpiContext::new_with_signer(merkle_tree_program_id.clone(), accounts, seeds);
@@ -89,13 +217,14 @@ pub fn insert_two_leaves_cpi<'a, 'b>(
     program_id: &Pubkey,
     merkle_tree_program_id: &'b AccountInfo<'a>,
     authority: &'b AccountInfo<'a>,
-    two_leaves_pda: &'b AccountInfo<'a>,
+    leaves_queue: &'b AccountInfo<'a>,
     transaction_merkle_tree_account: &'b AccountInfo<'a>,
     system_program: &'b AccountInfo<'a>,
     registered_verifier_pda: &'b AccountInfo<'a>,
+    merkle_tree_authority_pda: &'b AccountInfo<'a>,
     leaf_left: [u8; 32],
     leaf_right: [u8; 32],
-    encrypted_utxos: Vec<u8>,
+    first_leaves: bool,
 ) -> Result<()> {
     let (seed, bump) = get_seeds(program_id, merkle_tree_program_id)?;
     let bump = &[bump];
@@ -103,25 +232,15 @@ pub fn insert_two_leaves_cpi<'a, 'b>(
 
     let accounts = merkle_tree_program::cpi::accounts::InsertTwoLeaves {
         authority: authority.clone(),
-        two_leaves_pda: two_leaves_pda.clone(),
+        leaves_queue: leaves_queue.clone(),
         system_program: system_program.clone(),
         transaction_merkle_tree: transaction_merkle_tree_account.clone(),
         registered_verifier_pda: registered_verifier_pda.clone(),
+        merkle_tree_authority_pda: merkle_tree_authority_pda.clone(),
     };
 
     let cpi_ctx = CpiContext::new_with_signer(merkle_tree_program_id.clone(), accounts, seeds);
-    merkle_tree_program::cpi::insert_two_leaves(
-        cpi_ctx,
-        leaf_left,
-        leaf_right,
-        [
-            encrypted_utxos.to_vec(),
-            vec![0u8; 256 - encrypted_utxos.len()],
-        ]
-        .concat()
-        .try_into()
-        .unwrap(),
-    )
+    merkle_tree_program::cpi::insert_two_leaves(cpi_ctx, leaf_left, leaf_right, first_leaves)
 }
 
 pub fn insert_two_leaves_event_cpi<'a, 'b>(
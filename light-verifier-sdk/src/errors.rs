@@ -40,4 +40,30 @@ pub enum VerifierSdkError {
     EventMerkleTreeInvalidHashFunction,
     #[msg("Invalid noop progam key")]
     InvalidNoopPubkey,
+    #[msg("Transaction Merkle tree is not the newest one")]
+    TransactionMerkleTreeNotNewest,
+    #[msg("System verifier is not the registered verifier")]
+    InvalidSystemVerifier,
+    #[msg("Verifying key account belongs to another verifier")]
+    InvalidVerifyingKeyAccount,
+    #[msg("Verifying key is not active")]
+    VerifyingKeyNotActive,
+    #[msg("Shields are paused")]
+    ShieldsPaused,
+    #[msg("Unshields are paused")]
+    UnshieldsPaused,
+    #[msg("Transfers are paused")]
+    TransfersPaused,
+    #[msg("Registered asset pool account does not belong to the spl pool")]
+    InvalidRegisteredAssetPool,
+    #[msg("Pool did not receive the shielded amount, the transfer fee is not supported")]
+    InvalidTransferFee,
+    #[msg("Encrypted utxos do not match the encrypted utxo length of the verifier")]
+    InvalidEncryptedUtxosLength,
+    #[msg("Account required by the system verifier is missing")]
+    MissingAccount,
+    #[msg("Nullifier tree is not the nullifier tree of the merkle tree program")]
+    InvalidNullifierTree,
+    #[msg("Another transaction kind is paused, leaves and nullifiers are rejected")]
+    ProtocolPaused,
 }
@@ -4,7 +4,10 @@ use ark_std::marker::PhantomData;
 
 use groth16_solana::groth16::{Groth16Verifier, Groth16Verifyingkey};
 
-use crate::{errors::VerifierSdkError, light_transaction::Proof, utils::change_endianness};
+use crate::{
+    accounts::LightAccounts, cpi_instructions::shielded_transfer_inputs_cpi,
+    errors::VerifierSdkError, light_transaction::Proof, utils::change_endianness,
+};
 
 use std::ops::Neg;
 
@@ -51,12 +54,30 @@ impl<'a, const NR_CHECKED_INPUTS: usize, T: Config> AppTransaction<'a, NR_CHECKE
         }
     }
 
-    // /// Transact is a wrapper function which verifies the zero knowledge proof and cpi's to the selected verifier.
-    // pub fn transact(&mut self) -> Result<()> {
-    //     self.verify()?;
-    //     self.send_transaction()?;
-    //     self.check_completion()
-    // }
+    /// Transact is a wrapper function which verifies the zero knowledge proof and cpi's to the
+    /// system verifier registered in the accounts of `ctx`.
+    pub fn transact<'info, A: LightAccounts<'info>>(
+        &mut self,
+        ctx: &Context<'_, '_, '_, 'info, A>,
+        system_verifier_program: &AccountInfo<'info>,
+        verifier_state: &AccountInfo<'info>,
+        verifier_state_seeds: &[&[u8]],
+        system_verifying_key: &AccountInfo<'info>,
+        system_proof: &Proof,
+        connecting_hash: &[u8; 32],
+    ) -> Result<()> {
+        self.verify()?;
+        self.send_transaction(
+            ctx,
+            system_verifier_program,
+            verifier_state,
+            verifier_state_seeds,
+            system_verifying_key,
+            system_proof,
+            connecting_hash,
+        )?;
+        self.check_completion()
+    }
 
     /// Verifies a Goth16 zero knowledge proof over the bn254 curve.
     pub fn verify(&mut self) -> Result<()> {
@@ -87,18 +108,433 @@ impl<'a, const NR_CHECKED_INPUTS: usize, T: Config> AppTransaction<'a, NR_CHECKE
         }
     }
 
-    // TODO: implement, has to pass contex struct otherwise not really worth
-    pub fn send_transaction() {
-        // match for configured system verifier program id
+    /// Invokes the system verifier with the system proof. The system verifier has to be the
+    /// verifier of the registered verifier pda in the accounts of `ctx`.
+    /// The verifier state is the app's verifier state pda which stores the inputs of the
+    /// system proof, it signs with `verifier_state_seeds`. The system verifying key is the
+    /// verifying key account of the system verifier.
+    pub fn send_transaction<'info, A: LightAccounts<'info>>(
+        &mut self,
+        ctx: &Context<'_, '_, '_, 'info, A>,
+        system_verifier_program: &AccountInfo<'info>,
+        verifier_state: &AccountInfo<'info>,
+        verifier_state_seeds: &[&[u8]],
+        system_verifying_key: &AccountInfo<'info>,
+        system_proof: &Proof,
+        connecting_hash: &[u8; 32],
+    ) -> Result<()> {
+        if !self.verified_proof {
+            msg!("Tried to invoke the system verifier without verifying the proof.");
+            return err!(VerifierSdkError::ProofNotVerified);
+        }
+
+        let registered_verifier = ctx.accounts.get_registered_verifier_pda().pubkey;
+        if system_verifier_program.key() != registered_verifier {
+            msg!(
+                "System verifier {} is not the registered verifier {}",
+                system_verifier_program.key(),
+                registered_verifier
+            );
+            return err!(VerifierSdkError::InvalidSystemVerifier);
+        }
+
+        shielded_transfer_inputs_cpi(
+            ctx,
+            system_verifier_program,
+            verifier_state,
+            verifier_state_seeds,
+            system_verifying_key,
+            system_proof,
+            connecting_hash,
+        )?;
+
+        self.invoked_system_verifier = true;
+        Ok(())
+    }
+
+    pub fn check_completion(&self) -> Result<()> {
+        if self.invoked_system_verifier && self.verified_proof {
+            return Ok(());
+        }
+        msg!("verified_proof {}", self.verified_proof);
+        msg!("invoked_system_verifier {}", self.invoked_system_verifier);
+        err!(VerifierSdkError::AppTransactionIncomplete)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use anchor_spl::token_interface::TokenInterface;
+    use merkle_tree_program::{
+        event_merkle_tree::EventMerkleTree, program::MerkleTreeProgram,
+        transaction_merkle_tree::state::TransactionMerkleTree, MerkleTreeAuthority,
+        RegisteredVerifier,
+    };
+    use std::collections::BTreeMap;
+
+    #[derive(Clone)]
+    struct TestConfig;
+    impl Config for TestConfig {
+        const ID: Pubkey = Pubkey::new_from_array([1u8; 32]);
+    }
+
+    const VERIFYING_KEY: Groth16Verifyingkey = Groth16Verifyingkey {
+        nr_pubinputs: 0,
+        vk_alpha_g1: [0u8; 64],
+        vk_beta_g2: [0u8; 128],
+        vk_gamme_g2: [0u8; 128],
+        vk_delta_g2: [0u8; 128],
+        vk_ic: &[[0u8; 64]],
+    };
+
+    struct TestAccounts<'info> {
+        signing_address: Signer<'info>,
+        system_program: Program<'info, System>,
+        program_merkle_tree: Program<'info, MerkleTreeProgram>,
+        transaction_merkle_tree: AccountLoader<'info, TransactionMerkleTree>,
+        authority: UncheckedAccount<'info>,
+        relayer_recipient_sol: UncheckedAccount<'info>,
+        registered_verifier_pda: Account<'info, RegisteredVerifier>,
+        log_wrapper: UncheckedAccount<'info>,
+        event_merkle_tree: AccountLoader<'info, EventMerkleTree>,
+        merkle_tree_authority_pda: Account<'info, MerkleTreeAuthority>,
+    }
+
+    impl<'info> LightAccounts<'info> for TestAccounts<'info> {
+        fn get_signing_address(&self) -> &Signer<'info> {
+            &self.signing_address
+        }
+        fn get_system_program(&self) -> &Program<'info, System> {
+            &self.system_program
+        }
+        fn get_program_merkle_tree(&self) -> &Program<'info, MerkleTreeProgram> {
+            &self.program_merkle_tree
+        }
+        fn get_transaction_merkle_tree(&self) -> &AccountLoader<'info, TransactionMerkleTree> {
+            &self.transaction_merkle_tree
+        }
+        fn get_authority(&self) -> &UncheckedAccount<'info> {
+            &self.authority
+        }
+        fn get_relayer_recipient_sol(&self) -> &UncheckedAccount<'info> {
+            &self.relayer_recipient_sol
+        }
+        fn get_registered_verifier_pda(&self) -> &Account<'info, RegisteredVerifier> {
+            &self.registered_verifier_pda
+        }
+        fn get_sender_sol(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_recipient_sol(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_token_program(&self) -> Option<&Interface<'info, TokenInterface>> {
+            None
+        }
+        fn get_token_authority(&self) -> Option<&AccountInfo<'info>> {
+            None
+        }
+        fn get_sender_spl(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_recipient_spl(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_registered_asset_pool_spl(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_mint_spl(&self) -> Option<&UncheckedAccount<'info>> {
+            None
+        }
+        fn get_log_wrapper(&self) -> &UncheckedAccount<'info> {
+            &self.log_wrapper
+        }
+        fn get_event_merkle_tree(&self) -> &AccountLoader<'info, EventMerkleTree> {
+            &self.event_merkle_tree
+        }
+        fn get_merkle_tree_authority_pda(&self) -> &Account<'info, MerkleTreeAuthority> {
+            &self.merkle_tree_authority_pda
+        }
+    }
+
+    fn account_info(
+        key: Pubkey,
+        is_signer: bool,
+        owner: Pubkey,
+        data: Vec<u8>,
+        executable: bool,
+    ) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            true,
+            Box::leak(Box::new(0)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            executable,
+            0,
+        )))
+    }
+
+    /// Zeroed account data with the discriminator of `T`.
+    fn account_data<T: Discriminator>(len: usize) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        data[..8].copy_from_slice(&T::discriminator());
+        data
+    }
+
+    fn test_accounts(registered_verifier: Pubkey) -> TestAccounts<'static> {
+        let merkle_tree_program_id = merkle_tree_program::ID;
+        let unchecked = || {
+            UncheckedAccount::try_from(
+                account_info(
+                    Pubkey::new_unique(),
+                    false,
+                    Pubkey::default(),
+                    Vec::new(),
+                    false,
+                )
+                .clone(),
+            )
+        };
+        let mut registered_verifier_data = account_data::<RegisteredVerifier>(128);
+        registered_verifier_data[8..40].copy_from_slice(&registered_verifier.to_bytes());
+
+        TestAccounts {
+            signing_address: Signer::try_from(account_info(
+                Pubkey::new_unique(),
+                true,
+                Pubkey::default(),
+                Vec::new(),
+                false,
+            ))
+            .unwrap(),
+            system_program: Program::try_from(account_info(
+                System::id(),
+                false,
+                Pubkey::default(),
+                Vec::new(),
+                true,
+            ))
+            .unwrap(),
+            program_merkle_tree: Program::try_from(account_info(
+                merkle_tree_program_id,
+                false,
+                Pubkey::default(),
+                Vec::new(),
+                true,
+            ))
+            .unwrap(),
+            transaction_merkle_tree: AccountLoader::try_from_unchecked(
+                &merkle_tree_program_id,
+                account_info(
+                    Pubkey::new_unique(),
+                    false,
+                    merkle_tree_program_id,
+                    Vec::new(),
+                    false,
+                ),
+            )
+            .unwrap(),
+            authority: unchecked(),
+            relayer_recipient_sol: unchecked(),
+            registered_verifier_pda: Account::try_from(account_info(
+                Pubkey::new_unique(),
+                false,
+                merkle_tree_program_id,
+                registered_verifier_data,
+                false,
+            ))
+            .unwrap(),
+            log_wrapper: unchecked(),
+            event_merkle_tree: AccountLoader::try_from_unchecked(
+                &merkle_tree_program_id,
+                account_info(
+                    Pubkey::new_unique(),
+                    false,
+                    merkle_tree_program_id,
+                    Vec::new(),
+                    false,
+                ),
+            )
+            .unwrap(),
+            merkle_tree_authority_pda: Account::try_from(account_info(
+                Pubkey::new_unique(),
+                false,
+                merkle_tree_program_id,
+                account_data::<MerkleTreeAuthority>(1024),
+                false,
+            ))
+            .unwrap(),
+        }
+    }
+
+    fn app_transaction<'a>(
+        proof: Proof,
+        checked_public_inputs: &'a [[u8; 32]; 0],
+    ) -> AppTransaction<'a, 0, TestConfig> {
+        AppTransaction {
+            checked_public_inputs,
+            proof,
+            e_phantom: PhantomData,
+            verifyingkey: &VERIFYING_KEY,
+            verified_proof: false,
+            invoked_system_verifier: false,
+        }
+    }
+
+    fn proof() -> Proof {
+        Proof {
+            a: [0u8; 64],
+            b: [0u8; 128],
+            c: [0u8; 64],
+        }
     }
 
-    // pub fn check_completion(&self) -> Result<()> {
-    //     if self.invoked_system_verifier
-    //         && self.verified_proof
-    //     {
-    //         return Ok(());
-    //     }
-    //     msg!("verified_proof {}", self.verified_proof);
-    //     err!(VerifierSdkError::AppTransactionIncomplete)
-    // }
+    #[test]
+    fn test_check_completion() {
+        let mut tx = app_transaction(proof(), &[]);
+        assert_eq!(
+            tx.check_completion(),
+            err!(VerifierSdkError::AppTransactionIncomplete)
+        );
+        tx.verified_proof = true;
+        assert_eq!(
+            tx.check_completion(),
+            err!(VerifierSdkError::AppTransactionIncomplete)
+        );
+        tx.verified_proof = false;
+        tx.invoked_system_verifier = true;
+        assert_eq!(
+            tx.check_completion(),
+            err!(VerifierSdkError::AppTransactionIncomplete)
+        );
+        tx.verified_proof = true;
+        assert!(tx.check_completion().is_ok());
+    }
+
+    #[test]
+    fn test_send_transaction() {
+        let system_verifier = Pubkey::new_unique();
+        let mut accounts = test_accounts(system_verifier);
+        let ctx = Context {
+            program_id: &TestConfig::ID,
+            accounts: &mut accounts,
+            remaining_accounts: &[],
+            bumps: BTreeMap::new(),
+        };
+        let system_verifier_program =
+            account_info(system_verifier, false, Pubkey::default(), Vec::new(), true);
+        let other_verifier_program = account_info(
+            Pubkey::new_unique(),
+            false,
+            Pubkey::default(),
+            Vec::new(),
+            true,
+        );
+        let verifier_state = account_info(
+            Pubkey::new_unique(),
+            false,
+            TestConfig::ID,
+            Vec::new(),
+            false,
+        );
+        let system_verifying_key = account_info(
+            Pubkey::new_unique(),
+            false,
+            merkle_tree_program::ID,
+            Vec::new(),
+            false,
+        );
+        let send = |tx: &mut AppTransaction<'_, 0, TestConfig>, program: &AccountInfo<'static>| {
+            tx.send_transaction(
+                &ctx,
+                program,
+                verifier_state,
+                &[],
+                system_verifying_key,
+                &proof(),
+                &[0u8; 32],
+            )
+        };
+
+        // the proof has to be verified first
+        let mut tx = app_transaction(proof(), &[]);
+        assert_eq!(
+            send(&mut tx, system_verifier_program),
+            err!(VerifierSdkError::ProofNotVerified)
+        );
+        assert!(!tx.invoked_system_verifier);
+
+        // only the registered verifier can be invoked
+        tx.verified_proof = true;
+        assert_eq!(
+            send(&mut tx, other_verifier_program),
+            err!(VerifierSdkError::InvalidSystemVerifier)
+        );
+        assert!(!tx.invoked_system_verifier);
+
+        // the system verifier expects sol and spl accounts
+        assert_eq!(
+            send(&mut tx, system_verifier_program),
+            err!(VerifierSdkError::MissingAccount)
+        );
+        assert!(!tx.invoked_system_verifier);
+        assert_eq!(
+            tx.check_completion(),
+            err!(VerifierSdkError::AppTransactionIncomplete)
+        );
+    }
+
+    #[test]
+    fn test_transact() {
+        let system_verifier = Pubkey::new_unique();
+        let mut accounts = test_accounts(system_verifier);
+        let ctx = Context {
+            program_id: &TestConfig::ID,
+            accounts: &mut accounts,
+            remaining_accounts: &[],
+            bumps: BTreeMap::new(),
+        };
+        let other_verifier_program = account_info(
+            Pubkey::new_unique(),
+            false,
+            Pubkey::default(),
+            Vec::new(),
+            true,
+        );
+        let verifier_state = account_info(
+            Pubkey::new_unique(),
+            false,
+            TestConfig::ID,
+            Vec::new(),
+            false,
+        );
+        let system_verifying_key = account_info(
+            Pubkey::new_unique(),
+            false,
+            merkle_tree_program::ID,
+            Vec::new(),
+            false,
+        );
+
+        // fails before the system verifier is invoked, either because the proof
+        // does not verify or because the system verifier is not registered
+        let mut tx = app_transaction(proof(), &[]);
+        assert!(tx
+            .transact(
+                &ctx,
+                other_verifier_program,
+                verifier_state,
+                &[],
+                system_verifying_key,
+                &proof(),
+                &[0u8; 32],
+            )
+            .is_err());
+        assert!(!tx.invoked_system_verifier);
+        assert!(tx.check_completion().is_err());
+    }
 }
@@ -2,12 +2,10 @@ use anchor_lang::{
     prelude::*,
     solana_program::{
         hash::{hash, hashv},
-        msg,
-        program_pack::Pack,
-        sysvar,
+        msg, sysvar,
     },
 };
-use anchor_spl::token::Transfer;
+use anchor_spl::{token_2022::spl_token_2022, token_interface::TransferChecked};
 use ark_ff::{
     bytes::{FromBytes, ToBytes},
     BigInteger, BigInteger256, Fp256, FpParameters, PrimeField,
@@ -22,23 +20,30 @@ use light_merkle_tree::HashFunction;
 use crate::{
     accounts::LightAccounts,
     cpi_instructions::{
-        insert_nullifiers_cpi, insert_two_leaves_cpi, insert_two_leaves_event_cpi,
-        invoke_indexer_transaction_event, withdraw_sol_cpi, withdraw_spl_cpi,
+        insert_nullifiers_cpi, insert_nullifiers_into_queue_cpi, insert_two_leaves_cpi,
+        insert_two_leaves_event_cpi, invoke_indexer_transaction_event, withdraw_sol_cpi,
+        withdraw_spl_cpi,
     },
     errors::VerifierSdkError,
-    state::TransactionIndexerEvent,
+    state::{TransactionIndexerEvent, TransactionIndexerEventV1, TransactionKind},
     utils::{change_endianness, close_account::close_account},
 };
 
 use std::ops::Neg;
 
 use merkle_tree_program::{
+    get_inverse_transfer_fee, get_mint_decimals,
+    nullifier_tree::indexed_tree::NullifierTree,
     program::MerkleTreeProgram,
     state::TransactionMerkleTree,
+    unpack_token_account,
     utils::{
-        constants::{POOL_CONFIG_SEED, POOL_SEED, TRANSACTION_MERKLE_TREE_SEED},
+        constants::{
+            NULLIFIER_TREE_SEED, POOL_CONFIG_SEED, POOL_SEED, TRANSACTION_MERKLE_TREE_SEED,
+        },
         create_pda::create_and_check_pda,
     },
+    MerkleTreeAuthority, RegisteredAssetPool,
 };
 pub const VERIFIER_STATE_SEED: &[u8] = b"VERIFIER_STATE";
 type G1 = ark_ec::short_weierstrass_jacobian::GroupAffine<ark_bn254::g1::Parameters>;
@@ -64,6 +69,7 @@ pub struct Transaction<
     pub input: TransactionInput<'a, 'b, 'c, 'info, NR_CHECKED_INPUTS, NR_LEAVES, NR_NULLIFIERS, A>,
     // State of transaction.
     pub merkle_root: [u8; 32],
+    pub nullifier_tree_root: [u8; 32],
     pub event_hash: [u8; 32],
     pub tx_integrity_hash: [u8; 32],
     pub mint_pubkey: [u8; 32],
@@ -88,6 +94,27 @@ impl<'a> Message<'a> {
     }
 }
 
+/// Splits the encrypted utxos of a transaction into the encrypted utxo of each output.
+/// Every output carries exactly `encrypted_utxo_len` bytes.
+pub fn split_encrypted_utxos(
+    encrypted_utxos: &[u8],
+    nr_outputs: usize,
+    encrypted_utxo_len: usize,
+) -> Result<Vec<Vec<u8>>> {
+    if encrypted_utxos.len() != nr_outputs * encrypted_utxo_len {
+        msg!(
+            "Encrypted utxos length {} != {} outputs * {} bytes",
+            encrypted_utxos.len(),
+            nr_outputs,
+            encrypted_utxo_len
+        );
+        return err!(VerifierSdkError::InvalidEncryptedUtxosLength);
+    }
+    Ok((0..nr_outputs)
+        .map(|i| encrypted_utxos[i * encrypted_utxo_len..(i + 1) * encrypted_utxo_len].to_vec())
+        .collect())
+}
+
 pub struct Proof {
     pub a: [u8; 64],
     pub b: [u8; 128],
@@ -99,6 +126,19 @@ pub struct Amounts {
     pub sol: [u8; 32],
 }
 
+/// Defines how nullifiers are stored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NullifierMode {
+    /// One pda per nullifier, the nullifier pdas are the first remaining accounts.
+    Pda,
+    /// Nullifiers are queued for insertion into the indexed nullifier tree. The proof
+    /// shows their non-inclusion in the nullifier tree root at `root_index`, it is an
+    /// additional public input after the checked public inputs.
+    /// The nullifier queue, the nullifier tree and the nullifier pdas are the first
+    /// remaining accounts, nullifiers spent before the nullifier tree was enabled have a pda.
+    IndexedTree { root_index: usize },
+}
+
 #[derive(Clone)]
 pub struct TransactionInput<
     'a,
@@ -117,11 +157,15 @@ pub struct TransactionInput<
     pub checked_public_inputs: &'a [[u8; 32]; NR_CHECKED_INPUTS],
     pub nullifiers: &'a [[u8; 32]; NR_NULLIFIERS],
     pub leaves: &'a [[[u8; 32]; 2]; NR_LEAVES],
+    /// Encrypted utxos of all outputs, `encrypted_utxo_len` bytes per output.
     pub encrypted_utxos: &'a Vec<u8>,
+    /// Length of the encrypted utxo of one output, a parameter of the verifier.
+    pub encrypted_utxo_len: usize,
     pub relayer_fee: u64,
     pub merkle_root_index: usize,
     pub pool_type: &'a [u8; 32],
     pub verifyingkey: &'a Groth16Verifyingkey<'a>,
+    pub nullifier_mode: NullifierMode,
 }
 
 impl<
@@ -153,6 +197,7 @@ impl<
         Transaction {
             input,
             merkle_root: [0u8; 32],
+            nullifier_tree_root: [0u8; 32],
             event_hash: [0u8; 32],
             tx_integrity_hash: [0u8; 32],
             mint_pubkey: [0u8; 32],
@@ -169,6 +214,12 @@ impl<
     /// Transact is a wrapper function which computes the integrity hash, checks the root,
     /// verifies the zero knowledge proof, inserts leaves, inserts nullifiers, transfers funds and fees.
     pub fn transact(&mut self) -> Result<()> {
+        self.check_not_paused()?;
+        split_encrypted_utxos(
+            self.input.encrypted_utxos,
+            NR_LEAVES * 2,
+            self.input.encrypted_utxo_len,
+        )?;
         self.compute_event_hash();
         self.insert_event_leaves()?;
         self.compute_tx_integrity_hash()?;
@@ -188,8 +239,10 @@ impl<
         // Initialize the vector of leaves
         let mut leaves_vec: Vec<[u8; 32]> = Vec::new();
 
-        let merkle_tree = self.input.ctx.accounts.get_transaction_merkle_tree();
-        let merkle_tree = merkle_tree.load_mut()?;
+        let transaction_merkle_tree = self.leaves_merkle_tree()?;
+        let merkle_tree_loader: AccountLoader<TransactionMerkleTree> =
+            AccountLoader::try_from(&transaction_merkle_tree)?;
+        let merkle_tree = merkle_tree_loader.load()?;
 
         let mut first_leaf_index = merkle_tree.next_queued_index;
 
@@ -205,26 +258,37 @@ impl<
             Some(message) => message.content.clone(),
             None => Vec::<u8>::new(),
         };
-        let transaction_data_event = TransactionIndexerEvent {
+        let transaction_data_event = TransactionIndexerEvent::V1(TransactionIndexerEventV1 {
+            transaction_merkle_tree: transaction_merkle_tree.key(),
+            event_merkle_tree_index: self
+                .input
+                .ctx
+                .accounts
+                .get_event_merkle_tree()
+                .load()?
+                .merkle_tree_nr,
+            sequence_number: merkle_tree.transaction_sequence_number,
+            verifier: *self.input.ctx.program_id,
+            kind: self.transaction_kind()?,
             leaves: leaves_vec.clone(),
             public_amount_sol: self.input.public_amount.sol.clone(),
             public_amount_spl: self.input.public_amount.spl.clone(),
             relayer_fee: self.input.relayer_fee.clone(),
-            encrypted_utxos: self.input.encrypted_utxos.clone(),
+            encrypted_utxos: split_encrypted_utxos(
+                self.input.encrypted_utxos,
+                NR_LEAVES * 2,
+                self.input.encrypted_utxo_len,
+            )?,
             nullifiers: self.input.nullifiers.to_vec(),
             first_leaf_index: first_leaf_index.clone(),
             message,
-        };
+        });
+        drop(merkle_tree);
 
         invoke_indexer_transaction_event(
             &transaction_data_event,
             &self.input.ctx.accounts.get_log_wrapper().to_account_info(),
-            &self
-                .input
-                .ctx
-                .accounts
-                .get_transaction_merkle_tree()
-                .to_account_info(),
+            &transaction_merkle_tree,
         )?;
 
         Ok(())
@@ -244,9 +308,13 @@ impl<
             msg!("Tried to verify proof without fetching root.");
         }
 
+        let nr_nullifier_tree_inputs = match self.input.nullifier_mode {
+            NullifierMode::Pda => 0,
+            NullifierMode::IndexedTree { .. } => 1,
+        };
         assert_eq!(
             NR_PUBLIC_INPUTS,
-            5 + NR_NULLIFIERS + NR_LEAVES * 2 + NR_CHECKED_INPUTS,
+            5 + NR_NULLIFIERS + NR_LEAVES * 2 + NR_CHECKED_INPUTS + nr_nullifier_tree_inputs,
         );
 
         let mut public_inputs: [[u8; 32]; NR_PUBLIC_INPUTS] = [[0u8; 32]; NR_PUBLIC_INPUTS];
@@ -270,6 +338,11 @@ impl<
             public_inputs[5 + NR_NULLIFIERS + NR_LEAVES * 2 + i] = *input;
         }
 
+        if let NullifierMode::IndexedTree { .. } = self.input.nullifier_mode {
+            public_inputs[5 + NR_NULLIFIERS + NR_LEAVES * 2 + NR_CHECKED_INPUTS] =
+                self.nullifier_tree_root;
+        }
+
         let proof_a_neg_g1: G1 = <G1 as FromBytes>::read(
             &*[&change_endianness(&self.input.proof.a)[..], &[0u8][..]].concat(),
         )
@@ -299,6 +372,7 @@ impl<
             Err(e) => {
                 msg!("Public Inputs:");
                 msg!("merkle tree root {:?}", self.merkle_root);
+                msg!("nullifier tree root {:?}", self.nullifier_tree_root);
                 msg!("public_amount_spl {:?}", self.input.public_amount.spl);
                 msg!("tx_integrity_hash {:?}", self.tx_integrity_hash);
                 msg!("public_amount_sol {:?}", self.input.public_amount.sol);
@@ -468,16 +542,49 @@ impl<
         let merkle_tree = self.input.ctx.accounts.get_transaction_merkle_tree();
         let merkle_tree = merkle_tree.load()?;
         self.merkle_root = change_endianness(&merkle_tree.roots[self.input.merkle_root_index]);
+
+        if let NullifierMode::IndexedTree { root_index } = self.input.nullifier_mode {
+            let nullifier_tree: AccountLoader<NullifierTree> =
+                AccountLoader::try_from(&self.input.ctx.remaining_accounts[1])?;
+            // Non-inclusion in any other tree does not show that a nullifier is unspent.
+            let (pubkey, _) =
+                Pubkey::find_program_address(&[NULLIFIER_TREE_SEED], &MerkleTreeProgram::id());
+            if nullifier_tree.key() != pubkey {
+                msg!(
+                    "Nullifier tree address is invalid, expected: {}, got: {}",
+                    pubkey,
+                    nullifier_tree.key()
+                );
+                return err!(VerifierSdkError::InvalidNullifierTree);
+            }
+            let nullifier_tree = nullifier_tree.load()?;
+            match nullifier_tree.roots.get(root_index) {
+                Some(root) => self.nullifier_tree_root = change_endianness(root),
+                None => {
+                    msg!("Nullifier tree root index {} is out of bounds", root_index);
+                    return err!(VerifierSdkError::InvalidMerkleTreeRoot);
+                }
+            }
+        }
         self.fetched_root = true;
         Ok(())
     }
 
+    /// Number of remaining accounts which precede the leaves accounts.
+    fn nr_nullifier_accounts(&self) -> usize {
+        match self.input.nullifier_mode {
+            NullifierMode::Pda => NR_NULLIFIERS,
+            // Nullifier queue, nullifier tree and nullifier pdas.
+            NullifierMode::IndexedTree { .. } => 2 + NR_NULLIFIERS,
+        }
+    }
+
     /// Fetches the token mint from passed in sender_spl account. If the sender_spl account is not a
     /// token account, native mint is assumed.
     pub fn fetch_mint(&mut self) -> Result<()> {
         match &self.input.ctx.accounts.get_sender_spl() {
             Some(sender_spl) => {
-                match spl_token::state::Account::unpack(sender_spl.data.borrow().as_ref()) {
+                match unpack_token_account(sender_spl.data.borrow().as_ref()) {
                     Ok(sender_mint) => {
                         // Omits the last byte for the mint pubkey bytes to fit into the bn254 field.
                         // msg!(
@@ -515,10 +622,10 @@ impl<
     }
 
     fn check_inputs(&self) -> Result<()> {
-        let nr_nullifiers_leaves = NR_NULLIFIERS + NR_LEAVES;
+        let nr_nullifiers_leaves = self.nr_nullifier_accounts() + 1;
         let remaining_accounts_len = self.input.ctx.remaining_accounts.len();
-        if remaining_accounts_len != nr_nullifiers_leaves // Only nullifiers and leaves.
-            // Nullifiers, leaves and next transaction Merkle tree.
+        if remaining_accounts_len != nr_nullifiers_leaves // Only nullifiers and leaves queue.
+            // Nullifiers, leaves queue and next transaction Merkle tree.
             && remaining_accounts_len != nr_nullifiers_leaves + 1
         {
             msg!(
@@ -540,30 +647,23 @@ impl<
             return err!(VerifierSdkError::ProofNotVerified);
         }
 
-        let transaction_merkle_tree_ix = NR_NULLIFIERS + self.input.leaves.len();
-        let transaction_merkle_tree =
-            if self.input.ctx.remaining_accounts.len() == transaction_merkle_tree_ix + 1 {
-                let transaction_merkle_tree =
-                    self.input.ctx.remaining_accounts[transaction_merkle_tree_ix].to_account_info();
-                self.validate_transaction_merkle_tree(&transaction_merkle_tree)?;
-                transaction_merkle_tree
-            } else {
-                self.input
-                    .ctx
-                    .accounts
-                    .get_transaction_merkle_tree()
-                    .to_account_info()
-            };
-
-        // check merkle tree
-        for (i, leaves) in self.input.leaves.iter().enumerate() {
-            let mut msg = Vec::new();
+        // The leaves queue of the transaction Merkle tree follows the nullifier accounts.
+        let leaves_queue_ix = self.nr_nullifier_accounts();
+        let transaction_merkle_tree = self.leaves_merkle_tree()?;
 
-            if self.input.encrypted_utxos.len() > i * 256 {
-                msg.append(&mut self.input.encrypted_utxos[i * 256..(i + 1) * 256].to_vec());
-            }
+        // Only the newest transaction Merkle tree accepts new leaves, clients pass it in
+        // as the last remaining account.
+        let merkle_tree_loader: AccountLoader<TransactionMerkleTree> =
+            AccountLoader::try_from(&transaction_merkle_tree)?;
+        if merkle_tree_loader.load()?.newest != 1 {
+            msg!(
+                "Transaction Merkle tree {} is read-only, expected the newest transaction Merkle tree.",
+                merkle_tree_loader.load()?.merkle_tree_nr
+            );
+            return err!(VerifierSdkError::TransactionMerkleTreeNotNewest);
+        }
 
-            // check account integrities
+        for (i, leaves) in self.input.leaves.iter().enumerate() {
             insert_two_leaves_cpi(
                 &self.input.ctx.program_id,
                 &self
@@ -573,7 +673,7 @@ impl<
                     .get_program_merkle_tree()
                     .to_account_info(),
                 &self.input.ctx.accounts.get_authority().to_account_info(),
-                &self.input.ctx.remaining_accounts[NR_NULLIFIERS + i].to_account_info(),
+                &self.input.ctx.remaining_accounts[leaves_queue_ix].to_account_info(),
                 &transaction_merkle_tree,
                 &self
                     .input
@@ -587,9 +687,15 @@ impl<
                     .accounts
                     .get_registered_verifier_pda()
                     .to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_merkle_tree_authority_pda()
+                    .to_account_info(),
                 change_endianness(&leaves[0]).try_into().unwrap(),
                 change_endianness(&leaves[1]).try_into().unwrap(),
-                msg,
+                i == 0,
             )?;
         }
 
@@ -597,6 +703,26 @@ impl<
         Ok(())
     }
 
+    /// Transaction Merkle tree the leaves are inserted into, the newest transaction
+    /// Merkle tree passed in as the last remaining account. Without it the leaves
+    /// go into the transaction Merkle tree of the proof which fails once it rolled over.
+    fn leaves_merkle_tree(&self) -> Result<AccountInfo<'info>> {
+        let transaction_merkle_tree_ix = self.nr_nullifier_accounts() + 1;
+        if self.input.ctx.remaining_accounts.len() == transaction_merkle_tree_ix + 1 {
+            let transaction_merkle_tree =
+                self.input.ctx.remaining_accounts[transaction_merkle_tree_ix].to_account_info();
+            self.validate_transaction_merkle_tree(&transaction_merkle_tree)?;
+            Ok(transaction_merkle_tree)
+        } else {
+            Ok(self
+                .input
+                .ctx
+                .accounts
+                .get_transaction_merkle_tree()
+                .to_account_info())
+        }
+    }
+
     fn validate_transaction_merkle_tree(
         &self,
         transaction_merkle_tree: &AccountInfo,
@@ -626,31 +752,65 @@ impl<
             return err!(VerifierSdkError::ProofNotVerified);
         }
 
-        insert_nullifiers_cpi(
-            &self.input.ctx.program_id,
-            &self
-                .input
-                .ctx
-                .accounts
-                .get_program_merkle_tree()
-                .to_account_info(),
-            &self.input.ctx.accounts.get_authority().to_account_info(),
-            &self
-                .input
-                .ctx
-                .accounts
-                .get_system_program()
-                .to_account_info()
-                .clone(),
-            &self
-                .input
-                .ctx
-                .accounts
-                .get_registered_verifier_pda()
-                .to_account_info(),
-            self.input.nullifiers.to_vec(),
-            self.input.ctx.remaining_accounts.to_vec(),
-        )?;
+        match self.input.nullifier_mode {
+            NullifierMode::Pda => insert_nullifiers_cpi(
+                &self.input.ctx.program_id,
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_program_merkle_tree()
+                    .to_account_info(),
+                &self.input.ctx.accounts.get_authority().to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_system_program()
+                    .to_account_info()
+                    .clone(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_registered_verifier_pda()
+                    .to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_merkle_tree_authority_pda()
+                    .to_account_info(),
+                self.input.nullifiers.to_vec(),
+                self.input.ctx.remaining_accounts[..NR_NULLIFIERS].to_vec(),
+            )?,
+            NullifierMode::IndexedTree { .. } => insert_nullifiers_into_queue_cpi(
+                &self.input.ctx.program_id,
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_program_merkle_tree()
+                    .to_account_info(),
+                &self.input.ctx.accounts.get_authority().to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_registered_verifier_pda()
+                    .to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_merkle_tree_authority_pda()
+                    .to_account_info(),
+                &self.input.ctx.remaining_accounts[0].to_account_info(),
+                &self.input.ctx.remaining_accounts[1].to_account_info(),
+                self.input.nullifiers.to_vec(),
+                self.input.ctx.remaining_accounts[2..2 + NR_NULLIFIERS].to_vec(),
+            )?,
+        }
 
         self.inserted_nullifier = true;
         Ok(())
@@ -674,7 +834,7 @@ impl<
 
         // Only transfer if pub amount is greater than zero otherwise recipient_spl and sender_spl accounts are not checked
         if pub_amount_checked > 0 {
-            let recipient_mint = spl_token::state::Account::unpack(
+            let recipient_mint = unpack_token_account(
                 &self
                     .input
                     .ctx
@@ -685,7 +845,7 @@ impl<
                     .data
                     .borrow(),
             )?;
-            let sender_mint = spl_token::state::Account::unpack(
+            let sender_mint = unpack_token_account(
                 &self
                     .input
                     .ctx
@@ -720,6 +880,7 @@ impl<
                         .key(),
                     &recipient_mint.mint,
                 )?;
+                self.check_spl_deposit_cap(&recipient_mint, pub_amount_checked)?;
 
                 let seed = merkle_tree_program::ID.to_bytes();
                 let (_, bump) = anchor_lang::prelude::Pubkey::find_program_address(
@@ -729,7 +890,20 @@ impl<
                 let bump = &[bump];
                 let seeds = &[&[seed.as_slice(), bump][..]];
 
-                let accounts = Transfer {
+                let mint = self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_mint_spl()
+                    .unwrap()
+                    .to_account_info();
+                if mint.key() != recipient_mint.mint {
+                    return err!(VerifierSdkError::InconsistentMintProofSenderOrRecipient);
+                }
+                // Token-2022 transfer fees are paid on top such that the pool receives the
+                // shielded amount.
+                let transfer_fee = get_inverse_transfer_fee(&mint, pub_amount_checked)?;
+                let accounts = TransferChecked {
                     from: self
                         .input
                         .ctx
@@ -739,6 +913,7 @@ impl<
                         .unwrap()
                         .to_account_info()
                         .clone(),
+                    mint: mint.clone(),
                     to: self
                         .input
                         .ctx
@@ -768,7 +943,34 @@ impl<
                     accounts,
                     seeds,
                 );
-                anchor_spl::token::transfer(cpi_ctx, pub_amount_checked)?;
+                anchor_spl::token_interface::transfer_checked(
+                    cpi_ctx,
+                    pub_amount_checked
+                        .checked_add(transfer_fee)
+                        .ok_or(VerifierSdkError::WrongPubAmount)?,
+                    get_mint_decimals(&mint)?,
+                )?;
+
+                let pool_balance = unpack_token_account(
+                    &self
+                        .input
+                        .ctx
+                        .accounts
+                        .get_recipient_spl()
+                        .as_ref()
+                        .unwrap()
+                        .data
+                        .borrow(),
+                )?
+                .amount;
+                if pool_balance != recipient_mint.amount + pub_amount_checked {
+                    msg!(
+                        "Pool received {} instead of the shielded amount {}.",
+                        pool_balance.saturating_sub(recipient_mint.amount),
+                        pub_amount_checked
+                    );
+                    return err!(VerifierSdkError::InvalidTransferFee);
+                }
             } else {
                 self.check_spl_pool_account_derivation(
                     &self
@@ -824,12 +1026,34 @@ impl<
                         .as_ref()
                         .unwrap()
                         .to_account_info(),
+                    &self
+                        .input
+                        .ctx
+                        .accounts
+                        .get_mint_spl()
+                        .as_ref()
+                        .unwrap()
+                        .to_account_info(),
                     &self
                         .input
                         .ctx
                         .accounts
                         .get_registered_verifier_pda()
                         .to_account_info(),
+                    &self
+                        .input
+                        .ctx
+                        .accounts
+                        .get_merkle_tree_authority_pda()
+                        .to_account_info(),
+                    &self
+                        .input
+                        .ctx
+                        .accounts
+                        .get_registered_asset_pool_spl()
+                        .as_ref()
+                        .unwrap()
+                        .to_account_info(),
                     pub_amount_checked,
                 )?;
             }
@@ -923,6 +1147,12 @@ impl<
                         .accounts
                         .get_registered_verifier_pda()
                         .to_account_info(),
+                    &self
+                        .input
+                        .ctx
+                        .accounts
+                        .get_merkle_tree_authority_pda()
+                        .to_account_info(),
                     fee_amount_checked,
                 )?;
                 msg!("withdrew sol for the user");
@@ -960,6 +1190,12 @@ impl<
                     .accounts
                     .get_registered_verifier_pda()
                     .to_account_info(),
+                &self
+                    .input
+                    .ctx
+                    .accounts
+                    .get_merkle_tree_authority_pda()
+                    .to_account_info(),
                 relayer_fee,
             )?;
         }
@@ -974,6 +1210,14 @@ impl<
             &*recipient_spl.data.try_borrow().unwrap(),
         )?;
         // TODO: add check that recipient_spl account is initialized
+        let asset_pool =
+            RegisteredAssetPool::try_deserialize(&mut &recipient_spl.data.borrow()[..])?;
+        asset_pool.check_deposit(
+            recipient_spl
+                .lamports()
+                .checked_add(amount_checked)
+                .ok_or(VerifierSdkError::WrongPubAmount)?,
+        )?;
 
         msg!("is deposit");
         let rent = <Rent as sysvar::Sysvar>::get()?;
@@ -1040,6 +1284,58 @@ impl<
         false
     }
 
+    /// Fails if any transaction kind (shield, unshield or transfer) is paused, the Merkle
+    /// tree program rejects leaves and nullifiers of every kind then. The error names the
+    /// kind of this transaction if it is paused itself.
+    pub fn check_not_paused(&self) -> Result<()> {
+        let merkle_tree_authority = self.input.ctx.accounts.get_merkle_tree_authority_pda();
+        match self.transaction_kind()? {
+            TransactionKind::Shield
+                if merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_SHIELD) =>
+            {
+                err!(VerifierSdkError::ShieldsPaused)
+            }
+            TransactionKind::Unshield
+                if merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_UNSHIELD) =>
+            {
+                err!(VerifierSdkError::UnshieldsPaused)
+            }
+            TransactionKind::Transfer
+                if merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_TRANSFER) =>
+            {
+                err!(VerifierSdkError::TransfersPaused)
+            }
+            _ if merkle_tree_authority.is_paused(MerkleTreeAuthority::PAUSE_ALL) => {
+                err!(VerifierSdkError::ProtocolPaused)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Derives the kind of this transaction from its public amounts.
+    pub fn transaction_kind(&self) -> Result<TransactionKind> {
+        if self.is_deposit() || self.is_deposit_fee() {
+            return Ok(TransactionKind::Shield);
+        }
+        let (spl_amount, _) = self.check_amount(
+            0,
+            change_endianness(&self.input.public_amount.spl)
+                .try_into()
+                .unwrap(),
+        )?;
+        let (sol_amount, _) = self.check_amount(
+            self.input.relayer_fee,
+            change_endianness(&self.input.public_amount.sol)
+                .try_into()
+                .unwrap(),
+        )?;
+        if spl_amount > 0 || sol_amount > 0 {
+            Ok(TransactionKind::Unshield)
+        } else {
+            Ok(TransactionKind::Transfer)
+        }
+    }
+
     pub fn check_sol_pool_account_derivation(&self, pubkey: &Pubkey, data: &[u8]) -> Result<()> {
         let derived_pubkey = Pubkey::find_program_address(
             &[&[0u8; 32], self.input.pool_type, POOL_CONFIG_SEED],
@@ -1054,6 +1350,40 @@ impl<
         Ok(())
     }
 
+    /// Checks that the balance of the spl pool after the deposit does not exceed its deposit cap.
+    pub fn check_spl_deposit_cap(
+        &self,
+        pool_token_account: &spl_token_2022::state::Account,
+        amount: u64,
+    ) -> Result<()> {
+        let asset_pool_info = self
+            .input
+            .ctx
+            .accounts
+            .get_registered_asset_pool_spl()
+            .unwrap()
+            .to_account_info();
+        let derived_pubkey = Pubkey::find_program_address(
+            &[
+                &pool_token_account.mint.to_bytes(),
+                self.input.pool_type,
+                POOL_CONFIG_SEED,
+            ],
+            &MerkleTreeProgram::id(),
+        );
+        if derived_pubkey.0 != asset_pool_info.key() {
+            return err!(VerifierSdkError::InvalidRegisteredAssetPool);
+        }
+        let asset_pool =
+            RegisteredAssetPool::try_deserialize(&mut &asset_pool_info.data.borrow()[..])?;
+        asset_pool.check_deposit(
+            pool_token_account
+                .amount
+                .checked_add(amount)
+                .ok_or(VerifierSdkError::WrongPubAmount)?,
+        )
+    }
+
     pub fn check_spl_pool_account_derivation(&self, pubkey: &Pubkey, mint: &Pubkey) -> Result<()> {
         let derived_pubkey = Pubkey::find_program_address(
             &[&mint.to_bytes(), self.input.pool_type, POOL_SEED],
@@ -1130,3 +1460,33 @@ impl<
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_encrypted_utxos() {
+        let encrypted_utxos: Vec<u8> = (0..240).map(|i| i as u8).collect();
+        let split = split_encrypted_utxos(&encrypted_utxos, 2, 120).unwrap();
+        assert_eq!(
+            split,
+            vec![
+                encrypted_utxos[..120].to_vec(),
+                encrypted_utxos[120..].to_vec()
+            ]
+        );
+        assert_eq!(
+            split_encrypted_utxos(&encrypted_utxos, 4, 120),
+            err!(VerifierSdkError::InvalidEncryptedUtxosLength)
+        );
+        assert_eq!(
+            split_encrypted_utxos(&encrypted_utxos[..200], 2, 120),
+            err!(VerifierSdkError::InvalidEncryptedUtxosLength)
+        );
+        assert_eq!(
+            split_encrypted_utxos(&[], 2, 0).unwrap(),
+            vec![Vec::<u8>::new(); 2]
+        );
+    }
+}
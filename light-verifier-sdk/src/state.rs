@@ -2,8 +2,13 @@ use crate::light_transaction::Config;
 use anchor_lang::prelude::*;
 use std::marker::PhantomData;
 
+pub use light_indexer_events::{
+    TransactionIndexerEvent, TransactionIndexerEventV1, TransactionKind,
+};
+
 /// Verifier state is a boiler plate struct which should be versatile enough to serve many use cases.
-/// For specialized use cases with less
+/// For specialized use cases `#[light_verifier_state]` generates a verifier state with the same
+/// common fields.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
 pub struct VerifierState10Ins<const NR_CHECKED_INPUTS: usize, T: Config> {
     pub signer: Pubkey,
@@ -27,14 +32,51 @@ pub struct VerifierState10Ins<const NR_CHECKED_INPUTS: usize, T: Config> {
 
 impl<const NR_CHECKED_INPUTS: usize, T: Config> VerifierState10Ins<NR_CHECKED_INPUTS, T> {
     pub const LEN: usize = 2048;
+
+    /// Deserializes the common fields of a verifier state owned by another program.
+    /// Verifier states start with a discriminator followed by the fields of this struct,
+    /// app specific fields of states generated with `#[light_verifier_state]` are ignored.
+    pub fn deserialize_common_fields(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound);
+        }
+        match VerifierState10Ins::deserialize(&mut &data[8..]) {
+            Ok(v) => Ok(v),
+            Err(_) => err!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize),
+        }
+    }
+}
+
+impl<const NR_CHECKED_INPUTS: usize, T: Config> anchor_lang::Discriminator
+    for VerifierState10Ins<NR_CHECKED_INPUTS, T>
+{
+    /// sha256("account:VerifierState10Ins")[..8]
+    const DISCRIMINATOR: [u8; 8] = [123, 235, 229, 80, 83, 134, 126, 106];
 }
 
 impl<const NR_CHECKED_INPUTS: usize, T: Config> anchor_lang::AccountDeserialize
     for VerifierState10Ins<NR_CHECKED_INPUTS, T>
 {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() < 8 {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound);
+        }
+        if buf[..8] != <Self as anchor_lang::Discriminator>::DISCRIMINATOR {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch);
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
-        match VerifierState10Ins::deserialize(buf) {
-            Ok(v) => Ok(v),
+        if buf.len() < 8 {
+            return err!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize);
+        }
+        let mut data: &[u8] = &buf[8..];
+        match VerifierState10Ins::deserialize(&mut data) {
+            Ok(v) => {
+                *buf = data;
+                Ok(v)
+            }
             Err(_) => err!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize),
         }
     }
@@ -44,7 +86,12 @@ impl<const NR_CHECKED_INPUTS: usize, T: Config> anchor_lang::AccountSerialize
     for VerifierState10Ins<NR_CHECKED_INPUTS, T>
 {
     fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
-        self.serialize(writer).unwrap();
+        if writer
+            .write_all(&<Self as anchor_lang::Discriminator>::DISCRIMINATOR)
+            .is_err()
+        {
+            return err!(anchor_lang::error::ErrorCode::AccountDidNotSerialize);
+        }
         match self.serialize(writer) {
             Ok(_) => Ok(()),
             Err(_) => err!(anchor_lang::error::ErrorCode::AccountDidNotSerialize),
@@ -60,14 +107,56 @@ impl<const NR_CHECKED_INPUTS: usize, T: Config> anchor_lang::Owner
     }
 }
 
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
-pub struct TransactionIndexerEvent {
-    pub leaves: Vec<[u8; 32]>,
-    pub public_amount_spl: [u8; 32],
-    pub public_amount_sol: [u8; 32],
-    pub relayer_fee: u64,
-    pub encrypted_utxos: Vec<u8>,
-    pub nullifiers: Vec<[u8; 32]>,
-    pub first_leaf_index: u64,
-    pub message: Vec<u8>,
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::{AccountDeserialize, AccountSerialize};
+
+    #[derive(Clone, Debug)]
+    struct TestConfig;
+    impl Config for TestConfig {
+        const ID: Pubkey = Pubkey::new_from_array([1u8; 32]);
+    }
+
+    #[test]
+    fn test_try_deserialize_unchecked() {
+        let state = VerifierState10Ins::<0, TestConfig> {
+            signer: Pubkey::new_unique(),
+            nullifiers: vec![[1u8; 32]; 2],
+            leaves: vec![[2u8; 32]; 2],
+            public_amount_spl: [3u8; 32],
+            public_amount_sol: [4u8; 32],
+            mint_pubkey: [5u8; 32],
+            merkle_root: [6u8; 32],
+            tx_integrity_hash: [7u8; 32],
+            relayer_fee: 8,
+            encrypted_utxos: vec![9u8; 240],
+            merkle_root_index: 10,
+            checked_public_inputs: [],
+            proof_a: [11u8; 64],
+            proof_b: [12u8; 128],
+            proof_c: [13u8; 64],
+            transaction_hash: [14u8; 32],
+            e_phantom: PhantomData,
+        };
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        let len = data.len();
+        data.extend_from_slice(&[0u8; 16]);
+
+        let mut buf = data.as_slice();
+        let deserialized = VerifierState10Ins::<0, TestConfig>::try_deserialize(&mut buf).unwrap();
+        assert_eq!(deserialized.signer, state.signer);
+        assert_eq!(deserialized.encrypted_utxos, state.encrypted_utxos);
+        assert_eq!(deserialized.transaction_hash, state.transaction_hash);
+        // the buffer is advanced past the state
+        assert_eq!(buf.len(), data.len() - len);
+
+        for data in [&data[..0], &data[..7], &data[..len - 1]] {
+            let mut buf = data;
+            assert!(
+                VerifierState10Ins::<0, TestConfig>::try_deserialize_unchecked(&mut buf).is_err()
+            );
+        }
+    }
 }
@@ -1,4 +1,5 @@
 pub mod close_account;
+pub mod verifying_key;
 
 const CHUNK_SIZE: usize = 32;
 
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use groth16_solana::groth16::Groth16Verifyingkey;
+use merkle_tree_program::VerifyingKeyAccount;
+
+use crate::errors::VerifierSdkError;
+
+/// Loads the verifying key of `verifier` for transactions with `nr_inputs` input and
+/// `nr_outputs` output utxos from a verifying key account registered with the
+/// merkle tree program. Fails if the key belongs to another verifier or number of utxos,
+/// is incomplete, still timelocked or deactivated.
+pub fn load_verifying_key<'a>(
+    verifying_key_account: &'a VerifyingKeyAccount,
+    verifier: &Pubkey,
+    nr_inputs: usize,
+    nr_outputs: usize,
+    slot: u64,
+) -> Result<Groth16Verifyingkey<'a>> {
+    if verifying_key_account.verifier != *verifier {
+        msg!(
+            "Verifying key belongs to verifier {}.",
+            verifying_key_account.verifier
+        );
+        return err!(VerifierSdkError::InvalidVerifyingKeyAccount);
+    }
+    if verifying_key_account.nr_inputs as usize != nr_inputs
+        || verifying_key_account.nr_outputs as usize != nr_outputs
+    {
+        msg!(
+            "Verifying key is for {} input and {} output utxos, expected {} and {}.",
+            verifying_key_account.nr_inputs,
+            verifying_key_account.nr_outputs,
+            nr_inputs,
+            nr_outputs
+        );
+        return err!(VerifierSdkError::InvalidVerifyingKeyAccount);
+    }
+    if !verifying_key_account.is_active(slot) {
+        msg!(
+            "Verifying key version {} is not active.",
+            verifying_key_account.version
+        );
+        return err!(VerifierSdkError::VerifyingKeyNotActive);
+    }
+    Ok(Groth16Verifyingkey {
+        nr_pubinputs: verifying_key_account.nr_pubinputs as usize,
+        vk_alpha_g1: verifying_key_account.vk_alpha_g1,
+        vk_beta_g2: verifying_key_account.vk_beta_g2,
+        vk_gamme_g2: verifying_key_account.vk_gamme_g2,
+        vk_delta_g2: verifying_key_account.vk_delta_g2,
+        vk_ic: &verifying_key_account.vk_ic,
+    })
+}
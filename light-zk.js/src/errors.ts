@@ -139,7 +139,8 @@ export enum TransactionParametersErrorCode {
   EVENT_MERKLE_TREE_UNDEFINED = "EVENT_MERKLE_TREE_UNDEFINED",
   MESSAGE_UNDEFINED = "MESSAGE_UNDEFINED",
   PROGRAM_ID_CONSTANT_UNDEFINED = "PROGRAM_ID_CONSTANT_UNDEFINED",
-  ENCRYPTED_UTXOS_TOO_LONG = "ENCRYPTED_UTXOS_TOO_LONG",
+  INVALID_ENCRYPTED_UTXOS_LENGTH = "INVALID_ENCRYPTED_UTXOS_LENGTH",
+  ENCRYPTED_UTXO_LEN_CONSTANT_UNDEFINED = "ENCRYPTED_UTXO_LEN_CONSTANT_UNDEFINED",
 }
 
 export enum TransactionErrorCode {
@@ -3,29 +3,149 @@ export type MerkleTreeProgram = {
   "name": "merkle_tree_program",
   "constants": [
     {
-      "name": "ENCRYPTED_UTXOS_LENGTH",
+      "name": "SUPPORTED_MINT_EXTENSIONS",
+      "type": {
+        "array": [
+          {
+            "defined": "ExtensionType"
+          },
+          3
+        ]
+      },
+      "value": "[ExtensionType :: TransferFeeConfig , ExtensionType :: MintCloseAuthority , ExtensionType :: InterestBearingConfig]"
+    },
+    {
+      "name": "LEGACY_TRANSACTION_MERKLE_TREE_SIZE",
       "type": {
         "defined": "usize"
       },
-      "value": "174"
+      "value": "8880"
     },
     {
       "name": "MERKLE_TREE_TMP_PDA_SIZE",
       "type": {
         "defined": "usize"
       },
-      "value": "2048"
+      "value": "2304"
     },
     {
       "name": "MERKLE_TREE_HISTORY_SIZE",
       "type": "u64",
       "value": "256"
     },
+    {
+      "name": "MERKLE_TREE_CHANGELOG_SIZE",
+      "type": "u64",
+      "value": "16"
+    },
+    {
+      "name": "LEAVES_QUEUE_SIZE",
+      "type": "u64",
+      "value": "128"
+    },
     {
       "name": "MERKLE_TREE_HEIGHT",
       "type": "u64",
       "value": "18"
     },
+    {
+      "name": "MERKLE_TREE_MIN_HEIGHT",
+      "type": "u64",
+      "value": "16"
+    },
+    {
+      "name": "MERKLE_TREE_MAX_HEIGHT",
+      "type": "u64",
+      "value": "26"
+    },
+    {
+      "name": "MERKLE_TREE_ROLLOVER_THRESHOLD",
+      "type": "u64",
+      "value": "95"
+    },
+    {
+      "name": "NULLIFIER_TREE_HEIGHT",
+      "type": "u64",
+      "value": "26"
+    },
+    {
+      "name": "NULLIFIER_TREE_HISTORY_SIZE",
+      "type": "u64",
+      "value": "32"
+    },
+    {
+      "name": "NULLIFIER_QUEUE_SIZE",
+      "type": "u64",
+      "value": "128"
+    },
+    {
+      "name": "VERIFYING_KEY_TIMELOCK",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "MAX_MULTISIG_SIGNERS",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "10"
+    },
+    {
+      "name": "MAX_VERIFIER_ALLOWED_POOL_TYPES",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "4"
+    },
+    {
+      "name": "MAX_VERIFIER_ALLOWED_MERKLE_TREES",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "4"
+    },
+    {
+      "name": "MAX_VERIFYING_KEY_PUBLIC_INPUTS",
+      "type": "u64",
+      "value": "64"
+    },
+    {
+      "name": "DEFAULT_WITHDRAWAL_WINDOW",
+      "type": "u64",
+      "value": "9_000"
+    },
+    {
+      "name": "DELAYED_WITHDRAWAL_TIMELOCK",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "MAX_DELAYED_WITHDRAWALS",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "16"
+    },
+    {
+      "name": "UPDATE_STATE_TAKEOVER_REWARD",
+      "type": "u64",
+      "value": "20"
+    },
+    {
+      "name": "RELAYER_STAKE_PENALTY",
+      "type": "u64",
+      "value": "10"
+    },
+    {
+      "name": "RELAYER_UNSTAKE_DELAY",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "DEFAULT_LEAF_FEE",
+      "type": "u64",
+      "value": "5_000"
+    },
     {
       "name": "INITIAL_MERKLE_TREE_AUTHORITY",
       "type": {
@@ -37,7 +157,7 @@ export type MerkleTreeProgram = {
       "value": "[2 , 99 , 226 , 251 , 88 , 66 , 92 , 33 , 25 , 216 , 211 , 185 , 112 , 203 , 212 , 238 , 105 , 144 , 72 , 121 , 176 , 253 , 106 , 168 , 115 , 158 , 154 , 188 , 62 , 255 , 166 , 81]"
     },
     {
-      "name": "ZERO_BYTES_MERKLE_TREE_18",
+      "name": "ZERO_BYTES_MERKLE_TREE",
       "type": {
         "array": [
           {
@@ -46,20 +166,10 @@ export type MerkleTreeProgram = {
               32
             ]
           },
-          19
-        ]
-      },
-      "value": "[[40 , 66 , 58 , 227 , 48 , 224 , 249 , 227 , 188 , 18 , 133 , 168 , 156 , 214 , 220 , 144 , 244 , 144 , 67 , 82 , 76 , 6 , 135 , 78 , 64 , 186 , 52 , 113 , 234 , 47 , 27 , 32] , [227 , 42 , 164 , 149 , 188 , 70 , 170 , 8 , 197 , 44 , 134 , 162 , 211 , 186 , 50 , 238 , 97 , 71 , 25 , 130 , 77 , 70 , 37 , 128 , 172 , 154 , 54 , 111 , 93 , 193 , 105 , 27] , [25 , 241 , 255 , 33 , 65 , 214 , 48 , 229 , 38 , 116 , 134 , 103 , 44 , 146 , 163 , 214 , 31 , 238 , 148 , 206 , 34 , 137 , 144 , 221 , 184 , 11 , 5 , 213 , 10 , 188 , 143 , 18] , [211 , 61 , 251 , 33 , 128 , 34 , 4 , 100 , 229 , 47 , 99 , 121 , 109 , 204 , 224 , 90 , 200 , 149 , 219 , 20 , 48 , 206 , 210 , 177 , 161 , 66 , 44 , 10 , 169 , 56 , 248 , 8] , [200 , 15 , 65 , 80 , 151 , 74 , 72 , 69 , 229 , 131 , 25 , 215 , 86 , 36 , 195 , 74 , 67 , 59 , 117 , 179 , 51 , 60 , 181 , 13 , 242 , 192 , 228 , 228 , 189 , 238 , 70 , 8] , [171 , 62 , 122 , 81 , 181 , 197 , 22 , 238 , 224 , 40 , 154 , 231 , 127 , 202 , 201 , 169 , 196 , 109 , 244 , 175 , 117 , 101 , 23 , 67 , 103 , 57 , 127 , 200 , 37 , 43 , 111 , 7] , [59 , 78 , 126 , 104 , 199 , 143 , 213 , 10 , 2 , 158 , 64 , 78 , 153 , 25 , 107 , 190 , 32 , 122 , 123 , 211 , 116 , 179 , 175 , 172 , 70 , 54 , 175 , 59 , 201 , 120 , 64 , 44] , [110 , 91 , 92 , 81 , 205 , 89 , 122 , 223 , 55 , 163 , 42 , 227 , 109 , 54 , 38 , 22 , 110 , 217 , 29 , 148 , 107 , 99 , 128 , 106 , 146 , 47 , 239 , 41 , 55 , 157 , 155 , 22] , [18 , 231 , 42 , 5 , 245 , 159 , 211 , 227 , 239 , 89 , 35 , 142 , 223 , 69 , 166 , 224 , 14 , 114 , 128 , 14 , 123 , 123 , 215 , 2 , 241 , 185 , 191 , 60 , 252 , 61 , 146 , 12] , [231 , 0 , 84 , 227 , 127 , 64 , 158 , 7 , 171 , 179 , 137 , 231 , 92 , 87 , 25 , 221 , 156 , 229 , 53 , 208 , 194 , 201 , 12 , 165 , 105 , 150 , 41 , 142 , 29 , 205 , 136 , 29] , [195 , 2 , 103 , 231 , 62 , 207 , 214 , 105 , 214 , 210 , 108 , 23 , 28 , 151 , 77 , 100 , 78 , 194 , 210 , 29 , 227 , 14 , 17 , 242 , 211 , 50 , 33 , 194 , 106 , 18 , 246 , 45] , [131 , 178 , 24 , 157 , 251 , 247 , 103 , 69 , 101 , 229 , 194 , 14 , 167 , 57 , 158 , 128 , 212 , 19 , 140 , 234 , 69 , 37 , 10 , 156 , 249 , 96 , 152 , 52 , 97 , 96 , 119 , 41] , [30 , 223 , 20 , 181 , 108 , 110 , 112 , 102 , 234 , 54 , 99 , 29 , 213 , 3 , 55 , 225 , 125 , 185 , 223 , 234 , 188 , 108 , 83 , 89 , 27 , 3 , 100 , 6 , 65 , 107 , 3 , 24] , [167 , 32 , 85 , 233 , 205 , 253 , 154 , 214 , 236 , 82 , 147 , 75 , 252 , 144 , 109 , 73 , 63 , 167 , 77 , 233 , 12 , 201 , 150 , 242 , 103 , 15 , 158 , 83 , 137 , 24 , 170 , 16] , [45 , 98 , 238 , 69 , 136 , 141 , 101 , 226 , 94 , 209 , 58 , 215 , 212 , 14 , 210 , 135 , 110 , 96 , 52 , 16 , 101 , 177 , 121 , 109 , 134 , 81 , 189 , 146 , 113 , 243 , 97 , 42] , [71 , 51 , 251 , 48 , 95 , 193 , 94 , 26 , 180 , 17 , 124 , 203 , 48 , 98 , 55 , 17 , 60 , 104 , 186 , 175 , 213 , 189 , 7 , 239 , 92 , 175 , 16 , 5 , 220 , 168 , 70 , 21] , [35 , 92 , 72 , 197 , 23 , 142 , 16 , 200 , 136 , 38 , 44 , 255 , 162 , 115 , 11 , 1 , 248 , 182 , 236 , 78 , 90 , 24 , 128 , 245 , 168 , 17 , 130 , 2 , 73 , 51 , 196 , 6] , [89 , 178 , 154 , 246 , 236 , 130 , 30 , 100 , 27 , 230 , 24 , 196 , 8 , 172 , 176 , 196 , 197 , 13 , 157 , 194 , 169 , 106 , 207 , 70 , 66 , 117 , 69 , 53 , 56 , 154 , 78 , 0] , [231 , 174 , 226 , 37 , 211 , 160 , 187 , 178 , 149 , 82 , 17 , 60 , 110 , 116 , 28 , 61 , 58 , 145 , 58 , 71 , 25 , 42 , 67 , 46 , 189 , 214 , 248 , 234 , 182 , 251 , 238 , 34]]"
-    },
-    {
-      "name": "IX_ORDER",
-      "type": {
-        "array": [
-          "u8",
-          57
+          27
         ]
       },
-      "value": "[34 , 14 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 241]"
+      "value": "[[40 , 66 , 58 , 227 , 48 , 224 , 249 , 227 , 188 , 18 , 133 , 168 , 156 , 214 , 220 , 144 , 244 , 144 , 67 , 82 , 76 , 6 , 135 , 78 , 64 , 186 , 52 , 113 , 234 , 47 , 27 , 32] , [227 , 42 , 164 , 149 , 188 , 70 , 170 , 8 , 197 , 44 , 134 , 162 , 211 , 186 , 50 , 238 , 97 , 71 , 25 , 130 , 77 , 70 , 37 , 128 , 172 , 154 , 54 , 111 , 93 , 193 , 105 , 27] , [25 , 241 , 255 , 33 , 65 , 214 , 48 , 229 , 38 , 116 , 134 , 103 , 44 , 146 , 163 , 214 , 31 , 238 , 148 , 206 , 34 , 137 , 144 , 221 , 184 , 11 , 5 , 213 , 10 , 188 , 143 , 18] , [211 , 61 , 251 , 33 , 128 , 34 , 4 , 100 , 229 , 47 , 99 , 121 , 109 , 204 , 224 , 90 , 200 , 149 , 219 , 20 , 48 , 206 , 210 , 177 , 161 , 66 , 44 , 10 , 169 , 56 , 248 , 8] , [200 , 15 , 65 , 80 , 151 , 74 , 72 , 69 , 229 , 131 , 25 , 215 , 86 , 36 , 195 , 74 , 67 , 59 , 117 , 179 , 51 , 60 , 181 , 13 , 242 , 192 , 228 , 228 , 189 , 238 , 70 , 8] , [171 , 62 , 122 , 81 , 181 , 197 , 22 , 238 , 224 , 40 , 154 , 231 , 127 , 202 , 201 , 169 , 196 , 109 , 244 , 175 , 117 , 101 , 23 , 67 , 103 , 57 , 127 , 200 , 37 , 43 , 111 , 7] , [59 , 78 , 126 , 104 , 199 , 143 , 213 , 10 , 2 , 158 , 64 , 78 , 153 , 25 , 107 , 190 , 32 , 122 , 123 , 211 , 116 , 179 , 175 , 172 , 70 , 54 , 175 , 59 , 201 , 120 , 64 , 44] , [110 , 91 , 92 , 81 , 205 , 89 , 122 , 223 , 55 , 163 , 42 , 227 , 109 , 54 , 38 , 22 , 110 , 217 , 29 , 148 , 107 , 99 , 128 , 106 , 146 , 47 , 239 , 41 , 55 , 157 , 155 , 22] , [18 , 231 , 42 , 5 , 245 , 159 , 211 , 227 , 239 , 89 , 35 , 142 , 223 , 69 , 166 , 224 , 14 , 114 , 128 , 14 , 123 , 123 , 215 , 2 , 241 , 185 , 191 , 60 , 252 , 61 , 146 , 12] , [231 , 0 , 84 , 227 , 127 , 64 , 158 , 7 , 171 , 179 , 137 , 231 , 92 , 87 , 25 , 221 , 156 , 229 , 53 , 208 , 194 , 201 , 12 , 165 , 105 , 150 , 41 , 142 , 29 , 205 , 136 , 29] , [195 , 2 , 103 , 231 , 62 , 207 , 214 , 105 , 214 , 210 , 108 , 23 , 28 , 151 , 77 , 100 , 78 , 194 , 210 , 29 , 227 , 14 , 17 , 242 , 211 , 50 , 33 , 194 , 106 , 18 , 246 , 45] , [131 , 178 , 24 , 157 , 251 , 247 , 103 , 69 , 101 , 229 , 194 , 14 , 167 , 57 , 158 , 128 , 212 , 19 , 140 , 234 , 69 , 37 , 10 , 156 , 249 , 96 , 152 , 52 , 97 , 96 , 119 , 41] , [30 , 223 , 20 , 181 , 108 , 110 , 112 , 102 , 234 , 54 , 99 , 29 , 213 , 3 , 55 , 225 , 125 , 185 , 223 , 234 , 188 , 108 , 83 , 89 , 27 , 3 , 100 , 6 , 65 , 107 , 3 , 24] , [167 , 32 , 85 , 233 , 205 , 253 , 154 , 214 , 236 , 82 , 147 , 75 , 252 , 144 , 109 , 73 , 63 , 167 , 77 , 233 , 12 , 201 , 150 , 242 , 103 , 15 , 158 , 83 , 137 , 24 , 170 , 16] , [45 , 98 , 238 , 69 , 136 , 141 , 101 , 226 , 94 , 209 , 58 , 215 , 212 , 14 , 210 , 135 , 110 , 96 , 52 , 16 , 101 , 177 , 121 , 109 , 134 , 81 , 189 , 146 , 113 , 243 , 97 , 42] , [71 , 51 , 251 , 48 , 95 , 193 , 94 , 26 , 180 , 17 , 124 , 203 , 48 , 98 , 55 , 17 , 60 , 104 , 186 , 175 , 213 , 189 , 7 , 239 , 92 , 175 , 16 , 5 , 220 , 168 , 70 , 21] , [35 , 92 , 72 , 197 , 23 , 142 , 16 , 200 , 136 , 38 , 44 , 255 , 162 , 115 , 11 , 1 , 248 , 182 , 236 , 78 , 90 , 24 , 128 , 245 , 168 , 17 , 130 , 2 , 73 , 51 , 196 , 6] , [89 , 178 , 154 , 246 , 236 , 130 , 30 , 100 , 27 , 230 , 24 , 196 , 8 , 172 , 176 , 196 , 197 , 13 , 157 , 194 , 169 , 106 , 207 , 70 , 66 , 117 , 69 , 53 , 56 , 154 , 78 , 0] , [231 , 174 , 226 , 37 , 211 , 160 , 187 , 178 , 149 , 82 , 17 , 60 , 110 , 116 , 28 , 61 , 58 , 145 , 58 , 71 , 25 , 42 , 67 , 46 , 189 , 214 , 248 , 234 , 182 , 251 , 238 , 34] , [154 , 31 , 139 , 147 , 198 , 116 , 198 , 21 , 178 , 226 , 37 , 28 , 188 , 65 , 80 , 186 , 104 , 217 , 80 , 136 , 219 , 185 , 137 , 112 , 222 , 230 , 85 , 166 , 242 , 213 , 78 , 21] , [110 , 143 , 173 , 69 , 35 , 46 , 186 , 59 , 181 , 249 , 248 , 202 , 81 , 141 , 108 , 105 , 34 , 44 , 109 , 82 , 106 , 111 , 226 , 5 , 74 , 57 , 243 , 224 , 28 , 83 , 90 , 9] , [76 , 147 , 78 , 218 , 174 , 113 , 73 , 205 , 158 , 246 , 173 , 237 , 116 , 169 , 81 , 221 , 246 , 29 , 244 , 137 , 63 , 121 , 192 , 123 , 107 , 185 , 66 , 134 , 97 , 83 , 101 , 43] , [194 , 76 , 54 , 227 , 200 , 20 , 5 , 189 , 30 , 137 , 16 , 50 , 99 , 252 , 59 , 94 , 91 , 147 , 43 , 95 , 141 , 5 , 188 , 200 , 229 , 207 , 48 , 41 , 164 , 126 , 197 , 23] , [153 , 114 , 73 , 246 , 25 , 237 , 228 , 134 , 41 , 171 , 246 , 134 , 69 , 229 , 115 , 24 , 160 , 72 , 104 , 113 , 255 , 161 , 176 , 16 , 221 , 193 , 139 , 240 , 55 , 210 , 134 , 19] , [232 , 96 , 167 , 26 , 45 , 54 , 61 , 216 , 22 , 255 , 57 , 109 , 251 , 171 , 52 , 39 , 167 , 112 , 1 , 100 , 203 , 164 , 15 , 14 , 229 , 67 , 118 , 16 , 81 , 45 , 22 , 25] , [159 , 195 , 41 , 229 , 0 , 143 , 186 , 215 , 216 , 108 , 12 , 46 , 212 , 70 , 87 , 43 , 215 , 170 , 178 , 113 , 116 , 149 , 237 , 74 , 154 , 135 , 23 , 237 , 87 , 14 , 191 , 6] , [244 , 145 , 68 , 51 , 72 , 253 , 131 , 90 , 86 , 87 , 156 , 15 , 55 , 205 , 118 , 157 , 35 , 1 , 7 , 111 , 190 , 73 , 142 , 7 , 170 , 199 , 227 , 136 , 79 , 228 , 64 , 7]]"
     },
     {
       "name": "MERKLE_TREE_UPDATE_START",
@@ -112,9 +222,14 @@ export type MerkleTreeProgram = {
       "value": "[115, 116, 111, 114, 97, 103, 101]"
     },
     {
-      "name": "LEAVES_SEED",
+      "name": "RELAYER_SEED",
+      "type": "bytes",
+      "value": "[114, 101, 108, 97, 121, 101, 114]"
+    },
+    {
+      "name": "LEAVES_QUEUE_SEED",
       "type": "bytes",
-      "value": "[108, 101, 97, 118, 101, 115]"
+      "value": "[108, 101, 97, 118, 101, 115, 95, 113, 117, 101, 117, 101]"
     },
     {
       "name": "NULLIFIER_SEED",
@@ -151,6 +266,46 @@ export type MerkleTreeProgram = {
       "type": "bytes",
       "value": "[116, 114, 97, 110, 115, 97, 99, 116, 105, 111, 110, 95, 109, 101, 114, 107, 108, 101, 95, 116, 114, 101, 101]"
     },
+    {
+      "name": "NULLIFIER_TREE_SEED",
+      "type": "bytes",
+      "value": "[110, 117, 108, 108, 105, 102, 105, 101, 114, 95, 116, 114, 101, 101]"
+    },
+    {
+      "name": "NULLIFIER_QUEUE_SEED",
+      "type": "bytes",
+      "value": "[110, 117, 108, 108, 105, 102, 105, 101, 114, 95, 113, 117, 101, 117, 101]"
+    },
+    {
+      "name": "METADATA_SEED",
+      "type": "bytes",
+      "value": "[109, 101, 116, 97, 100, 97, 116, 97]"
+    },
+    {
+      "name": "VERIFYING_KEY_SEED",
+      "type": "bytes",
+      "value": "[118, 101, 114, 105, 102, 121, 105, 110, 103, 95, 107, 101, 121]"
+    },
+    {
+      "name": "NFT_POOL_TYPE",
+      "type": {
+        "array": [
+          "u8",
+          32
+        ]
+      },
+      "value": "[1 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0]"
+    },
+    {
+      "name": "TOKEN_METADATA_PROGRAM_ID",
+      "type": "publicKey",
+      "value": "pubkey ! (\"metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s\")"
+    },
+    {
+      "name": "NOOP_PROGRAM_ID",
+      "type": "publicKey",
+      "value": "pubkey ! (\"noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV\")"
+    },
     {
       "name": "EVENT_MERKLE_TREE_HEIGHT",
       "type": {
@@ -164,7 +319,13 @@ export type MerkleTreeProgram = {
       "name": "initializeNewTransactionMerkleTree",
       "docs": [
         "Initializes a new Merkle tree from config bytes.",
-        "Can only be called from the merkle_tree_authority."
+        "Can only be called from the merkle_tree_authority, or by anyone once the",
+        "newest Merkle tree reached the rollover threshold or if permissionless Merkle",
+        "tree registration is enabled. A tree created by anyone else than the authority",
+        "has to use the height and lock duration of the old Merkle tree.",
+        "The height has to be within MERKLE_TREE_MIN_HEIGHT and MERKLE_TREE_MAX_HEIGHT.",
+        "The old Merkle tree is the first remaining account, multisig signers of the",
+        "authority follow it."
       ],
       "accounts": [
         {
@@ -177,6 +338,11 @@ export type MerkleTreeProgram = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -197,11 +363,59 @@ export type MerkleTreeProgram = {
         {
           "name": "lockDuration",
           "type": "u64"
+        },
+        {
+          "name": "height",
+          "type": "u64"
         }
       ]
     },
+    {
+      "name": "migrateTransactionMerkleTree",
+      "docs": [
+        "Migrates a transaction Merkle tree created before the account grew from 8880",
+        "to 9424 bytes, these do not deserialize anymore. Reallocates the account, the",
+        "authority pays the additional rent, and creates its leaves queue.",
+        "Can only be called from the merkle_tree_authority once all queued leaves of the",
+        "Merkle tree are inserted, multisig signers of the authority follow as remaining",
+        "accounts."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
       "name": "initializeNewEventMerkleTree",
+      "docs": [
+        "Initializes a new event Merkle tree.",
+        "Can only be called from the merkle_tree_authority, or by anyone if permissionless",
+        "Merkle tree registration is enabled."
+      ],
       "accounts": [
         {
           "name": "authority",
@@ -226,6 +440,76 @@ export type MerkleTreeProgram = {
       ],
       "args": []
     },
+    {
+      "name": "initializeNewNullifierTree",
+      "docs": [
+        "Initializes the nullifier tree and its nullifier queue.",
+        "Can only be called from the merkle_tree_authority, permissionless Merkle tree",
+        "registration does not apply to the single nullifier tree."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nullifierQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "enableNullifierTree",
+      "docs": [
+        "Switches nullifier storage from nullifier pdas to the nullifier tree, can not be undone.",
+        "Verifiers which store nullifiers in pdas fail afterwards, the authority enables the",
+        "nullifier tree once these are migrated or deregistered."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The nullifier tree has to be initialized before nullifiers can be queued."
+          ]
+        }
+      ],
+      "args": []
+    },
     {
       "name": "initializeMerkleTreeAuthority",
       "docs": [
@@ -243,6 +527,11 @@ export type MerkleTreeProgram = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "authority",
           "isMut": true,
@@ -262,9 +551,10 @@ export type MerkleTreeProgram = {
       "args": []
     },
     {
-      "name": "updateMerkleTreeAuthority",
+      "name": "proposeMerkleTreeAuthority",
       "docs": [
-        "Updates the merkle tree authority to a new authority."
+        "Proposes a new merkle tree authority, the new authority has to accept the",
+        "transfer with accept_merkle_tree_authority."
       ],
       "accounts": [
         {
@@ -286,9 +576,9 @@ export type MerkleTreeProgram = {
       "args": []
     },
     {
-      "name": "updateLockDuration",
+      "name": "acceptMerkleTreeAuthority",
       "docs": [
-        "Updates the lock duration for a specific merkle tree."
+        "Transfers the merkle tree authority to the pending authority."
       ],
       "accounts": [
         {
@@ -297,27 +587,38 @@ export type MerkleTreeProgram = {
           "isSigner": false
         },
         {
-          "name": "authority",
+          "name": "newAuthority",
           "isMut": false,
           "isSigner": true
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelMerkleTreeAuthorityTransfer",
+      "docs": [
+        "Cancels a proposed merkle tree authority transfer."
+      ],
+      "accounts": [
         {
-          "name": "transactionMerkleTree",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "lockDuration",
-          "type": "u64"
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "enableNfts",
+      "name": "configureMultisig",
       "docs": [
-        "Enables permissionless deposits of any spl token with supply of one and zero decimals."
+        "Configures an M-of-N multisig, once configured authority instructions need the",
+        "signatures of threshold multisig signers passed in as remaining accounts.",
+        "A threshold of zero disables the multisig."
       ],
       "accounts": [
         {
@@ -333,15 +634,22 @@ export type MerkleTreeProgram = {
       ],
       "args": [
         {
-          "name": "enablePermissionless",
-          "type": "bool"
+          "name": "signers",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "enablePermissionlessSplTokens",
+      "name": "setGuardian",
       "docs": [
-        "Enables anyone to create token pools."
+        "Sets the guardian which can pause but not unpause transactions.",
+        "The default pubkey removes the guardian."
       ],
       "accounts": [
         {
@@ -357,220 +665,169 @@ export type MerkleTreeProgram = {
       ],
       "args": [
         {
-          "name": "enablePermissionless",
-          "type": "bool"
+          "name": "guardian",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "registerVerifier",
+      "name": "pause",
       "docs": [
-        "Registers a new verifier which can withdraw tokens, insert new nullifiers, add new leaves.",
-        "These functions can only be invoked from registered verifiers."
+        "Pauses shields, unshields and or transfers, flags are defined on MerkleTreeAuthority.",
+        "Can be called by the merkle_tree_authority or the guardian.",
+        "Leaves and nullifiers are rejected if any kind is paused, the merkle tree program can",
+        "not tell the kind of a transaction. Only withdrawals distinguish kinds, unshields",
+        "pause withdrawals and claims, transfers pause withdrawals of relayer fees."
       ],
       "accounts": [
         {
-          "name": "registeredVerifierPda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "merkleTreeAuthorityPda",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "verifierPubkey",
-          "type": "publicKey"
+          "name": "flags",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "registerPoolType",
+      "name": "unpause",
       "docs": [
-        "Registers a new pooltype."
+        "Unpauses shields, unshields and or transfers.",
+        "Can only be called by the merkle_tree_authority."
       ],
       "accounts": [
         {
-          "name": "registeredPoolTypePda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "merkleTreeAuthorityPda",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "poolType",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
+          "name": "flags",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "registerSplPool",
+      "name": "updateLockDuration",
       "docs": [
-        "Creates a new spl token pool which can be used by any registered verifier."
+        "Updates the lock duration for a specific merkle tree."
       ],
       "accounts": [
         {
-          "name": "registeredAssetPoolPda",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "merkleTreePdaToken",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "mint",
+          "name": "transactionMerkleTree",
           "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "tokenAuthority",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "lockDuration",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateLeafFee",
+      "docs": [
+        "Updates the fee paid into the fee vault of a Merkle tree for every inserted pair of leaves."
+      ],
+      "accounts": [
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "registeredPoolTypePda",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Just needs to exist and be derived correctly."
-          ]
+          "isSigner": true
         },
         {
-          "name": "merkleTreeAuthorityPda",
+          "name": "transactionMerkleTree",
           "isMut": true,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "leafFee",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "registerSolPool",
+      "name": "updateRelayerGating",
       "docs": [
-        "Creates a new sol pool which can be used by any registered verifier."
+        "Restricts updates of a Merkle tree to registered relayers which are whitelisted or",
+        "staked at least min_relayer_stake lamports."
       ],
       "accounts": [
         {
-          "name": "registeredAssetPoolPda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "systemProgram",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "rent",
-          "isMut": false,
+          "name": "transactionMerkleTree",
+          "isMut": true,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "registeredPoolTypePda",
-          "isMut": false,
-          "isSigner": false
+          "name": "gatedRelayers",
+          "type": "bool"
         },
         {
-          "name": "merkleTreeAuthorityPda",
-          "isMut": true,
-          "isSigner": false
+          "name": "minRelayerStake",
+          "type": "u64"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "initializeMerkleTreeUpdateState",
+      "name": "registerRelayer",
       "docs": [
-        "Initializes a merkle tree update state pda. This pda stores the leaves to be inserted",
-        "and state of the computation of poseidon hashes to update the Merkle tree.",
-        "A maximum of 16 pairs of leaves can be passed in as leaves accounts as remaining accounts.",
-        "Every leaf is copied into this account such that no further accounts or data have to be",
-        "passed in during the following instructions which compute the poseidon hashes to update the tree.",
-        "The hashes are computed with the update merkle tree instruction and the new root is inserted",
-        "with the insert root merkle tree instruction."
+        "Registers the signer as relayer, registered relayers can be whitelisted or stake."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "relayer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "merkleTreeUpdateState",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "transactionMerkleTree",
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         },
@@ -578,76 +835,55 @@ export type MerkleTreeProgram = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": []
     },
     {
-      "name": "updateTransactionMerkleTree",
+      "name": "stakeRelayer",
       "docs": [
-        "Computes poseidon hashes to update the Merkle tree."
+        "Adds lamports to the stake of a relayer, the stake is locked for RELAYER_UNSTAKE_DELAY slots."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "relayer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "merkleTreeUpdateState",
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "transactionMerkleTree",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "bump",
+          "name": "amount",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "insertRootMerkleTree",
+      "name": "unstakeRelayer",
       "docs": [
-        "This is the last step of a Merkle tree update which inserts the prior computed Merkle tree",
-        "root."
+        "Withdraws unlocked stake of a relayer."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "relayer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "merkleTreeUpdateState",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "Merkle tree is locked by merkle_tree_update_state",
-            "Is in correct instruction for root insert thus Merkle Tree update has been completed.",
-            "The account is closed to the authority at the end of the instruction."
-          ]
-        },
-        {
-          "name": "transactionMerkleTree",
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "logWrapper",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -656,107 +892,160 @@ export type MerkleTreeProgram = {
       ],
       "args": [
         {
-          "name": "bump",
+          "name": "amount",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "closeMerkleTreeUpdateState",
+      "name": "whitelistRelayer",
       "docs": [
-        "Closes the Merkle tree update state.",
-        "A relayer can only close its own update state account."
+        "Whitelists a registered relayer, whitelisted relayers do not need to stake."
       ],
       "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "authority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "merkleTreeUpdateState",
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "whitelisted",
+          "type": "bool"
+        }
+      ]
     },
     {
-      "name": "insertTwoLeaves",
+      "name": "enableNfts",
       "docs": [
-        "Creates and initializes a pda which stores two merkle tree leaves and encrypted Utxos.",
-        "The inserted leaves are not part of the Merkle tree yet and marked accordingly.",
-        "The Merkle tree has to be updated after.",
-        "Can only be called from a registered verifier program."
+        "Enables anyone to create nft pools for spl tokens with supply of one and zero decimals."
       ],
       "accounts": [
         {
-          "name": "authority",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "twoLeavesPda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "transactionMerkleTree",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "enablePermissionlessSplTokens",
+      "docs": [
+        "Enables anyone to create token pools."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "authority",
           "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "enablePermissionlessMerkleTreeRegistration",
+      "docs": [
+        "Enables anyone to create new transaction and event Merkle trees, new transaction",
+        "Merkle trees use the config of the old Merkle tree. The nullifier tree is always",
+        "created by the authority."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "registeredVerifierPda",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "leafLeft",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "enablePermissionlessVerifierRegistration",
+      "docs": [
+        "Enables anyone to register new verifiers."
+      ],
+      "accounts": [
         {
-          "name": "leafRight",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "encryptedUtxo",
-          "type": {
-            "array": [
-              "u8",
-              256
-            ]
-          }
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "insertTwoLeavesEvent",
+      "name": "registerVerifier",
+      "docs": [
+        "Registers a new verifier which can withdraw tokens, insert new nullifiers, add new leaves.",
+        "These functions can only be invoked from registered verifiers.",
+        "Verifiers registered by the authority get all capabilities, permissionless registrations",
+        "get none until the authority sets them with set verifier capabilities."
+      ],
       "accounts": [
+        {
+          "name": "registeredVerifierPda",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "authority",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "eventMerkleTree",
-          "isMut": true,
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
           "isSigner": false
         },
         {
@@ -765,426 +1054,2191 @@ export type MerkleTreeProgram = {
           "isSigner": false
         },
         {
-          "name": "registeredVerifier",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "leafLeft",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
-        },
-        {
-          "name": "leafRight",
-          "type": {
-            "array": [
-              "u8",
-              32
-            ]
-          }
+          "name": "verifierPubkey",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "withdrawSol",
+      "name": "pauseVerifier",
       "docs": [
-        "Withdraws sol from a liquidity pool.",
-        "An arbitrary number of recipients can be passed in with remaining accounts.",
-        "Can only be called from a registered verifier program."
+        "Pauses a registered verifier, paused verifiers cannot invoke any verifier invoked",
+        "instruction until they are resumed."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "registeredVerifierPda",
           "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "merkleTreeToken",
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "resumeVerifier",
+      "docs": [
+        "Resumes a paused verifier."
+      ],
+      "accounts": [
+        {
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "deregisterVerifier",
+      "docs": [
+        "Permanently deregisters a verifier, a deregistered verifier cannot be resumed."
+      ],
+      "accounts": [
         {
           "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "recipient",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setVerifierCapabilities",
+      "docs": [
+        "Sets the capabilities of a registered verifier and restricts it to pool types and",
+        "Merkle trees. Empty lists allow any pool type or Merkle tree."
+      ],
+      "accounts": [
+        {
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "capabilities",
+          "type": "u8"
+        },
+        {
+          "name": "allowedPoolTypes",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        },
+        {
+          "name": "allowedMerkleTrees",
+          "type": {
+            "vec": "publicKey"
+          }
         }
       ]
     },
     {
-      "name": "withdrawSpl",
+      "name": "registerVerifyingKey",
       "docs": [
-        "Withdraws spl tokens from a liquidity pool.",
-        "An arbitrary number of recipients can be passed in with remaining accounts.",
-        "Can only be called from a registered verifier program."
+        "Registers a verifying key for transactions with `nr_inputs` input and `nr_outputs`",
+        "output utxos of a registered verifier. The vk_ic points are appended",
+        "with append_verifying_key_ic, the key becomes active VERIFYING_KEY_TIMELOCK slots",
+        "after the upload is complete.",
+        "Can only be called from the merkle_tree_authority."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "verifyingKeyPda",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "merkleTreeToken",
-          "isMut": true,
+          "name": "registeredVerifierPda",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "recipient",
+          "name": "authority",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "merkleTreeAuthorityPda",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAuthority",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "registeredVerifierPda",
+          "name": "rent",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
+          "name": "verifierPubkey",
+          "type": "publicKey"
+        },
+        {
+          "name": "nrInputs",
+          "type": "u8"
+        },
+        {
+          "name": "nrOutputs",
+          "type": "u8"
+        },
+        {
+          "name": "version",
+          "type": "u64"
+        },
+        {
+          "name": "nrPubinputs",
           "type": "u64"
+        },
+        {
+          "name": "vkAlphaG1",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        },
+        {
+          "name": "vkBetaG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
+        },
+        {
+          "name": "vkGammeG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
+        },
+        {
+          "name": "vkDeltaG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
         }
       ]
     },
     {
-      "name": "initializeNullifiers",
+      "name": "appendVerifyingKeyIc",
+      "docs": [
+        "Appends vk_ic points to an incomplete verifying key."
+      ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "verifyingKeyPda",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "registeredVerifierPda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "nullifiers",
+          "name": "vkIc",
           "type": {
             "vec": {
               "array": [
                 "u8",
-                32
+                64
               ]
             }
           }
         }
       ]
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "registeredAssetPool",
+      "name": "deactivateVerifyingKey",
       "docs": [
-        "Nullfier pdas are derived from the nullifier",
-        "existence of a nullifier is the check to prevent double spends."
+        "Deactivates a verifying key, for example if the circuit is compromised.",
+        "Deactivation takes effect immediately and cannot be reverted."
       ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "assetPoolPubkey",
-            "type": "publicKey"
-          },
-          {
-            "name": "poolType",
-            "type": {
-              "array": [
+      "accounts": [
+        {
+          "name": "verifyingKeyPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerPoolType",
+      "docs": [
+        "Registers a new pooltype."
+      ],
+      "accounts": [
+        {
+          "name": "registeredPoolTypePda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "poolType",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "registerSplPool",
+      "docs": [
+        "Creates a new spl token pool which can be used by any registered verifier.",
+        "Nft pools are created for mints with supply one and zero decimals, the metaplex",
+        "master edition of the mint has to be passed in as remaining account."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreePdaToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Spl token or Token-2022 mint, extensions are checked in instruction."
+          ]
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredPoolTypePda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Just needs to exist and be derived correctly."
+          ]
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerSolPool",
+      "docs": [
+        "Creates a new sol pool which can be used by any registered verifier."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredPoolTypePda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateAssetPoolLimits",
+      "docs": [
+        "Updates the deposit cap, withdrawal limit and minimum delayed withdrawal of an asset pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositCap",
+          "type": "u64"
+        },
+        {
+          "name": "withdrawalLimit",
+          "type": "u64"
+        },
+        {
+          "name": "withdrawalWindow",
+          "type": "u64"
+        },
+        {
+          "name": "minDelayedWithdrawal",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelDelayedWithdrawal",
+      "docs": [
+        "Cancels all delayed withdrawals of a recipient, the amounts remain in the pool.",
+        "Can only be called from the merkle_tree_authority."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
+    },
+    {
+      "name": "initializeMerkleTreeUpdateState",
+      "docs": [
+        "Initializes a merkle tree update state pda. This pda stores the leaves to be inserted",
+        "and state of the computation of poseidon hashes to update the Merkle tree.",
+        "Up to 16 pairs of leaves are read from the leaves queue, starting at `start_index` or",
+        "the next index of the Merkle tree. Leaves before `start_index` are inserted by other",
+        "relayers in parallel. Every leaf is copied into this account such that no further accounts or data have to be",
+        "passed in during the following instructions which compute the poseidon hashes to update the tree.",
+        "The hashes are computed with the update merkle tree instruction and the new root is inserted",
+        "with the insert root merkle tree instruction."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The stake of a registered relayer is locked, the authority has to be registered",
+            "if the Merkle tree is gated to registered relayers."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "startIndex",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "updateTransactionMerkleTree",
+      "docs": [
+        "Computes poseidon hashes to update the Merkle tree."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The update state is not derived from the authority since it can be taken over."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The Merkle tree is only read, updates of several relayers can be computed in parallel."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "insertRootMerkleTree",
+      "docs": [
+        "This is the last step of a Merkle tree update which inserts the prior computed Merkle tree",
+        "root.",
+        "The new root and the inserted leaves are logged as a RootUpdateEvent through the noop program."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Is in correct instruction for root insert thus Merkle Tree update has been completed.",
+            "The account is closed to the authority at the end of the instruction."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "batchUpdateTransactionMerkleTree",
+      "docs": [
+        "Inserts up to 16 pairs of leaves and the resulting root in one instruction.",
+        "Hashes are computed with the Poseidon syscall, if the runtime does not provide it",
+        "the multi-instruction update (initialize_merkle_tree_update_state,",
+        "update_transaction_merkle_tree, insert_root_merkle_tree) has to be used.",
+        "Leaves are read from the leaves queue of the Merkle tree, the new root is logged",
+        "through the noop program."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Receives the updater fee."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required if the Merkle tree is gated to registered relayers."
+          ]
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeMerkleTreeUpdateState",
+      "docs": [
+        "Closes the Merkle tree update state.",
+        "A relayer can close its own update state account, expired update states can be closed",
+        "by anyone for a share of the rent."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The relayer of the update state or anyone if the update state is expired."
+          ]
+        },
+        {
+          "name": "relayer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "penalizedRelayer",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Registration of the relayer, its stake is penalized if the update state is expired",
+            "and the Merkle tree is gated to registered relayers."
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "takeOverMerkleTreeUpdateState",
+      "docs": [
+        "Takes over an expired Merkle tree update state of another relayer.",
+        "The update continues from the saved instruction index."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The new relayer of the update state."
+          ]
+        },
+        {
+          "name": "relayer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required if the Merkle tree is gated to registered relayers."
+          ]
+        },
+        {
+          "name": "penalizedRelayer",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Registration of the previous relayer, its stake is penalized on gated Merkle trees."
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "insertTwoLeaves",
+      "docs": [
+        "Appends two merkle tree leaves to the leaves queue of the Merkle tree.",
+        "The inserted leaves are not part of the Merkle tree yet and marked accordingly.",
+        "The Merkle tree has to be updated after.",
+        "Can only be called from a registered verifier program.",
+        "`first_leaves` is set for the first pair of leaves of a transaction, it counts",
+        "the transaction in the sequence number of the Merkle tree."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "leafLeft",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "leafRight",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "firstLeaves",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "insertTwoLeavesEvent",
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "eventMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifier",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "leafLeft",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "leafRight",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        }
+      ]
+    },
+    {
+      "name": "withdrawSol",
+      "docs": [
+        "Withdraws sol from a liquidity pool.",
+        "An arbitrary number of recipients can be passed in with remaining accounts.",
+        "Can only be called from a registered verifier program.",
+        "Withdrawals over the withdrawal limit of the pool are delayed."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Withdrawals happen in unshields and relayer fee payments of transfers."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawSpl",
+      "docs": [
+        "Withdraws spl tokens from a liquidity pool.",
+        "An arbitrary number of recipients can be passed in with remaining accounts.",
+        "Can only be called from a registered verifier program.",
+        "Withdrawals over the withdrawal limit of the pool are delayed."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Withdrawals happen in unshields and relayer fee payments of transfers."
+          ]
+        },
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Tracks the withdrawal limit of the pool."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimDelayedSolWithdrawal",
+      "docs": [
+        "Pays out the released delayed sol withdrawals of a recipient.",
+        "Claims count against the withdrawal limit of the pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimDelayedSplWithdrawal",
+      "docs": [
+        "Pays out the released delayed spl withdrawals of a recipient token account.",
+        "Claims count against the withdrawal limit of the pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeNullifiers",
+      "docs": [
+        "Creates a pda for every nullifier, fails if a nullifier is spent already.",
+        "Can only be called from a registered verifier program, fails once the nullifier",
+        "tree is enabled."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused.",
+            "Nullifier pdas are rejected once the nullifier tree is enabled."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "insertNullifiersIntoQueue",
+      "docs": [
+        "Queues nullifiers for insertion into the nullifier tree, fails if a nullifier",
+        "is queued already or has a nullifier pda.",
+        "Can only be called from a registered verifier program."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "nullifierQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Non-inclusion is proven against the single nullifier tree."
+          ]
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "insertNullifierIntoTree",
+      "docs": [
+        "Inserts a queued nullifier into the nullifier tree. The low element is the element",
+        "which encloses the nullifier, it is proven against the current root with a Merkle proof.",
+        "Can be called by anyone, hashes are computed with the Poseidon syscall."
+      ],
+      "accounts": [
+        {
+          "name": "nullifierQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "queueIndex",
+          "type": "u64"
+        },
+        {
+          "name": "lowElement",
+          "type": {
+            "defined": "IndexedElement"
+          }
+        },
+        {
+          "name": "lowElementIndex",
+          "type": "u64"
+        },
+        {
+          "name": "lowElementProof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "registeredAssetPool",
+      "docs": [
+        "Config of a sol or spl token liquidity pool.",
+        "Deposits are capped by deposit_cap, withdrawals are rate limited to withdrawal_limit",
+        "per withdrawal_window slots. Withdrawals over the limit are queued in delayed_withdrawals",
+        "if they are at least min_delayed_withdrawal, claims of delayed withdrawals count against",
+        "the limit as well."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetPoolPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "poolType",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "depositCap",
+            "docs": [
+              "Maximum pool balance after a deposit, u64::MAX if uncapped."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalLimit",
+            "docs": [
+              "Maximum amount withdrawn per window, u64::MAX if unlimited."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalWindow",
+            "type": "u64"
+          },
+          {
+            "name": "windowStartSlot",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawnInWindow",
+            "type": "u64"
+          },
+          {
+            "name": "minDelayedWithdrawal",
+            "docs": [
+              "Minimum amount of a delayed withdrawal, smaller withdrawals over the limit fail",
+              "such that dust withdrawals cannot fill the queue."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "delayedWithdrawals",
+            "type": {
+              "vec": {
+                "defined": "DelayedWithdrawal"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "registeredPoolType",
+      "docs": [
+        "Pool type"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "poolType",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "eventMerkleTree",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "merkleTree",
+            "type": {
+              "defined": "MerkleTree"
+            }
+          },
+          {
+            "name": "merkleTreeNr",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nullifierTree",
+      "docs": [
+        "Indexed Merkle tree of nullifiers. Its elements form a linked list sorted",
+        "by value, a nullifier is not part of the tree if an element encloses it.",
+        "Nullifiers are big endian, nodes and roots little endian."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nullifierQueue",
+            "type": "publicKey"
+          },
+          {
+            "name": "filledSubtrees",
+            "docs": [
+              "Only the first `height` subtrees are used."
+            ],
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
+          },
+          {
+            "name": "roots",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "height",
+            "type": "u64"
+          },
+          {
+            "name": "currentRootIndex",
+            "type": "u64"
+          },
+          {
+            "name": "nextIndex",
+            "type": "u64"
+          },
+          {
+            "name": "sequenceNumber",
+            "docs": [
+              "Number of inserted nullifiers."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nullifierQueue",
+      "docs": [
+        "Nullifiers inserted by verifier programs, they are inserted into the",
+        "nullifier tree afterwards. Inserted nullifiers stay in the queue until all",
+        "roots in the history of the nullifier tree contain them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nullifierTree",
+            "type": "publicKey"
+          },
+          {
+            "name": "elements",
+            "type": {
+              "array": [
+                {
+                  "defined": "NullifierQueueElement"
+                },
+                128
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "merkleTreePdaToken",
+      "type": {
+        "kind": "struct",
+        "fields": []
+      }
+    },
+    {
+      "name": "preInsertedLeavesIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nextIndex",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leavesQueue",
+      "docs": [
+        "Leaves inserted by verifier programs which are not inserted into the transaction",
+        "Merkle tree yet. The pair of leaves starting at `leaf_index` is stored at",
+        "`(leaf_index / 2) % LEAVES_QUEUE_SIZE`, it can be overwritten once it is inserted.",
+        "Encrypted utxos are not stored, they are emitted with the indexer transaction event."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "transactionMerkleTree",
+            "type": "publicKey"
+          },
+          {
+            "name": "leaves",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    {
+                      "array": [
+                        "u8",
+                        32
+                      ]
+                    },
+                    2
+                  ]
+                },
+                128
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "merkleTreeAuthority",
+      "docs": [
+        "Configures the authority of the merkle tree which can:",
+        "- register new verifiers",
+        "- register new asset pools",
+        "- register new asset pool types",
+        "- set permissions for new asset pool creation, Merkle tree creation and verifier registration",
+        "- keeps current highest index for assets and merkle trees to enable lookups of these"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "transactionMerkleTreeIndex",
+            "type": "u64"
+          },
+          {
+            "name": "eventMerkleTreeIndex",
+            "type": "u64"
+          },
+          {
+            "name": "registeredAssetIndex",
+            "type": "u64"
+          },
+          {
+            "name": "enableNfts",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessSplTokens",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessMerkleTreeRegistration",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessVerifierRegistration",
+            "type": "bool"
+          },
+          {
+            "name": "pendingAuthority",
+            "docs": [
+              "Proposed new authority, the default pubkey if no transfer is pending."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "multisigThreshold",
+            "docs": [
+              "Number of multisig signers which have to sign authority instructions,",
+              "zero if no multisig is configured."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "nrMultisigSigners",
+            "type": "u8"
+          },
+          {
+            "name": "multisigSigners",
+            "type": {
+              "array": [
+                "publicKey",
+                10
+              ]
+            }
+          },
+          {
+            "name": "guardian",
+            "docs": [
+              "Guardian which can pause but not unpause, the default pubkey if not set."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Bitmask of the paused transaction kinds."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "nullifierTreeEnabled",
+            "docs": [
+              "Nullifiers are queued for the nullifier tree instead of stored in nullifier pdas.",
+              "Set once by the authority and never unset, otherwise a nullifier could be spent",
+              "once in each mode."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "merkleTreeUpdateState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nodeLeft",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "nodeRight",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "leafLeft",
+            "type": {
+              "array": [
                 "u8",
                 32
               ]
             }
           },
           {
-            "name": "index",
+            "name": "leafRight",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "relayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "merkleTreePdaPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "state",
+            "type": {
+              "array": [
+                "u8",
+                96
+              ]
+            }
+          },
+          {
+            "name": "currentRound",
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "registeredPoolType",
-      "docs": [
-        "Pool type"
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "poolType",
+            "name": "currentRoundIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentInstructionIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentLevel",
+            "type": "u64"
+          },
+          {
+            "name": "currentLevelHash",
             "type": {
               "array": [
                 "u8",
                 32
               ]
             }
+          },
+          {
+            "name": "tmpLeavesIndex",
+            "type": "u64"
+          },
+          {
+            "name": "filledSubtrees",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
+          },
+          {
+            "name": "leaves",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    {
+                      "array": [
+                        "u8",
+                        32
+                      ]
+                    },
+                    2
+                  ]
+                },
+                16
+              ]
+            }
+          },
+          {
+            "name": "numberOfLeaves",
+            "type": "u8"
+          },
+          {
+            "name": "padding1",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "insertLeavesIndex",
+            "type": "u8"
+          },
+          {
+            "name": "padding2",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "sequenceNumber",
+            "docs": [
+              "Merkle tree state the update is computed for, the copied filled subtrees",
+              "are the ones of the tree with `base_index` leaves."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "baseIndex",
+            "type": "u64"
+          },
+          {
+            "name": "startIndex",
+            "docs": [
+              "Leaves from `base_index` to `start_index` are inserted by other relayers in",
+              "parallel, nodes which cover them are patched once they are inserted."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "docs": [
+              "Slot of the last instruction of the relayer, used to detect abandoned updates."
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "eventMerkleTree",
+      "name": "registeredVerifier",
+      "docs": [
+        "Registered verifiers can invoke the verifier invoked instructions they have",
+        "capabilities for, as long as they are active."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "merkleTree",
+            "name": "pubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "status",
             "type": {
-              "defined": "MerkleTree"
+              "defined": "VerifierStatus"
             }
           },
           {
-            "name": "merkleTreeNr",
-            "type": "u64"
+            "name": "capabilities",
+            "docs": [
+              "Bitmask of the capability flags defined on RegisteredVerifier."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "allowedPoolTypes",
+            "docs": [
+              "Pool types the verifier can withdraw from, any pool type if empty."
+            ],
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "allowedMerkleTrees",
+            "docs": [
+              "Merkle trees the verifier can insert leaves into, any tree if empty.",
+              "Nullifiers are inserted into the single nullifier tree."
+            ],
+            "type": {
+              "vec": "publicKey"
+            }
           }
         ]
       }
     },
     {
-      "name": "merkleTreePdaToken",
-      "type": {
-        "kind": "struct",
-        "fields": []
-      }
-    },
-    {
-      "name": "preInsertedLeavesIndex",
+      "name": "registeredRelayer",
+      "docs": [
+        "Registers a relayer for Merkle tree updates of gated trees.",
+        "Relayers are either whitelisted by the merkle tree authority or stake lamports",
+        "which are held by this account."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "nextIndex",
+            "name": "relayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "whitelisted",
+            "type": "bool"
+          },
+          {
+            "name": "stake",
+            "docs": [
+              "Staked lamports, held in addition to the rent of this account."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "unlockSlot",
+            "docs": [
+              "Slot from which stake can be withdrawn."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "penalties",
+            "docs": [
+              "Number of expired update states of the relayer which were taken over or closed."
+            ],
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "merkleTreeAuthority",
+      "name": "verifyingKeyAccount",
       "docs": [
-        "Configures the authority of the merkle tree which can:",
-        "- register new verifiers",
-        "- register new asset pools",
-        "- register new asset pool types",
-        "- set permissions for new asset pool creation",
-        "- keeps current highest index for assets and merkle trees to enable lookups of these"
+        "Groth16 verifying key of a registered verifier for transactions with `nr_inputs` input",
+        "and `nr_outputs` output utxos.",
+        "The key is uploaded in chunks, once all vk_ic points are written it becomes active",
+        "after VERIFYING_KEY_TIMELOCK slots. A deactivated key cannot be activated again,",
+        "keys are rotated by registering a new version."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "pubkey",
+            "name": "verifier",
             "type": "publicKey"
           },
           {
-            "name": "transactionMerkleTreeIndex",
-            "type": "u64"
+            "name": "nrInputs",
+            "type": "u8"
           },
           {
-            "name": "eventMerkleTreeIndex",
+            "name": "nrOutputs",
+            "type": "u8"
+          },
+          {
+            "name": "version",
             "type": "u64"
           },
           {
-            "name": "registeredAssetIndex",
+            "name": "activationSlot",
+            "docs": [
+              "Slot from which on the key can be used, u64::MAX until the upload is complete."
+            ],
             "type": "u64"
           },
           {
-            "name": "enableNfts",
+            "name": "deactivated",
             "type": "bool"
           },
           {
-            "name": "enablePermissionlessSplTokens",
-            "type": "bool"
+            "name": "nrPubinputs",
+            "type": "u64"
           },
           {
-            "name": "enablePermissionlessMerkleTreeRegistration",
-            "type": "bool"
+            "name": "vkAlphaG1",
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          },
+          {
+            "name": "vkBetaG2",
+            "type": {
+              "array": [
+                "u8",
+                128
+              ]
+            }
+          },
+          {
+            "name": "vkGammeG2",
+            "type": {
+              "array": [
+                "u8",
+                128
+              ]
+            }
+          },
+          {
+            "name": "vkDeltaG2",
+            "type": {
+              "array": [
+                "u8",
+                128
+              ]
+            }
+          },
+          {
+            "name": "vkIc",
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  64
+                ]
+              }
+            }
           }
         ]
       }
     },
     {
-      "name": "merkleTreeUpdateState",
+      "name": "transactionMerkleTree",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "nodeLeft",
+            "name": "filledSubtrees",
+            "docs": [
+              "Only the first `height` subtrees are used."
+            ],
             "type": {
               "array": [
-                "u8",
-                32
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
               ]
             }
           },
           {
-            "name": "nodeRight",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "currentRootIndex",
+            "type": "u64"
           },
           {
-            "name": "leafLeft",
+            "name": "nextIndex",
+            "type": "u64"
+          },
+          {
+            "name": "roots",
             "type": {
               "array": [
-                "u8",
-                32
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                256
               ]
             }
           },
           {
-            "name": "leafRight",
+            "name": "sequenceNumber",
+            "docs": [
+              "Number of root updates, the changelog entry of an update is stored at",
+              "`sequence_number % MERKLE_TREE_CHANGELOG_SIZE`."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "changelog",
             "type": {
               "array": [
-                "u8",
-                32
+                {
+                  "defined": "ChangelogEntry"
+                },
+                16
               ]
             }
           },
           {
-            "name": "relayer",
-            "type": "publicKey"
+            "name": "height",
+            "type": "u64"
           },
           {
-            "name": "merkleTreePdaPubkey",
-            "type": "publicKey"
+            "name": "merkleTreeNr",
+            "type": "u64"
           },
           {
-            "name": "state",
-            "type": {
-              "array": [
-                "u8",
-                96
-              ]
-            }
+            "name": "lockDuration",
+            "type": "u64"
           },
           {
-            "name": "currentRound",
+            "name": "nextQueuedIndex",
             "type": "u64"
           },
           {
-            "name": "currentRoundIndex",
-            "type": "u64"
+            "name": "lastRelayer",
+            "docs": [
+              "Relayer which inserted the last root and the slot it was inserted at."
+            ],
+            "type": "publicKey"
           },
           {
-            "name": "currentInstructionIndex",
+            "name": "lastRootSlot",
             "type": "u64"
           },
           {
-            "name": "currentIndex",
+            "name": "minRelayerStake",
+            "docs": [
+              "Stake relayers which are not whitelisted need if relayers are gated."
+            ],
             "type": "u64"
           },
           {
-            "name": "currentLevel",
+            "name": "leafFee",
+            "docs": [
+              "Fee paid into the fee vault for every inserted pair of leaves."
+            ],
             "type": "u64"
           },
           {
-            "name": "currentLevelHash",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "feeVaultBalance",
+            "docs": [
+              "Fees of queued leaves, held by this account in addition to its rent."
+            ],
+            "type": "u64"
           },
           {
-            "name": "tmpLeavesIndex",
+            "name": "transactionSequenceNumber",
+            "docs": [
+              "Number of transactions which inserted leaves, indexer events carry it",
+              "to detect missed events."
+            ],
             "type": "u64"
           },
           {
-            "name": "filledSubtrees",
-            "type": {
-              "array": [
-                {
-                  "array": [
-                    "u8",
-                    32
-                  ]
-                },
-                18
-              ]
-            }
+            "name": "newest",
+            "docs": [
+              "Only the newest tree accepts new leaves, older trees are read-only."
+            ],
+            "type": "u8"
           },
           {
-            "name": "leaves",
+            "name": "gatedRelayers",
+            "docs": [
+              "If set, only whitelisted or staked relayers can update the tree."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
             "type": {
               "array": [
-                {
-                  "array": [
-                    {
-                      "array": [
-                        "u8",
-                        32
-                      ]
-                    },
-                    2
-                  ]
-                },
-                16
+                "u8",
+                6
               ]
             }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "DelayedWithdrawal",
+      "docs": [
+        "Withdrawal which exceeded the withdrawal limit of its pool,",
+        "it can be claimed once release_slot is reached."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "recipient",
+            "docs": [
+              "Recipient sol account or recipient token account."
+            ],
+            "type": "publicKey"
           },
           {
-            "name": "numberOfLeaves",
-            "type": "u8"
+            "name": "amount",
+            "type": "u64"
           },
           {
-            "name": "padding1",
+            "name": "releaseSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "IndexedElement",
+      "docs": [
+        "Element of the nullifier tree, points to the element with the next higher value.",
+        "The element with the highest value points to zero."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "value",
             "type": {
               "array": [
                 "u8",
-                7
+                32
               ]
             }
           },
           {
-            "name": "insertLeavesIndex",
-            "type": "u8"
+            "name": "nextIndex",
+            "type": "u64"
           },
           {
-            "name": "padding2",
+            "name": "nextValue",
             "type": {
               "array": [
                 "u8",
-                7
+                32
               ]
             }
           }
@@ -1192,22 +3246,39 @@ export type MerkleTreeProgram = {
       }
     },
     {
-      "name": "registeredVerifier",
-      "docs": [
-        ""
-      ],
+      "name": "NullifierQueueElement",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "pubkey",
-            "type": "publicKey"
+            "name": "value",
+            "docs": [
+              "Zero if the element is empty."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "sequenceNumber",
+            "docs": [
+              "Sequence number of the nullifier tree after the nullifier was inserted,",
+              "zero while it is pending."
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "transactionMerkleTree",
+      "name": "LegacyTransactionMerkleTree",
+      "docs": [
+        "Layout of transaction Merkle trees created before the account grew to 9424 bytes.",
+        "It only supports height 18 and updates are locked to one relayer at a time."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -1256,82 +3327,139 @@ export type MerkleTreeProgram = {
             "type": "u64"
           },
           {
-            "name": "height",
+            "name": "height",
+            "type": "u64"
+          },
+          {
+            "name": "merkleTreeNr",
+            "type": "u64"
+          },
+          {
+            "name": "lockDuration",
+            "type": "u64"
+          },
+          {
+            "name": "nextQueuedIndex",
+            "type": "u64"
+          },
+          {
+            "name": "newest",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "RootUpdateEvent",
+      "docs": [
+        "Emitted through the noop program when a root is inserted."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "merkleTree",
+            "type": "publicKey"
+          },
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "rootIndex",
+            "type": "u64"
+          },
+          {
+            "name": "sequenceNumber",
+            "type": "u64"
+          },
+          {
+            "name": "startIndex",
+            "docs": [
+              "Leaves from `start_index` to `end_index` (exclusive) are part of the root."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endIndex",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ChangelogEntry",
+      "docs": [
+        "Leaves range inserted by a root update."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startIndex",
             "type": "u64"
           },
           {
-            "name": "merkleTreeNr",
+            "name": "endIndex",
             "type": "u64"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "Permission",
+      "docs": [
+        "Permissions which can be granted to anyone by the merkle tree authority."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
           {
-            "name": "lockDuration",
-            "type": "u64"
+            "name": "Nfts"
           },
           {
-            "name": "nextQueuedIndex",
-            "type": "u64"
+            "name": "SplTokens"
           },
           {
-            "name": "newest",
-            "type": "u8"
+            "name": "MerkleTreeRegistration"
           },
           {
-            "name": "padding",
-            "type": {
-              "array": [
-                "u8",
-                7
-              ]
-            }
+            "name": "VerifierRegistration"
           }
         ]
       }
     },
     {
-      "name": "twoLeavesBytesPda",
+      "name": "VerifierStatus",
       "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "nodeLeft",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
-          {
-            "name": "nodeRight",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
-          },
+        "kind": "enum",
+        "variants": [
           {
-            "name": "merkleTreePubkey",
-            "type": "publicKey"
+            "name": "Active"
           },
           {
-            "name": "encryptedUtxos",
-            "type": {
-              "array": [
-                "u8",
-                256
-              ]
-            }
+            "name": "Paused"
           },
           {
-            "name": "leftLeafIndex",
-            "type": "u64"
+            "name": "Deregistered"
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
     {
       "name": "MerkleTree",
       "type": {
@@ -1406,130 +3534,404 @@ export type MerkleTreeProgram = {
       }
     },
     {
-      "name": "HashFunction",
-      "type": {
-        "kind": "enum",
-        "variants": [
-          {
-            "name": "Sha256"
-          },
-          {
-            "name": "Poseidon"
-          }
-        ]
-      }
-    }
-  ],
-  "errors": [
+      "name": "HashFunction",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Sha256"
+          },
+          {
+            "name": "Poseidon"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "PermissionUpdated",
+      "fields": [
+        {
+          "name": "permission",
+          "type": {
+            "defined": "Permission"
+          },
+          "index": false
+        },
+        {
+          "name": "enabled",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "MtTmpPdaInitFailed",
+      "msg": "Merkle tree tmp account init failed wrong pda."
+    },
+    {
+      "code": 6001,
+      "name": "MerkleTreeInitFailed",
+      "msg": "Merkle tree tmp account init failed."
+    },
+    {
+      "code": 6002,
+      "name": "ContractStillLocked",
+      "msg": "Contract is still locked."
+    },
+    {
+      "code": 6003,
+      "name": "InvalidMerkleTree",
+      "msg": "InvalidMerkleTree."
+    },
+    {
+      "code": 6004,
+      "name": "InvalidMerkleTreeOwner",
+      "msg": "InvalidMerkleTreeOwner."
+    },
+    {
+      "code": 6005,
+      "name": "PubkeyCheckFailed",
+      "msg": "PubkeyCheckFailed"
+    },
+    {
+      "code": 6006,
+      "name": "CloseAccountFailed",
+      "msg": "CloseAccountFailed"
+    },
+    {
+      "code": 6007,
+      "name": "WithdrawalFailed",
+      "msg": "WithdrawalFailed"
+    },
+    {
+      "code": 6008,
+      "name": "MerkleTreeUpdateNotInRootInsert",
+      "msg": "MerkleTreeUpdateNotInRootInsert"
+    },
+    {
+      "code": 6009,
+      "name": "MerkleTreeUpdateNotInRootInsertState",
+      "msg": "MerkleTreeUpdateNotInRootInsert"
+    },
+    {
+      "code": 6010,
+      "name": "InvalidNumberOfLeaves",
+      "msg": "InvalidNumberOfLeaves"
+    },
+    {
+      "code": 6011,
+      "name": "LeafAlreadyInserted",
+      "msg": "LeafAlreadyInserted"
+    },
+    {
+      "code": 6012,
+      "name": "WrongLeavesLastTx",
+      "msg": "WrongLeavesLastTx"
+    },
+    {
+      "code": 6013,
+      "name": "FirstLeavesPdaIncorrectIndex",
+      "msg": "FirstLeavesPdaIncorrectIndex"
+    },
+    {
+      "code": 6014,
+      "name": "NullifierAlreadyExists",
+      "msg": "NullifierAlreadyExists"
+    },
+    {
+      "code": 6015,
+      "name": "LeavesOfWrongTree",
+      "msg": "LeavesOfWrongTree"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidAuthority",
+      "msg": "InvalidAuthority"
+    },
+    {
+      "code": 6017,
+      "name": "InvalidVerifier",
+      "msg": "InvalidVerifier"
+    },
+    {
+      "code": 6018,
+      "name": "PubkeyTryFromFailed",
+      "msg": "PubkeyTryFromFailed"
+    },
+    {
+      "code": 6019,
+      "name": "ExpectedOldMerkleTree",
+      "msg": "Expected old Merkle tree as a remaining account."
+    },
+    {
+      "code": 6020,
+      "name": "InvalidOldMerkleTree",
+      "msg": "Invalid old Merkle tree account."
+    },
+    {
+      "code": 6021,
+      "name": "NotNewestOldMerkleTree",
+      "msg": "Provided old Merkle tree is not the newest one."
+    },
+    {
+      "code": 6022,
+      "name": "PoseidonHashFailed",
+      "msg": "Poseidon hash computation failed."
+    },
+    {
+      "code": 6023,
+      "name": "NativePoseidonUnavailable",
+      "msg": "Native Poseidon is not available, use the multi-instruction Merkle tree update."
+    },
+    {
+      "code": 6024,
+      "name": "InvalidMerkleTreeHeight",
+      "msg": "Merkle tree height is out of the supported range."
+    },
+    {
+      "code": 6025,
+      "name": "MerkleTreeFull",
+      "msg": "Merkle tree is full."
+    },
+    {
+      "code": 6026,
+      "name": "MerkleTreeReadOnly",
+      "msg": "Merkle tree is read-only, leaves have to be inserted into the newest Merkle tree."
+    },
+    {
+      "code": 6027,
+      "name": "MerkleTreeUpdateStateOutdated",
+      "msg": "Merkle tree update state is outdated."
+    },
+    {
+      "code": 6028,
+      "name": "NullifierQueueFull",
+      "msg": "Nullifier queue is full."
+    },
+    {
+      "code": 6029,
+      "name": "NullifierQueueElementNotPending",
+      "msg": "Nullifier queue element is not pending insertion."
+    },
+    {
+      "code": 6030,
+      "name": "InvalidNullifierQueue",
+      "msg": "Nullifier queue does not belong to the nullifier tree."
+    },
+    {
+      "code": 6031,
+      "name": "InvalidLowElement",
+      "msg": "Low element does not enclose the inserted value."
+    },
+    {
+      "code": 6032,
+      "name": "InvalidMerkleProof",
+      "msg": "Merkle proof does not match the current root."
+    },
+    {
+      "code": 6033,
+      "name": "InvalidNumberOfNullifiers",
+      "msg": "Number of nullifiers does not match the number of nullifier accounts."
+    },
+    {
+      "code": 6034,
+      "name": "InvalidVerifyingKey",
+      "msg": "Verifying key is invalid or already complete."
+    },
+    {
+      "code": 6035,
+      "name": "InvalidMasterEdition",
+      "msg": "Master edition account does not belong to the nft mint."
+    },
+    {
+      "code": 6036,
+      "name": "InvalidPoolType",
+      "msg": "Pool type does not match the asset type of the mint."
+    },
+    {
+      "code": 6037,
+      "name": "NotEnoughMultisigSignatures",
+      "msg": "Not enough multisig signers signed the transaction."
+    },
+    {
+      "code": 6038,
+      "name": "InvalidMultisigConfig",
+      "msg": "Invalid multisig signers or threshold."
+    },
+    {
+      "code": 6039,
+      "name": "VerifierNotActive",
+      "msg": "Verifier is paused or deregistered."
+    },
+    {
+      "code": 6040,
+      "name": "VerifierDeregistered",
+      "msg": "Verifier is deregistered."
+    },
+    {
+      "code": 6041,
+      "name": "MissingVerifierCapability",
+      "msg": "Verifier does not have the capability for this instruction."
+    },
+    {
+      "code": 6042,
+      "name": "MerkleTreeNotAllowed",
+      "msg": "Verifier is not allowed to use this Merkle tree."
+    },
+    {
+      "code": 6043,
+      "name": "PoolTypeNotAllowed",
+      "msg": "Verifier is not allowed to use this pool type."
+    },
+    {
+      "code": 6044,
+      "name": "InvalidVerifierCapabilities",
+      "msg": "Invalid verifier capabilities."
+    },
+    {
+      "code": 6045,
+      "name": "ProtocolPaused",
+      "msg": "Transactions are paused."
+    },
+    {
+      "code": 6046,
+      "name": "InvalidPauseFlags",
+      "msg": "Invalid pause flags."
+    },
+    {
+      "code": 6047,
+      "name": "DepositCapExceeded",
+      "msg": "Deposit exceeds the deposit cap of the pool."
+    },
+    {
+      "code": 6048,
+      "name": "DelayedWithdrawalQueueFull",
+      "msg": "Delayed withdrawal queue of the pool is full."
+    },
+    {
+      "code": 6049,
+      "name": "NoReleasedWithdrawal",
+      "msg": "No released delayed withdrawal for this recipient."
+    },
     {
-      "code": 6000,
-      "name": "MtTmpPdaInitFailed",
-      "msg": "Merkle tree tmp account init failed wrong pda."
+      "code": 6050,
+      "name": "InvalidAssetPool",
+      "msg": "Asset pool does not belong to the pool account."
     },
     {
-      "code": 6001,
-      "name": "MerkleTreeInitFailed",
-      "msg": "Merkle tree tmp account init failed."
+      "code": 6051,
+      "name": "InvalidWithdrawalWindow",
+      "msg": "Withdrawal window needs to be greater than zero."
     },
     {
-      "code": 6002,
-      "name": "ContractStillLocked",
-      "msg": "Contract is still locked."
+      "code": 6052,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint uses a token extension which is not supported by pools."
     },
     {
-      "code": 6003,
-      "name": "InvalidMerkleTree",
-      "msg": "InvalidMerkleTree."
+      "code": 6053,
+      "name": "UpdateStateNotExpired",
+      "msg": "Merkle tree update state is not expired."
     },
     {
-      "code": 6004,
-      "name": "InvalidMerkleTreeOwner",
-      "msg": "InvalidMerkleTreeOwner."
+      "code": 6054,
+      "name": "RelayerNotRegistered",
+      "msg": "Relayer is not registered for this Merkle tree."
     },
     {
-      "code": 6005,
-      "name": "PubkeyCheckFailed",
-      "msg": "PubkeyCheckFailed"
+      "code": 6055,
+      "name": "InsufficientRelayerStake",
+      "msg": "Relayer is not whitelisted and its stake is below the minimum stake."
     },
     {
-      "code": 6006,
-      "name": "CloseAccountFailed",
-      "msg": "CloseAccountFailed"
+      "code": 6056,
+      "name": "RelayerRotation",
+      "msg": "Relayer inserted the last root, other relayers have priority."
     },
     {
-      "code": 6007,
-      "name": "WithdrawalFailed",
-      "msg": "WithdrawalFailed"
+      "code": 6057,
+      "name": "RelayerStakeLocked",
+      "msg": "Relayer stake is locked."
     },
     {
-      "code": 6008,
-      "name": "MerkleTreeUpdateNotInRootInsert",
-      "msg": "MerkleTreeUpdateNotInRootInsert"
+      "code": 6058,
+      "name": "InvalidRelayerStake",
+      "msg": "Invalid relayer stake amount."
     },
     {
-      "code": 6009,
-      "name": "MerkleTreeUpdateNotInRootInsertState",
-      "msg": "MerkleTreeUpdateNotInRootInsert"
+      "code": 6059,
+      "name": "LeavesQueueFull",
+      "msg": "Leaves queue is full, the Merkle tree has to be updated first."
     },
     {
-      "code": 6010,
-      "name": "InvalidNumberOfLeaves",
-      "msg": "InvalidNumberOfLeaves"
+      "code": 6060,
+      "name": "InvalidLeavesQueue",
+      "msg": "Leaves queue does not belong to the Merkle tree."
     },
     {
-      "code": 6011,
-      "name": "LeafAlreadyInserted",
-      "msg": "LeafAlreadyInserted"
+      "code": 6061,
+      "name": "InvalidNoopPubkey",
+      "msg": "Invalid noop program key."
     },
     {
-      "code": 6012,
-      "name": "WrongLeavesLastTx",
-      "msg": "WrongLeavesLastTx"
+      "code": 6062,
+      "name": "LeavesBeforeUpdateNotInserted",
+      "msg": "Leaves before the update state are not inserted into the Merkle tree yet."
     },
     {
-      "code": 6013,
-      "name": "FirstLeavesPdaIncorrectIndex",
-      "msg": "FirstLeavesPdaIncorrectIndex"
+      "code": 6063,
+      "name": "InvalidUpdateStateSequenceNumber",
+      "msg": "Sequence number of the update state is ahead of the Merkle tree."
     },
     {
-      "code": 6014,
-      "name": "NullifierAlreadyExists",
-      "msg": "NullifierAlreadyExists"
+      "code": 6064,
+      "name": "InvalidUpdateStartIndex",
+      "msg": "Start index of the update has to be a queued pair of leaves."
     },
     {
-      "code": 6015,
-      "name": "LeavesOfWrongTree",
-      "msg": "LeavesOfWrongTree"
+      "code": 6065,
+      "name": "DelayedWithdrawalTooSmall",
+      "msg": "Withdrawal over the withdrawal limit is below the minimum delayed withdrawal."
     },
     {
-      "code": 6016,
-      "name": "InvalidAuthority",
-      "msg": "InvalidAuthority"
+      "code": 6066,
+      "name": "WithdrawalLimitExceeded",
+      "msg": "Claim exceeds the withdrawal limit of the current window."
     },
     {
-      "code": 6017,
-      "name": "InvalidVerifier",
-      "msg": "InvalidVerifier"
+      "code": 6067,
+      "name": "NoDelayedWithdrawal",
+      "msg": "No delayed withdrawal for this recipient."
     },
     {
-      "code": 6018,
-      "name": "PubkeyTryFromFailed",
-      "msg": "PubkeyTryFromFailed"
+      "code": 6068,
+      "name": "NullifierPdasDisabled",
+      "msg": "Nullifier pdas are disabled, nullifiers are inserted into the nullifier tree."
     },
     {
-      "code": 6019,
-      "name": "ExpectedOldMerkleTree",
-      "msg": "Expected old Merkle tree as a remaining account."
+      "code": 6069,
+      "name": "NullifierTreeNotEnabled",
+      "msg": "Nullifier tree is not enabled."
     },
     {
-      "code": 6020,
-      "name": "InvalidOldMerkleTree",
-      "msg": "Invalid old Merkle tree account."
+      "code": 6070,
+      "name": "InvalidNullifierPda",
+      "msg": "Nullifier pda address does not match the nullifier."
     },
     {
-      "code": 6021,
-      "name": "NotNewestOldMerkleTree",
-      "msg": "Provided old Merkle tree is not the newest one."
+      "code": 6071,
+      "name": "InvalidLegacyMerkleTree",
+      "msg": "Account is not a transaction Merkle tree in the legacy layout."
+    },
+    {
+      "code": 6072,
+      "name": "LegacyMerkleTreeHasQueuedLeaves",
+      "msg": "Queued leaves of the legacy Merkle tree have to be inserted before the migration."
     }
   ]
 };
@@ -1539,29 +3941,149 @@ export const IDL: MerkleTreeProgram = {
   "name": "merkle_tree_program",
   "constants": [
     {
-      "name": "ENCRYPTED_UTXOS_LENGTH",
+      "name": "SUPPORTED_MINT_EXTENSIONS",
+      "type": {
+        "array": [
+          {
+            "defined": "ExtensionType"
+          },
+          3
+        ]
+      },
+      "value": "[ExtensionType :: TransferFeeConfig , ExtensionType :: MintCloseAuthority , ExtensionType :: InterestBearingConfig]"
+    },
+    {
+      "name": "LEGACY_TRANSACTION_MERKLE_TREE_SIZE",
       "type": {
         "defined": "usize"
       },
-      "value": "174"
+      "value": "8880"
     },
     {
       "name": "MERKLE_TREE_TMP_PDA_SIZE",
       "type": {
         "defined": "usize"
       },
-      "value": "2048"
+      "value": "2304"
     },
     {
       "name": "MERKLE_TREE_HISTORY_SIZE",
       "type": "u64",
       "value": "256"
     },
+    {
+      "name": "MERKLE_TREE_CHANGELOG_SIZE",
+      "type": "u64",
+      "value": "16"
+    },
+    {
+      "name": "LEAVES_QUEUE_SIZE",
+      "type": "u64",
+      "value": "128"
+    },
     {
       "name": "MERKLE_TREE_HEIGHT",
       "type": "u64",
       "value": "18"
     },
+    {
+      "name": "MERKLE_TREE_MIN_HEIGHT",
+      "type": "u64",
+      "value": "16"
+    },
+    {
+      "name": "MERKLE_TREE_MAX_HEIGHT",
+      "type": "u64",
+      "value": "26"
+    },
+    {
+      "name": "MERKLE_TREE_ROLLOVER_THRESHOLD",
+      "type": "u64",
+      "value": "95"
+    },
+    {
+      "name": "NULLIFIER_TREE_HEIGHT",
+      "type": "u64",
+      "value": "26"
+    },
+    {
+      "name": "NULLIFIER_TREE_HISTORY_SIZE",
+      "type": "u64",
+      "value": "32"
+    },
+    {
+      "name": "NULLIFIER_QUEUE_SIZE",
+      "type": "u64",
+      "value": "128"
+    },
+    {
+      "name": "VERIFYING_KEY_TIMELOCK",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "MAX_MULTISIG_SIGNERS",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "10"
+    },
+    {
+      "name": "MAX_VERIFIER_ALLOWED_POOL_TYPES",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "4"
+    },
+    {
+      "name": "MAX_VERIFIER_ALLOWED_MERKLE_TREES",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "4"
+    },
+    {
+      "name": "MAX_VERIFYING_KEY_PUBLIC_INPUTS",
+      "type": "u64",
+      "value": "64"
+    },
+    {
+      "name": "DEFAULT_WITHDRAWAL_WINDOW",
+      "type": "u64",
+      "value": "9_000"
+    },
+    {
+      "name": "DELAYED_WITHDRAWAL_TIMELOCK",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "MAX_DELAYED_WITHDRAWALS",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "16"
+    },
+    {
+      "name": "UPDATE_STATE_TAKEOVER_REWARD",
+      "type": "u64",
+      "value": "20"
+    },
+    {
+      "name": "RELAYER_STAKE_PENALTY",
+      "type": "u64",
+      "value": "10"
+    },
+    {
+      "name": "RELAYER_UNSTAKE_DELAY",
+      "type": "u64",
+      "value": "216_000"
+    },
+    {
+      "name": "DEFAULT_LEAF_FEE",
+      "type": "u64",
+      "value": "5_000"
+    },
     {
       "name": "INITIAL_MERKLE_TREE_AUTHORITY",
       "type": {
@@ -1573,7 +4095,7 @@ export const IDL: MerkleTreeProgram = {
       "value": "[2 , 99 , 226 , 251 , 88 , 66 , 92 , 33 , 25 , 216 , 211 , 185 , 112 , 203 , 212 , 238 , 105 , 144 , 72 , 121 , 176 , 253 , 106 , 168 , 115 , 158 , 154 , 188 , 62 , 255 , 166 , 81]"
     },
     {
-      "name": "ZERO_BYTES_MERKLE_TREE_18",
+      "name": "ZERO_BYTES_MERKLE_TREE",
       "type": {
         "array": [
           {
@@ -1582,134 +4104,721 @@ export const IDL: MerkleTreeProgram = {
               32
             ]
           },
-          19
+          27
         ]
       },
-      "value": "[[40 , 66 , 58 , 227 , 48 , 224 , 249 , 227 , 188 , 18 , 133 , 168 , 156 , 214 , 220 , 144 , 244 , 144 , 67 , 82 , 76 , 6 , 135 , 78 , 64 , 186 , 52 , 113 , 234 , 47 , 27 , 32] , [227 , 42 , 164 , 149 , 188 , 70 , 170 , 8 , 197 , 44 , 134 , 162 , 211 , 186 , 50 , 238 , 97 , 71 , 25 , 130 , 77 , 70 , 37 , 128 , 172 , 154 , 54 , 111 , 93 , 193 , 105 , 27] , [25 , 241 , 255 , 33 , 65 , 214 , 48 , 229 , 38 , 116 , 134 , 103 , 44 , 146 , 163 , 214 , 31 , 238 , 148 , 206 , 34 , 137 , 144 , 221 , 184 , 11 , 5 , 213 , 10 , 188 , 143 , 18] , [211 , 61 , 251 , 33 , 128 , 34 , 4 , 100 , 229 , 47 , 99 , 121 , 109 , 204 , 224 , 90 , 200 , 149 , 219 , 20 , 48 , 206 , 210 , 177 , 161 , 66 , 44 , 10 , 169 , 56 , 248 , 8] , [200 , 15 , 65 , 80 , 151 , 74 , 72 , 69 , 229 , 131 , 25 , 215 , 86 , 36 , 195 , 74 , 67 , 59 , 117 , 179 , 51 , 60 , 181 , 13 , 242 , 192 , 228 , 228 , 189 , 238 , 70 , 8] , [171 , 62 , 122 , 81 , 181 , 197 , 22 , 238 , 224 , 40 , 154 , 231 , 127 , 202 , 201 , 169 , 196 , 109 , 244 , 175 , 117 , 101 , 23 , 67 , 103 , 57 , 127 , 200 , 37 , 43 , 111 , 7] , [59 , 78 , 126 , 104 , 199 , 143 , 213 , 10 , 2 , 158 , 64 , 78 , 153 , 25 , 107 , 190 , 32 , 122 , 123 , 211 , 116 , 179 , 175 , 172 , 70 , 54 , 175 , 59 , 201 , 120 , 64 , 44] , [110 , 91 , 92 , 81 , 205 , 89 , 122 , 223 , 55 , 163 , 42 , 227 , 109 , 54 , 38 , 22 , 110 , 217 , 29 , 148 , 107 , 99 , 128 , 106 , 146 , 47 , 239 , 41 , 55 , 157 , 155 , 22] , [18 , 231 , 42 , 5 , 245 , 159 , 211 , 227 , 239 , 89 , 35 , 142 , 223 , 69 , 166 , 224 , 14 , 114 , 128 , 14 , 123 , 123 , 215 , 2 , 241 , 185 , 191 , 60 , 252 , 61 , 146 , 12] , [231 , 0 , 84 , 227 , 127 , 64 , 158 , 7 , 171 , 179 , 137 , 231 , 92 , 87 , 25 , 221 , 156 , 229 , 53 , 208 , 194 , 201 , 12 , 165 , 105 , 150 , 41 , 142 , 29 , 205 , 136 , 29] , [195 , 2 , 103 , 231 , 62 , 207 , 214 , 105 , 214 , 210 , 108 , 23 , 28 , 151 , 77 , 100 , 78 , 194 , 210 , 29 , 227 , 14 , 17 , 242 , 211 , 50 , 33 , 194 , 106 , 18 , 246 , 45] , [131 , 178 , 24 , 157 , 251 , 247 , 103 , 69 , 101 , 229 , 194 , 14 , 167 , 57 , 158 , 128 , 212 , 19 , 140 , 234 , 69 , 37 , 10 , 156 , 249 , 96 , 152 , 52 , 97 , 96 , 119 , 41] , [30 , 223 , 20 , 181 , 108 , 110 , 112 , 102 , 234 , 54 , 99 , 29 , 213 , 3 , 55 , 225 , 125 , 185 , 223 , 234 , 188 , 108 , 83 , 89 , 27 , 3 , 100 , 6 , 65 , 107 , 3 , 24] , [167 , 32 , 85 , 233 , 205 , 253 , 154 , 214 , 236 , 82 , 147 , 75 , 252 , 144 , 109 , 73 , 63 , 167 , 77 , 233 , 12 , 201 , 150 , 242 , 103 , 15 , 158 , 83 , 137 , 24 , 170 , 16] , [45 , 98 , 238 , 69 , 136 , 141 , 101 , 226 , 94 , 209 , 58 , 215 , 212 , 14 , 210 , 135 , 110 , 96 , 52 , 16 , 101 , 177 , 121 , 109 , 134 , 81 , 189 , 146 , 113 , 243 , 97 , 42] , [71 , 51 , 251 , 48 , 95 , 193 , 94 , 26 , 180 , 17 , 124 , 203 , 48 , 98 , 55 , 17 , 60 , 104 , 186 , 175 , 213 , 189 , 7 , 239 , 92 , 175 , 16 , 5 , 220 , 168 , 70 , 21] , [35 , 92 , 72 , 197 , 23 , 142 , 16 , 200 , 136 , 38 , 44 , 255 , 162 , 115 , 11 , 1 , 248 , 182 , 236 , 78 , 90 , 24 , 128 , 245 , 168 , 17 , 130 , 2 , 73 , 51 , 196 , 6] , [89 , 178 , 154 , 246 , 236 , 130 , 30 , 100 , 27 , 230 , 24 , 196 , 8 , 172 , 176 , 196 , 197 , 13 , 157 , 194 , 169 , 106 , 207 , 70 , 66 , 117 , 69 , 53 , 56 , 154 , 78 , 0] , [231 , 174 , 226 , 37 , 211 , 160 , 187 , 178 , 149 , 82 , 17 , 60 , 110 , 116 , 28 , 61 , 58 , 145 , 58 , 71 , 25 , 42 , 67 , 46 , 189 , 214 , 248 , 234 , 182 , 251 , 238 , 34]]"
+      "value": "[[40 , 66 , 58 , 227 , 48 , 224 , 249 , 227 , 188 , 18 , 133 , 168 , 156 , 214 , 220 , 144 , 244 , 144 , 67 , 82 , 76 , 6 , 135 , 78 , 64 , 186 , 52 , 113 , 234 , 47 , 27 , 32] , [227 , 42 , 164 , 149 , 188 , 70 , 170 , 8 , 197 , 44 , 134 , 162 , 211 , 186 , 50 , 238 , 97 , 71 , 25 , 130 , 77 , 70 , 37 , 128 , 172 , 154 , 54 , 111 , 93 , 193 , 105 , 27] , [25 , 241 , 255 , 33 , 65 , 214 , 48 , 229 , 38 , 116 , 134 , 103 , 44 , 146 , 163 , 214 , 31 , 238 , 148 , 206 , 34 , 137 , 144 , 221 , 184 , 11 , 5 , 213 , 10 , 188 , 143 , 18] , [211 , 61 , 251 , 33 , 128 , 34 , 4 , 100 , 229 , 47 , 99 , 121 , 109 , 204 , 224 , 90 , 200 , 149 , 219 , 20 , 48 , 206 , 210 , 177 , 161 , 66 , 44 , 10 , 169 , 56 , 248 , 8] , [200 , 15 , 65 , 80 , 151 , 74 , 72 , 69 , 229 , 131 , 25 , 215 , 86 , 36 , 195 , 74 , 67 , 59 , 117 , 179 , 51 , 60 , 181 , 13 , 242 , 192 , 228 , 228 , 189 , 238 , 70 , 8] , [171 , 62 , 122 , 81 , 181 , 197 , 22 , 238 , 224 , 40 , 154 , 231 , 127 , 202 , 201 , 169 , 196 , 109 , 244 , 175 , 117 , 101 , 23 , 67 , 103 , 57 , 127 , 200 , 37 , 43 , 111 , 7] , [59 , 78 , 126 , 104 , 199 , 143 , 213 , 10 , 2 , 158 , 64 , 78 , 153 , 25 , 107 , 190 , 32 , 122 , 123 , 211 , 116 , 179 , 175 , 172 , 70 , 54 , 175 , 59 , 201 , 120 , 64 , 44] , [110 , 91 , 92 , 81 , 205 , 89 , 122 , 223 , 55 , 163 , 42 , 227 , 109 , 54 , 38 , 22 , 110 , 217 , 29 , 148 , 107 , 99 , 128 , 106 , 146 , 47 , 239 , 41 , 55 , 157 , 155 , 22] , [18 , 231 , 42 , 5 , 245 , 159 , 211 , 227 , 239 , 89 , 35 , 142 , 223 , 69 , 166 , 224 , 14 , 114 , 128 , 14 , 123 , 123 , 215 , 2 , 241 , 185 , 191 , 60 , 252 , 61 , 146 , 12] , [231 , 0 , 84 , 227 , 127 , 64 , 158 , 7 , 171 , 179 , 137 , 231 , 92 , 87 , 25 , 221 , 156 , 229 , 53 , 208 , 194 , 201 , 12 , 165 , 105 , 150 , 41 , 142 , 29 , 205 , 136 , 29] , [195 , 2 , 103 , 231 , 62 , 207 , 214 , 105 , 214 , 210 , 108 , 23 , 28 , 151 , 77 , 100 , 78 , 194 , 210 , 29 , 227 , 14 , 17 , 242 , 211 , 50 , 33 , 194 , 106 , 18 , 246 , 45] , [131 , 178 , 24 , 157 , 251 , 247 , 103 , 69 , 101 , 229 , 194 , 14 , 167 , 57 , 158 , 128 , 212 , 19 , 140 , 234 , 69 , 37 , 10 , 156 , 249 , 96 , 152 , 52 , 97 , 96 , 119 , 41] , [30 , 223 , 20 , 181 , 108 , 110 , 112 , 102 , 234 , 54 , 99 , 29 , 213 , 3 , 55 , 225 , 125 , 185 , 223 , 234 , 188 , 108 , 83 , 89 , 27 , 3 , 100 , 6 , 65 , 107 , 3 , 24] , [167 , 32 , 85 , 233 , 205 , 253 , 154 , 214 , 236 , 82 , 147 , 75 , 252 , 144 , 109 , 73 , 63 , 167 , 77 , 233 , 12 , 201 , 150 , 242 , 103 , 15 , 158 , 83 , 137 , 24 , 170 , 16] , [45 , 98 , 238 , 69 , 136 , 141 , 101 , 226 , 94 , 209 , 58 , 215 , 212 , 14 , 210 , 135 , 110 , 96 , 52 , 16 , 101 , 177 , 121 , 109 , 134 , 81 , 189 , 146 , 113 , 243 , 97 , 42] , [71 , 51 , 251 , 48 , 95 , 193 , 94 , 26 , 180 , 17 , 124 , 203 , 48 , 98 , 55 , 17 , 60 , 104 , 186 , 175 , 213 , 189 , 7 , 239 , 92 , 175 , 16 , 5 , 220 , 168 , 70 , 21] , [35 , 92 , 72 , 197 , 23 , 142 , 16 , 200 , 136 , 38 , 44 , 255 , 162 , 115 , 11 , 1 , 248 , 182 , 236 , 78 , 90 , 24 , 128 , 245 , 168 , 17 , 130 , 2 , 73 , 51 , 196 , 6] , [89 , 178 , 154 , 246 , 236 , 130 , 30 , 100 , 27 , 230 , 24 , 196 , 8 , 172 , 176 , 196 , 197 , 13 , 157 , 194 , 169 , 106 , 207 , 70 , 66 , 117 , 69 , 53 , 56 , 154 , 78 , 0] , [231 , 174 , 226 , 37 , 211 , 160 , 187 , 178 , 149 , 82 , 17 , 60 , 110 , 116 , 28 , 61 , 58 , 145 , 58 , 71 , 25 , 42 , 67 , 46 , 189 , 214 , 248 , 234 , 182 , 251 , 238 , 34] , [154 , 31 , 139 , 147 , 198 , 116 , 198 , 21 , 178 , 226 , 37 , 28 , 188 , 65 , 80 , 186 , 104 , 217 , 80 , 136 , 219 , 185 , 137 , 112 , 222 , 230 , 85 , 166 , 242 , 213 , 78 , 21] , [110 , 143 , 173 , 69 , 35 , 46 , 186 , 59 , 181 , 249 , 248 , 202 , 81 , 141 , 108 , 105 , 34 , 44 , 109 , 82 , 106 , 111 , 226 , 5 , 74 , 57 , 243 , 224 , 28 , 83 , 90 , 9] , [76 , 147 , 78 , 218 , 174 , 113 , 73 , 205 , 158 , 246 , 173 , 237 , 116 , 169 , 81 , 221 , 246 , 29 , 244 , 137 , 63 , 121 , 192 , 123 , 107 , 185 , 66 , 134 , 97 , 83 , 101 , 43] , [194 , 76 , 54 , 227 , 200 , 20 , 5 , 189 , 30 , 137 , 16 , 50 , 99 , 252 , 59 , 94 , 91 , 147 , 43 , 95 , 141 , 5 , 188 , 200 , 229 , 207 , 48 , 41 , 164 , 126 , 197 , 23] , [153 , 114 , 73 , 246 , 25 , 237 , 228 , 134 , 41 , 171 , 246 , 134 , 69 , 229 , 115 , 24 , 160 , 72 , 104 , 113 , 255 , 161 , 176 , 16 , 221 , 193 , 139 , 240 , 55 , 210 , 134 , 19] , [232 , 96 , 167 , 26 , 45 , 54 , 61 , 216 , 22 , 255 , 57 , 109 , 251 , 171 , 52 , 39 , 167 , 112 , 1 , 100 , 203 , 164 , 15 , 14 , 229 , 67 , 118 , 16 , 81 , 45 , 22 , 25] , [159 , 195 , 41 , 229 , 0 , 143 , 186 , 215 , 216 , 108 , 12 , 46 , 212 , 70 , 87 , 43 , 215 , 170 , 178 , 113 , 116 , 149 , 237 , 74 , 154 , 135 , 23 , 237 , 87 , 14 , 191 , 6] , [244 , 145 , 68 , 51 , 72 , 253 , 131 , 90 , 86 , 87 , 156 , 15 , 55 , 205 , 118 , 157 , 35 , 1 , 7 , 111 , 190 , 73 , 142 , 7 , 170 , 199 , 227 , 136 , 79 , 228 , 64 , 7]]"
+    },
+    {
+      "name": "MERKLE_TREE_UPDATE_START",
+      "type": "u8",
+      "value": "14"
+    },
+    {
+      "name": "LOCK_START",
+      "type": "u8",
+      "value": "34"
+    },
+    {
+      "name": "HASH_0",
+      "type": "u8",
+      "value": "0"
+    },
+    {
+      "name": "HASH_1",
+      "type": "u8",
+      "value": "1"
+    },
+    {
+      "name": "HASH_2",
+      "type": "u8",
+      "value": "2"
+    },
+    {
+      "name": "ROOT_INSERT",
+      "type": "u8",
+      "value": "241"
+    },
+    {
+      "name": "AUTHORITY_SEED",
+      "type": "bytes",
+      "value": "[65, 85, 84, 72, 79, 82, 73, 84, 89, 95, 83, 69, 69, 68]"
+    },
+    {
+      "name": "MERKLE_TREE_AUTHORITY_SEED",
+      "type": "bytes",
+      "value": "[77, 69, 82, 75, 76, 69, 95, 84, 82, 69, 69, 95, 65, 85, 84, 72, 79, 82, 73, 84, 89]"
+    },
+    {
+      "name": "TREE_ROOT_SEED",
+      "type": "bytes",
+      "value": "[84, 82, 69, 69, 95, 82, 79, 79, 84, 95, 83, 69, 69, 68]"
+    },
+    {
+      "name": "STORAGE_SEED",
+      "type": "bytes",
+      "value": "[115, 116, 111, 114, 97, 103, 101]"
+    },
+    {
+      "name": "RELAYER_SEED",
+      "type": "bytes",
+      "value": "[114, 101, 108, 97, 121, 101, 114]"
+    },
+    {
+      "name": "LEAVES_QUEUE_SEED",
+      "type": "bytes",
+      "value": "[108, 101, 97, 118, 101, 115, 95, 113, 117, 101, 117, 101]"
+    },
+    {
+      "name": "NULLIFIER_SEED",
+      "type": "bytes",
+      "value": "[110, 102]"
+    },
+    {
+      "name": "POOL_TYPE_SEED",
+      "type": "bytes",
+      "value": "[112, 111, 111, 108, 116, 121, 112, 101]"
+    },
+    {
+      "name": "POOL_CONFIG_SEED",
+      "type": "bytes",
+      "value": "[112, 111, 111, 108, 45, 99, 111, 110, 102, 105, 103]"
+    },
+    {
+      "name": "POOL_SEED",
+      "type": "bytes",
+      "value": "[112, 111, 111, 108]"
+    },
+    {
+      "name": "TOKEN_AUTHORITY_SEED",
+      "type": "bytes",
+      "value": "[115, 112, 108]"
+    },
+    {
+      "name": "EVENT_MERKLE_TREE_SEED",
+      "type": "bytes",
+      "value": "[101, 118, 101, 110, 116, 95, 109, 101, 114, 107, 108, 101, 95, 116, 114, 101, 101]"
+    },
+    {
+      "name": "TRANSACTION_MERKLE_TREE_SEED",
+      "type": "bytes",
+      "value": "[116, 114, 97, 110, 115, 97, 99, 116, 105, 111, 110, 95, 109, 101, 114, 107, 108, 101, 95, 116, 114, 101, 101]"
+    },
+    {
+      "name": "NULLIFIER_TREE_SEED",
+      "type": "bytes",
+      "value": "[110, 117, 108, 108, 105, 102, 105, 101, 114, 95, 116, 114, 101, 101]"
+    },
+    {
+      "name": "NULLIFIER_QUEUE_SEED",
+      "type": "bytes",
+      "value": "[110, 117, 108, 108, 105, 102, 105, 101, 114, 95, 113, 117, 101, 117, 101]"
+    },
+    {
+      "name": "METADATA_SEED",
+      "type": "bytes",
+      "value": "[109, 101, 116, 97, 100, 97, 116, 97]"
+    },
+    {
+      "name": "VERIFYING_KEY_SEED",
+      "type": "bytes",
+      "value": "[118, 101, 114, 105, 102, 121, 105, 110, 103, 95, 107, 101, 121]"
     },
     {
-      "name": "IX_ORDER",
+      "name": "NFT_POOL_TYPE",
       "type": {
         "array": [
           "u8",
-          57
+          32
         ]
       },
-      "value": "[34 , 14 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 0 , 1 , 2 , 241]"
+      "value": "[1 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0 , 0]"
     },
     {
-      "name": "MERKLE_TREE_UPDATE_START",
-      "type": "u8",
-      "value": "14"
+      "name": "TOKEN_METADATA_PROGRAM_ID",
+      "type": "publicKey",
+      "value": "pubkey ! (\"metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s\")"
     },
     {
-      "name": "LOCK_START",
-      "type": "u8",
-      "value": "34"
+      "name": "NOOP_PROGRAM_ID",
+      "type": "publicKey",
+      "value": "pubkey ! (\"noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV\")"
     },
     {
-      "name": "HASH_0",
-      "type": "u8",
-      "value": "0"
+      "name": "EVENT_MERKLE_TREE_HEIGHT",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "8"
+    }
+  ],
+  "instructions": [
+    {
+      "name": "initializeNewTransactionMerkleTree",
+      "docs": [
+        "Initializes a new Merkle tree from config bytes.",
+        "Can only be called from the merkle_tree_authority, or by anyone once the",
+        "newest Merkle tree reached the rollover threshold or if permissionless Merkle",
+        "tree registration is enabled. A tree created by anyone else than the authority",
+        "has to use the height and lock duration of the old Merkle tree.",
+        "The height has to be within MERKLE_TREE_MIN_HEIGHT and MERKLE_TREE_MAX_HEIGHT.",
+        "The old Merkle tree is the first remaining account, multisig signers of the",
+        "authority follow it."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "newTransactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lockDuration",
+          "type": "u64"
+        },
+        {
+          "name": "height",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "HASH_1",
-      "type": "u8",
-      "value": "1"
+      "name": "migrateTransactionMerkleTree",
+      "docs": [
+        "Migrates a transaction Merkle tree created before the account grew from 8880",
+        "to 9424 bytes, these do not deserialize anymore. Reallocates the account, the",
+        "authority pays the additional rent, and creates its leaves queue.",
+        "Can only be called from the merkle_tree_authority once all queued leaves of the",
+        "Merkle tree are inserted, multisig signers of the authority follow as remaining",
+        "accounts."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "HASH_2",
-      "type": "u8",
-      "value": "2"
+      "name": "initializeNewEventMerkleTree",
+      "docs": [
+        "Initializes a new event Merkle tree.",
+        "Can only be called from the merkle_tree_authority, or by anyone if permissionless",
+        "Merkle tree registration is enabled."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "eventMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "ROOT_INSERT",
-      "type": "u8",
-      "value": "241"
+      "name": "initializeNewNullifierTree",
+      "docs": [
+        "Initializes the nullifier tree and its nullifier queue.",
+        "Can only be called from the merkle_tree_authority, permissionless Merkle tree",
+        "registration does not apply to the single nullifier tree."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "nullifierQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "AUTHORITY_SEED",
-      "type": "bytes",
-      "value": "[65, 85, 84, 72, 79, 82, 73, 84, 89, 95, 83, 69, 69, 68]"
+      "name": "enableNullifierTree",
+      "docs": [
+        "Switches nullifier storage from nullifier pdas to the nullifier tree, can not be undone.",
+        "Verifiers which store nullifiers in pdas fail afterwards, the authority enables the",
+        "nullifier tree once these are migrated or deregistered."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "nullifierTree",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The nullifier tree has to be initialized before nullifiers can be queued."
+          ]
+        }
+      ],
+      "args": []
     },
     {
-      "name": "MERKLE_TREE_AUTHORITY_SEED",
-      "type": "bytes",
-      "value": "[77, 69, 82, 75, 76, 69, 95, 84, 82, 69, 69, 95, 65, 85, 84, 72, 79, 82, 73, 84, 89]"
+      "name": "initializeMerkleTreeAuthority",
+      "docs": [
+        "Initializes a new merkle tree authority which can register new verifiers and configure",
+        "permissions to create new pools."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "TREE_ROOT_SEED",
-      "type": "bytes",
-      "value": "[84, 82, 69, 69, 95, 82, 79, 79, 84, 95, 83, 69, 69, 68]"
+      "name": "proposeMerkleTreeAuthority",
+      "docs": [
+        "Proposes a new merkle tree authority, the new authority has to accept the",
+        "transfer with accept_merkle_tree_authority."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "acceptMerkleTreeAuthority",
+      "docs": [
+        "Transfers the merkle tree authority to the pending authority."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "newAuthority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "name": "STORAGE_SEED",
-      "type": "bytes",
-      "value": "[115, 116, 111, 114, 97, 103, 101]"
+      "name": "cancelMerkleTreeAuthorityTransfer",
+      "docs": [
+        "Cancels a proposed merkle tree authority transfer."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": []
     },
     {
-      "name": "LEAVES_SEED",
-      "type": "bytes",
-      "value": "[108, 101, 97, 118, 101, 115]"
+      "name": "configureMultisig",
+      "docs": [
+        "Configures an M-of-N multisig, once configured authority instructions need the",
+        "signatures of threshold multisig signers passed in as remaining accounts.",
+        "A threshold of zero disables the multisig."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "signers",
+          "type": {
+            "vec": "publicKey"
+          }
+        },
+        {
+          "name": "threshold",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "NULLIFIER_SEED",
-      "type": "bytes",
-      "value": "[110, 102]"
+      "name": "setGuardian",
+      "docs": [
+        "Sets the guardian which can pause but not unpause transactions.",
+        "The default pubkey removes the guardian."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "guardian",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "name": "POOL_TYPE_SEED",
-      "type": "bytes",
-      "value": "[112, 111, 111, 108, 116, 121, 112, 101]"
+      "name": "pause",
+      "docs": [
+        "Pauses shields, unshields and or transfers, flags are defined on MerkleTreeAuthority.",
+        "Can be called by the merkle_tree_authority or the guardian.",
+        "Leaves and nullifiers are rejected if any kind is paused, the merkle tree program can",
+        "not tell the kind of a transaction. Only withdrawals distinguish kinds, unshields",
+        "pause withdrawals and claims, transfers pause withdrawals of relayer fees."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "flags",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "POOL_CONFIG_SEED",
-      "type": "bytes",
-      "value": "[112, 111, 111, 108, 45, 99, 111, 110, 102, 105, 103]"
+      "name": "unpause",
+      "docs": [
+        "Unpauses shields, unshields and or transfers.",
+        "Can only be called by the merkle_tree_authority."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "flags",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "POOL_SEED",
-      "type": "bytes",
-      "value": "[112, 111, 111, 108]"
+      "name": "updateLockDuration",
+      "docs": [
+        "Updates the lock duration for a specific merkle tree."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "lockDuration",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "TOKEN_AUTHORITY_SEED",
-      "type": "bytes",
-      "value": "[115, 112, 108]"
+      "name": "updateLeafFee",
+      "docs": [
+        "Updates the fee paid into the fee vault of a Merkle tree for every inserted pair of leaves."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "leafFee",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "EVENT_MERKLE_TREE_SEED",
-      "type": "bytes",
-      "value": "[101, 118, 101, 110, 116, 95, 109, 101, 114, 107, 108, 101, 95, 116, 114, 101, 101]"
+      "name": "updateRelayerGating",
+      "docs": [
+        "Restricts updates of a Merkle tree to registered relayers which are whitelisted or",
+        "staked at least min_relayer_stake lamports."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "gatedRelayers",
+          "type": "bool"
+        },
+        {
+          "name": "minRelayerStake",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "TRANSACTION_MERKLE_TREE_SEED",
-      "type": "bytes",
-      "value": "[116, 114, 97, 110, 115, 97, 99, 116, 105, 111, 110, 95, 109, 101, 114, 107, 108, 101, 95, 116, 114, 101, 101]"
+      "name": "registerRelayer",
+      "docs": [
+        "Registers the signer as relayer, registered relayers can be whitelisted or stake."
+      ],
+      "accounts": [
+        {
+          "name": "relayer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "EVENT_MERKLE_TREE_HEIGHT",
-      "type": {
-        "defined": "usize"
-      },
-      "value": "8"
-    }
-  ],
-  "instructions": [
+      "name": "stakeRelayer",
+      "docs": [
+        "Adds lamports to the stake of a relayer, the stake is locked for RELAYER_UNSTAKE_DELAY slots."
+      ],
+      "accounts": [
+        {
+          "name": "relayer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
     {
-      "name": "initializeNewTransactionMerkleTree",
+      "name": "unstakeRelayer",
       "docs": [
-        "Initializes a new Merkle tree from config bytes.",
-        "Can only be called from the merkle_tree_authority."
+        "Withdraws unlocked stake of a relayer."
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "relayer",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "newTransactionMerkleTree",
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         },
@@ -1717,56 +4826,122 @@ export const IDL: MerkleTreeProgram = {
           "name": "systemProgram",
           "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "rent",
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "whitelistRelayer",
+      "docs": [
+        "Whitelists a registered relayer, whitelisted relayers do not need to stake."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "merkleTreeAuthorityPda",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "registeredRelayer",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "lockDuration",
-          "type": "u64"
+          "name": "whitelisted",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "initializeNewEventMerkleTree",
+      "name": "enableNfts",
+      "docs": [
+        "Enables anyone to create nft pools for spl tokens with supply of one and zero decimals."
+      ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "eventMerkleTree",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "enablePermissionlessSplTokens",
+      "docs": [
+        "Enables anyone to create token pools."
+      ],
+      "accounts": [
+        {
+          "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "authority",
           "isMut": false,
-          "isSigner": false
-        },
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "enablePermissionlessMerkleTreeRegistration",
+      "docs": [
+        "Enables anyone to create new transaction and event Merkle trees, new transaction",
+        "Merkle trees use the config of the old Merkle tree. The nullifier tree is always",
+        "created by the authority."
+      ],
+      "accounts": [
         {
           "name": "merkleTreeAuthorityPda",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
     },
     {
-      "name": "initializeMerkleTreeAuthority",
+      "name": "enablePermissionlessVerifierRegistration",
       "docs": [
-        "Initializes a new merkle tree authority which can register new verifiers and configure",
-        "permissions to create new pools."
+        "Enables anyone to register new verifiers."
       ],
       "accounts": [
         {
@@ -1775,7 +4950,29 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": false
         },
         {
-          "name": "transactionMerkleTree",
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enablePermissionless",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "registerVerifier",
+      "docs": [
+        "Registers a new verifier which can withdraw tokens, insert new nullifiers, add new leaves.",
+        "These functions can only be invoked from registered verifiers.",
+        "Verifiers registered by the authority get all capabilities, permissionless registrations",
+        "get none until the authority sets them with set verifier capabilities."
+      ],
+      "accounts": [
+        {
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
         },
@@ -1784,6 +4981,11 @@ export const IDL: MerkleTreeProgram = {
           "isMut": true,
           "isSigner": true
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -1795,16 +4997,22 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "verifierPubkey",
+          "type": "publicKey"
+        }
+      ]
     },
     {
-      "name": "updateMerkleTreeAuthority",
+      "name": "pauseVerifier",
       "docs": [
-        "Updates the merkle tree authority to a new authority."
+        "Pauses a registered verifier, paused verifiers cannot invoke any verifier invoked",
+        "instruction until they are resumed."
       ],
       "accounts": [
         {
-          "name": "merkleTreeAuthorityPda",
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
         },
@@ -1814,7 +5022,7 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": true
         },
         {
-          "name": "newAuthority",
+          "name": "merkleTreeAuthorityPda",
           "isMut": false,
           "isSigner": false
         }
@@ -1822,13 +5030,37 @@ export const IDL: MerkleTreeProgram = {
       "args": []
     },
     {
-      "name": "updateLockDuration",
+      "name": "resumeVerifier",
       "docs": [
-        "Updates the lock duration for a specific merkle tree."
+        "Resumes a paused verifier."
       ],
       "accounts": [
+        {
+          "name": "registeredVerifierPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "deregisterVerifier",
+      "docs": [
+        "Permanently deregisters a verifier, a deregistered verifier cannot be resumed."
+      ],
+      "accounts": [
+        {
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
         },
@@ -1838,50 +5070,168 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": true
         },
         {
-          "name": "transactionMerkleTree",
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setVerifierCapabilities",
+      "docs": [
+        "Sets the capabilities of a registered verifier and restricts it to pool types and",
+        "Merkle trees. Empty lists allow any pool type or Merkle tree."
+      ],
+      "accounts": [
+        {
+          "name": "registeredVerifierPda",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "lockDuration",
-          "type": "u64"
+          "name": "capabilities",
+          "type": "u8"
+        },
+        {
+          "name": "allowedPoolTypes",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        },
+        {
+          "name": "allowedMerkleTrees",
+          "type": {
+            "vec": "publicKey"
+          }
         }
       ]
     },
     {
-      "name": "enableNfts",
+      "name": "registerVerifyingKey",
       "docs": [
-        "Enables permissionless deposits of any spl token with supply of one and zero decimals."
+        "Registers a verifying key for transactions with `nr_inputs` input and `nr_outputs`",
+        "output utxos of a registered verifier. The vk_ic points are appended",
+        "with append_verifying_key_ic, the key becomes active VERIFYING_KEY_TIMELOCK slots",
+        "after the upload is complete.",
+        "Can only be called from the merkle_tree_authority."
       ],
       "accounts": [
         {
-          "name": "merkleTreeAuthorityPda",
+          "name": "verifyingKeyPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
           "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "rent",
+          "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "verifierPubkey",
+          "type": "publicKey"
+        },
+        {
+          "name": "nrInputs",
+          "type": "u8"
+        },
+        {
+          "name": "nrOutputs",
+          "type": "u8"
+        },
+        {
+          "name": "version",
+          "type": "u64"
+        },
+        {
+          "name": "nrPubinputs",
+          "type": "u64"
+        },
+        {
+          "name": "vkAlphaG1",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        },
+        {
+          "name": "vkBetaG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
         },
         {
-          "name": "authority",
-          "isMut": false,
-          "isSigner": true
-        }
-      ],
-      "args": [
+          "name": "vkGammeG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
+        },
         {
-          "name": "enablePermissionless",
-          "type": "bool"
+          "name": "vkDeltaG2",
+          "type": {
+            "array": [
+              "u8",
+              128
+            ]
+          }
         }
       ]
     },
     {
-      "name": "enablePermissionlessSplTokens",
+      "name": "appendVerifyingKeyIc",
       "docs": [
-        "Enables anyone to create token pools."
+        "Appends vk_ic points to an incomplete verifying key."
       ],
       "accounts": [
         {
-          "name": "merkleTreeAuthorityPda",
+          "name": "verifyingKeyPda",
           "isMut": true,
           "isSigner": false
         },
@@ -1889,54 +5239,51 @@ export const IDL: MerkleTreeProgram = {
           "name": "authority",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "enablePermissionless",
-          "type": "bool"
+          "name": "vkIc",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
         }
       ]
     },
     {
-      "name": "registerVerifier",
+      "name": "deactivateVerifyingKey",
       "docs": [
-        "Registers a new verifier which can withdraw tokens, insert new nullifiers, add new leaves.",
-        "These functions can only be invoked from registered verifiers."
+        "Deactivates a verifying key, for example if the circuit is compromised.",
+        "Deactivation takes effect immediately and cannot be reverted."
       ],
       "accounts": [
         {
-          "name": "registeredVerifierPda",
+          "name": "verifyingKeyPda",
           "isMut": true,
           "isSigner": false
         },
         {
           "name": "authority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
         },
         {
           "name": "merkleTreeAuthorityPda",
           "isMut": false,
           "isSigner": false
-        },
-        {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "verifierPubkey",
-          "type": "publicKey"
-        }
-      ]
+      "args": []
     },
     {
       "name": "registerPoolType",
@@ -1985,7 +5332,9 @@ export const IDL: MerkleTreeProgram = {
     {
       "name": "registerSplPool",
       "docs": [
-        "Creates a new spl token pool which can be used by any registered verifier."
+        "Creates a new spl token pool which can be used by any registered verifier.",
+        "Nft pools are created for mints with supply one and zero decimals, the metaplex",
+        "master edition of the mint has to be passed in as remaining account."
       ],
       "accounts": [
         {
@@ -2016,7 +5365,10 @@ export const IDL: MerkleTreeProgram = {
         {
           "name": "mint",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Spl token or Token-2022 mint, extensions are checked in instruction."
+          ]
         },
         {
           "name": "tokenAuthority",
@@ -2083,13 +5435,85 @@ export const IDL: MerkleTreeProgram = {
       ],
       "args": []
     },
+    {
+      "name": "updateAssetPoolLimits",
+      "docs": [
+        "Updates the deposit cap, withdrawal limit and minimum delayed withdrawal of an asset pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "depositCap",
+          "type": "u64"
+        },
+        {
+          "name": "withdrawalLimit",
+          "type": "u64"
+        },
+        {
+          "name": "withdrawalWindow",
+          "type": "u64"
+        },
+        {
+          "name": "minDelayedWithdrawal",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "cancelDelayedWithdrawal",
+      "docs": [
+        "Cancels all delayed withdrawals of a recipient, the amounts remain in the pool.",
+        "Can only be called from the merkle_tree_authority."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "recipient",
+          "type": "publicKey"
+        }
+      ]
+    },
     {
       "name": "initializeMerkleTreeUpdateState",
       "docs": [
         "Initializes a merkle tree update state pda. This pda stores the leaves to be inserted",
         "and state of the computation of poseidon hashes to update the Merkle tree.",
-        "A maximum of 16 pairs of leaves can be passed in as leaves accounts as remaining accounts.",
-        "Every leaf is copied into this account such that no further accounts or data have to be",
+        "Up to 16 pairs of leaves are read from the leaves queue, starting at `start_index` or",
+        "the next index of the Merkle tree. Leaves before `start_index` are inserted by other",
+        "relayers in parallel. Every leaf is copied into this account such that no further accounts or data have to be",
         "passed in during the following instructions which compute the poseidon hashes to update the tree.",
         "The hashes are computed with the update merkle tree instruction and the new root is inserted",
         "with the insert root merkle tree instruction."
@@ -2107,9 +5531,23 @@ export const IDL: MerkleTreeProgram = {
         },
         {
           "name": "transactionMerkleTree",
-          "isMut": true,
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "registeredRelayer",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The stake of a registered relayer is locked, the authority has to be registered",
+            "if the Merkle tree is gated to registered relayers."
+          ]
+        },
         {
           "name": "systemProgram",
           "isMut": false,
@@ -2121,7 +5559,14 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "startIndex",
+          "type": {
+            "option": "u64"
+          }
+        }
+      ]
     },
     {
       "name": "updateTransactionMerkleTree",
@@ -2130,9 +5575,145 @@ export const IDL: MerkleTreeProgram = {
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The update state is not derived from the authority since it can be taken over."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The Merkle tree is only read, updates of several relayers can be computed in parallel."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "insertRootMerkleTree",
+      "docs": [
+        "This is the last step of a Merkle tree update which inserts the prior computed Merkle tree",
+        "root.",
+        "The new root and the inserted leaves are logged as a RootUpdateEvent through the noop program."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Is in correct instruction for root insert thus Merkle Tree update has been completed.",
+            "The account is closed to the authority at the end of the instruction."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "bump",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "batchUpdateTransactionMerkleTree",
+      "docs": [
+        "Inserts up to 16 pairs of leaves and the resulting root in one instruction.",
+        "Hashes are computed with the Poseidon syscall, if the runtime does not provide it",
+        "the multi-instruction update (initialize_merkle_tree_update_state,",
+        "update_transaction_merkle_tree, insert_root_merkle_tree) has to be used.",
+        "Leaves are read from the leaves queue of the Merkle tree, the new root is logged",
+        "through the noop program."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Receives the updater fee."
+          ]
+        },
+        {
+          "name": "transactionMerkleTree",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "leavesQueue",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredRelayer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required if the Merkle tree is gated to registered relayers."
+          ]
+        },
+        {
+          "name": "logWrapper",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeMerkleTreeUpdateState",
+      "docs": [
+        "Closes the Merkle tree update state.",
+        "A relayer can close its own update state account, expired update states can be closed",
+        "by anyone for a share of the rent."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "The relayer of the update state or anyone if the update state is expired."
+          ]
+        },
+        {
+          "name": "relayer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "merkleTreeUpdateState",
@@ -2141,77 +5722,73 @@ export const IDL: MerkleTreeProgram = {
         },
         {
           "name": "transactionMerkleTree",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "bump",
-          "type": "u64"
+          "name": "penalizedRelayer",
+          "isMut": true,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Registration of the relayer, its stake is penalized if the update state is expired",
+            "and the Merkle tree is gated to registered relayers."
+          ]
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "insertRootMerkleTree",
+      "name": "takeOverMerkleTreeUpdateState",
       "docs": [
-        "This is the last step of a Merkle tree update which inserts the prior computed Merkle tree",
-        "root."
+        "Takes over an expired Merkle tree update state of another relayer.",
+        "The update continues from the saved instruction index."
       ],
       "accounts": [
         {
           "name": "authority",
           "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "merkleTreeUpdateState",
-          "isMut": true,
-          "isSigner": false,
+          "isSigner": true,
           "docs": [
-            "Merkle tree is locked by merkle_tree_update_state",
-            "Is in correct instruction for root insert thus Merkle Tree update has been completed.",
-            "The account is closed to the authority at the end of the instruction."
+            "The new relayer of the update state."
           ]
         },
         {
-          "name": "transactionMerkleTree",
+          "name": "relayer",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "logWrapper",
-          "isMut": false,
+          "name": "merkleTreeUpdateState",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "transactionMerkleTree",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "bump",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "closeMerkleTreeUpdateState",
-      "docs": [
-        "Closes the Merkle tree update state.",
-        "A relayer can only close its own update state account."
-      ],
-      "accounts": [
+          "name": "registeredRelayer",
+          "isMut": false,
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Required if the Merkle tree is gated to registered relayers."
+          ]
+        },
         {
-          "name": "authority",
+          "name": "penalizedRelayer",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "isOptional": true,
+          "docs": [
+            "Registration of the previous relayer, its stake is penalized on gated Merkle trees."
+          ]
         },
         {
-          "name": "merkleTreeUpdateState",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
@@ -2220,10 +5797,12 @@ export const IDL: MerkleTreeProgram = {
     {
       "name": "insertTwoLeaves",
       "docs": [
-        "Creates and initializes a pda which stores two merkle tree leaves and encrypted Utxos.",
+        "Appends two merkle tree leaves to the leaves queue of the Merkle tree.",
         "The inserted leaves are not part of the Merkle tree yet and marked accordingly.",
         "The Merkle tree has to be updated after.",
-        "Can only be called from a registered verifier program."
+        "Can only be called from a registered verifier program.",
+        "`first_leaves` is set for the first pair of leaves of a transaction, it counts",
+        "the transaction in the sequence number of the Merkle tree."
       ],
       "accounts": [
         {
@@ -2232,7 +5811,7 @@ export const IDL: MerkleTreeProgram = {
           "isSigner": true
         },
         {
-          "name": "twoLeavesPda",
+          "name": "leavesQueue",
           "isMut": true,
           "isSigner": false
         },
@@ -2250,6 +5829,15 @@ export const IDL: MerkleTreeProgram = {
           "name": "registeredVerifierPda",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused."
+          ]
         }
       ],
       "args": [
@@ -2272,13 +5860,8 @@ export const IDL: MerkleTreeProgram = {
           }
         },
         {
-          "name": "encryptedUtxo",
-          "type": {
-            "array": [
-              "u8",
-              256
-            ]
-          }
+          "name": "firstLeaves",
+          "type": "bool"
         }
       ]
     },
@@ -2332,7 +5915,8 @@ export const IDL: MerkleTreeProgram = {
       "docs": [
         "Withdraws sol from a liquidity pool.",
         "An arbitrary number of recipients can be passed in with remaining accounts.",
-        "Can only be called from a registered verifier program."
+        "Can only be called from a registered verifier program.",
+        "Withdrawals over the withdrawal limit of the pool are delayed."
       ],
       "accounts": [
         {
@@ -2354,6 +5938,14 @@ export const IDL: MerkleTreeProgram = {
           "name": "recipient",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Withdrawals happen in unshields and relayer fee payments of transfers."
+          ]
         }
       ],
       "args": [
@@ -2368,359 +5960,1223 @@ export const IDL: MerkleTreeProgram = {
       "docs": [
         "Withdraws spl tokens from a liquidity pool.",
         "An arbitrary number of recipients can be passed in with remaining accounts.",
+        "Can only be called from a registered verifier program.",
+        "Withdrawals over the withdrawal limit of the pool are delayed."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "merkleTreeToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Withdrawals happen in unshields and relayer fee payments of transfers."
+          ]
+        },
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "Tracks the withdrawal limit of the pool."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "claimDelayedSolWithdrawal",
+      "docs": [
+        "Pays out the released delayed sol withdrawals of a recipient.",
+        "Claims count against the withdrawal limit of the pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimDelayedSplWithdrawal",
+      "docs": [
+        "Pays out the released delayed spl withdrawals of a recipient token account.",
+        "Claims count against the withdrawal limit of the pool."
+      ],
+      "accounts": [
+        {
+          "name": "registeredAssetPoolPda",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeToken",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "recipient",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "mint",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeNullifiers",
+      "docs": [
+        "Creates a pda for every nullifier, fails if a nullifier is spent already.",
+        "Can only be called from a registered verifier program, fails once the nullifier",
+        "tree is enabled."
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "registeredVerifierPda",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused.",
+            "Nullifier pdas are rejected once the nullifier tree is enabled."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "insertNullifiersIntoQueue",
+      "docs": [
+        "Queues nullifiers for insertion into the nullifier tree, fails if a nullifier",
+        "is queued already or has a nullifier pda.",
         "Can only be called from a registered verifier program."
       ],
       "accounts": [
         {
           "name": "authority",
-          "isMut": true,
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "merkleTreeToken",
-          "isMut": true,
+          "name": "registeredVerifierPda",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "recipient",
+          "name": "nullifierQueue",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "nullifierTree",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAuthority",
-          "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Non-inclusion is proven against the single nullifier tree."
+          ]
         },
         {
-          "name": "registeredVerifierPda",
+          "name": "merkleTreeAuthorityPda",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "Every transaction inserts leaves and nullifiers, the kind of a transaction is only",
+            "known to the verifier. They are rejected if any transaction kind is paused."
+          ]
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "nullifiers",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
         }
       ]
     },
     {
-      "name": "initializeNullifiers",
+      "name": "insertNullifierIntoTree",
+      "docs": [
+        "Inserts a queued nullifier into the nullifier tree. The low element is the element",
+        "which encloses the nullifier, it is proven against the current root with a Merkle proof.",
+        "Can be called by anyone, hashes are computed with the Poseidon syscall."
+      ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "nullifierQueue",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
+          "name": "nullifierTree",
+          "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "queueIndex",
+          "type": "u64"
         },
         {
-          "name": "registeredVerifierPda",
-          "isMut": false,
-          "isSigner": false
+          "name": "lowElement",
+          "type": {
+            "defined": "IndexedElement"
+          }
+        },
+        {
+          "name": "lowElementIndex",
+          "type": "u64"
+        },
+        {
+          "name": "lowElementProof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
         }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "registeredAssetPool",
+      "docs": [
+        "Config of a sol or spl token liquidity pool.",
+        "Deposits are capped by deposit_cap, withdrawals are rate limited to withdrawal_limit",
+        "per withdrawal_window slots. Withdrawals over the limit are queued in delayed_withdrawals",
+        "if they are at least min_delayed_withdrawal, claims of delayed withdrawals count against",
+        "the limit as well."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "assetPoolPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "poolType",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "index",
+            "type": "u64"
+          },
+          {
+            "name": "depositCap",
+            "docs": [
+              "Maximum pool balance after a deposit, u64::MAX if uncapped."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalLimit",
+            "docs": [
+              "Maximum amount withdrawn per window, u64::MAX if unlimited."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalWindow",
+            "type": "u64"
+          },
+          {
+            "name": "windowStartSlot",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawnInWindow",
+            "type": "u64"
+          },
+          {
+            "name": "minDelayedWithdrawal",
+            "docs": [
+              "Minimum amount of a delayed withdrawal, smaller withdrawals over the limit fail",
+              "such that dust withdrawals cannot fill the queue."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "delayedWithdrawals",
+            "type": {
+              "vec": {
+                "defined": "DelayedWithdrawal"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "registeredPoolType",
+      "docs": [
+        "Pool type"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "poolType",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "eventMerkleTree",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "merkleTree",
+            "type": {
+              "defined": "MerkleTree"
+            }
+          },
+          {
+            "name": "merkleTreeNr",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nullifierTree",
+      "docs": [
+        "Indexed Merkle tree of nullifiers. Its elements form a linked list sorted",
+        "by value, a nullifier is not part of the tree if an element encloses it.",
+        "Nullifiers are big endian, nodes and roots little endian."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nullifierQueue",
+            "type": "publicKey"
+          },
+          {
+            "name": "filledSubtrees",
+            "docs": [
+              "Only the first `height` subtrees are used."
+            ],
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
+          },
+          {
+            "name": "roots",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                32
+              ]
+            }
+          },
+          {
+            "name": "height",
+            "type": "u64"
+          },
+          {
+            "name": "currentRootIndex",
+            "type": "u64"
+          },
+          {
+            "name": "nextIndex",
+            "type": "u64"
+          },
+          {
+            "name": "sequenceNumber",
+            "docs": [
+              "Number of inserted nullifiers."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "nullifierQueue",
+      "docs": [
+        "Nullifiers inserted by verifier programs, they are inserted into the",
+        "nullifier tree afterwards. Inserted nullifiers stay in the queue until all",
+        "roots in the history of the nullifier tree contain them."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nullifierTree",
+            "type": "publicKey"
+          },
+          {
+            "name": "elements",
+            "type": {
+              "array": [
+                {
+                  "defined": "NullifierQueueElement"
+                },
+                128
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "merkleTreePdaToken",
+      "type": {
+        "kind": "struct",
+        "fields": []
+      }
+    },
+    {
+      "name": "preInsertedLeavesIndex",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nextIndex",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leavesQueue",
+      "docs": [
+        "Leaves inserted by verifier programs which are not inserted into the transaction",
+        "Merkle tree yet. The pair of leaves starting at `leaf_index` is stored at",
+        "`(leaf_index / 2) % LEAVES_QUEUE_SIZE`, it can be overwritten once it is inserted.",
+        "Encrypted utxos are not stored, they are emitted with the indexer transaction event."
       ],
-      "args": [
-        {
-          "name": "nullifiers",
-          "type": {
-            "vec": {
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "transactionMerkleTree",
+            "type": "publicKey"
+          },
+          {
+            "name": "leaves",
+            "type": {
               "array": [
-                "u8",
-                32
+                {
+                  "array": [
+                    {
+                      "array": [
+                        "u8",
+                        32
+                      ]
+                    },
+                    2
+                  ]
+                },
+                128
               ]
             }
           }
-        }
-      ]
-    }
-  ],
-  "accounts": [
+        ]
+      }
+    },
     {
-      "name": "registeredAssetPool",
+      "name": "merkleTreeAuthority",
       "docs": [
-        "Nullfier pdas are derived from the nullifier",
-        "existence of a nullifier is the check to prevent double spends."
+        "Configures the authority of the merkle tree which can:",
+        "- register new verifiers",
+        "- register new asset pools",
+        "- register new asset pool types",
+        "- set permissions for new asset pool creation, Merkle tree creation and verifier registration",
+        "- keeps current highest index for assets and merkle trees to enable lookups of these"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "assetPoolPubkey",
+            "name": "pubkey",
             "type": "publicKey"
           },
           {
-            "name": "poolType",
+            "name": "transactionMerkleTreeIndex",
+            "type": "u64"
+          },
+          {
+            "name": "eventMerkleTreeIndex",
+            "type": "u64"
+          },
+          {
+            "name": "registeredAssetIndex",
+            "type": "u64"
+          },
+          {
+            "name": "enableNfts",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessSplTokens",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessMerkleTreeRegistration",
+            "type": "bool"
+          },
+          {
+            "name": "enablePermissionlessVerifierRegistration",
+            "type": "bool"
+          },
+          {
+            "name": "pendingAuthority",
+            "docs": [
+              "Proposed new authority, the default pubkey if no transfer is pending."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "multisigThreshold",
+            "docs": [
+              "Number of multisig signers which have to sign authority instructions,",
+              "zero if no multisig is configured."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "nrMultisigSigners",
+            "type": "u8"
+          },
+          {
+            "name": "multisigSigners",
+            "type": {
+              "array": [
+                "publicKey",
+                10
+              ]
+            }
+          },
+          {
+            "name": "guardian",
+            "docs": [
+              "Guardian which can pause but not unpause, the default pubkey if not set."
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Bitmask of the paused transaction kinds."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "nullifierTreeEnabled",
+            "docs": [
+              "Nullifiers are queued for the nullifier tree instead of stored in nullifier pdas.",
+              "Set once by the authority and never unset, otherwise a nullifier could be spent",
+              "once in each mode."
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "merkleTreeUpdateState",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nodeLeft",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "nodeRight",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "leafLeft",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "leafRight",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "relayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "merkleTreePdaPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "state",
+            "type": {
+              "array": [
+                "u8",
+                96
+              ]
+            }
+          },
+          {
+            "name": "currentRound",
+            "type": "u64"
+          },
+          {
+            "name": "currentRoundIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentInstructionIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentIndex",
+            "type": "u64"
+          },
+          {
+            "name": "currentLevel",
+            "type": "u64"
+          },
+          {
+            "name": "currentLevelHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "tmpLeavesIndex",
+            "type": "u64"
+          },
+          {
+            "name": "filledSubtrees",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
+          },
+          {
+            "name": "leaves",
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    {
+                      "array": [
+                        "u8",
+                        32
+                      ]
+                    },
+                    2
+                  ]
+                },
+                16
+              ]
+            }
+          },
+          {
+            "name": "numberOfLeaves",
+            "type": "u8"
+          },
+          {
+            "name": "padding1",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "insertLeavesIndex",
+            "type": "u8"
+          },
+          {
+            "name": "padding2",
             "type": {
               "array": [
                 "u8",
-                32
+                7
               ]
             }
           },
           {
-            "name": "index",
+            "name": "sequenceNumber",
+            "docs": [
+              "Merkle tree state the update is computed for, the copied filled subtrees",
+              "are the ones of the tree with `base_index` leaves."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "baseIndex",
+            "type": "u64"
+          },
+          {
+            "name": "startIndex",
+            "docs": [
+              "Leaves from `base_index` to `start_index` are inserted by other relayers in",
+              "parallel, nodes which cover them are patched once they are inserted."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "docs": [
+              "Slot of the last instruction of the relayer, used to detect abandoned updates."
+            ],
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "registeredPoolType",
+      "name": "registeredVerifier",
       "docs": [
-        "Pool type"
+        "Registered verifiers can invoke the verifier invoked instructions they have",
+        "capabilities for, as long as they are active."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "poolType",
+            "name": "pubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "status",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "defined": "VerifierStatus"
             }
-          }
-        ]
-      }
-    },
-    {
-      "name": "eventMerkleTree",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "merkleTree",
+            "name": "capabilities",
+            "docs": [
+              "Bitmask of the capability flags defined on RegisteredVerifier."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "allowedPoolTypes",
+            "docs": [
+              "Pool types the verifier can withdraw from, any pool type if empty."
+            ],
             "type": {
-              "defined": "MerkleTree"
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
             }
           },
           {
-            "name": "merkleTreeNr",
-            "type": "u64"
+            "name": "allowedMerkleTrees",
+            "docs": [
+              "Merkle trees the verifier can insert leaves into, any tree if empty.",
+              "Nullifiers are inserted into the single nullifier tree."
+            ],
+            "type": {
+              "vec": "publicKey"
+            }
           }
         ]
       }
     },
     {
-      "name": "merkleTreePdaToken",
-      "type": {
-        "kind": "struct",
-        "fields": []
-      }
-    },
-    {
-      "name": "preInsertedLeavesIndex",
+      "name": "registeredRelayer",
+      "docs": [
+        "Registers a relayer for Merkle tree updates of gated trees.",
+        "Relayers are either whitelisted by the merkle tree authority or stake lamports",
+        "which are held by this account."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "nextIndex",
+            "name": "relayer",
+            "type": "publicKey"
+          },
+          {
+            "name": "whitelisted",
+            "type": "bool"
+          },
+          {
+            "name": "stake",
+            "docs": [
+              "Staked lamports, held in addition to the rent of this account."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "unlockSlot",
+            "docs": [
+              "Slot from which stake can be withdrawn."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "penalties",
+            "docs": [
+              "Number of expired update states of the relayer which were taken over or closed."
+            ],
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "merkleTreeAuthority",
+      "name": "verifyingKeyAccount",
       "docs": [
-        "Configures the authority of the merkle tree which can:",
-        "- register new verifiers",
-        "- register new asset pools",
-        "- register new asset pool types",
-        "- set permissions for new asset pool creation",
-        "- keeps current highest index for assets and merkle trees to enable lookups of these"
+        "Groth16 verifying key of a registered verifier for transactions with `nr_inputs` input",
+        "and `nr_outputs` output utxos.",
+        "The key is uploaded in chunks, once all vk_ic points are written it becomes active",
+        "after VERIFYING_KEY_TIMELOCK slots. A deactivated key cannot be activated again,",
+        "keys are rotated by registering a new version."
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "pubkey",
+            "name": "verifier",
             "type": "publicKey"
           },
           {
-            "name": "transactionMerkleTreeIndex",
-            "type": "u64"
+            "name": "nrInputs",
+            "type": "u8"
           },
           {
-            "name": "eventMerkleTreeIndex",
-            "type": "u64"
+            "name": "nrOutputs",
+            "type": "u8"
           },
           {
-            "name": "registeredAssetIndex",
+            "name": "version",
             "type": "u64"
           },
           {
-            "name": "enableNfts",
-            "type": "bool"
+            "name": "activationSlot",
+            "docs": [
+              "Slot from which on the key can be used, u64::MAX until the upload is complete."
+            ],
+            "type": "u64"
           },
           {
-            "name": "enablePermissionlessSplTokens",
+            "name": "deactivated",
             "type": "bool"
           },
           {
-            "name": "enablePermissionlessMerkleTreeRegistration",
-            "type": "bool"
-          }
-        ]
-      }
-    },
-    {
-      "name": "merkleTreeUpdateState",
-      "type": {
-        "kind": "struct",
-        "fields": [
+            "name": "nrPubinputs",
+            "type": "u64"
+          },
           {
-            "name": "nodeLeft",
+            "name": "vkAlphaG1",
             "type": {
               "array": [
                 "u8",
-                32
+                64
               ]
             }
           },
           {
-            "name": "nodeRight",
+            "name": "vkBetaG2",
             "type": {
               "array": [
                 "u8",
-                32
+                128
               ]
             }
           },
           {
-            "name": "leafLeft",
+            "name": "vkGammeG2",
             "type": {
               "array": [
                 "u8",
-                32
+                128
               ]
             }
           },
           {
-            "name": "leafRight",
+            "name": "vkDeltaG2",
             "type": {
               "array": [
                 "u8",
-                32
+                128
               ]
             }
           },
           {
-            "name": "relayer",
-            "type": "publicKey"
+            "name": "vkIc",
+            "type": {
+              "vec": {
+                "array": [
+                  "u8",
+                  64
+                ]
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "transactionMerkleTree",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "filledSubtrees",
+            "docs": [
+              "Only the first `height` subtrees are used."
+            ],
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
           },
           {
-            "name": "merkleTreePdaPubkey",
-            "type": "publicKey"
+            "name": "currentRootIndex",
+            "type": "u64"
           },
           {
-            "name": "state",
+            "name": "nextIndex",
+            "type": "u64"
+          },
+          {
+            "name": "roots",
             "type": {
               "array": [
-                "u8",
-                96
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                256
               ]
             }
           },
           {
-            "name": "currentRound",
+            "name": "sequenceNumber",
+            "docs": [
+              "Number of root updates, the changelog entry of an update is stored at",
+              "`sequence_number % MERKLE_TREE_CHANGELOG_SIZE`."
+            ],
             "type": "u64"
           },
           {
-            "name": "currentRoundIndex",
+            "name": "changelog",
+            "type": {
+              "array": [
+                {
+                  "defined": "ChangelogEntry"
+                },
+                16
+              ]
+            }
+          },
+          {
+            "name": "height",
             "type": "u64"
           },
           {
-            "name": "currentInstructionIndex",
+            "name": "merkleTreeNr",
             "type": "u64"
           },
           {
-            "name": "currentIndex",
+            "name": "lockDuration",
             "type": "u64"
           },
           {
-            "name": "currentLevel",
+            "name": "nextQueuedIndex",
             "type": "u64"
           },
           {
-            "name": "currentLevelHash",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "lastRelayer",
+            "docs": [
+              "Relayer which inserted the last root and the slot it was inserted at."
+            ],
+            "type": "publicKey"
           },
           {
-            "name": "tmpLeavesIndex",
+            "name": "lastRootSlot",
             "type": "u64"
           },
           {
-            "name": "filledSubtrees",
-            "type": {
-              "array": [
-                {
-                  "array": [
-                    "u8",
-                    32
-                  ]
-                },
-                18
-              ]
-            }
+            "name": "minRelayerStake",
+            "docs": [
+              "Stake relayers which are not whitelisted need if relayers are gated."
+            ],
+            "type": "u64"
           },
           {
-            "name": "leaves",
-            "type": {
-              "array": [
-                {
-                  "array": [
-                    {
-                      "array": [
-                        "u8",
-                        32
-                      ]
-                    },
-                    2
-                  ]
-                },
-                16
+            "name": "leafFee",
+            "docs": [
+              "Fee paid into the fee vault for every inserted pair of leaves."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeVaultBalance",
+            "docs": [
+              "Fees of queued leaves, held by this account in addition to its rent."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "transactionSequenceNumber",
+            "docs": [
+              "Number of transactions which inserted leaves, indexer events carry it",
+              "to detect missed events."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "newest",
+            "docs": [
+              "Only the newest tree accepts new leaves, older trees are read-only."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "gatedRelayers",
+            "docs": [
+              "If set, only whitelisted or staked relayers can update the tree."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                6
               ]
             }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "DelayedWithdrawal",
+      "docs": [
+        "Withdrawal which exceeded the withdrawal limit of its pool,",
+        "it can be claimed once release_slot is reached."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "recipient",
+            "docs": [
+              "Recipient sol account or recipient token account."
+            ],
+            "type": "publicKey"
           },
           {
-            "name": "numberOfLeaves",
-            "type": "u8"
+            "name": "amount",
+            "type": "u64"
           },
           {
-            "name": "padding1",
+            "name": "releaseSlot",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "IndexedElement",
+      "docs": [
+        "Element of the nullifier tree, points to the element with the next higher value.",
+        "The element with the highest value points to zero."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "value",
             "type": {
               "array": [
                 "u8",
-                7
+                32
               ]
             }
           },
           {
-            "name": "insertLeavesIndex",
-            "type": "u8"
+            "name": "nextIndex",
+            "type": "u64"
           },
           {
-            "name": "padding2",
+            "name": "nextValue",
             "type": {
               "array": [
                 "u8",
-                7
+                32
               ]
             }
           }
@@ -2728,22 +7184,39 @@ export const IDL: MerkleTreeProgram = {
       }
     },
     {
-      "name": "registeredVerifier",
-      "docs": [
-        ""
-      ],
+      "name": "NullifierQueueElement",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "pubkey",
-            "type": "publicKey"
+            "name": "value",
+            "docs": [
+              "Zero if the element is empty."
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "sequenceNumber",
+            "docs": [
+              "Sequence number of the nullifier tree after the nullifier was inserted,",
+              "zero while it is pending."
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "transactionMerkleTree",
+      "name": "LegacyTransactionMerkleTree",
+      "docs": [
+        "Layout of transaction Merkle trees created before the account grew to 9424 bytes.",
+        "It only supports height 18 and updates are locked to one relayer at a time."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -2824,21 +7297,19 @@ export const IDL: MerkleTreeProgram = {
       }
     },
     {
-      "name": "twoLeavesBytesPda",
+      "name": "RootUpdateEvent",
+      "docs": [
+        "Emitted through the noop program when a root is inserted."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "nodeLeft",
-            "type": {
-              "array": [
-                "u8",
-                32
-              ]
-            }
+            "name": "merkleTree",
+            "type": "publicKey"
           },
           {
-            "name": "nodeRight",
+            "name": "root",
             "type": {
               "array": [
                 "u8",
@@ -2847,27 +7318,86 @@ export const IDL: MerkleTreeProgram = {
             }
           },
           {
-            "name": "merkleTreePubkey",
-            "type": "publicKey"
+            "name": "rootIndex",
+            "type": "u64"
           },
           {
-            "name": "encryptedUtxos",
-            "type": {
-              "array": [
-                "u8",
-                256
-              ]
-            }
+            "name": "sequenceNumber",
+            "type": "u64"
+          },
+          {
+            "name": "startIndex",
+            "docs": [
+              "Leaves from `start_index` to `end_index` (exclusive) are part of the root."
+            ],
+            "type": "u64"
           },
           {
-            "name": "leftLeafIndex",
+            "name": "endIndex",
             "type": "u64"
           }
         ]
       }
-    }
-  ],
-  "types": [
+    },
+    {
+      "name": "ChangelogEntry",
+      "docs": [
+        "Leaves range inserted by a root update."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "startIndex",
+            "type": "u64"
+          },
+          {
+            "name": "endIndex",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Permission",
+      "docs": [
+        "Permissions which can be granted to anyone by the merkle tree authority."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Nfts"
+          },
+          {
+            "name": "SplTokens"
+          },
+          {
+            "name": "MerkleTreeRegistration"
+          },
+          {
+            "name": "VerifierRegistration"
+          }
+        ]
+      }
+    },
+    {
+      "name": "VerifierStatus",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Active"
+          },
+          {
+            "name": "Paused"
+          },
+          {
+            "name": "Deregistered"
+          }
+        ]
+      }
+    },
     {
       "name": "MerkleTree",
       "type": {
@@ -2956,6 +7486,25 @@ export const IDL: MerkleTreeProgram = {
       }
     }
   ],
+  "events": [
+    {
+      "name": "PermissionUpdated",
+      "fields": [
+        {
+          "name": "permission",
+          "type": {
+            "defined": "Permission"
+          },
+          "index": false
+        },
+        {
+          "name": "enabled",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    }
+  ],
   "errors": [
     {
       "code": 6000,
@@ -3066,6 +7615,261 @@ export const IDL: MerkleTreeProgram = {
       "code": 6021,
       "name": "NotNewestOldMerkleTree",
       "msg": "Provided old Merkle tree is not the newest one."
+    },
+    {
+      "code": 6022,
+      "name": "PoseidonHashFailed",
+      "msg": "Poseidon hash computation failed."
+    },
+    {
+      "code": 6023,
+      "name": "NativePoseidonUnavailable",
+      "msg": "Native Poseidon is not available, use the multi-instruction Merkle tree update."
+    },
+    {
+      "code": 6024,
+      "name": "InvalidMerkleTreeHeight",
+      "msg": "Merkle tree height is out of the supported range."
+    },
+    {
+      "code": 6025,
+      "name": "MerkleTreeFull",
+      "msg": "Merkle tree is full."
+    },
+    {
+      "code": 6026,
+      "name": "MerkleTreeReadOnly",
+      "msg": "Merkle tree is read-only, leaves have to be inserted into the newest Merkle tree."
+    },
+    {
+      "code": 6027,
+      "name": "MerkleTreeUpdateStateOutdated",
+      "msg": "Merkle tree update state is outdated."
+    },
+    {
+      "code": 6028,
+      "name": "NullifierQueueFull",
+      "msg": "Nullifier queue is full."
+    },
+    {
+      "code": 6029,
+      "name": "NullifierQueueElementNotPending",
+      "msg": "Nullifier queue element is not pending insertion."
+    },
+    {
+      "code": 6030,
+      "name": "InvalidNullifierQueue",
+      "msg": "Nullifier queue does not belong to the nullifier tree."
+    },
+    {
+      "code": 6031,
+      "name": "InvalidLowElement",
+      "msg": "Low element does not enclose the inserted value."
+    },
+    {
+      "code": 6032,
+      "name": "InvalidMerkleProof",
+      "msg": "Merkle proof does not match the current root."
+    },
+    {
+      "code": 6033,
+      "name": "InvalidNumberOfNullifiers",
+      "msg": "Number of nullifiers does not match the number of nullifier accounts."
+    },
+    {
+      "code": 6034,
+      "name": "InvalidVerifyingKey",
+      "msg": "Verifying key is invalid or already complete."
+    },
+    {
+      "code": 6035,
+      "name": "InvalidMasterEdition",
+      "msg": "Master edition account does not belong to the nft mint."
+    },
+    {
+      "code": 6036,
+      "name": "InvalidPoolType",
+      "msg": "Pool type does not match the asset type of the mint."
+    },
+    {
+      "code": 6037,
+      "name": "NotEnoughMultisigSignatures",
+      "msg": "Not enough multisig signers signed the transaction."
+    },
+    {
+      "code": 6038,
+      "name": "InvalidMultisigConfig",
+      "msg": "Invalid multisig signers or threshold."
+    },
+    {
+      "code": 6039,
+      "name": "VerifierNotActive",
+      "msg": "Verifier is paused or deregistered."
+    },
+    {
+      "code": 6040,
+      "name": "VerifierDeregistered",
+      "msg": "Verifier is deregistered."
+    },
+    {
+      "code": 6041,
+      "name": "MissingVerifierCapability",
+      "msg": "Verifier does not have the capability for this instruction."
+    },
+    {
+      "code": 6042,
+      "name": "MerkleTreeNotAllowed",
+      "msg": "Verifier is not allowed to use this Merkle tree."
+    },
+    {
+      "code": 6043,
+      "name": "PoolTypeNotAllowed",
+      "msg": "Verifier is not allowed to use this pool type."
+    },
+    {
+      "code": 6044,
+      "name": "InvalidVerifierCapabilities",
+      "msg": "Invalid verifier capabilities."
+    },
+    {
+      "code": 6045,
+      "name": "ProtocolPaused",
+      "msg": "Transactions are paused."
+    },
+    {
+      "code": 6046,
+      "name": "InvalidPauseFlags",
+      "msg": "Invalid pause flags."
+    },
+    {
+      "code": 6047,
+      "name": "DepositCapExceeded",
+      "msg": "Deposit exceeds the deposit cap of the pool."
+    },
+    {
+      "code": 6048,
+      "name": "DelayedWithdrawalQueueFull",
+      "msg": "Delayed withdrawal queue of the pool is full."
+    },
+    {
+      "code": 6049,
+      "name": "NoReleasedWithdrawal",
+      "msg": "No released delayed withdrawal for this recipient."
+    },
+    {
+      "code": 6050,
+      "name": "InvalidAssetPool",
+      "msg": "Asset pool does not belong to the pool account."
+    },
+    {
+      "code": 6051,
+      "name": "InvalidWithdrawalWindow",
+      "msg": "Withdrawal window needs to be greater than zero."
+    },
+    {
+      "code": 6052,
+      "name": "UnsupportedMintExtension",
+      "msg": "Mint uses a token extension which is not supported by pools."
+    },
+    {
+      "code": 6053,
+      "name": "UpdateStateNotExpired",
+      "msg": "Merkle tree update state is not expired."
+    },
+    {
+      "code": 6054,
+      "name": "RelayerNotRegistered",
+      "msg": "Relayer is not registered for this Merkle tree."
+    },
+    {
+      "code": 6055,
+      "name": "InsufficientRelayerStake",
+      "msg": "Relayer is not whitelisted and its stake is below the minimum stake."
+    },
+    {
+      "code": 6056,
+      "name": "RelayerRotation",
+      "msg": "Relayer inserted the last root, other relayers have priority."
+    },
+    {
+      "code": 6057,
+      "name": "RelayerStakeLocked",
+      "msg": "Relayer stake is locked."
+    },
+    {
+      "code": 6058,
+      "name": "InvalidRelayerStake",
+      "msg": "Invalid relayer stake amount."
+    },
+    {
+      "code": 6059,
+      "name": "LeavesQueueFull",
+      "msg": "Leaves queue is full, the Merkle tree has to be updated first."
+    },
+    {
+      "code": 6060,
+      "name": "InvalidLeavesQueue",
+      "msg": "Leaves queue does not belong to the Merkle tree."
+    },
+    {
+      "code": 6061,
+      "name": "InvalidNoopPubkey",
+      "msg": "Invalid noop program key."
+    },
+    {
+      "code": 6062,
+      "name": "LeavesBeforeUpdateNotInserted",
+      "msg": "Leaves before the update state are not inserted into the Merkle tree yet."
+    },
+    {
+      "code": 6063,
+      "name": "InvalidUpdateStateSequenceNumber",
+      "msg": "Sequence number of the update state is ahead of the Merkle tree."
+    },
+    {
+      "code": 6064,
+      "name": "InvalidUpdateStartIndex",
+      "msg": "Start index of the update has to be a queued pair of leaves."
+    },
+    {
+      "code": 6065,
+      "name": "DelayedWithdrawalTooSmall",
+      "msg": "Withdrawal over the withdrawal limit is below the minimum delayed withdrawal."
+    },
+    {
+      "code": 6066,
+      "name": "WithdrawalLimitExceeded",
+      "msg": "Claim exceeds the withdrawal limit of the current window."
+    },
+    {
+      "code": 6067,
+      "name": "NoDelayedWithdrawal",
+      "msg": "No delayed withdrawal for this recipient."
+    },
+    {
+      "code": 6068,
+      "name": "NullifierPdasDisabled",
+      "msg": "Nullifier pdas are disabled, nullifiers are inserted into the nullifier tree."
+    },
+    {
+      "code": 6069,
+      "name": "NullifierTreeNotEnabled",
+      "msg": "Nullifier tree is not enabled."
+    },
+    {
+      "code": 6070,
+      "name": "InvalidNullifierPda",
+      "msg": "Nullifier pda address does not match the nullifier."
+    },
+    {
+      "code": 6071,
+      "name": "InvalidLegacyMerkleTree",
+      "msg": "Account is not a transaction Merkle tree in the legacy layout."
+    },
+    {
+      "code": 6072,
+      "name": "LegacyMerkleTreeHasQueuedLeaves",
+      "msg": "Queued leaves of the legacy Merkle tree have to be inserted before the migration."
     }
   ]
 };
@@ -7,6 +7,13 @@ export type VerifierProgramOne = {
       "type": "string",
       "value": "\"J85SuNBBsba7FQS66BiBCQjiQrQTif7v249zL2ffmRZc\""
     },
+    {
+      "name": "ENCRYPTED_UTXO_LEN",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "120"
+    },
     {
       "name": "VERIFYINGKEY",
       "type": {
@@ -119,6 +126,19 @@ export type VerifierProgramOne = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "registeredAssetPoolSpl",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "deposit caps and withdrawal limits."
+          ]
+        },
+        {
+          "name": "mintSpl",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "registeredVerifierPda",
           "isMut": true,
@@ -137,10 +157,26 @@ export type VerifierProgramOne = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
@@ -572,6 +608,13 @@ export const IDL: VerifierProgramOne = {
       "type": "string",
       "value": "\"J85SuNBBsba7FQS66BiBCQjiQrQTif7v249zL2ffmRZc\""
     },
+    {
+      "name": "ENCRYPTED_UTXO_LEN",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "120"
+    },
     {
       "name": "VERIFYINGKEY",
       "type": {
@@ -684,6 +727,19 @@ export const IDL: VerifierProgramOne = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "registeredAssetPoolSpl",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "deposit caps and withdrawal limits."
+          ]
+        },
+        {
+          "name": "mintSpl",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "registeredVerifierPda",
           "isMut": true,
@@ -702,10 +758,26 @@ export const IDL: VerifierProgramOne = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
@@ -36,11 +36,11 @@ export type VerifierProgramStorage = {
       "value": "MESSAGE_MAX_SIZE + 8"
     },
     {
-      "name": "ENCRYPTED_UTXOS_SIZE",
+      "name": "ENCRYPTED_UTXO_LEN",
       "type": {
         "defined": "usize"
       },
-      "value": "256"
+      "value": "120"
     },
     {
       "name": "VERIFYINGKEY",
@@ -165,10 +165,26 @@ export type VerifierProgramStorage = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
@@ -283,12 +299,7 @@ export type VerifierProgramStorage = {
           },
           {
             "name": "encryptedUtxos",
-            "type": {
-              "array": [
-                "u8",
-                256
-              ]
-            }
+            "type": "bytes"
           }
         ]
       }
@@ -639,11 +650,11 @@ export const IDL: VerifierProgramStorage = {
       "value": "MESSAGE_MAX_SIZE + 8"
     },
     {
-      "name": "ENCRYPTED_UTXOS_SIZE",
+      "name": "ENCRYPTED_UTXO_LEN",
       "type": {
         "defined": "usize"
       },
-      "value": "256"
+      "value": "120"
     },
     {
       "name": "VERIFYINGKEY",
@@ -768,10 +779,26 @@ export const IDL: VerifierProgramStorage = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
@@ -886,12 +913,7 @@ export const IDL: VerifierProgramStorage = {
           },
           {
             "name": "encryptedUtxos",
-            "type": {
-              "array": [
-                "u8",
-                256
-              ]
-            }
+            "type": "bytes"
           }
         ]
       }
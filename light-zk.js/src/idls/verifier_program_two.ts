@@ -7,6 +7,13 @@ export type VerifierProgramTwo = {
       "type": "string",
       "value": "\"2cxC8e8uNYLcymH6RTGuJs3N8fXGkwmMpw45pY65Ay86\""
     },
+    {
+      "name": "ENCRYPTED_UTXO_LEN",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "120"
+    },
     {
       "name": "VERIFYINGKEY",
       "type": {
@@ -82,6 +89,19 @@ export type VerifierProgramTwo = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "registeredAssetPoolSpl",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "deposit caps and withdrawal limits."
+          ]
+        },
+        {
+          "name": "mintSpl",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "registeredVerifierPda",
           "isMut": true,
@@ -100,10 +120,26 @@ export type VerifierProgramTwo = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
@@ -470,6 +506,13 @@ export const IDL: VerifierProgramTwo = {
       "type": "string",
       "value": "\"2cxC8e8uNYLcymH6RTGuJs3N8fXGkwmMpw45pY65Ay86\""
     },
+    {
+      "name": "ENCRYPTED_UTXO_LEN",
+      "type": {
+        "defined": "usize"
+      },
+      "value": "120"
+    },
     {
       "name": "VERIFYINGKEY",
       "type": {
@@ -545,6 +588,19 @@ export const IDL: VerifierProgramTwo = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "registeredAssetPoolSpl",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "deposit caps and withdrawal limits."
+          ]
+        },
+        {
+          "name": "mintSpl",
+          "isMut": false,
+          "isSigner": false
+        },
         {
           "name": "registeredVerifierPda",
           "isMut": true,
@@ -563,10 +619,26 @@ export const IDL: VerifierProgramTwo = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "merkleTreeAuthorityPda",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Holds the pause state which is checked before any transaction."
+          ]
+        },
         {
           "name": "verifierState",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "verifyingKey",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Verifying key of this verifier registered with the merkle tree program."
+          ]
         }
       ],
       "args": [
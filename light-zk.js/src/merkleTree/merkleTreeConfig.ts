@@ -9,7 +9,12 @@ import {
   sendAndConfirmTransaction,
 } from "@solana/web3.js";
 
-import { confirmConfig, DEFAULT_PROGRAMS, merkleTreeProgramId } from "../index";
+import {
+  confirmConfig,
+  DEFAULT_PROGRAMS,
+  MERKLE_TREE_HEIGHT,
+  merkleTreeProgramId,
+} from "../index";
 import { Program } from "@coral-xyz/anchor";
 
 export class MerkleTreeConfig {
@@ -50,14 +55,21 @@ export class MerkleTreeConfig {
   async initializeNewTransactionMerkleTree(
     oldTransactionMerkleTree: PublicKey,
     newTransactionMerkleTree: PublicKey,
+    height: number = MERKLE_TREE_HEIGHT,
   ) {
     if (!this.payer) throw new Error("Payer undefined");
     this.getMerkleTreeAuthorityPda();
     const tx = await this.merkleTreeProgram.methods
-      .initializeNewTransactionMerkleTree(new anchor.BN("50"))
+      .initializeNewTransactionMerkleTree(
+        new anchor.BN("50"),
+        new anchor.BN(height),
+      )
       .accounts({
         authority: this.payer.publicKey,
         newTransactionMerkleTree: newTransactionMerkleTree,
+        leavesQueue: MerkleTreeConfig.getLeavesQueuePda(
+          newTransactionMerkleTree,
+        ),
         systemProgram: DEFAULT_PROGRAMS.systemProgram,
         rent: DEFAULT_PROGRAMS.rent,
         merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
@@ -81,12 +93,14 @@ export class MerkleTreeConfig {
 
     await this.checkTransactionMerkleTreeIsInitialized(
       newTransactionMerkleTree,
+      height,
     );
     return txHash;
   }
 
   async checkTransactionMerkleTreeIsInitialized(
     transactionMerkleTreePda: PublicKey,
+    height: number = MERKLE_TREE_HEIGHT,
   ) {
     var transactionMerkleTreeAccountInfo =
       await this.merkleTreeProgram.account.transactionMerkleTree.fetch(
@@ -101,6 +115,36 @@ export class MerkleTreeConfig {
     // roots are empty save for 0
     // lock duration is correct
     assert(transactionMerkleTreeAccountInfo.lockDuration.toString() == "50");
+    assert(transactionMerkleTreeAccountInfo.height.toNumber() == height);
+  }
+
+  /**
+   * Migrates a transaction Merkle tree created before the account grew to
+   * 9424 bytes. All queued leaves of the Merkle tree have to be inserted first.
+   */
+  async migrateTransactionMerkleTree(transactionMerkleTree: PublicKey) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .migrateTransactionMerkleTree()
+      .accounts({
+        authority: this.payer.publicKey,
+        transactionMerkleTree,
+        leavesQueue: MerkleTreeConfig.getLeavesQueuePda(transactionMerkleTree),
+        systemProgram: DEFAULT_PROGRAMS.systemProgram,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    return await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
   }
 
   async initializeNewEventMerkleTree() {
@@ -180,6 +224,19 @@ export class MerkleTreeConfig {
     return transactionMerkleTreePda;
   }
 
+  static getLeavesQueuePda(
+    transactionMerkleTree: PublicKey,
+    programId: PublicKey = merkleTreeProgramId,
+  ) {
+    return PublicKey.findProgramAddressSync(
+      [
+        transactionMerkleTree.toBytes(),
+        anchor.utils.bytes.utf8.encode("leaves_queue"),
+      ],
+      programId,
+    )[0];
+  }
+
   static getEventMerkleTreePda(
     eventMerkleTreeIndex: anchor.BN = new anchor.BN(0),
   ) {
@@ -215,6 +272,7 @@ export class MerkleTreeConfig {
         authority: authority?.publicKey,
         merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
         transactionMerkleTree: transactionMerkleTree,
+        leavesQueue: MerkleTreeConfig.getLeavesQueuePda(transactionMerkleTree),
         ...DEFAULT_PROGRAMS,
       })
       .signers([authority!])
@@ -256,7 +314,51 @@ export class MerkleTreeConfig {
     return accountInfo !== null && accountInfo.data.length >= 0;
   }
 
-  async updateMerkleTreeAuthority(newAuthority: PublicKey, test = false) {
+  /**
+   * Proposes a new merkle tree authority, the transfer completes once the new authority
+   * calls acceptMerkleTreeAuthority.
+   */
+  async proposeMerkleTreeAuthority(newAuthority: PublicKey, test = false) {
+    if (!this.merkleTreeAuthorityPda) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    if (!this.payer) throw new Error("Payer undefined");
+
+    const tx = await this.merkleTreeProgram.methods
+      .proposeMerkleTreeAuthority()
+      .accounts({
+        authority: this.payer.publicKey,
+        newAuthority,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        ...DEFAULT_PROGRAMS,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+
+    if (test != true) {
+      let merkleTreeAuthority =
+        await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+          this.merkleTreeAuthorityPda!,
+        );
+      assert.equal(
+        merkleTreeAuthority.pendingAuthority.toBase58(),
+        newAuthority.toBase58(),
+      );
+    }
+    return txHash;
+  }
+
+  /**
+   * Accepts a proposed merkle tree authority transfer, the payer has to be the pending authority.
+   */
+  async acceptMerkleTreeAuthority(test = false) {
     if (!this.merkleTreeAuthorityPda) {
       await this.getMerkleTreeAuthorityPda();
     }
@@ -274,12 +376,10 @@ export class MerkleTreeConfig {
     }
 
     const tx = await this.merkleTreeProgram.methods
-      .updateMerkleTreeAuthority()
+      .acceptMerkleTreeAuthority()
       .accounts({
-        authority: this.payer.publicKey,
-        newAuthority,
+        newAuthority: this.payer.publicKey,
         merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
-        ...DEFAULT_PROGRAMS,
       })
       .signers([this.payer])
       .transaction();
@@ -307,34 +407,66 @@ export class MerkleTreeConfig {
       );
       assert.equal(
         merkleTreeAuthority.pubkey.toBase58(),
-        newAuthority.toBase58(),
+        this.payer.publicKey.toBase58(),
+      );
+      assert.equal(
+        merkleTreeAuthority.pendingAuthority.toBase58(),
+        PublicKey.default.toBase58(),
       );
     }
     return txHash;
   }
 
-  // commented in program
-  // async enableNfts(configValue: Boolean) {
-  //   if (this.merkleTreeAuthorityPda == undefined) {
-  //     await this.getMerkleTreeAuthorityPda();
-  //   }
-  //   const tx = await this.merkleTreeProgram.methods
-  //     .enableNfts(configValue)
-  //     .accounts({
-  //       authority: this.payer.publicKey,
-  //       merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
-  //       ...DEFAULT_PROGRAMS,
-  //     })
-  //     .signers([this.payer])
-  //     .rpc(confirmConfig);
-  //   let merkleTreeAuthority =
-  //     await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
-  //       this.merkleTreeAuthorityPda,
-  //     );
-  //   assert(merkleTreeAuthority.enableNfts == configValue);
-
-  //   return tx;
-  // }
+  async cancelMerkleTreeAuthorityTransfer() {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .cancelMerkleTreeAuthorityTransfer()
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    return await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+  }
+
+  async enableNfts(configValue: boolean) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .enableNfts(configValue)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        ...DEFAULT_PROGRAMS,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTreeAuthority =
+      await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        this.merkleTreeAuthorityPda!,
+      );
+    assert(merkleTreeAuthority.enableNfts == configValue);
+    return txHash;
+  }
 
   async enablePermissionlessSplTokens(configValue: boolean) {
     if (!this.payer) throw new Error("Payer undefined");
@@ -365,6 +497,127 @@ export class MerkleTreeConfig {
     return txHash;
   }
 
+  async enablePermissionlessMerkleTreeRegistration(configValue: boolean) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .enablePermissionlessMerkleTreeRegistration(configValue)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        ...DEFAULT_PROGRAMS,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTreeAuthority =
+      await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        this.merkleTreeAuthorityPda!,
+      );
+    assert(merkleTreeAuthority.enablePermissionlessMerkleTreeRegistration == configValue);
+    return txHash;
+  }
+
+  async enablePermissionlessVerifierRegistration(configValue: boolean) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .enablePermissionlessVerifierRegistration(configValue)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        ...DEFAULT_PROGRAMS,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTreeAuthority =
+      await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        this.merkleTreeAuthorityPda!,
+      );
+    assert(merkleTreeAuthority.enablePermissionlessVerifierRegistration == configValue);
+    return txHash;
+  }
+
+  async setGuardian(guardian: PublicKey) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .setGuardian(guardian)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTreeAuthority =
+      await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        this.merkleTreeAuthorityPda!,
+      );
+    assert(merkleTreeAuthority.guardian.toBase58() == guardian.toBase58());
+    return txHash;
+  }
+
+  /**
+   * Pauses (or unpauses) shields (1), unshields (2) and or transfers (4).
+   * Pausing can be done by the authority or the guardian, unpausing only by the authority.
+   */
+  async setPaused(flags: number, paused: boolean, signer?: Keypair) {
+    signer = signer ? signer : this.payer;
+    if (!signer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const method = paused
+      ? this.merkleTreeProgram.methods.pause(flags)
+      : this.merkleTreeProgram.methods.unpause(flags);
+    const tx = await method
+      .accounts({
+        authority: signer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([signer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [signer],
+      confirmConfig,
+    );
+    let merkleTreeAuthority =
+      await this.merkleTreeProgram.account.merkleTreeAuthority.fetch(
+        this.merkleTreeAuthorityPda!,
+      );
+    assert(((merkleTreeAuthority.paused & flags) == flags) == paused);
+    return txHash;
+  }
+
   async updateLockDuration(lockDuration: Number) {
     if (!this.payer) throw new Error("Payer undefined");
     if (this.merkleTreeAuthorityPda == undefined) {
@@ -401,6 +654,120 @@ export class MerkleTreeConfig {
     return txHash;
   }
 
+  async updateLeafFee(leafFee: anchor.BN) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+
+    const transactionMerkleTreePda =
+      MerkleTreeConfig.getTransactionMerkleTreePda();
+
+    const tx = await this.merkleTreeProgram.methods
+      .updateLeafFee(leafFee)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        transactionMerkleTree: transactionMerkleTreePda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTree =
+      await this.merkleTreeProgram.account.transactionMerkleTree.fetch(
+        transactionMerkleTreePda!,
+      );
+    assert.equal(merkleTree.leafFee.toString(), leafFee.toString());
+    return txHash;
+  }
+
+  async updateRelayerGating(gatedRelayers: boolean, minRelayerStake: anchor.BN) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+
+    const transactionMerkleTreePda =
+      MerkleTreeConfig.getTransactionMerkleTreePda();
+
+    const tx = await this.merkleTreeProgram.methods
+      .updateRelayerGating(gatedRelayers, minRelayerStake)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        transactionMerkleTree: transactionMerkleTreePda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let merkleTree =
+      await this.merkleTreeProgram.account.transactionMerkleTree.fetch(
+        transactionMerkleTreePda!,
+      );
+    assert.equal(merkleTree.gatedRelayers, gatedRelayers ? 1 : 0);
+    assert.equal(
+      merkleTree.minRelayerStake.toString(),
+      minRelayerStake.toString(),
+    );
+    return txHash;
+  }
+
+  static getRegisteredRelayerPda(
+    relayer: PublicKey,
+    programId: PublicKey = merkleTreeProgramId,
+  ) {
+    return PublicKey.findProgramAddressSync(
+      [relayer.toBytes(), anchor.utils.bytes.utf8.encode("relayer")],
+      programId,
+    )[0];
+  }
+
+  async whitelistRelayer(relayer: PublicKey, whitelisted: boolean) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const registeredRelayer = MerkleTreeConfig.getRegisteredRelayerPda(
+      relayer,
+      this.merkleTreeProgram.programId,
+    );
+
+    const tx = await this.merkleTreeProgram.methods
+      .whitelistRelayer(whitelisted)
+      .accounts({
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+        registeredRelayer,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let registeredRelayerAccount =
+      await this.merkleTreeProgram.account.registeredRelayer.fetch(
+        registeredRelayer,
+      );
+    assert.equal(registeredRelayerAccount.whitelisted, whitelisted);
+    return txHash;
+  }
+
   async getRegisteredVerifierPda(verifierPubkey: PublicKey) {
     // TODO: add check whether already exists
     this.registeredVerifierPdas.push({
@@ -583,6 +950,79 @@ export class MerkleTreeConfig {
     };
   }
 
+  /**
+   * Updates the deposit cap and the withdrawal limit per window (in slots) of an asset pool.
+   * Withdrawals over the limit are delayed and can be claimed after the timelock,
+   * withdrawals over the limit below minDelayedWithdrawal fail.
+   */
+  async updateAssetPoolLimits(
+    assetPoolPda: PublicKey,
+    depositCap: anchor.BN,
+    withdrawalLimit: anchor.BN,
+    withdrawalWindow: anchor.BN,
+    minDelayedWithdrawal: anchor.BN,
+  ) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .updateAssetPoolLimits(
+        depositCap,
+        withdrawalLimit,
+        withdrawalWindow,
+        minDelayedWithdrawal,
+      )
+      .accounts({
+        registeredAssetPoolPda: assetPoolPda,
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    const txHash = await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+    let assetPool =
+      await this.merkleTreeProgram.account.registeredAssetPool.fetch(
+        assetPoolPda,
+      );
+    assert(assetPool.depositCap.eq(depositCap));
+    assert(assetPool.withdrawalLimit.eq(withdrawalLimit));
+    assert(assetPool.minDelayedWithdrawal.eq(minDelayedWithdrawal));
+    return txHash;
+  }
+
+  /**
+   * Cancels all delayed withdrawals of a recipient, the amounts remain in the pool.
+   */
+  async cancelDelayedWithdrawal(assetPoolPda: PublicKey, recipient: PublicKey) {
+    if (!this.payer) throw new Error("Payer undefined");
+    if (this.merkleTreeAuthorityPda == undefined) {
+      await this.getMerkleTreeAuthorityPda();
+    }
+    const tx = await this.merkleTreeProgram.methods
+      .cancelDelayedWithdrawal(recipient)
+      .accounts({
+        registeredAssetPoolPda: assetPoolPda,
+        authority: this.payer.publicKey,
+        merkleTreeAuthorityPda: this.merkleTreeAuthorityPda,
+      })
+      .signers([this.payer])
+      .transaction();
+
+    return await sendAndConfirmTransaction(
+      this.connection,
+      tx,
+      [this.payer!],
+      confirmConfig,
+    );
+  }
+
   async registerSolPool(poolType: Array<number>) {
     if (!this.payer) throw new Error("Payer undefined");
     if (!this.merkleTreeAuthorityPda)
@@ -642,6 +1082,21 @@ export class MerkleTreeConfig {
     return pda;
   }
 
+  static getSplPoolConfigPda(
+    mint: PublicKey,
+    programId: PublicKey,
+    poolType: Array<number> = new Array(32).fill(0),
+  ) {
+    return PublicKey.findProgramAddressSync(
+      [
+        mint.toBytes(),
+        Buffer.from(poolType),
+        anchor.utils.bytes.utf8.encode("pool-config"),
+      ],
+      programId,
+    )[0];
+  }
+
   async getSplPoolPda(
     mint: PublicKey,
     poolType: Array<number> = new Array(32).fill(0),
@@ -673,7 +1128,15 @@ export class MerkleTreeConfig {
     return this.tokenAuthority;
   }
 
-  async registerSplPool(poolType: Array<number>, mint: PublicKey) {
+  /**
+   * Registers a spl token pool, nft pools require the metaplex master edition of the mint.
+   */
+  async registerSplPool(
+    poolType: Array<number>,
+    mint: PublicKey,
+    masterEdition?: PublicKey,
+    tokenProgram: PublicKey = DEFAULT_PROGRAMS.tokenProgram,
+  ) {
     if (!this.payer) throw new Error("Payer undefined");
     let registeredPoolTypePda = this.poolTypes.filter((item) => {
       return item.poolType === poolType;
@@ -700,7 +1163,13 @@ export class MerkleTreeConfig {
         tokenAuthority: this.tokenAuthority,
         mint,
         ...DEFAULT_PROGRAMS,
+        tokenProgram,
       })
+      .remainingAccounts(
+        masterEdition
+          ? [{ pubkey: masterEdition, isSigner: false, isWritable: false }]
+          : [],
+      )
       .signers([this.payer])
       .transaction();
 
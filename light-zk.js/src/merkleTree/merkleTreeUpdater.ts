@@ -4,6 +4,7 @@ import {
   checkMerkleTreeUpdateStateCreated,
   checkMerkleTreeBatchUpdateSuccess,
 } from "../test-utils/testChecks";
+import { MerkleTreeConfig } from "./merkleTreeConfig";
 
 import {
   confirmConfig,
@@ -22,16 +23,31 @@ import {
 } from "@solana/web3.js";
 import { Program } from "@coral-xyz/anchor";
 
+/**
+ * Returns the registration of a relayer, null if the relayer is not registered.
+ */
+async function getRegisteredRelayer(
+  relayer: PublicKey,
+  merkleTreeProgram: Program<MerkleTreeProgram>,
+  connection: Connection,
+) {
+  const registeredRelayer = MerkleTreeConfig.getRegisteredRelayerPda(
+    relayer,
+    merkleTreeProgram.programId,
+  );
+  return (await connection.getAccountInfo(registeredRelayer))
+    ? registeredRelayer
+    : null;
+}
+
 export async function executeUpdateMerkleTreeTransactions({
   signer,
   merkleTreeProgram,
-  leavesPdas,
   transactionMerkleTree,
   connection,
 }: {
   signer: Keypair;
   merkleTreeProgram: Program<MerkleTreeProgram>;
-  leavesPdas: any;
   transactionMerkleTree: PublicKey;
   connection: Connection;
 }) {
@@ -50,15 +66,22 @@ export async function executeUpdateMerkleTreeTransactions({
   )[0];
   try {
     const tx1 = await merkleTreeProgram.methods
-      .initializeMerkleTreeUpdateState()
+      .initializeMerkleTreeUpdateState(null)
       .accounts({
         authority: signer.publicKey,
         merkleTreeUpdateState: merkleTreeUpdateState,
         systemProgram: SystemProgram.programId,
         rent: DEFAULT_PROGRAMS.rent,
         transactionMerkleTree: transactionMerkleTree,
+        leavesQueue: MerkleTreeConfig.getLeavesQueuePda(
+          transactionMerkleTree,
+          merkleTreeProgram.programId,
+        ),
+        registeredRelayer: MerkleTreeConfig.getRegisteredRelayerPda(
+          signer.publicKey,
+          merkleTreeProgram.programId,
+        ),
       })
-      .remainingAccounts(leavesPdas)
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
       ])
@@ -69,12 +92,20 @@ export async function executeUpdateMerkleTreeTransactions({
     throw err;
   }
 
+  // Up to 16 pairs of queued leaves are copied into the update state.
+  const numberOfLeaves = Math.min(
+    merkleTreeAccountPrior.nextQueuedIndex
+      .sub(merkleTreeAccountPrior.nextIndex)
+      .divn(2)
+      .toNumber(),
+    16,
+  );
   await checkMerkleTreeUpdateStateCreated({
     connection: connection,
     merkleTreeUpdateState,
     transactionMerkleTree: transactionMerkleTree,
     relayer: signer.publicKey,
-    leavesPdas,
+    numberOfLeaves,
     current_instruction_index: 1,
     merkleTreeProgram,
   });
@@ -92,7 +123,7 @@ export async function executeUpdateMerkleTreeTransactions({
     merkleTreeUpdateState,
     transactionMerkleTree: transactionMerkleTree,
     relayer: signer.publicKey,
-    leavesPdas,
+    numberOfLeaves,
     current_instruction_index: 56,
     merkleTreeProgram,
   });
@@ -106,7 +137,6 @@ export async function executeUpdateMerkleTreeTransactions({
         transactionMerkleTree: transactionMerkleTree,
         logWrapper: SPL_NOOP_ADDRESS,
       })
-      .remainingAccounts(leavesPdas)
       .preInstructions([
         ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
       ])
@@ -121,12 +151,76 @@ export async function executeUpdateMerkleTreeTransactions({
     connection: connection,
     merkleTreeUpdateState: merkleTreeUpdateState,
     merkleTreeAccountPrior,
-    numberOfLeaves: leavesPdas.length * 2,
-    leavesPdas,
+    numberOfLeaves: numberOfLeaves * 2,
     transactionMerkleTree: transactionMerkleTree,
     merkleTreeProgram,
   });
 }
+/**
+ * Takes over an expired Merkle tree update state of another relayer and completes the update
+ * from its saved instruction index.
+ */
+export async function takeOverMerkleTreeUpdateState({
+  signer,
+  merkleTreeProgram,
+  merkleTreeUpdateState,
+  connection,
+}: {
+  signer: Keypair;
+  merkleTreeProgram: Program<MerkleTreeProgram>;
+  merkleTreeUpdateState: PublicKey;
+  connection: Connection;
+}) {
+  const updateState =
+    await merkleTreeProgram.account.merkleTreeUpdateState.fetch(
+      merkleTreeUpdateState,
+    );
+  const transactionMerkleTree = updateState.merkleTreePdaPubkey;
+  const tx = await merkleTreeProgram.methods
+    .takeOverMerkleTreeUpdateState()
+    .accounts({
+      authority: signer.publicKey,
+      relayer: updateState.relayer,
+      merkleTreeUpdateState,
+      transactionMerkleTree,
+      registeredRelayer: await getRegisteredRelayer(
+        signer.publicKey,
+        merkleTreeProgram,
+        connection,
+      ),
+      penalizedRelayer: await getRegisteredRelayer(
+        updateState.relayer,
+        merkleTreeProgram,
+        connection,
+      ),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+  await sendAndConfirmTransaction(connection, tx, [signer], confirmConfig);
+
+  await executeMerkleTreeUpdateTransactions({
+    signer,
+    merkleTreeProgram,
+    transactionMerkleTree,
+    merkleTreeUpdateState,
+    connection,
+  });
+
+  const tx1 = await merkleTreeProgram.methods
+    .insertRootMerkleTree(new anchor.BN(254))
+    .accounts({
+      authority: signer.publicKey,
+      merkleTreeUpdateState,
+      transactionMerkleTree,
+      logWrapper: SPL_NOOP_ADDRESS,
+    })
+    .preInstructions([
+      ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 }),
+    ])
+    .transaction();
+  await sendAndConfirmTransaction(connection, tx1, [signer], confirmConfig);
+}
+
 const createTransactions = async ({
   counter,
   merkleTreeProgram,
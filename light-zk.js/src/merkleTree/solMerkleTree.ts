@@ -8,15 +8,14 @@ import {
 } from "../index";
 import { IDL_MERKLE_TREE_PROGRAM, MerkleTreeProgram } from "../idls/index";
 import { MerkleTree } from "./merkleTree";
+import { MerkleTreeConfig } from "./merkleTreeConfig";
 const anchor = require("@coral-xyz/anchor");
 var ffjavascript = require("ffjavascript");
 const { unstringifyBigInts, leInt2Buff } = ffjavascript.utils;
-export type QueuedLeavesPda = {
+export type QueuedLeaves = {
   leftLeafIndex: BN;
-  encryptedUtxos: Array<number>;
   nodeLeft: Array<number>;
   nodeRight: Array<number>;
-  merkleTreePubkey: PublicKey;
 };
 
 // TODO: once we have multiple trees add merkleTree[] and fetchTree(pubkey);
@@ -49,10 +48,24 @@ export class SolMerkleTree {
         "processed",
       );
     const merkleTreeIndex = mtFetched.nextIndex;
-    // ProgramAccount<MerkleTreeProgram["accounts"][7]>
-    var leavesAccounts: Array<any> =
-      await merkleTreeProgram.account.twoLeavesBytesPda.all();
-    return { leavesAccounts, merkleTreeIndex, mtFetched };
+    const leavesQueue = await merkleTreeProgram.account.leavesQueue.fetch(
+      MerkleTreeConfig.getLeavesQueuePda(merkleTreePubkey),
+      "processed",
+    );
+    // The pair of leaves starting at leaf index i is stored at (i / 2) % queue size.
+    let queuedLeaves: QueuedLeaves[] = [];
+    for (
+      let leafIndex = merkleTreeIndex;
+      leafIndex.lt(mtFetched.nextQueuedIndex);
+      leafIndex = leafIndex.addn(2)
+    ) {
+      const [nodeLeft, nodeRight] =
+        leavesQueue.leaves[
+          leafIndex.divn(2).modn(leavesQueue.leaves.length)
+        ];
+      queuedLeaves.push({ leftLeafIndex: leafIndex, nodeLeft, nodeRight });
+    }
+    return { queuedLeaves, merkleTreeIndex, mtFetched };
   }
 
   static async build({
@@ -145,44 +158,11 @@ export class SolMerkleTree {
   static async getUninsertedLeaves(
     merkleTreePubkey: PublicKey,
     provider?: Provider,
-  ): Promise<
-    Array<{
-      publicKey: PublicKey;
-      account: any;
-    }>
-  > {
-    const { leavesAccounts, merkleTreeIndex } = await SolMerkleTree.getLeaves(
+  ): Promise<QueuedLeaves[]> {
+    const { queuedLeaves } = await SolMerkleTree.getLeaves(
       merkleTreePubkey,
       provider,
     );
-
-    let filteredLeaves = leavesAccounts
-      .filter((pda) => {
-        if (
-          pda.account.merkleTreePubkey.toBase58() ===
-          merkleTreePubkey.toBase58()
-        ) {
-          return (
-            pda.account.leftLeafIndex.toNumber() >= merkleTreeIndex.toNumber()
-          );
-        }
-      })
-      .sort(
-        (a, b) =>
-          a.account.leftLeafIndex.toNumber() -
-          b.account.leftLeafIndex.toNumber(),
-      );
-    return filteredLeaves;
-  }
-
-  static async getUninsertedLeavesRelayer(
-    merkleTreePubkey: PublicKey,
-    provider?: Provider,
-  ) {
-    return (
-      await SolMerkleTree.getUninsertedLeaves(merkleTreePubkey, provider)
-    ).map((pda) => {
-      return { isSigner: false, isWritable: true, pubkey: pda.publicKey };
-    });
+    return queuedLeaves;
   }
 }
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { PublicKey } from "@solana/web3.js";
+import { Keypair } from "@solana/web3.js";
 import {
   IDL_VERIFIER_PROGRAM_ONE,
   IDL_VERIFIER_PROGRAM_TWO,
@@ -23,7 +23,7 @@ import { MerkleTreeConfig } from "../merkleTree/merkleTreeConfig";
 
 export async function setUpMerkleTree(
   provider: anchor.AnchorProvider,
-  merkleTreeAuthority: PublicKey,
+  merkleTreeAuthority?: Keypair,
 ) {
   let merkleTreeConfig = new MerkleTreeConfig({
     payer: ADMIN_AUTH_KEYPAIR,
@@ -133,6 +133,15 @@ export async function setUpMerkleTree(
     );
   }
   if (merkleTreeAuthority) {
-    await merkleTreeConfig.updateMerkleTreeAuthority(merkleTreeAuthority, true);
+    await merkleTreeConfig.proposeMerkleTreeAuthority(
+      merkleTreeAuthority.publicKey,
+      true,
+    );
+    // the new authority has to accept the transfer
+    const newAuthorityConfig = new MerkleTreeConfig({
+      payer: merkleTreeAuthority,
+      connection: provider.connection,
+    });
+    await newAuthorityConfig.acceptMerkleTreeAuthority(true);
   }
 }
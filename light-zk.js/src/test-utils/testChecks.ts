@@ -22,7 +22,7 @@ export async function checkMerkleTreeUpdateStateCreated({
   merkleTreeUpdateState,
   relayer,
   transactionMerkleTree,
-  leavesPdas,
+  numberOfLeaves,
   current_instruction_index,
   merkleTreeProgram,
 }: {
@@ -30,7 +30,7 @@ export async function checkMerkleTreeUpdateStateCreated({
   merkleTreeUpdateState: PublicKey;
   relayer: PublicKey;
   transactionMerkleTree: PublicKey;
-  leavesPdas: Array<any>;
+  numberOfLeaves: number;
   current_instruction_index: number;
   merkleTreeProgram: anchor.Program<MerkleTreeProgram>;
 }) {
@@ -69,7 +69,7 @@ export async function checkMerkleTreeUpdateStateCreated({
     "merkleTreeUpdateStateData.numberOfLeaves ",
     merkleTreeUpdateStateData.numberOfLeaves,
   );
-  console.log("leavesPdas.length ", leavesPdas.length);
+  console.log("numberOfLeaves ", numberOfLeaves);
   console.log(
     "merkleTreeUpdateStateData.currentInstructionIndex ",
     merkleTreeUpdateStateData.currentInstructionIndex,
@@ -88,7 +88,7 @@ export async function checkMerkleTreeUpdateStateCreated({
   );
   assert.equal(
     merkleTreeUpdateStateData.numberOfLeaves,
-    leavesPdas.length,
+    numberOfLeaves,
     "The incorrect number of leaves was saved",
   );
   assert(
@@ -97,9 +97,11 @@ export async function checkMerkleTreeUpdateStateCreated({
     ),
     "The instruction index is wrong",
   );
-  assert.equal(
-    MerkleTreeAccountInfo.pubkeyLocked.toBase58(),
-    merkleTreeUpdateState.toBase58(),
+  assert(
+    merkleTreeUpdateStateData.sequenceNumber.eq(
+      MerkleTreeAccountInfo.sequenceNumber,
+    ),
+    "The update state was not created for the current Merkle tree state",
   );
   console.log("checkMerkleTreeUpdateStateCreated: success");
   console.log = x;
@@ -117,7 +119,6 @@ export async function checkMerkleTreeBatchUpdateSuccess({
   merkleTreeUpdateState: PublicKey;
   merkleTreeAccountPrior: any;
   numberOfLeaves: number;
-  leavesPdas: any;
   transactionMerkleTree: PublicKey;
   merkleTreeProgram: Program<MerkleTreeProgram>;
 }) {
@@ -137,16 +138,11 @@ export async function checkMerkleTreeBatchUpdateSuccess({
       transactionMerkleTree,
       "confirmed",
     );
-  // Merkle tree is locked by merkleTreeUpdateState
-  assert.equal(
-    merkleTreeAccount.pubkeyLocked.toBase58(),
-    new solana.PublicKey(new Uint8Array(32).fill(0)).toBase58(),
-  );
-
-  assert.equal(
-    merkleTreeAccount.timeLocked.toNumber(),
-    0,
-    "Lock has not been taken within prior  20 slots",
+  assert(
+    merkleTreeAccount.sequenceNumber.eq(
+      merkleTreeAccountPrior.sequenceNumber.add(new anchor.BN(1)),
+    ),
+    "The root update was not recorded in the changelog",
   );
 
   let merkle_tree_prior_leaves_index = merkleTreeAccountPrior.nextIndex;
@@ -232,8 +232,8 @@ export class TestTransaction {
       throw new Error("remainingAccounts.nullifierPdaPubkeys undefined");
     }
 
-    if (!remainingAccounts.leavesPdaPubkeys) {
-      throw new Error("remainingAccounts.leavesPdaPubkeys undefined");
+    if (!remainingAccounts.leavesQueue) {
+      throw new Error("remainingAccounts.leavesQueue undefined");
     }
     if (!this.testValues) {
       throw new Error("test values undefined");
@@ -278,45 +278,55 @@ export class TestTransaction {
         connection: this.provider.provider!.connection,
       });
     }
-    var leavesAccountData;
-    // Checking that leaves were inserted
-    for (var i = 0; i < remainingAccounts.leavesPdaPubkeys.length; i += 2) {
-      leavesAccountData =
-        await this.merkleTreeProgram.account.twoLeavesBytesPda.fetch(
-          remainingAccounts.leavesPdaPubkeys[i / 2].pubkey,
-          "processed",
-        );
+    const merkleTreeAfterUpdate =
+      await this.merkleTreeProgram.account.transactionMerkleTree.fetch(
+        this.provider.solMerkleTree.pubkey,
+        "processed",
+      );
+    const leavesQueue = await this.merkleTreeProgram.account.leavesQueue.fetch(
+      remainingAccounts.leavesQueue.pubkey,
+      "processed",
+    );
+    assert.equal(
+      leavesQueue.transactionMerkleTree.toBase58(),
+      this.provider.solMerkleTree.pubkey.toBase58(),
+      "leaves queue of the wrong merkle tree",
+    );
+    // Checking that leaves were queued, the leaves of this transaction are the last queued leaves
+    const outputCommitments = transactionInputs.publicInputs.outputCommitment;
+    const firstLeafIndex = merkleTreeAfterUpdate.nextQueuedIndex.subn(
+      outputCommitments.length,
+    );
+    let lightProvider = await Provider.loadMock();
+    for (var i = 0; i < outputCommitments.length; i += 2) {
+      const [nodeLeft, nodeRight] =
+        leavesQueue.leaves[
+          firstLeafIndex.addn(i).divn(2).modn(leavesQueue.leaves.length)
+        ];
 
       assert.equal(
-        leavesAccountData.nodeLeft.toString(),
-        transactionInputs.publicInputs.outputCommitment[i].reverse().toString(),
+        nodeLeft.toString(),
+        outputCommitments[i].reverse().toString(),
         "left leaf not inserted correctly",
       );
       assert.equal(
-        leavesAccountData.nodeRight.toString(),
-        transactionInputs.publicInputs.outputCommitment[i + 1]
-          .reverse()
-          .toString(),
+        nodeRight.toString(),
+        outputCommitments[i + 1].reverse().toString(),
         "right leaf not inserted correctly",
       );
-      assert.equal(
-        leavesAccountData.merkleTreePubkey.toBase58(),
-        this.provider.solMerkleTree.pubkey.toBase58(),
-        "merkleTreePubkey not inserted correctly",
-      );
-      let lightProvider = await Provider.loadMock();
 
-      for (var j = 0; j < this.params.encryptedUtxos.length / 256; j++) {
+      const { encryptedUtxoLength } = this.params.verifierConfig;
+      for (var j = 0; j < 2; j++) {
         let decryptedUtxo1 = await Utxo.decrypt({
           poseidon: this.provider.poseidon,
-          encBytes: this.params!.encryptedUtxos,
+          encBytes: this.params!.encryptedUtxos.slice(
+            (i + j) * encryptedUtxoLength,
+            (i + j + 1) * encryptedUtxoLength,
+          ),
           account: account ? account : this.params!.outputUtxos![0].account,
           index: 0, // this is just a placeholder
           merkleTreePdaPublicKey: this.params!.accounts.transactionMerkleTree,
-          commitment:
-            j === 0
-              ? Buffer.from(leavesAccountData.nodeLeft)
-              : Buffer.from(leavesAccountData.nodeRight),
+          commitment: j === 0 ? Buffer.from(nodeLeft) : Buffer.from(nodeRight),
           assetLookupTable: lightProvider.lookUpTables.assetLookupTable,
           verifierProgramLookupTable:
             lightProvider.lookUpTables.verifierProgramLookupTable,
@@ -332,28 +342,6 @@ export class TestTransaction {
       }
     }
 
-    try {
-      const merkleTreeAfterUpdate =
-        await this.merkleTreeProgram.account.transactionMerkleTree.fetch(
-          MerkleTreeConfig.getTransactionMerkleTreePda(),
-          "confirmed",
-        );
-
-      leavesAccountData =
-        await this.merkleTreeProgram.account.twoLeavesBytesPda.fetch(
-          remainingAccounts.leavesPdaPubkeys[0].pubkey,
-          "confirmed",
-        );
-
-      assert.equal(
-        Number(merkleTreeAfterUpdate.nextQueuedIndex),
-        Number(leavesAccountData.leftLeafIndex) +
-          remainingAccounts.leavesPdaPubkeys.length * 2,
-      );
-    } catch (e) {
-      console.log("preInsertedLeavesIndex: ", e);
-      throw e;
-    }
     var nrInstructions;
     if (this.appParams) {
       nrInstructions = 2;
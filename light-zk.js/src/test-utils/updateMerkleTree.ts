@@ -28,11 +28,11 @@ export async function updateMerkleTreeForTest(payer: Keypair, url: string) {
     const transactionMerkleTreePda =
       MerkleTreeConfig.getTransactionMerkleTreePda();
 
-    let leavesPdas: any[] = [];
+    let queuedLeaves: any[] = [];
     let retries = 5;
-    while (leavesPdas.length === 0 && retries > 0) {
+    while (queuedLeaves.length === 0 && retries > 0) {
       if (retries !== 5) await sleep(1000);
-      leavesPdas = await SolMerkleTree.getUninsertedLeavesRelayer(
+      queuedLeaves = await SolMerkleTree.getUninsertedLeaves(
         transactionMerkleTreePda,
         anchorProvider && anchorProvider,
       );
@@ -43,7 +43,6 @@ export async function updateMerkleTreeForTest(payer: Keypair, url: string) {
       connection,
       signer: payer,
       merkleTreeProgram,
-      leavesPdas,
       transactionMerkleTree: transactionMerkleTreePda,
     });
   } catch (err) {
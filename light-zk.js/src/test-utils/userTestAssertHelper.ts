@@ -170,7 +170,7 @@ export class UserTestAssertHelper {
       type: Action;
       publicAmountSol: BN;
       publicAmountSpl: BN;
-      encryptedUtxos?: Buffer | any[];
+      encryptedUtxos?: Buffer[];
       leaves?: number[][];
       nullifiers?: BN[];
       relayerFee: BN;
@@ -15,6 +15,7 @@ import {
   VERIFIER_PUBLIC_KEYS,
   MAX_U64,
   FIELD_SIZE,
+  NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
   merkleTreeProgramId,
   BN_0,
 } from "../constants";
@@ -31,21 +32,78 @@ import {
 import { Utxo } from "../utxo";
 import { TokenUtxoBalance, Provider } from "../wallet";
 
+/**
+ * Decodes the events verifiers log through the noop program.
+ * Versioned events start with the version, events logged before
+ * versioning (version 0) are not tagged.
+ */
 export class TransactionIndexerEvent {
-  borshSchema = borsh.struct([
+  borshSchemaV0 = borsh.struct([
     borsh.vec(borsh.array(borsh.u8(), 32), "leaves"),
     borsh.array(borsh.u8(), 32, "publicAmountSpl"),
     borsh.array(borsh.u8(), 32, "publicAmountSol"),
     borsh.u64("relayerFee"),
-    borsh.vec(borsh.u8(), "encryptedUtxos"),
+    borsh.vecU8("encryptedUtxos"),
     borsh.vec(borsh.array(borsh.u8(), 32), "nullifiers"),
     borsh.u64("firstLeafIndex"),
     borsh.vecU8("message"),
   ]);
 
+  borshSchema = borsh.rustEnum([
+    borsh.struct([], "v0"),
+    borsh.struct(
+      [
+        borsh.publicKey("transactionMerkleTree"),
+        borsh.u64("eventMerkleTreeIndex"),
+        borsh.u64("sequenceNumber"),
+        borsh.publicKey("verifier"),
+        borsh.rustEnum(
+          [
+            borsh.struct([], "shield"),
+            borsh.struct([], "transfer"),
+            borsh.struct([], "unshield"),
+          ],
+          "kind",
+        ),
+        borsh.vec(borsh.array(borsh.u8(), 32), "leaves"),
+        borsh.array(borsh.u8(), 32, "publicAmountSpl"),
+        borsh.array(borsh.u8(), 32, "publicAmountSol"),
+        borsh.u64("relayerFee"),
+        borsh.vec(borsh.vecU8(), "encryptedUtxos"),
+        borsh.vec(borsh.array(borsh.u8(), 32), "nullifiers"),
+        borsh.u64("firstLeafIndex"),
+        borsh.vecU8("message"),
+      ],
+      "v1",
+    ),
+  ]);
+
   deserialize(buffer: Buffer): any | null {
     try {
-      return this.borshSchema.decode(buffer);
+      // Other noop instructions, e.g. root update events, must not be decoded.
+      if (this.borshSchema.getSpan(buffer) === buffer.length) {
+        const event = this.borshSchema.decode(buffer);
+        if (event.v1) {
+          const kind = Object.keys(event.v1.kind)[0];
+          return { ...event.v1, kind, version: 1 };
+        }
+      }
+    } catch (e) {}
+    try {
+      if (this.borshSchemaV0.getSpan(buffer) !== buffer.length) return null;
+      const event = this.borshSchemaV0.decode(buffer);
+      const encryptedUtxos: Buffer[] = [];
+      for (let i = 0; i < event.leaves.length; i++) {
+        encryptedUtxos.push(
+          Buffer.from(
+            event.encryptedUtxos.slice(
+              i * NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
+              (i + 1) * NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
+            ),
+          ),
+        );
+      }
+      return { ...event, encryptedUtxos, version: 0 };
     } catch (e) {
       return null;
     }
@@ -164,6 +222,11 @@ async function enrichParsedTransactionEvents(
     leaves,
     encryptedUtxos,
     message,
+    version,
+    kind,
+    transactionMerkleTree,
+    eventMerkleTreeIndex,
+    sequenceNumber,
   } = event;
   if (!tx || !tx.meta || tx.meta.err) return;
 
@@ -212,10 +275,10 @@ async function enrichParsedTransactionEvents(
     return { amountSpl, amountSol, type };
   };
 
-  const { type, amountSpl, amountSol } = getTypeAndAmounts(
-    publicAmountSpl,
-    publicAmountSol,
-  );
+  const amounts = getTypeAndAmounts(publicAmountSpl, publicAmountSol);
+  const { amountSpl, amountSol } = amounts;
+  // Versioned events carry the kind of the transaction.
+  const type = kind ? (kind.toUpperCase() as Action) : amounts.type;
   const convertToPublicKey = (key: PublicKey | string): PublicKey => {
     return key instanceof PublicKey ? key : new PublicKey(key);
   };
@@ -274,6 +337,10 @@ async function enrichParsedTransactionEvents(
     relayerFee: relayerFee.toString("hex"),
     firstLeafIndex: firstLeafIndex.toString("hex"),
     message: Buffer.from(message),
+    version,
+    transactionMerkleTree,
+    eventMerkleTreeIndex: eventMerkleTreeIndex?.toString("hex"),
+    sequenceNumber: sequenceNumber?.toString("hex"),
   });
 }
 
@@ -24,7 +24,6 @@ import {
   SendVersionedTransactionsResult,
   AUTHORITY,
   MerkleTreeConfig,
-  TRANSACTION_MERKLE_TREE_SWITCH_TRESHOLD,
   BN_0,
 } from "../index";
 import { IDL_MERKLE_TREE_PROGRAM } from "../idls/index";
@@ -85,7 +84,7 @@ export class Transaction {
 
   remainingAccounts?: {
     nullifierPdaPubkeys?: remainingAccount[];
-    leavesPdaPubkeys?: remainingAccount[];
+    leavesQueue?: remainingAccount;
     nextTransactionMerkleTree?: remainingAccount;
   };
 
@@ -474,26 +473,24 @@ export class Transaction {
         );
       }
 
-      if (
-        merkle_tree_account_data.nextIndex.gte(
-          TRANSACTION_MERKLE_TREE_SWITCH_TRESHOLD,
-        )
-      ) {
-        let merkleTreeConfig = new MerkleTreeConfig({
-          connection: this.provider.provider.connection,
-        });
-        let nextTrancactionMerkleTreeIndex =
-          await merkleTreeConfig.getTransactionMerkleTreeIndex();
-        const nextTransactionMerkleTreePubkey =
-          MerkleTreeConfig.getTransactionMerkleTreePda(
-            nextTrancactionMerkleTreeIndex,
-          );
-        this.remainingAccounts!.nextTransactionMerkleTree = {
-          isSigner: false,
-          isWritable: true,
-          pubkey: nextTransactionMerkleTreePubkey,
-        };
-      }
+      // Only the newest transaction Merkle tree accepts new leaves, it is
+      // always passed in since the Merkle tree can roll over before the
+      // transaction lands.
+      let merkleTreeConfig = new MerkleTreeConfig({
+        connection: this.provider.provider.connection,
+      });
+      // The Merkle tree authority stores the index of the next Merkle tree
+      // to be created.
+      let newestTransactionMerkleTreeIndex = (
+        await merkleTreeConfig.getTransactionMerkleTreeIndex()
+      ).subn(1);
+      this.remainingAccounts!.nextTransactionMerkleTree = {
+        isSigner: false,
+        isWritable: true,
+        pubkey: MerkleTreeConfig.getTransactionMerkleTreePda(
+          newestTransactionMerkleTreeIndex,
+        ),
+      };
     } else {
       console.log(
         "Provider is not defined. Unable to fetch rootIndex. Setting root index to 0 as a default value.",
@@ -630,6 +627,24 @@ export class Transaction {
     )[0];
   }
 
+  static getVerifyingKeyPda(
+    merkleTreeProgramId: PublicKey,
+    verifierProgramId: PublicKey,
+    nrInputs: number,
+    nrOutputs: number,
+    version: number = 0,
+  ): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        verifierProgramId.toBytes(),
+        utils.bytes.utf8.encode("verifying_key"),
+        Buffer.from([nrInputs, nrOutputs]),
+        new BN(version).toArrayLike(Buffer, "le", 8),
+      ],
+      merkleTreeProgramId,
+    )[0];
+  }
+
   // TODO: evaluate whether we need this function
   // async getInstructionsJson(): Promise<string[]> {
   //   if (!this.params)
@@ -695,7 +710,7 @@ export class Transaction {
         );
       }
 
-      if (!this.remainingAccounts?.leavesPdaPubkeys) {
+      if (!this.remainingAccounts?.leavesQueue) {
         throw new TransactionError(
           TransactionErrorCode.REMAINING_ACCOUNTS_NOT_CREATED,
           "sendTransaction",
@@ -782,9 +797,6 @@ export class Transaction {
 
       return orderedInstructionNames;
     };
-    if (this.params.verifierConfig.out == 2) {
-      this.params.encryptedUtxos! = this.params.encryptedUtxos!.slice(0, 240);
-    }
     let inputObject = {
       message: this.params.message,
       ...this.transactionInputs.proofBytes,
@@ -866,12 +878,24 @@ export class Transaction {
       if (i === instructionNames.length - 1) {
         let remainingAccounts = [
           ...this.remainingAccounts!.nullifierPdaPubkeys!,
-          ...this.remainingAccounts!.leavesPdaPubkeys!,
         ];
-        if (this.remainingAccounts!.nextTransactionMerkleTree !== undefined) {
+        const nextTransactionMerkleTree =
+          this.remainingAccounts!.nextTransactionMerkleTree;
+        if (nextTransactionMerkleTree !== undefined) {
+          // Leaves are queued in the leaves queue of the newest Merkle tree.
           remainingAccounts.push(
-            this.remainingAccounts!.nextTransactionMerkleTree,
+            {
+              isSigner: false,
+              isWritable: true,
+              pubkey: MerkleTreeConfig.getLeavesQueuePda(
+                nextTransactionMerkleTree.pubkey,
+                merkleTreeProgramId,
+              ),
+            },
+            nextTransactionMerkleTree,
           );
+        } else {
+          remainingAccounts.push(this.remainingAccounts!.leavesQueue!);
         }
         method.remainingAccounts(remainingAccounts);
       }
@@ -973,29 +997,14 @@ export class Transaction {
       });
     }
 
-    this.remainingAccounts.leavesPdaPubkeys = [];
-
-    for (
-      var j = 0;
-      j < this.transactionInputs.publicInputs.outputCommitment.length;
-      j += 2
-    ) {
-      this.remainingAccounts.leavesPdaPubkeys.push({
-        isSigner: false,
-        isWritable: true,
-        pubkey: PublicKey.findProgramAddressSync(
-          [
-            Buffer.from(
-              Array.from(
-                this.transactionInputs.publicInputs.outputCommitment[j],
-              ).reverse(),
-            ),
-            utils.bytes.utf8.encode("leaves"),
-          ],
-          merkleTreeProgramId,
-        )[0],
-      });
-    }
+    this.remainingAccounts.leavesQueue = {
+      isSigner: false,
+      isWritable: true,
+      pubkey: MerkleTreeConfig.getLeavesQueuePda(
+        this.params.accounts.transactionMerkleTree,
+        merkleTreeProgramId,
+      ),
+    };
 
     if (this.appParams) {
       this.params.accounts.verifierState = PublicKey.findProgramAddressSync(
@@ -2,7 +2,11 @@ import { PublicKey, SystemProgram } from "@solana/web3.js";
 import * as anchor from "@coral-xyz/anchor";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { BN, BorshAccountsCoder, Program, Idl } from "@coral-xyz/anchor";
-import { AUTHORITY, verifierProgramStorageProgramId } from "../constants";
+import {
+  AUTHORITY,
+  MERKLE_TREE_AUTHORITY_PDA,
+  verifierProgramStorageProgramId,
+} from "../constants";
 import { N_ASSET_PUBKEYS, Utxo } from "../utxo";
 import { MerkleTreeConfig } from "../merkleTree/merkleTreeConfig";
 import {
@@ -37,6 +41,8 @@ import nacl from "tweetnacl";
 type VerifierConfig = {
   in: number;
   out: number;
+  /** Length of the encrypted utxo of each output. */
+  encryptedUtxoLength: number;
 };
 
 export class TransactionParameters implements transactionParameters {
@@ -75,6 +81,7 @@ export class TransactionParameters implements transactionParameters {
     action,
     ataCreationFee,
     verifierIdl,
+    tokenProgram,
   }: {
     message?: Buffer;
     eventMerkleTreePubkey: PublicKey;
@@ -92,6 +99,8 @@ export class TransactionParameters implements transactionParameters {
     provider?: Provider;
     ataCreationFee?: boolean;
     verifierIdl: Idl;
+    /** Token program of the mint, either the spl token or the Token-2022 program. */
+    tokenProgram?: PublicKey;
   }) {
     if (!outputUtxos && !inputUtxos) {
       throw new TransactionParametersError(
@@ -417,7 +426,7 @@ export class TransactionParameters implements transactionParameters {
 
     this.accounts = {
       systemProgramId: SystemProgram.programId,
-      tokenProgram: TOKEN_PROGRAM_ID,
+      tokenProgram: tokenProgram ? tokenProgram : TOKEN_PROGRAM_ID,
       logWrapper: SPL_NOOP_PROGRAM_ID,
       eventMerkleTree: eventMerkleTreePubkey,
       transactionMerkleTree: transactionMerkleTreePubkey,
@@ -425,6 +434,13 @@ export class TransactionParameters implements transactionParameters {
         merkleTreeProgramId,
         this.verifierProgramId,
       ),
+      merkleTreeAuthorityPda: MERKLE_TREE_AUTHORITY_PDA,
+      verifyingKey: Transaction.getVerifyingKeyPda(
+        merkleTreeProgramId,
+        this.verifierProgramId,
+        this.inputUtxos.length,
+        this.outputUtxos.length,
+      ),
       authority: Transaction.getSignerAuthorityPda(
         merkleTreeProgramId,
         this.verifierProgramId,
@@ -548,7 +564,22 @@ export class TransactionParameters implements transactionParameters {
     const inputNullifierLength = inputNullifierField.type.array[1];
     const outputCommitmentLength = outputCommitmentField.type.array[1];
 
-    return { in: inputNullifierLength, out: outputCommitmentLength };
+    const encryptedUtxoLengthObj = verifierIdl.constants?.find(
+      (constant) => constant.name === "ENCRYPTED_UTXO_LEN",
+    );
+    if (!encryptedUtxoLengthObj) {
+      throw new TransactionParametersError(
+        TransactionParametersErrorCode.ENCRYPTED_UTXO_LEN_CONSTANT_UNDEFINED,
+        "getVerifierConfig",
+        "ENCRYPTED_UTXO_LEN constant not found in idl. Example: pub const ENCRYPTED_UTXO_LEN: usize = 120;",
+      );
+    }
+
+    return {
+      in: inputNullifierLength,
+      out: outputCommitmentLength,
+      encryptedUtxoLength: parseInt(encryptedUtxoLengthObj.value),
+    };
   }
 
   static async fromBytes({
@@ -820,6 +851,12 @@ export class TransactionParameters implements transactionParameters {
         "assignAccounts",
       );
 
+    this.accounts.registeredAssetPoolSpl = MerkleTreeConfig.getSplPoolConfigPda(
+      this.assetPubkeys[1],
+      merkleTreeProgramId,
+    );
+    this.accounts.mintSpl = this.assetPubkeys[1];
+
     if (
       this.action.toString() === Action.UNSHIELD.toString() ||
       this.action.toString() === Action.TRANSFER.toString()
@@ -1071,16 +1108,16 @@ export class TransactionParameters implements transactionParameters {
         "getTxIntegrityHash",
         "",
       );
+    const encryptedUtxosLength =
+      this.verifierConfig.encryptedUtxoLength * this.verifierConfig.out;
     if (
       this.encryptedUtxos &&
-      this.encryptedUtxos.length > 128 * this.verifierConfig.out
+      this.encryptedUtxos.length !== encryptedUtxosLength
     )
       throw new TransactionParametersError(
-        TransactionParametersErrorCode.ENCRYPTED_UTXOS_TOO_LONG,
+        TransactionParametersErrorCode.INVALID_ENCRYPTED_UTXOS_LENGTH,
         "getTxIntegrityHash",
-        `Encrypted utxos are too long: ${this.encryptedUtxos.length} > ${
-          128 * this.verifierConfig.out
-        }`,
+        `Encrypted utxos length ${this.encryptedUtxos.length} != ${encryptedUtxosLength}`,
       );
 
     if (!this.encryptedUtxos) {
@@ -1168,51 +1205,37 @@ export class TransactionParameters implements transactionParameters {
   }
 
   async encryptOutUtxos(poseidon: any, encryptedUtxos?: Uint8Array) {
-    let encryptedOutputs = new Array<any>();
-    if (encryptedUtxos) {
-      encryptedOutputs = Array.from(encryptedUtxos);
-    } else if (this && this.outputUtxos) {
-      for (var utxo in this.outputUtxos) {
-        if (
-          this.outputUtxos[utxo].appDataHash.toString() !== "0" &&
-          this.outputUtxos[utxo].includeAppData
-        )
-          throw new TransactionError(
-            TransactionErrorCode.UNIMPLEMENTED,
-            "encryptUtxos",
-            "Automatic encryption for utxos with application data is not implemented.",
-          );
-        encryptedOutputs.push(
-          await this.outputUtxos[utxo].encrypt(
-            poseidon,
-            this.accounts.transactionMerkleTree,
-          ),
-        );
-      }
-      encryptedOutputs = encryptedOutputs
-        .map((elem) => Array.from(elem))
-        .flat();
+    if (encryptedUtxos) return encryptedUtxos;
+    const { encryptedUtxoLength } = this.verifierConfig;
+    let encryptedOutputs = new Array<number>();
+    for (var utxo in this.outputUtxos) {
       if (
-        encryptedOutputs.length < 128 * this.verifierConfig.out &&
-        this.verifierConfig.out == 2
-      ) {
-        return new Uint8Array([
-          ...encryptedOutputs,
-          ...new Array(
-            128 * this.verifierConfig.out - encryptedOutputs.length,
-          ).fill(0),
-          // for verifier zero and one these bytes are not sent and just added for the integrity hash
-          // to be consistent, if the bytes were sent to the chain use rnd bytes for padding
-        ]);
-      }
-      if (encryptedOutputs.length < 128 * this.verifierConfig.out) {
-        return new Uint8Array([
-          ...encryptedOutputs,
-          ...nacl.randomBytes(
-            128 * this.verifierConfig.out - encryptedOutputs.length,
-          ),
-        ]);
-      }
+        this.outputUtxos[utxo].appDataHash.toString() !== "0" &&
+        this.outputUtxos[utxo].includeAppData
+      )
+        throw new TransactionError(
+          TransactionErrorCode.UNIMPLEMENTED,
+          "encryptUtxos",
+          "Automatic encryption for utxos with application data is not implemented.",
+        );
+      const encryptedUtxo = Array.from(
+        await this.outputUtxos[utxo].encrypt(
+          poseidon,
+          this.accounts.transactionMerkleTree,
+        ),
+      );
+      if (encryptedUtxo.length > encryptedUtxoLength)
+        throw new TransactionParametersError(
+          TransactionParametersErrorCode.INVALID_ENCRYPTED_UTXOS_LENGTH,
+          "encryptOutUtxos",
+          `Encrypted utxo length ${encryptedUtxo.length} > ${encryptedUtxoLength}`,
+        );
+      // Every output carries encryptedUtxoLength bytes, shorter encrypted utxos are padded with random bytes.
+      encryptedOutputs.push(
+        ...encryptedUtxo,
+        ...nacl.randomBytes(encryptedUtxoLength - encryptedUtxo.length),
+      );
     }
+    return new Uint8Array(encryptedOutputs);
   }
 }
@@ -3,6 +3,8 @@ import { PublicKey } from "@solana/web3.js";
 export type lightAccounts = {
   senderSpl?: PublicKey;
   recipientSpl?: PublicKey;
+  registeredAssetPoolSpl?: PublicKey;
+  mintSpl?: PublicKey;
   senderSol?: PublicKey;
   recipientSol?: PublicKey;
   verifierState?: PublicKey;
@@ -12,6 +14,8 @@ export type lightAccounts = {
   transactionMerkleTree: PublicKey;
   tokenProgram: PublicKey;
   registeredVerifierPda: PublicKey;
+  merkleTreeAuthorityPda?: PublicKey;
+  verifyingKey?: PublicKey;
   authority: PublicKey;
   signingAddress?: PublicKey;
   programMerkleTree: PublicKey;
@@ -1,4 +1,5 @@
 import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
 
 export type IndexedTransactionData = {
   publicAmountSpl: Uint8Array;
@@ -10,6 +11,11 @@ export type IndexedTransactionData = {
   firstLeafIndex: BN;
   tx: any;
   message: number[];
+  version: number;
+  kind?: string;
+  transactionMerkleTree?: PublicKey;
+  eventMerkleTreeIndex?: BN;
+  sequenceNumber?: BN;
 };
 
 export type IndexedTransactionDecodedData = {
@@ -31,11 +31,11 @@ export type transactionParameters = {
     isWritable: boolean;
     pubkey: PublicKey;
   }[];
-  leavesPdaPubkeys?: {
+  leavesQueue?: {
     isSigner: boolean;
     isWritable: boolean;
     pubkey: PublicKey;
-  }[];
+  };
 };
 
 export type IndexedTransaction = {
@@ -52,12 +52,18 @@ export type IndexedTransaction = {
   changeSolAmount: string;
   publicAmountSol: string;
   publicAmountSpl: string;
-  encryptedUtxos: Buffer | any[];
+  encryptedUtxos: Buffer[];
   leaves: number[][];
   firstLeafIndex: string;
   nullifiers: BN[];
   relayerFee: string;
   message: Buffer;
+  /** Version of the indexer event, 0 for events logged before versioning. */
+  version?: number;
+  /** Fields of versioned events. */
+  transactionMerkleTree?: PublicKey;
+  eventMerkleTreeIndex?: string;
+  sequenceNumber?: string;
 };
 export type ParsedIndexedTransaction = {
   blockTime: number;
@@ -73,7 +79,7 @@ export type ParsedIndexedTransaction = {
   changeSolAmount: BN;
   publicAmountSol: BN;
   publicAmountSpl: BN;
-  encryptedUtxos: Buffer | any[];
+  encryptedUtxos: Buffer[];
   leaves: number[][];
   firstLeafIndex: BN;
   nullifiers: BN[];
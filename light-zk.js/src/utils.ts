@@ -1,4 +1,4 @@
-import { BN } from "@coral-xyz/anchor";
+import { BN, BorshAccountsCoder } from "@coral-xyz/anchor";
 import {
   AUTHORITY,
   DEFAULT_PROGRAMS,
@@ -24,6 +24,7 @@ import {
   SystemProgram,
 } from "@solana/web3.js";
 import { MerkleTreeConfig } from "./merkleTree";
+import { IDL_MERKLE_TREE_PROGRAM } from "./idls";
 import { MINT } from "./test-utils/constants_system_verifier";
 import * as anchor from "@coral-xyz/anchor";
 import { Utxo } from "./utxo";
@@ -186,16 +187,22 @@ export const fetchNullifierAccountInfo = async (
   return connection.getAccountInfo(nullifierPubkey, "processed");
 };
 
-// use
-export const fetchQueuedLeavesAccountInfo = async (
-  leftLeaf: Uint8Array,
+// Leaves are queued until the transaction Merkle tree is updated.
+export const isLeafQueued = async (
+  leafIndex: number,
+  transactionMerkleTree: PublicKey,
   connection: Connection,
 ) => {
-  const queuedLeavesPubkey = PublicKey.findProgramAddressSync(
-    [leftLeaf, anchor.utils.bytes.utf8.encode("leaves")],
-    merkleTreeProgramId,
-  )[0];
-  return connection.getAccountInfo(queuedLeavesPubkey, "confirmed");
+  const merkleTreeAccountInfo = await connection.getAccountInfo(
+    transactionMerkleTree,
+    "confirmed",
+  );
+  if (!merkleTreeAccountInfo) return false;
+  const merkleTree = new BorshAccountsCoder(IDL_MERKLE_TREE_PROGRAM).decode(
+    "transactionMerkleTree",
+    merkleTreeAccountInfo.data,
+  );
+  return merkleTree.nextIndex.lten(leafIndex);
 };
 
 export const sleep = (ms: number) => {
@@ -6,7 +6,7 @@ import { Connection, PublicKey } from "@solana/web3.js";
 import { Account } from "../account";
 import {
   fetchNullifierAccountInfo,
-  fetchQueuedLeavesAccountInfo,
+  isLeafQueued,
 } from "../utils";
 import {
   TokenUtxoBalanceError,
@@ -222,8 +222,9 @@ export async function decryptAddUtxoToBalance({
     nullifier,
     connection,
   );
-  const queuedLeavesPdaExists = await fetchQueuedLeavesAccountInfo(
-    leftLeaf,
+  const leafQueued = await isLeafQueued(
+    index,
+    merkleTreePdaPublicKey,
     connection,
   );
 
@@ -252,7 +253,7 @@ export async function decryptAddUtxoToBalance({
       );
     }
     const assetKey = decryptedUtxo.assets[assetIndex].toBase58();
-    const utxoType = queuedLeavesPdaExists
+    const utxoType = leafQueued
       ? "committedUtxos"
       : nullifierExists
       ? "spentUtxos"
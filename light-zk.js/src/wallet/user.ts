@@ -32,7 +32,6 @@ import {
   Balance,
   InboxBalance,
   TokenUtxoBalance,
-  NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
   decryptAddUtxoToBalance,
   fetchNullifierAccountInfo,
   getUserIndexTransactions,
@@ -192,12 +191,7 @@ export class User {
 
         // transaction nonce is the same for all utxos in one transaction
         await decryptAddUtxoToBalance({
-          encBytes: Buffer.from(
-            trx.encryptedUtxos.slice(
-              (index / 2) * 240,
-              (index / 2) * 240 + NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
-            ),
-          ),
+          encBytes: Buffer.from(trx.encryptedUtxos[index]),
           index: leftLeafIndex,
           commitment: Buffer.from([...leafLeft]),
           account: this.account,
@@ -212,14 +206,7 @@ export class User {
           assetLookupTable: this.provider.lookUpTables.assetLookupTable,
         });
         await decryptAddUtxoToBalance({
-          encBytes: Buffer.from(
-            trx.encryptedUtxos.slice(
-              (index / 2) * 240 + 120,
-              (index / 2) * 240 +
-                NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH +
-                120,
-            ),
-          ),
+          encBytes: Buffer.from(trx.encryptedUtxos[index + 1]),
           index: leftLeafIndex + 1,
           commitment: Buffer.from([...leafRight]),
           account: this.account,
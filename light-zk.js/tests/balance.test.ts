@@ -26,7 +26,6 @@ import {
   TOKEN_REGISTRY,
   ParsedIndexedTransaction,
   User,
-  NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
   MerkleTreeConfig,
   BN_0,
 } from "../src";
@@ -138,7 +137,7 @@ describe("Utxo Functional", () => {
     const account = new Account({ poseidon: poseidon, seed: seed32 });
     for (let j = 2; j < 4; j += 2) {
       let utxos: Utxo[] = [];
-      let encryptedUtxos: any[] = [];
+      let encryptedUtxos: Buffer[] = [];
       for (let index = 0; index < j; index++) {
         const depositAmount = index;
         const depositFeeAmount = index;
@@ -154,14 +153,15 @@ describe("Utxo Functional", () => {
           blinding: new BN(1),
         });
         utxos.push(utxo);
-        encryptedUtxos = [
-          ...encryptedUtxos,
-          ...(await utxo.encrypt(
-            poseidon,
-            MerkleTreeConfig.getTransactionMerkleTreePda(),
-            true,
-          )),
-        ];
+        encryptedUtxos.push(
+          Buffer.from(
+            await utxo.encrypt(
+              poseidon,
+              MerkleTreeConfig.getTransactionMerkleTreePda(),
+              true,
+            ),
+          ),
+        );
       }
       let indexedTransactions = [
         {
@@ -181,12 +181,7 @@ describe("Utxo Functional", () => {
           const leafRight = trx.leaves[index + 1];
           let decryptedUtxo = await Utxo.decrypt({
             poseidon,
-            encBytes: Buffer.from(
-              trx.encryptedUtxos.slice(
-                (index / 2) * 240,
-                (index / 2) * 240 + NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH,
-              ),
-            ),
+            encBytes: trx.encryptedUtxos[index],
             account,
             index: leftLeafIndex + index,
             commitment: leafLeft,
@@ -199,14 +194,7 @@ describe("Utxo Functional", () => {
           decryptedUtxos.push(decryptedUtxo);
           decryptedUtxo = await Utxo.decrypt({
             poseidon,
-            encBytes: Buffer.from(
-              trx.encryptedUtxos.slice(
-                (index / 2) * 240 + 120,
-                (index / 2) * 240 +
-                  NACL_ENCRYPTED_COMPRESSED_UTXO_BYTES_LENGTH +
-                  120,
-              ),
-            ),
+            encBytes: trx.encryptedUtxos[index + 1],
             account,
             index: leftLeafIndex + index + 1,
             commitment: leafRight,
@@ -206,7 +206,7 @@ describe("Masp circuit tests", () => {
       action: Action.SHIELD,
       poseidon,
       // automatic encryption for app utxos is not implemented
-      encryptedUtxos: new Uint8Array(256).fill(1),
+      encryptedUtxos: new Uint8Array(240).fill(1),
       verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
     });
   });
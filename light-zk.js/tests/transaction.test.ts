@@ -31,6 +31,8 @@ import {
   BN_0,
   BN_1,
   BN_2,
+  FIELD_SIZE,
+  TransactionParametersErrorCode,
 } from "../src";
 import { bs58 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 
@@ -523,24 +525,70 @@ describe("Transaction Functional Tests", () => {
       recipientSol: lightProvider.wallet?.publicKey,
       action: Action.UNSHIELD,
       relayer: relayerConst,
-      encryptedUtxos: new Uint8Array(256).fill(1),
+      encryptedUtxos: new Uint8Array(240).fill(1),
       verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
     });
 
     let txIntegrityHash = await paramsStaticEncryptedUtxos.getTxIntegrityHash(
       poseidon,
     );
+    assert.isTrue(txIntegrityHash.lt(FIELD_SIZE));
 
-    assert.equal(
+    const paramsOtherEncryptedUtxos = new TransactionParameters({
+      inputUtxos: [deposit_utxo1, deposit_utxo1],
+      outputUtxos: [deposit_utxo1, deposit_utxo1],
+      eventMerkleTreePubkey: AUTHORITY,
+      transactionMerkleTreePubkey: AUTHORITY,
+      poseidon,
+      recipientSpl: AUTHORITY,
+      recipientSol: lightProvider.wallet?.publicKey,
+      action: Action.UNSHIELD,
+      relayer: relayerConst,
+      encryptedUtxos: new Uint8Array(240).fill(2),
+      verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
+    });
+    assert.notEqual(
+      (
+        await paramsOtherEncryptedUtxos.getTxIntegrityHash(poseidon)
+      ).toString(),
       txIntegrityHash.toString(),
-      "6150353308703750134875659224593639995108994571023605893130935914916250029450",
+    );
+
+    const inputHash = poseidon.F.toString(
+      poseidon([
+        deposit_utxo1.getCommitment(poseidon),
+        deposit_utxo1.getCommitment(poseidon),
+      ]),
     );
     assert.equal(
       Transaction.getTransactionHash(
         paramsStaticEncryptedUtxos,
         poseidon,
       ).toString(),
-      "5933194464001103981860458884656917415381806542379509455129642519383560866951",
+      poseidon.F.toString(
+        poseidon([inputHash, inputHash, txIntegrityHash.toString()]),
+      ),
+    );
+  });
+
+  it("getTxIntegrityHash wrong encryptedUtxos length", async () => {
+    const relayerConst = new Relayer(AUTHORITY, AUTHORITY, new BN(5000));
+    const params = new TransactionParameters({
+      inputUtxos: [deposit_utxo1, deposit_utxo1],
+      outputUtxos: [deposit_utxo1, deposit_utxo1],
+      eventMerkleTreePubkey: AUTHORITY,
+      transactionMerkleTreePubkey: AUTHORITY,
+      poseidon,
+      recipientSpl: AUTHORITY,
+      recipientSol: lightProvider.wallet?.publicKey,
+      action: Action.UNSHIELD,
+      relayer: relayerConst,
+      encryptedUtxos: new Uint8Array(256).fill(1),
+      verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
+    });
+    await chai.assert.isRejected(
+      params.getTxIntegrityHash(poseidon),
+      TransactionParametersErrorCode.INVALID_ENCRYPTED_UTXOS_LENGTH,
     );
   });
 
@@ -614,7 +662,7 @@ describe("Transaction Functional Tests", () => {
       recipientSol: lightProvider.wallet?.publicKey,
       action: Action.UNSHIELD,
       relayer: relayerConst,
-      encryptedUtxos: new Uint8Array(256).fill(1),
+      encryptedUtxos: new Uint8Array(240).fill(1),
       verifierIdl: IDL_VERIFIER_PROGRAM_ZERO,
     });
     let tx = new Transaction({
@@ -637,7 +685,7 @@ describe("Transaction Functional Tests", () => {
       "A3rueqakAhxjJVUrygVZdpd3wUNUHiGuKy2M7zR7uHDh",
     ];
 
-    const refLeaves = ["6UuSTaJpEemGVuPkmtTiNe7VndXXenWCDU49aTkGSQqY"];
+    const refLeavesQueue = "7V9Hffnqj6uuaRmtxeqTfm2tnjLhrf2TdDuZSAQQb7yu";
     for (let i = 0; i < 2; i++) {
       assert.equal(
         tx.remainingAccounts?.nullifierPdaPubkeys![i].pubkey.toBase58(),
@@ -645,8 +693,8 @@ describe("Transaction Functional Tests", () => {
       );
     }
     assert.equal(
-      tx.remainingAccounts?.leavesPdaPubkeys![0].pubkey.toBase58(),
-      refLeaves[0],
+      tx.remainingAccounts?.leavesQueue!.pubkey.toBase58(),
+      refLeavesQueue,
     );
     assert.equal(
       tx.params.accounts.verifierState!.toBase58(),